@@ -0,0 +1,196 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr, Result};
+
+// #[derive(PointLayout)]
+//
+// 구조체 필드에서 PointField 목록, 바이트 (역)직렬화, offset 검사를 생성
+//   #[point_layout(step = 32)]      구조체: 패딩 포함 point_step
+//   #[point(name = "ring")]         필드: PointField 이름 변경
+//   #[point(offset = 16)]           필드: 명시적 offset (생략 시 앞 필드 바로 뒤)
+#[proc_macro_derive(PointLayout, attributes(point, point_layout))]
+pub fn derive_point_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+struct FieldInfo {
+    ident: syn::Ident,
+    ty: syn::Ident,
+    name: String,
+    offset: usize,
+    datatype: u8,
+    size: usize,
+}
+
+fn datatype_of(ty: &syn::Type) -> Option<(syn::Ident, u8, usize)> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let ident = path.path.get_ident()?.clone();
+    let (datatype, size) = match ident.to_string().as_str() {
+        "i8" => (1, 1),
+        "u8" => (2, 1),
+        "i16" => (3, 2),
+        "u16" => (4, 2),
+        "i32" => (5, 4),
+        "u32" => (6, 4),
+        "f32" => (7, 4),
+        "f64" => (8, 8),
+        _ => return None,
+    };
+    Some((ident, datatype, size))
+}
+
+fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "PointLayout 은 구조체에만 사용 가능",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(Error::new_spanned(
+            input,
+            "PointLayout 은 이름 있는 필드가 필요",
+        ));
+    };
+
+    let mut step = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("point_layout"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("step") {
+                step = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+                Ok(())
+            } else {
+                Err(meta.error("지원하지 않는 point_layout 속성"))
+            }
+        })?;
+    }
+
+    let mut fields = Vec::new();
+    let mut cursor = 0;
+    for field in &named.named {
+        let ident = field.ident.clone().unwrap();
+        let Some((ty, datatype, size)) = datatype_of(&field.ty) else {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "PointLayout 필드는 i8/u8/i16/u16/i32/u32/f32/f64 만 지원",
+            ));
+        };
+
+        let mut name = ident.to_string();
+        let mut offset = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("point")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("offset") {
+                    offset = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("지원하지 않는 point 속성"))
+                }
+            })?;
+        }
+
+        // offset 검사: 앞 필드와 겹치면 안 됨
+        let offset = offset.unwrap_or(cursor);
+        if offset < cursor {
+            return Err(Error::new_spanned(
+                field,
+                format!(
+                    "필드 '{}' 의 offset {} 이 앞 필드(끝 {})와 겹침",
+                    name, offset, cursor
+                ),
+            ));
+        }
+        cursor = offset + size;
+
+        fields.push(FieldInfo {
+            ident,
+            ty,
+            name,
+            offset,
+            datatype,
+            size,
+        });
+    }
+
+    let step = step.unwrap_or(cursor);
+    if step < cursor {
+        return Err(Error::new(
+            Span::call_site(),
+            format!(
+                "point_layout step {} 이 필드 범위 {} 보다 작음",
+                step, cursor
+            ),
+        ));
+    }
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_meta = fields.iter().map(|f| {
+        let (name, offset, datatype) = (&f.name, f.offset, f.datatype);
+        quote! { (#name, #offset, #datatype) }
+    });
+    let reads = fields.iter().map(|f| {
+        let (ident, ty, offset, size) = (&f.ident, &f.ty, f.offset, f.size);
+        quote! {
+            #ident: {
+                let mut bytes = [0u8; #size];
+                bytes.copy_from_slice(&data[#offset..#offset + #size]);
+                #ty::from_le_bytes(bytes)
+            }
+        }
+    });
+    let writes = fields.iter().map(|f| {
+        let (ident, offset, size) = (&f.ident, f.offset, f.size);
+        quote! {
+            out[start + #offset..start + #offset + #size]
+                .copy_from_slice(&self.#ident.to_le_bytes());
+        }
+    });
+    let values = fields.iter().map(|f| {
+        let ident = &f.ident;
+        quote! { self.#ident as f64 }
+    });
+    let from_values = fields.iter().enumerate().map(|(i, f)| {
+        let (ident, ty) = (&f.ident, &f.ty);
+        quote! { #ident: values[#i] as #ty }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::rust_lidar::cloud::PointLayout for #struct_name #ty_generics #where_clause {
+            const FIELDS: &'static [(&'static str, usize, u8)] = &[#(#field_meta),*];
+            const POINT_STEP: usize = #step;
+
+            fn read(data: &[u8]) -> Self {
+                #struct_name { #(#reads),* }
+            }
+
+            fn write(&self, out: &mut ::std::vec::Vec<u8>) {
+                let start = out.len();
+                out.resize(start + #step, 0);
+                #(#writes)*
+            }
+
+            fn values(&self, out: &mut ::std::vec::Vec<f64>) {
+                out.extend([#(#values),*]);
+            }
+
+            fn from_values(values: &[f64]) -> Self {
+                #struct_name { #(#from_values),* }
+            }
+        }
+    })
+}
@@ -0,0 +1,91 @@
+/* rust_lidar C API: 포인트 코덱(파싱/직렬화)과 기본 필터
+ * 링크: -lrust_lidar_ffi (cargo build -p rust_lidar_ffi --release)
+ * 실패한 함수는 NULL 또는 -1 을 반환하고, lidar_last_error() 로 원인을 확인 (스레드별) */
+#ifndef RUST_LIDAR_H
+#define RUST_LIDAR_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* sensor_msgs/PointField datatype */
+enum {
+    LIDAR_INT8 = 1,
+    LIDAR_UINT8 = 2,
+    LIDAR_INT16 = 3,
+    LIDAR_UINT16 = 4,
+    LIDAR_INT32 = 5,
+    LIDAR_UINT32 = 6,
+    LIDAR_FLOAT32 = 7,
+    LIDAR_FLOAT64 = 8,
+};
+
+/* Rust 의 rust_lidar::core::LidarPoint 와 같은 메모리 배치 (32 bytes) */
+typedef struct {
+    float x;
+    float y;
+    float z;
+    float intensity;
+    uint8_t tag;
+    uint8_t line;
+    double timestamp;
+} LidarPoint;
+
+/* 포인트 내 필드 하나 (line 은 ring 이름도 인식) */
+typedef struct {
+    const char *name;
+    uint32_t offset;
+    uint8_t datatype;
+} LidarField;
+
+/* lidar_serialize 결과, lidar_buffer_free 로 해제 */
+typedef struct {
+    uint8_t *data;
+    size_t len;
+    uint32_t point_step;
+} LidarBuffer;
+
+/* 불투명 포인트 클라우드, lidar_cloud_free 로 해제 */
+typedef struct LidarCloud LidarCloud;
+
+/* 마지막 오류 메시지 (UTF-8, 없으면 빈 문자열), 다음 실패 전까지 유효 */
+const char *lidar_last_error(void);
+
+/* point_step 간격 버퍼를 fields 에 따라 파싱, field_count 가 0 이면 Livox 기본 레이아웃 (26 bytes) */
+LidarCloud *lidar_parse(const uint8_t *data, size_t len, uint32_t point_step,
+                        const LidarField *fields, size_t field_count, bool big_endian);
+
+/* 이름 있는 레이아웃 ("livox_26", "xyzi16", "xyzi_label", "xyzrgb") 의 패딩 없는 버퍼 파싱 */
+LidarCloud *lidar_parse_layout(const uint8_t *data, size_t len, const char *layout_name);
+
+/* 포인트 배열을 복사해서 클라우드 생성 */
+LidarCloud *lidar_cloud_from_points(const LidarPoint *points, size_t count);
+
+void lidar_cloud_free(LidarCloud *cloud);
+
+size_t lidar_cloud_len(const LidarCloud *cloud);
+
+/* 포인트 배열, 클라우드를 필터링하거나 해제하기 전까지 유효 */
+const LidarPoint *lidar_cloud_points(const LidarCloud *cloud);
+
+/* 필터는 제자리에서 포인트를 걸러냄, 성공 0 / 실패 -1 */
+int lidar_filter_range(LidarCloud *cloud, float min_range, float max_range);
+int lidar_filter_z_band(LidarCloud *cloud, float min_z, float max_z);
+int lidar_filter_crop_box(LidarCloud *cloud, const float min[3], const float max[3]);
+/* 비/눈/안개 노이즈 제거, aggressiveness 0..1 (0 이면 아무것도 안 함) */
+int lidar_filter_weather(LidarCloud *cloud, float aggressiveness);
+
+/* 이름 있는 레이아웃으로 직렬화 (리틀 엔디안, 패딩 없음) */
+int lidar_serialize(const LidarCloud *cloud, const char *layout_name, LidarBuffer *out);
+
+void lidar_buffer_free(LidarBuffer *buffer);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUST_LIDAR_H */
@@ -0,0 +1,271 @@
+// C FFI: 포인트 코덱(파싱/직렬화)과 기본 필터를 C/C++ 에서 사용 (헤더는 include/rust_lidar.h)
+// 포인터 인자의 유효성(길이, NUL 종료, 해제 후 사용 금지)은 헤더 주석대로 호출자 책임
+#![allow(clippy::missing_safety_doc)]
+
+use anyhow::{anyhow, bail, Result};
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::core::{self as codec, FieldOffsets, LidarPoint};
+use rust_lidar::filter;
+use rust_lidar::layout;
+use rust_lidar::weather::WeatherFilter;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+#[cfg(feature = "python")]
+mod python;
+
+// C 에서는 불투명 포인터로만 다룸
+pub struct LidarCloud(PointCloud<LidarPoint>);
+
+#[repr(C)]
+pub struct LidarField {
+    pub name: *const c_char,
+    pub offset: u32,
+    pub datatype: u8,
+}
+
+#[repr(C)]
+pub struct LidarBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+    pub point_step: u32,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn set_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+// 오류와 패닉을 C 경계 밖으로 내보내지 않고 마지막 오류로 기록한 뒤 failed 반환
+fn guard<T>(failed: T, f: impl FnOnce() -> Result<T>) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            set_error(format!("{:#}", e));
+            failed
+        }
+        Err(_) => {
+            set_error("rust_lidar 내부 패닉".to_string());
+            failed
+        }
+    }
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        bail!("data 가 NULL 입니다");
+    }
+    Ok(std::slice::from_raw_parts(data, len))
+}
+
+unsafe fn string<'a>(s: *const c_char, what: &str) -> Result<&'a str> {
+    if s.is_null() {
+        bail!("{} 가 NULL 입니다", what);
+    }
+    Ok(CStr::from_ptr(s).to_str()?)
+}
+
+unsafe fn cloud_mut<'a>(cloud: *mut LidarCloud) -> Result<&'a mut PointCloud<LidarPoint>> {
+    cloud
+        .as_mut()
+        .map(|c| &mut c.0)
+        .ok_or_else(|| anyhow!("cloud 가 NULL 입니다"))
+}
+
+fn into_handle(points: Vec<LidarPoint>) -> *mut LidarCloud {
+    Box::into_raw(Box::new(LidarCloud(PointCloud::new(
+        Default::default(),
+        points,
+    ))))
+}
+
+// 마지막 오류 메시지 (UTF-8, 호출한 스레드 기준, 없으면 빈 문자열)
+#[no_mangle]
+pub extern "C" fn lidar_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+// fields 를 따라 point_step 간격 버퍼를 파싱, field_count 가 0 이면 Livox 기본 레이아웃
+#[no_mangle]
+pub unsafe extern "C" fn lidar_parse(
+    data: *const u8,
+    len: usize,
+    point_step: u32,
+    fields: *const LidarField,
+    field_count: usize,
+    big_endian: bool,
+) -> *mut LidarCloud {
+    guard(ptr::null_mut(), || {
+        let data = bytes(data, len)?;
+        let fields: &[LidarField] = if field_count == 0 {
+            &[]
+        } else if fields.is_null() {
+            bail!("fields 가 NULL 입니다");
+        } else {
+            std::slice::from_raw_parts(fields, field_count)
+        };
+        let fields = fields
+            .iter()
+            .map(|f| Ok((string(f.name, "필드 이름")?, f.offset as usize, f.datatype)))
+            .collect::<Result<Vec<_>>>()?;
+        let offsets = FieldOffsets::from_descriptors(&fields)?;
+        let points = codec::decode(data, point_step as usize, &offsets, big_endian)?;
+        Ok(into_handle(points))
+    })
+}
+
+// 이름 있는 레이아웃 (livox_26, xyzi16, ...) 으로 패딩 없는 버퍼를 파싱
+#[no_mangle]
+pub unsafe extern "C" fn lidar_parse_layout(
+    data: *const u8,
+    len: usize,
+    layout_name: *const c_char,
+) -> *mut LidarCloud {
+    guard(ptr::null_mut(), || {
+        let data = bytes(data, len)?;
+        let layout = layout::lookup(string(layout_name, "layout_name")?)?;
+        let point_step = layout.builder().point_step();
+        let points = codec::decode(data, point_step, &layout.offsets()?, false)?;
+        Ok(into_handle(points))
+    })
+}
+
+// C 쪽 포인트 배열을 복사해서 클라우드 생성 (필터/직렬화 입력용)
+#[no_mangle]
+pub unsafe extern "C" fn lidar_cloud_from_points(
+    points: *const LidarPoint,
+    count: usize,
+) -> *mut LidarCloud {
+    guard(ptr::null_mut(), || {
+        if count == 0 {
+            return Ok(into_handle(Vec::new()));
+        }
+        if points.is_null() {
+            bail!("points 가 NULL 입니다");
+        }
+        Ok(into_handle(
+            std::slice::from_raw_parts(points, count).to_vec(),
+        ))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lidar_cloud_free(cloud: *mut LidarCloud) {
+    if !cloud.is_null() {
+        drop(Box::from_raw(cloud));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lidar_cloud_len(cloud: *const LidarCloud) -> usize {
+    cloud.as_ref().map_or(0, |c| c.0.len())
+}
+
+// 포인트 배열 (cloud 를 수정하거나 해제하기 전까지 유효)
+#[no_mangle]
+pub unsafe extern "C" fn lidar_cloud_points(cloud: *const LidarCloud) -> *const LidarPoint {
+    cloud.as_ref().map_or(ptr::null(), |c| c.0.points.as_ptr())
+}
+
+// 필터는 모두 제자리에서 포인트를 걸러냄, 성공 0 / 실패 -1
+#[no_mangle]
+pub unsafe extern "C" fn lidar_filter_range(
+    cloud: *mut LidarCloud,
+    min_range: f32,
+    max_range: f32,
+) -> c_int {
+    guard(-1, || {
+        filter::range(cloud_mut(cloud)?, min_range, max_range);
+        Ok(0)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lidar_filter_z_band(
+    cloud: *mut LidarCloud,
+    min_z: f32,
+    max_z: f32,
+) -> c_int {
+    guard(-1, || {
+        filter::z_band(cloud_mut(cloud)?, min_z, max_z);
+        Ok(0)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lidar_filter_crop_box(
+    cloud: *mut LidarCloud,
+    min: *const f32,
+    max: *const f32,
+) -> c_int {
+    guard(-1, || {
+        if min.is_null() || max.is_null() {
+            bail!("min/max 가 NULL 입니다");
+        }
+        let min = *(min as *const [f32; 3]);
+        let max = *(max as *const [f32; 3]);
+        filter::crop_box(cloud_mut(cloud)?, min, max);
+        Ok(0)
+    })
+}
+
+// 비/눈/안개 노이즈 제거 (aggressiveness 0..1)
+#[no_mangle]
+pub unsafe extern "C" fn lidar_filter_weather(
+    cloud: *mut LidarCloud,
+    aggressiveness: f32,
+) -> c_int {
+    guard(-1, || {
+        WeatherFilter::new(aggressiveness).apply(&mut cloud_mut(cloud)?.points);
+        Ok(0)
+    })
+}
+
+// 이름 있는 레이아웃으로 직렬화, out 은 lidar_buffer_free 로 해제
+#[no_mangle]
+pub unsafe extern "C" fn lidar_serialize(
+    cloud: *const LidarCloud,
+    layout_name: *const c_char,
+    out: *mut LidarBuffer,
+) -> c_int {
+    guard(-1, || {
+        let cloud = cloud
+            .as_ref()
+            .ok_or_else(|| anyhow!("cloud 가 NULL 입니다"))?;
+        let out = out.as_mut().ok_or_else(|| anyhow!("out 이 NULL 입니다"))?;
+        let layout = layout::lookup(string(layout_name, "layout_name")?)?;
+        let mut data = Vec::new();
+        codec::encode(&cloud.0.points, layout.fields, &mut data);
+        let data = Box::into_raw(data.into_boxed_slice());
+        *out = LidarBuffer {
+            len: data.len(),
+            data: data as *mut u8,
+            point_step: layout.builder().point_step() as u32,
+        };
+        Ok(0)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn lidar_buffer_free(buffer: *mut LidarBuffer) {
+    let Some(buffer) = buffer.as_mut() else {
+        return;
+    };
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+    buffer.data = ptr::null_mut();
+    buffer.len = 0;
+}
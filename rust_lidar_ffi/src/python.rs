@@ -0,0 +1,307 @@
+// 파이썬 모듈 rust_lidar: 오프라인 분석에서 numpy 로 다시 구현하지 않도록 파싱/필터/BEV/파일 입출력 노출
+// 클라우드는 필드 이름 -> numpy 배열 dict (x, y, z, intensity, tag, line, timestamp)
+use numpy::{IntoPyArray, PyReadonlyArray1};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use rust_lidar::bev::{self, Aggregation, BevGrid, CellAggregation, Origin};
+use rust_lidar::cloud::{PointCloud, PointXYZI};
+use rust_lidar::core::{self as codec, FieldOffsets, LidarPoint};
+use rust_lidar::weather::WeatherFilter;
+use rust_lidar::{filter, layout, map_file, pcd};
+use std::path::PathBuf;
+
+// anyhow 오류는 원인까지 한 줄로 합쳐 ValueError 로 (C FFI 의 마지막 오류와 같은 형식)
+fn value_error(e: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{:#}", e))
+}
+
+fn to_dict<'py>(py: Python<'py>, points: &[LidarPoint]) -> PyResult<Bound<'py, PyDict>> {
+    let column = |f: fn(&LidarPoint) -> f32| points.iter().map(f).collect::<Vec<_>>();
+    let dict = PyDict::new(py);
+    dict.set_item("x", column(|p| p.x).into_pyarray(py))?;
+    dict.set_item("y", column(|p| p.y).into_pyarray(py))?;
+    dict.set_item("z", column(|p| p.z).into_pyarray(py))?;
+    dict.set_item("intensity", column(|p| p.intensity).into_pyarray(py))?;
+    let tag: Vec<u8> = points.iter().map(|p| p.tag).collect();
+    let line: Vec<u8> = points.iter().map(|p| p.line).collect();
+    let timestamp: Vec<f64> = points.iter().map(|p| p.timestamp).collect();
+    dict.set_item("tag", tag.into_pyarray(py))?;
+    dict.set_item("line", line.into_pyarray(py))?;
+    dict.set_item("timestamp", timestamp.into_pyarray(py))?;
+    Ok(dict)
+}
+
+// x/y/z 는 필수, 나머지 열은 없으면 0 (길이가 다르면 오류)
+fn from_dict(cloud: &Bound<'_, PyDict>) -> PyResult<Vec<LidarPoint>> {
+    fn column<T: numpy::Element + Copy>(
+        cloud: &Bound<'_, PyDict>,
+        name: &str,
+        required: bool,
+    ) -> PyResult<Option<Vec<T>>> {
+        match cloud.get_item(name)? {
+            Some(value) => {
+                let array: PyReadonlyArray1<T> = value.extract()?;
+                Ok(Some(array.as_array().iter().copied().collect()))
+            }
+            None if required => Err(PyKeyError::new_err(format!("'{}' 열이 없습니다", name))),
+            None => Ok(None),
+        }
+    }
+
+    let x = column::<f32>(cloud, "x", true)?.unwrap_or_default();
+    let y = column::<f32>(cloud, "y", true)?.unwrap_or_default();
+    let z = column::<f32>(cloud, "z", true)?.unwrap_or_default();
+    let intensity = column::<f32>(cloud, "intensity", false)?;
+    let tag = column::<u8>(cloud, "tag", false)?;
+    let line = column::<u8>(cloud, "line", false)?;
+    let timestamp = column::<f64>(cloud, "timestamp", false)?;
+
+    let len = x.len();
+    let lengths = [
+        Some(y.len()),
+        Some(z.len()),
+        intensity.as_ref().map(Vec::len),
+        tag.as_ref().map(Vec::len),
+        line.as_ref().map(Vec::len),
+        timestamp.as_ref().map(Vec::len),
+    ];
+    if lengths.into_iter().flatten().any(|n| n != len) {
+        return Err(PyValueError::new_err(format!(
+            "열 길이가 서로 다릅니다 (x: {})",
+            len
+        )));
+    }
+
+    Ok((0..len)
+        .map(|i| LidarPoint {
+            x: x[i],
+            y: y[i],
+            z: z[i],
+            intensity: intensity.as_ref().map_or(0.0, |v| v[i]),
+            tag: tag.as_ref().map_or(0, |v| v[i]),
+            line: line.as_ref().map_or(0, |v| v[i]),
+            timestamp: timestamp.as_ref().map_or(0.0, |v| v[i]),
+        })
+        .collect())
+}
+
+// 포인트 dict 에 필터를 적용한 새 dict 반환 (입력은 그대로)
+fn filtered<'py>(
+    cloud: &Bound<'py, PyDict>,
+    f: impl FnOnce(&mut PointCloud<LidarPoint>),
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut points = PointCloud::new(Default::default(), from_dict(cloud)?);
+    f(&mut points);
+    to_dict(cloud.py(), &points.points)
+}
+
+// 이름 있는 레이아웃 (livox_26, xyzi16, ...) 으로 패딩 없는 버퍼를 파싱
+#[pyfunction]
+#[pyo3(signature = (data, layout="livox_26"))]
+fn parse<'py>(py: Python<'py>, data: &[u8], layout: &str) -> PyResult<Bound<'py, PyDict>> {
+    let layout = layout::lookup(layout).map_err(value_error)?;
+    let point_step = layout.builder().point_step();
+    let points = codec::decode(
+        data,
+        point_step,
+        &layout.offsets().map_err(value_error)?,
+        false,
+    )
+    .map_err(value_error)?;
+    to_dict(py, &points)
+}
+
+// (이름, offset, datatype) 목록을 따라 point_step 간격 버퍼를 파싱, 목록이 비어있으면 Livox 기본 레이아웃
+#[pyfunction]
+#[pyo3(signature = (data, point_step, fields, big_endian=false))]
+fn parse_fields<'py>(
+    py: Python<'py>,
+    data: &[u8],
+    point_step: usize,
+    fields: Vec<(String, usize, u8)>,
+    big_endian: bool,
+) -> PyResult<Bound<'py, PyDict>> {
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|(name, offset, dt)| (name.as_str(), *offset, *dt))
+        .collect();
+    let offsets = FieldOffsets::from_descriptors(&fields).map_err(value_error)?;
+    let points = codec::decode(data, point_step, &offsets, big_endian).map_err(value_error)?;
+    to_dict(py, &points)
+}
+
+#[pyfunction]
+#[pyo3(signature = (cloud, layout="livox_26"))]
+fn serialize<'py>(cloud: &Bound<'py, PyDict>, layout: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let layout = layout::lookup(layout).map_err(value_error)?;
+    let mut data = Vec::new();
+    codec::encode(&from_dict(cloud)?, layout.fields, &mut data);
+    Ok(PyBytes::new(cloud.py(), &data))
+}
+
+#[pyfunction]
+fn filter_range<'py>(
+    cloud: &Bound<'py, PyDict>,
+    min_range: f32,
+    max_range: f32,
+) -> PyResult<Bound<'py, PyDict>> {
+    filtered(cloud, |c| filter::range(c, min_range, max_range))
+}
+
+#[pyfunction]
+fn filter_z_band<'py>(
+    cloud: &Bound<'py, PyDict>,
+    min_z: f32,
+    max_z: f32,
+) -> PyResult<Bound<'py, PyDict>> {
+    filtered(cloud, |c| filter::z_band(c, min_z, max_z))
+}
+
+#[pyfunction]
+fn filter_crop_box<'py>(
+    cloud: &Bound<'py, PyDict>,
+    min: [f32; 3],
+    max: [f32; 3],
+) -> PyResult<Bound<'py, PyDict>> {
+    filtered(cloud, |c| filter::crop_box(c, min, max))
+}
+
+// 비/눈/안개 노이즈 제거 (aggressiveness 0..1)
+#[pyfunction]
+fn filter_weather<'py>(
+    cloud: &Bound<'py, PyDict>,
+    aggressiveness: f32,
+) -> PyResult<Bound<'py, PyDict>> {
+    filtered(cloud, |c| {
+        WeatherFilter::new(aggressiveness).apply(&mut c.points)
+    })
+}
+
+// bev_pub 과 같은 래스터화: 범위 밖 제거 후 셀마다 포인트 하나, "count" 열에 셀의 포인트 수
+#[pyfunction]
+#[pyo3(name = "bev", signature = (
+    cloud,
+    cell_size,
+    extent_x=0.0,
+    extent_y=0.0,
+    origin="center",
+    height_agg="max",
+    intensity_agg="mean",
+))]
+fn rasterize<'py>(
+    cloud: &Bound<'py, PyDict>,
+    cell_size: f32,
+    extent_x: f32,
+    extent_y: f32,
+    origin: &str,
+    height_agg: &str,
+    intensity_agg: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    if cell_size <= 0.0 || extent_x < 0.0 || extent_y < 0.0 {
+        return Err(PyValueError::new_err(
+            "cell_size 는 양수, extent_x / extent_y 는 음수일 수 없습니다",
+        ));
+    }
+    let grid = BevGrid {
+        cell_size,
+        extent_x,
+        extent_y,
+        origin: Origin::parse(origin).map_err(value_error)?,
+    };
+    let agg = CellAggregation {
+        height: Aggregation::parse(height_agg).map_err(value_error)?,
+        intensity: Aggregation::parse(intensity_agg).map_err(value_error)?,
+    };
+
+    let mut points = PointCloud::new(Default::default(), from_dict(cloud)?);
+    grid.apply(&mut points);
+    let cells = bev::collect_cells(&grid, &points.points);
+    let rasterized: Vec<_> = cells
+        .iter()
+        .map(|cell| bev::cell_point(&grid, cell, &agg))
+        .collect();
+    let counts: Vec<u32> = cells.iter().map(|cell| cell.count as u32).collect();
+
+    let dict = to_dict(cloud.py(), &rasterized)?;
+    dict.set_item("count", counts.into_pyarray(cloud.py()))?;
+    Ok(dict)
+}
+
+// 지도 파일 전체를 읽어 (헤더 dict, 포인트 dict) 반환
+#[pyfunction]
+fn load_map<'py>(
+    py: Python<'py>,
+    path: PathBuf,
+) -> PyResult<(Bound<'py, PyDict>, Bound<'py, PyDict>)> {
+    let (header, points) = map_file::load(&path).map_err(value_error)?;
+    let info = PyDict::new(py);
+    info.set_item("block_size", header.block_size)?;
+    info.set_item("resolution", header.resolution)?;
+    info.set_item("points", header.points)?;
+    info.set_item("blocks", header.blocks)?;
+    info.set_item("min", header.min)?;
+    info.set_item("max", header.max)?;
+    info.set_item("frame_id", header.frame_id)?;
+
+    let points: Vec<_> = points
+        .iter()
+        .map(|p| LidarPoint {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+            intensity: p.intensity,
+            ..Default::default()
+        })
+        .collect();
+    Ok((info, to_dict(py, &points)?))
+}
+
+// 포인트 dict 를 지도 파일로 저장 (tag/line/timestamp 는 저장하지 않음)
+#[pyfunction]
+#[pyo3(signature = (path, cloud, frame_id="map", block_size=10.0, resolution=0.001))]
+fn save_map(
+    path: PathBuf,
+    cloud: &Bound<'_, PyDict>,
+    frame_id: &str,
+    block_size: f32,
+    resolution: f32,
+) -> PyResult<()> {
+    let points: Vec<_> = from_dict(cloud)?
+        .iter()
+        .map(|p| PointXYZI {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+            intensity: p.intensity,
+        })
+        .collect();
+    map_file::save(&path, frame_id, &points, block_size, resolution).map_err(value_error)?;
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, cloud, layout="livox_26"))]
+fn write_pcd(path: PathBuf, cloud: &Bound<'_, PyDict>, layout: &str) -> PyResult<()> {
+    let layout = layout::lookup(layout).map_err(value_error)?;
+    let msg = layout.encode(&from_dict(cloud)?, Default::default());
+    pcd::write_pcd(&path, &msg).map_err(value_error)?;
+    Ok(())
+}
+
+#[pymodule]
+#[pyo3(name = "rust_lidar")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_range, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_z_band, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_crop_box, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_weather, m)?)?;
+    m.add_function(wrap_pyfunction!(rasterize, m)?)?;
+    m.add_function(wrap_pyfunction!(load_map, m)?)?;
+    m.add_function(wrap_pyfunction!(save_map, m)?)?;
+    m.add_function(wrap_pyfunction!(write_pcd, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+// counting-alloc 기능을 켜면 전역 할당자로 등록되어 할당 횟수/사용량을 셈
+pub struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllocSnapshot {
+    pub allocations: u64,
+    pub current_bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl AllocSnapshot {
+    // 이전 스냅샷 이후 할당 횟수
+    pub fn allocations_since(&self, earlier: &AllocSnapshot) -> u64 {
+        self.allocations.saturating_sub(earlier.allocations)
+    }
+}
+
+// counting-alloc 기능이 꺼져 있으면 None
+pub fn snapshot() -> Option<AllocSnapshot> {
+    if !cfg!(feature = "counting-alloc") {
+        return None;
+    }
+    Some(AllocSnapshot {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        current_bytes: CURRENT_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed),
+    })
+}
+
+// 노드 내부 버퍼(큐, 포인트 벡터, 출력 메시지) 사용량의 현재/최대값
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BufferStats {
+    pub current_bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl BufferStats {
+    pub fn update(&mut self, current_bytes: usize) {
+        self.current_bytes = current_bytes;
+        self.peak_bytes = self.peak_bytes.max(current_bytes);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::classify::{ClassRules, ObjectClass};
+use crate::core::LidarPoint;
+use crate::dataset::LabelBox;
+use crate::sidecar::json_string;
+use crate::tracking::Track;
+use std::collections::HashMap;
+
+// 오프라인 자동 라벨링: 녹화(bag 재생) 구간 전체의 트랙을 모은 뒤 트랙 단위로 상자를 정리
+// - 상자 맞춤: 프레임마다 트랙에 속한 포인트로 면적이 가장 작은 회전 상자 (1도 간격 탐색)
+// - 크기: 트랙 전체 프레임의 중앙값 (한쪽 면만 보이는 프레임에서도 같은 크기)
+// - 클래스: 프레임별 규칙 분류의 다수결
+// - 신뢰도: 다수결 비율 x 관측 프레임 수 x 프레임 포인트 수 (사람이 검토할 때 낮은 것부터 확인)
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoLabelConfig {
+    // 이 프레임 수 이상 추적된 트랙은 길이 점수 1
+    pub confident_frames: usize,
+    // 프레임에 이 포인트 수 이상이면 밀도 점수 1
+    pub confident_points: usize,
+    // 이보다 낮은 신뢰도의 상자는 버림
+    pub min_score: f32,
+    // 이보다 빠른 트랙은 진행 방향을 heading 으로 (m/s)
+    pub moving_speed: f32,
+}
+
+impl Default for AutoLabelConfig {
+    fn default() -> Self {
+        AutoLabelConfig {
+            confident_frames: 10,
+            confident_points: 50,
+            min_score: 0.1,
+            moving_speed: 0.5,
+        }
+    }
+}
+
+// 회전 상자 (size: 길이, 너비, 높이 / yaw: 길이 방향)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxFit {
+    pub center: [f32; 3],
+    pub size: [f32; 3],
+    pub yaw: f32,
+}
+
+// points: (x, y, 지면 위 높이)
+pub fn fit_box(points: &[[f32; 3]]) -> Option<BoxFit> {
+    if points.len() < 3 {
+        return None;
+    }
+    let (z_min, z_max) = points.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+        (lo.min(p[2]), hi.max(p[2]))
+    });
+    let mut best: Option<(f32, BoxFit)> = None;
+    for step in 0..90 {
+        let yaw = (step as f32).to_radians();
+        let (c, s) = (yaw.cos(), yaw.sin());
+        let (mut u_min, mut u_max, mut v_min, mut v_max) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+        for p in points {
+            let u = p[0] * c + p[1] * s;
+            let v = -p[0] * s + p[1] * c;
+            u_min = u_min.min(u);
+            u_max = u_max.max(u);
+            v_min = v_min.min(v);
+            v_max = v_max.max(v);
+        }
+        let area = (u_max - u_min) * (v_max - v_min);
+        if best.as_ref().is_some_and(|(a, _)| area >= *a) {
+            continue;
+        }
+        let (u, v) = ((u_min + u_max) / 2.0, (v_min + v_max) / 2.0);
+        let (du, dv) = (u_max - u_min, v_max - v_min);
+        let center = [u * c - v * s, u * s + v * c, (z_min + z_max) / 2.0];
+        // 긴 변을 길이로
+        let fit = if du >= dv {
+            BoxFit {
+                center,
+                size: [du, dv, z_max - z_min],
+                yaw,
+            }
+        } else {
+            BoxFit {
+                center,
+                size: [dv, du, z_max - z_min],
+                yaw: yaw + std::f32::consts::FRAC_PI_2,
+            }
+        };
+        best = Some((area, fit));
+    }
+    best.map(|(_, fit)| fit)
+}
+
+struct Observation {
+    track_id: u32,
+    class: ObjectClass,
+    fit: BoxFit,
+    velocity: [f32; 2],
+    points: usize,
+}
+
+struct Frame {
+    time: f64,
+    points: Vec<LidarPoint>,
+    observations: Vec<Observation>,
+}
+
+// 검토용 트랙 요약
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackSummary {
+    pub id: u32,
+    pub class: ObjectClass,
+    pub frames: usize,
+    pub size: [f32; 3],
+    pub score: f32,
+}
+
+pub struct LabeledFrame {
+    pub time: f64,
+    pub points: Vec<LidarPoint>,
+    pub boxes: Vec<LabelBox>,
+}
+
+pub struct AutoLabeler {
+    config: AutoLabelConfig,
+    frames: Vec<Frame>,
+}
+
+impl AutoLabeler {
+    pub fn new(config: AutoLabelConfig) -> Self {
+        AutoLabeler {
+            config,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // points: 저장할 프레임 포인트, above: 클러스터링에 쓴 지면 위 포인트 (x, y, 높이)
+    pub fn push(
+        &mut self,
+        time: f64,
+        points: Vec<LidarPoint>,
+        above: &[[f32; 3]],
+        tracks: &[Track],
+        rules: &ClassRules,
+    ) {
+        // 지면 위 포인트를 클러스터 범위가 겹치는 첫 트랙에 배정
+        let visible: Vec<&Track> = tracks.iter().filter(|t| t.missed == 0).collect();
+        let mut assigned: Vec<Vec<[f32; 3]>> = vec![Vec::new(); visible.len()];
+        for p in above {
+            let inside = visible.iter().position(|t| {
+                let c = &t.cluster;
+                (0..3).all(|i| p[i] >= c.min[i] && p[i] <= c.max[i])
+            });
+            if let Some(i) = inside {
+                assigned[i].push(*p);
+            }
+        }
+        let observations = visible
+            .iter()
+            .zip(&assigned)
+            .filter_map(|(track, points)| {
+                Some(Observation {
+                    track_id: track.id,
+                    class: rules.classify(track),
+                    fit: fit_box(points)?,
+                    velocity: track.velocity,
+                    points: points.len(),
+                })
+            })
+            .collect();
+        self.frames.push(Frame {
+            time,
+            points,
+            observations,
+        });
+    }
+
+    fn summaries(&self) -> HashMap<u32, TrackSummary> {
+        let mut votes: HashMap<u32, ([usize; 3], Vec<[f32; 3]>)> = HashMap::new();
+        for o in self.frames.iter().flat_map(|f| &f.observations) {
+            let entry = votes.entry(o.track_id).or_default();
+            entry.0[o.class.id() as usize] += 1;
+            entry.1.push(o.fit.size);
+        }
+        votes
+            .into_iter()
+            .map(|(id, (counts, sizes))| {
+                let frames = sizes.len();
+                let (best, count) = counts
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, c)| **c)
+                    .map(|(i, c)| (i, *c))
+                    .unwrap_or_default();
+                let class = [
+                    ObjectClass::Other,
+                    ObjectClass::Pedestrian,
+                    ObjectClass::Vehicle,
+                ][best];
+                let size = [0, 1, 2].map(|i| median(sizes.iter().map(|s| s[i]).collect()));
+                let length = (frames as f32 / self.config.confident_frames.max(1) as f32).min(1.0);
+                let score = count as f32 / frames as f32 * length;
+                (
+                    id,
+                    TrackSummary {
+                        id,
+                        class,
+                        frames,
+                        size,
+                        score,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    // 모은 프레임 전체로 라벨을 만들고 검토용 요약 (신뢰도 낮은 순) 과 함께 돌려줌
+    pub fn finish(self) -> (Vec<LabeledFrame>, Vec<TrackSummary>) {
+        let summaries = self.summaries();
+        let config = self.config;
+        let frames = self
+            .frames
+            .into_iter()
+            .map(|frame| {
+                let boxes = frame
+                    .observations
+                    .iter()
+                    .filter_map(|o| {
+                        let track = &summaries[&o.track_id];
+                        let density =
+                            (o.points as f32 / config.confident_points.max(1) as f32).min(1.0);
+                        let score = track.score * density;
+                        if score < config.min_score {
+                            return None;
+                        }
+                        let speed = o.velocity[0].hypot(o.velocity[1]);
+                        let yaw = if speed >= config.moving_speed {
+                            o.velocity[1].atan2(o.velocity[0])
+                        } else {
+                            o.fit.yaw
+                        };
+                        Some(LabelBox {
+                            class: track.class,
+                            track_id: o.track_id,
+                            // 지면에 닿는 상자 (중앙값 높이 기준)
+                            center: [o.fit.center[0], o.fit.center[1], track.size[2] / 2.0],
+                            size: track.size,
+                            yaw,
+                            velocity: o.velocity,
+                            points: o.points,
+                            score: Some(score),
+                        })
+                    })
+                    .collect();
+                LabeledFrame {
+                    time: frame.time,
+                    points: frame.points,
+                    boxes,
+                }
+            })
+            .collect();
+        let mut review: Vec<TrackSummary> = summaries.into_values().collect();
+        review.sort_by(|a, b| a.score.total_cmp(&b.score).then(a.id.cmp(&b.id)));
+        (frames, review)
+    }
+}
+
+fn median(mut values: Vec<f32>) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f32::total_cmp);
+    values[values.len() / 2]
+}
+
+// 검토 목록 JSON (신뢰도 낮은 트랙부터)
+pub fn review_json(review: &[TrackSummary]) -> String {
+    let tracks: Vec<String> = review
+        .iter()
+        .map(|t| {
+            format!(
+                "{{\"track_id\": {}, \"class\": {}, \"frames\": {}, \"size\": [{:.2}, {:.2}, {:.2}], \"score\": {:.4}}}",
+                t.id,
+                json_string(t.class.name()),
+                t.frames,
+                t.size[0],
+                t.size[1],
+                t.size[2],
+                t.score
+            )
+        })
+        .collect();
+    format!("[\n  {}\n]\n", tracks.join(",\n  "))
+}
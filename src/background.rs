@@ -0,0 +1,169 @@
+use crate::point::LidarPoint;
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// 고정 설치(교차로 감시 등)용 배경 모델: 처음 learn_frames 프레임 동안 복셀별 점유 빈도를 세고
+// min_ratio 이상 점유된 복셀을 배경으로 확정, 이후에는 배경 복셀(과 이웃) 밖 전경 포인트만 남김
+// 학습 중에는 모든 포인트를 그대로 통과시킴
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundConfig {
+    // 복셀 크기 (m)
+    pub voxel: f32,
+    // 배경 학습 프레임 수
+    pub learn_frames: u32,
+    // 학습 프레임 중 이 비율 이상 점유된 복셀을 배경으로 봄
+    pub min_ratio: f32,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        BackgroundConfig {
+            voxel: 0.2,
+            learn_frames: 100,
+            min_ratio: 0.3,
+        }
+    }
+}
+
+pub struct BackgroundModel {
+    config: BackgroundConfig,
+    // 학습 중 복셀별 점유 프레임 수
+    hits: HashMap<(i32, i32, i32), u32>,
+    frames: u32,
+    background: HashSet<(i32, i32, i32)>,
+}
+
+impl BackgroundModel {
+    pub fn new(config: BackgroundConfig) -> Self {
+        BackgroundModel {
+            config,
+            hits: HashMap::new(),
+            frames: 0,
+            background: HashSet::new(),
+        }
+    }
+
+    pub fn learning(&self) -> bool {
+        self.frames < self.config.learn_frames
+    }
+
+    // 학습 진행률 (0~1)
+    pub fn progress(&self) -> f32 {
+        if self.config.learn_frames == 0 {
+            return 1.0;
+        }
+        (self.frames as f32 / self.config.learn_frames as f32).min(1.0)
+    }
+
+    pub fn background_voxels(&self) -> usize {
+        self.background.len()
+    }
+
+    fn key(&self, p: &LidarPoint) -> (i32, i32, i32) {
+        let v = self.config.voxel;
+        (
+            (p.x / v).floor() as i32,
+            (p.y / v).floor() as i32,
+            (p.z / v).floor() as i32,
+        )
+    }
+
+    // 주변 27 복셀 중 하나라도 배경이면 배경 (복셀 경계의 측정 잡음 흡수)
+    fn is_background(&self, (x, y, z): (i32, i32, i32)) -> bool {
+        (-1..=1).any(|dx| {
+            (-1..=1)
+                .any(|dy| (-1..=1).any(|dz| self.background.contains(&(x + dx, y + dy, z + dz))))
+        })
+    }
+
+    fn learn(&mut self, points: &[LidarPoint]) {
+        let current: HashSet<_> = points.iter().map(|p| self.key(p)).collect();
+        for key in current {
+            *self.hits.entry(key).or_insert(0) += 1;
+        }
+        self.frames += 1;
+        if !self.learning() {
+            let min_hits = (self.config.min_ratio * self.frames as f32).ceil().max(1.0) as u32;
+            self.background = self
+                .hits
+                .drain()
+                .filter(|&(_, hits)| hits >= min_hits)
+                .map(|(key, _)| key)
+                .collect();
+        }
+    }
+
+    // 남길 (전경) 포인트면 true, 학습 중이면 이번 프레임을 학습하고 모두 true
+    pub fn mask(&mut self, points: &[LidarPoint]) -> Vec<bool> {
+        if self.learning() {
+            self.learn(points);
+            return vec![true; points.len()];
+        }
+        points
+            .iter()
+            .map(|p| !self.is_background(self.key(p)))
+            .collect()
+    }
+
+    pub fn apply(&mut self, points: &mut Vec<LidarPoint>) {
+        let keep = self.mask(points);
+        let mut keep = keep.into_iter();
+        points.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    // 확정된 배경을 텍스트로 저장 (첫 줄 복셀 크기, 이후 줄마다 복셀 인덱스 "ix iy iz")
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut text = format!("# voxel {}\n", self.config.voxel);
+        let mut keys: Vec<_> = self.background.iter().collect();
+        keys.sort();
+        for (x, y, z) in keys {
+            text.push_str(&format!("{} {} {}\n", x, y, z));
+        }
+        fs::write(path, text).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+
+    // 저장한 배경을 읽어 학습을 건너뜀, 복셀 크기가 다르면 오류
+    pub fn load(config: BackgroundConfig, path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        let mut model = BackgroundModel::new(config);
+        for (n, raw) in text.lines().enumerate() {
+            if let Some(voxel) = raw.trim().strip_prefix("# voxel ") {
+                let voxel: f32 = voxel.parse().with_context(|| {
+                    format!("{} 복셀 크기 '{}' 해석 실패", path.display(), voxel)
+                })?;
+                if (voxel - config.voxel).abs() > 1e-6 {
+                    bail!(
+                        "{} 의 복셀 크기 {} 가 background_voxel {} 와 다릅니다",
+                        path.display(),
+                        voxel,
+                        config.voxel
+                    );
+                }
+                continue;
+            }
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let index: Vec<i32> = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .with_context(|| format!("{} {}번째 줄: 정수가 아닌 값", path.display(), n + 1))?;
+            let [x, y, z] = index[..] else {
+                bail!(
+                    "{} {}번째 줄: 'ix iy iz' 형식이 아닙니다",
+                    path.display(),
+                    n + 1
+                );
+            };
+            model.background.insert((x, y, z));
+        }
+        model.frames = config.learn_frames;
+        Ok(model)
+    }
+}
@@ -0,0 +1,378 @@
+use crate::cloud::{Point, PointCloud};
+use crate::msg::PointCloud2;
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::passthrough;
+use crate::point::LidarPoint;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashMap;
+
+// 센서 원점이 BEV 영역의 어디에 놓이는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    // 영역 한가운데 (전방위)
+    Center,
+    // 뒤쪽 가장자리 가운데 (전방만 사용)
+    Rear,
+}
+
+impl Origin {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "center" => Ok(Origin::Center),
+            "rear" => Ok(Origin::Rear),
+            _ => bail!("알 수 없는 bev_origin '{}' (center, rear)", name),
+        }
+    }
+}
+
+// BEV 격자: 셀 크기 (m, 0 이면 좌표를 양자화하지 않음) 와 x/y 범위 (m, 0 이면 제한 없음)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BevGrid {
+    pub cell_size: f32,
+    pub extent_x: f32,
+    pub extent_y: f32,
+    pub origin: Origin,
+}
+
+impl BevGrid {
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        let grid = BevGrid {
+            cell_size: params::float(node, "bev_cell_size", 0.0)? as f32,
+            extent_x: params::float(node, "bev_extent_x", 0.0)? as f32,
+            extent_y: params::float(node, "bev_extent_y", 0.0)? as f32,
+            origin: Origin::parse(&params::string(node, "bev_origin", "center")?)?,
+        };
+        if grid.cell_size < 0.0 || grid.extent_x < 0.0 || grid.extent_y < 0.0 {
+            bail!("bev_cell_size / bev_extent_x / bev_extent_y 는 음수일 수 없습니다");
+        }
+        Ok(grid)
+    }
+
+    // (min, max) x/y 범위, 제한 없는 축은 무한대
+    pub fn bounds(&self) -> ([f32; 2], [f32; 2]) {
+        let half = |extent: f32| {
+            if extent > 0.0 {
+                extent / 2.0
+            } else {
+                f32::INFINITY
+            }
+        };
+        let (x_min, x_max) = match self.origin {
+            Origin::Center => (-half(self.extent_x), half(self.extent_x)),
+            Origin::Rear => (0.0, half(self.extent_x) * 2.0),
+        };
+        let y = half(self.extent_y);
+        ([x_min, -y], [x_max, y])
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (min, max) = self.bounds();
+        x >= min[0] && x < max[0] && y >= min[1] && y < max[1]
+    }
+
+    // 셀 인덱스 (센서 원점 기준 floor(x / cell_size)), cell_size 가 0 이면 None
+    pub fn cell_of(&self, x: f32, y: f32) -> Option<(i32, i32)> {
+        if self.cell_size <= 0.0 {
+            return None;
+        }
+        Some((
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        ))
+    }
+
+    pub fn cell_center(&self, (ix, iy): (i32, i32)) -> (f32, f32) {
+        (
+            (ix as f32 + 0.5) * self.cell_size,
+            (iy as f32 + 0.5) * self.cell_size,
+        )
+    }
+
+    // 격자 전체의 (행: x 셀 수, 열: y 셀 수, 첫 셀 인덱스), 범위가 없거나 셀 크기가 0 이면 None
+    pub fn dimensions(&self) -> Option<(usize, usize, (i32, i32))> {
+        if self.extent_x <= 0.0 || self.extent_y <= 0.0 {
+            return None;
+        }
+        let (min, max) = self.bounds();
+        let first = self.cell_of(min[0], min[1])?;
+        let end = (
+            (max[0] / self.cell_size).ceil() as i32,
+            (max[1] / self.cell_size).ceil() as i32,
+        );
+        Some((
+            (end.0 - first.0) as usize,
+            (end.1 - first.1) as usize,
+            first,
+        ))
+    }
+
+    // 셀당 포인트 하나인 목록을 격자 순서 (x 셀 행 우선) 로 배치할 위치
+    pub fn organize<P: Point>(&self, points: &[P]) -> Option<Organized> {
+        let (rows, cols, first) = self.dimensions()?;
+        let mut slots = vec![None; rows * cols];
+        for (i, p) in points.iter().enumerate() {
+            let [x, y, _] = p.xyz();
+            let Some((ix, iy)) = self.cell_of(x, y) else {
+                continue;
+            };
+            let (row, col) = ((ix - first.0) as usize, (iy - first.1) as usize);
+            if row < rows && col < cols {
+                slots[row * cols + col] = Some(i);
+            }
+        }
+        Some(Organized { rows, cols, slots })
+    }
+
+    // 범위 밖 포인트를 버리고 x/y 를 셀 중심으로 옮김
+    pub fn apply<P: Point>(&self, cloud: &mut PointCloud<P>) {
+        cloud.retain(|p| {
+            let [x, y, _] = p.xyz();
+            self.contains(x, y)
+        });
+        if self.cell_size <= 0.0 {
+            return;
+        }
+        for p in cloud.points.iter_mut() {
+            p.set_xyz(self.snap(p.xyz()));
+        }
+    }
+
+    // x/y 를 셀 중심으로 (cell_size 가 0 이면 그대로)
+    pub fn snap(&self, [x, y, z]: [f32; 3]) -> [f32; 3] {
+        match self.cell_of(x, y) {
+            Some(cell) => {
+                let (cx, cy) = self.cell_center(cell);
+                [cx, cy, z]
+            }
+            None => [x, y, z],
+        }
+    }
+
+    // 포인트가 min_points 개 이상인 셀의 포인트만 남길 마스크 (원거리 고립 노이즈 제거)
+    // cell_size 가 0 이거나 min_points 가 1 이하면 모두 남김
+    pub fn dense_mask<P: Point>(&self, points: &[P], min_points: usize) -> Vec<bool> {
+        if self.cell_size <= 0.0 || min_points <= 1 {
+            return vec![true; points.len()];
+        }
+        let cells: Vec<Option<(i32, i32)>> = points
+            .iter()
+            .map(|p| {
+                let [x, y, _] = p.xyz();
+                self.cell_of(x, y)
+            })
+            .collect();
+        let mut counts: HashMap<(i32, i32), usize> = HashMap::new();
+        for cell in cells.iter().flatten() {
+            *counts.entry(*cell).or_insert(0) += 1;
+        }
+        cells
+            .iter()
+            .map(|cell| cell.is_none_or(|cell| counts[&cell] >= min_points))
+            .collect()
+    }
+
+    // 패스스루 모드용 apply (원본 버퍼를 제자리에서 처리)
+    pub fn apply_msg(&self, msg: &mut PointCloud2) -> Result<()> {
+        let keep = passthrough::xyz_mask(msg, |[x, y, _]| self.contains(x as f32, y as f32))?;
+        passthrough::compact(msg, &keep);
+        if self.cell_size <= 0.0 {
+            return Ok(());
+        }
+        passthrough::map_xyz(msg, |[x, y, z]| match self.cell_of(x as f32, y as f32) {
+            Some(cell) => {
+                let (cx, cy) = self.cell_center(cell);
+                [cx as f64, cy as f64, z]
+            }
+            None => [x, y, z],
+        })
+    }
+}
+
+// 정렬된(height > 1) 출력 배치: slots[row * cols + col] = 그 셀의 포인트 인덱스 (빈 셀은 None)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Organized {
+    pub rows: usize,
+    pub cols: usize,
+    pub slots: Vec<Option<usize>>,
+}
+
+impl Organized {
+    // values 를 격자 순서로, 빈 셀은 empty
+    pub fn arrange<T: Copy>(&self, values: &[T], empty: T) -> Vec<T> {
+        self.slots
+            .iter()
+            .map(|slot| slot.and_then(|i| values.get(i).copied()).unwrap_or(empty))
+            .collect()
+    }
+}
+
+// 한 셀에 들어온 여러 포인트의 값을 합치는 방법 (채널별로 선택)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Max,
+    Min,
+    Mean,
+    // 셀의 포인트 개수
+    Count,
+    // 마지막으로 들어온 포인트 값
+    Last,
+}
+
+impl Aggregation {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "max" => Ok(Aggregation::Max),
+            "min" => Ok(Aggregation::Min),
+            "mean" => Ok(Aggregation::Mean),
+            "count" => Ok(Aggregation::Count),
+            "last" => Ok(Aggregation::Last),
+            _ => bail!(
+                "알 수 없는 집계 방식 '{}' (max, min, mean, count, last)",
+                name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Channel {
+    min: f32,
+    max: f32,
+    sum: f64,
+    last: f32,
+}
+
+impl Channel {
+    fn new(value: f32) -> Self {
+        Channel {
+            min: value,
+            max: value,
+            sum: value as f64,
+            last: value,
+        }
+    }
+
+    fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value as f64;
+        self.last = value;
+    }
+
+    fn get(&self, aggregation: Aggregation, count: usize) -> f32 {
+        match aggregation {
+            Aggregation::Max => self.max,
+            Aggregation::Min => self.min,
+            Aggregation::Mean => (self.sum / count as f64) as f32,
+            Aggregation::Count => count as f32,
+            Aggregation::Last => self.last,
+        }
+    }
+}
+
+// 셀 하나의 누적 상태
+#[derive(Debug, Clone, Copy)]
+pub struct Cell {
+    pub index: (i32, i32),
+    pub count: usize,
+    height: Channel,
+    intensity: Channel,
+    last: LidarPoint,
+}
+
+impl Cell {
+    // 다른 계산 경로(GPU, CUDA)에서 모은 채널별 [min, max], 합계로 셀 생성 (last 는 마지막 포인트)
+    #[cfg(any(feature = "gpu", feature = "cuda"))]
+    pub(crate) fn from_stats(
+        index: (i32, i32),
+        count: usize,
+        (height, height_sum): ([f32; 2], f64),
+        (intensity, intensity_sum): ([f32; 2], f64),
+        last: LidarPoint,
+    ) -> Self {
+        let channel = |[min, max]: [f32; 2], sum: f64, last: f32| Channel {
+            min,
+            max,
+            sum,
+            last,
+        };
+        Cell {
+            index,
+            count,
+            height: channel(height, height_sum, last.z),
+            intensity: channel(intensity, intensity_sum, last.intensity),
+            last,
+        }
+    }
+}
+
+// 높이(z)/intensity 채널별 집계 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellAggregation {
+    pub height: Aggregation,
+    pub intensity: Aggregation,
+}
+
+impl CellAggregation {
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        Ok(CellAggregation {
+            height: Aggregation::parse(&params::string(node, "bev_height_agg", "max")?)?,
+            intensity: Aggregation::parse(&params::string(node, "bev_intensity_agg", "mean")?)?,
+        })
+    }
+}
+
+// 포인트를 셀별로 모음 (처음 들어온 순서 유지)
+pub fn collect_cells(grid: &BevGrid, points: &[LidarPoint]) -> Vec<Cell> {
+    let mut index: HashMap<(i32, i32), usize> = HashMap::new();
+    let mut cells: Vec<Cell> = Vec::new();
+    for p in points {
+        let Some(key) = grid.cell_of(p.x, p.y) else {
+            continue;
+        };
+        match index.get(&key) {
+            Some(&i) => {
+                let cell = &mut cells[i];
+                cell.count += 1;
+                cell.height.add(p.z);
+                cell.intensity.add(p.intensity);
+                cell.last = *p;
+            }
+            None => {
+                index.insert(key, cells.len());
+                cells.push(Cell {
+                    index: key,
+                    count: 1,
+                    height: Channel::new(p.z),
+                    intensity: Channel::new(p.intensity),
+                    last: *p,
+                });
+            }
+        }
+    }
+    cells
+}
+
+// 셀마다 포인트 하나: x/y 는 셀 중심, z/intensity 는 집계 값, 나머지 필드는 마지막 포인트 값
+pub fn aggregate(grid: &BevGrid, points: &[LidarPoint], agg: &CellAggregation) -> Vec<LidarPoint> {
+    collect_cells(grid, points)
+        .iter()
+        .map(|cell| cell_point(grid, cell, agg))
+        .collect()
+}
+
+pub fn cell_point(grid: &BevGrid, cell: &Cell, agg: &CellAggregation) -> LidarPoint {
+    let (x, y) = grid.cell_center(cell.index);
+    LidarPoint {
+        x,
+        y,
+        z: cell.height.get(agg.height, cell.count),
+        intensity: cell.intensity.get(agg.intensity, cell.count),
+        ..cell.last
+    }
+}
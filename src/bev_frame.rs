@@ -0,0 +1,126 @@
+use crate::msg::PointCloud2;
+use crate::passthrough;
+use crate::point::parse_pointcloud2;
+use crate::stamp;
+use anyhow::{bail, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// livox/lidar_bev 출력의 셀 (포인트) 하나
+//   bev_mode=cells/density 면 x/y 는 셀 중심, height 는 집계한 높이, points 모드면 원본 높이 (z=0 출력이면 0)
+//   density: 셀에 들어온 원본 포인트 수 (bev_mode=density), radial_velocity: scene flow (m/s)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BevCell {
+    pub x: f32,
+    pub y: f32,
+    pub height: f32,
+    pub intensity: f32,
+    pub tag: u8,
+    pub line: u8,
+    pub density: Option<u32>,
+    pub radial_velocity: Option<f32>,
+}
+
+// BEV 출력의 타입 표현: 다른 Rust 노드가 PointCloud2 레이아웃을 직접 해석하지 않고 사용
+// 정렬된 출력 (bev_organized) 이면 행 우선 rows x cols 셀 (빈 셀은 None), 아니면 포인트마다 하나
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BevFrame {
+    pub frame_id: String,
+    // 초 (헤더 시각)
+    pub stamp: f64,
+    // 정렬된 출력이면 (행: x 셀 수, 열: y 셀 수)
+    pub shape: Option<(usize, usize)>,
+    pub cells: Vec<Option<BevCell>>,
+}
+
+impl BevFrame {
+    // 출력 레이아웃 (livox_26, xyzi16 등) 과 추가 필드 (density, radial_velocity) 는 fields 로 판단
+    pub fn from_msg(msg: &PointCloud2) -> Result<Self> {
+        let points = parse_pointcloud2(msg)?;
+        let density = passthrough::field_values(msg, "density")?;
+        let velocity = passthrough::field_values(msg, "radial_velocity")?;
+        let shape = (msg.height > 1).then_some((msg.height as usize, msg.width as usize));
+        if let Some((rows, cols)) = shape {
+            if rows * cols != points.len() {
+                bail!(
+                    "정렬된 BEV 출력의 크기 {}x{} 가 포인트 수 {} 와 다릅니다",
+                    rows,
+                    cols,
+                    points.len()
+                );
+            }
+        }
+        let cells = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                // 정렬된 출력의 빈 셀은 x/y/z 가 NaN
+                if !(p.x.is_finite() && p.y.is_finite()) {
+                    return None;
+                }
+                Some(BevCell {
+                    x: p.x,
+                    y: p.y,
+                    height: p.z,
+                    intensity: p.intensity,
+                    tag: p.tag,
+                    line: p.line,
+                    density: density.as_ref().map(|d| d[i] as u32),
+                    radial_velocity: velocity.as_ref().map(|v| v[i] as f32),
+                })
+            })
+            .collect();
+        Ok(BevFrame {
+            frame_id: msg.header.frame_id.clone(),
+            stamp: stamp::to_secs(&msg.header.stamp),
+            shape,
+            cells,
+        })
+    }
+
+    // 비어 있지 않은 셀
+    pub fn occupied(&self) -> impl Iterator<Item = &BevCell> {
+        self.cells.iter().flatten()
+    }
+
+    pub fn occupied_count(&self) -> usize {
+        self.occupied().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.occupied().next().is_none()
+    }
+
+    // 정렬된 출력의 (행, 열) 셀 (정렬되지 않았거나 범위 밖이면 None)
+    pub fn get(&self, row: usize, col: usize) -> Option<&BevCell> {
+        let (rows, cols) = self.shape?;
+        if row >= rows || col >= cols {
+            return None;
+        }
+        self.cells[row * cols + col].as_ref()
+    }
+
+    // 셀 인덱스 (floor(x / cell_size), floor(y / cell_size)) 로 찾는 표
+    // 출력 좌표계 변환 (output_convention) 을 거친 좌표 기준이므로 cell_size 는 bev_cell_size 와 같게
+    pub fn by_cell(&self, cell_size: f32) -> HashMap<(i32, i32), BevCell> {
+        self.occupied()
+            .map(|c| {
+                let index = (
+                    (c.x / cell_size).floor() as i32,
+                    (c.y / cell_size).floor() as i32,
+                );
+                (index, *c)
+            })
+            .collect()
+    }
+
+    pub fn max_height(&self) -> Option<f32> {
+        self.occupied()
+            .map(|c| c.height)
+            .filter(|h| h.is_finite())
+            .reduce(f32::max)
+    }
+}
@@ -0,0 +1,250 @@
+// livox/lidar 를 BEV 로 바꿔 발행하는 노드
+// 기능마다 설정 (XConfig::from_node), 프레임 사이 상태, 프레임별 단계를 자기 모듈에 두고
+// run 은 모듈을 만들어 잇기만 함 (처리 순서는 stages, 발행은 process, 작업 스레드는 worker)
+mod attention;
+mod background;
+mod blockage;
+mod config;
+mod frame;
+mod ground;
+mod input;
+mod motion;
+mod outputs;
+mod process;
+mod record;
+mod report;
+mod stages;
+mod state;
+mod visibility;
+mod worker;
+
+use self::attention::Attention;
+use self::background::Background;
+use self::blockage::Blockage;
+use self::config::BevConfig;
+use self::ground::Ground;
+use self::input::Input;
+use self::motion::Motion;
+use self::outputs::{LatestClouds, Outputs};
+use self::record::History;
+use self::report::Report;
+use self::state::BevState;
+use self::visibility::Visibility;
+use self::worker::Worker;
+use crate::cli::OutputOptions;
+use crate::compute;
+use crate::crash_dump::CrashRecorder;
+use crate::diagnostics::{self, Diagnostics};
+use crate::executor::Executor;
+use crate::load::LoadManager;
+use crate::params;
+use crate::pipeline::{CloudOutput, FrameQueue};
+use crate::qos::QosPreset;
+use crate::ros1::Ros1Bridge;
+use crate::scene_flow::SceneFlow;
+use crate::shutdown::Shutdown;
+use crate::snapshot::ConfigSnapshot;
+use crate::supervisor::Supervisor;
+use crate::temporal::{CellSmoother, DustFilter};
+use crate::transform::GravityAlign;
+use anyhow::{anyhow, Error};
+use rclrs::{Context, Node};
+use sensor_msgs::msg::PointCloud2;
+use std::sync::Arc;
+use std_msgs::msg::String as StringMsg;
+
+// supervisor 재시작 후 진단을 경고로 유지하는 시간 (초)
+const SUPERVISOR_WARN_HOLD: f64 = 10.0;
+
+// BEV 노드 본체: node 의 파라미터로 설정을 읽고 shutdown 이 요청될 때까지 spin
+// bev_pub 바이너리와 통합 시험 (harness::TestGraph) 이 같이 사용
+// 전송 설정 (QosPreset::apply_transport) 은 Context 를 만들기 전에 호출자가 적용
+pub fn run(
+    context: &Context,
+    node: Arc<Node>,
+    shutdown: Arc<Shutdown>,
+    mut output_options: OutputOptions,
+) -> Result<(), Error> {
+    let qos = QosPreset::parse(&output_options.qos)?;
+    let ros_args = std::mem::take(&mut output_options.ros_args);
+
+    let config = BevConfig::from_node(&node)?;
+    // ros1_topics 에 있는 토픽은 ROS 1 master 에도 발행
+    let ros1 = Ros1Bridge::from_node(&node)?;
+
+    // 파라미터 선언이 모두 끝난 뒤 실제 설정을 스냅샷으로 남김: 파일, 크래시 덤프, livox/config
+    let snapshot = ConfigSnapshot::new("lidar_bev_publisher", params::take_recorded(&node))
+        .with_filter_chain(&config.filter_chain());
+    config.record.write_snapshot(&snapshot);
+
+    // 최근 입력 프레임을 보관하다가 패닉 (crash_dump::install_panic_hook) 이나 요청 시 Ctrl-C 때
+    // 설정과 함께 저장
+    let recorder = CrashRecorder::new(
+        config.record.crash_dump_frames,
+        &config.record.crash_dump_dir,
+        &snapshot,
+    );
+    let dump_on_exit = recorder.enabled() && config.record.crash_dump_on_exit;
+
+    // BEV 포인트 클라우드 발행자 생성
+    let bev_publisher = node.create_publisher::<PointCloud2>("livox/lidar_bev", qos.profile())?;
+    let latest = config
+        .outputs
+        .latest_cloud_service
+        .then(LatestClouds::default);
+    let output = CloudOutput::new(bev_publisher, config.double_buffer)
+        .with_ros1(outputs::ros1_publisher(ros1.as_ref(), "livox/lidar_bev")?)
+        .with_latest(latest.as_ref().map(|latest| Arc::clone(&latest.processed)));
+    let latest_services = latest
+        .as_ref()
+        .map(|latest| latest.serve(&node))
+        .transpose()?;
+
+    // 기능별 처리 상태 (구독 콜백이 채우는 상태는 구독과 함께 만듦)
+    let state_store = config.record.state_store()?;
+    let (motion, motion_inputs) = Motion::subscribe(&node, &config.motion)?;
+    let (attention, attention_inputs) = Attention::subscribe(&node, &config.roi, &config.capture)?;
+    let gravity_ground = config.motion.gravity_align == GravityAlign::Ground;
+    let mut state = BevState {
+        motion,
+        attention,
+        ground: Ground::new(&node, &config.ground, gravity_ground)?,
+        dust: config.dust.map(DustFilter::new),
+        blockage: config.blockage.map(Blockage::new),
+        background: config
+            .background
+            .as_ref()
+            .map(|settings| Background::new(settings, state_store.as_ref()))
+            .transpose()?,
+        visibility: config
+            .visibility
+            .as_ref()
+            .map(|visibility| {
+                Visibility::new(&node, visibility, &config.grid, state_store.as_ref())
+            })
+            .transpose()?,
+        outputs: Outputs::new(&node, &config, qos.profile(), ros1.as_ref())?,
+        compute: compute::backend(&config.compute_backend)?,
+        load: config.load.map(LoadManager::new),
+        scene_flow: config.scene_flow.map(SceneFlow::new),
+        smoothing: config.smoothing.map(CellSmoother::new),
+    };
+    if let Some(store) = &state_store {
+        if let Err(e) = state.restore_state(store) {
+            eprintln!("상태 불러오기 오류: {}", e);
+        }
+    }
+
+    // 진단 값 시계열 (stats_history_file 이 있을 때만)
+    let history = History::new(&config.record);
+    let history_service = history
+        .as_ref()
+        .map(|history| history.serve(&node))
+        .transpose()?;
+    let supervisor = Arc::new(Supervisor::new(config.supervisor));
+
+    // 수신 콜백은 큐에 넣기만 하고 처리는 작업 스레드에서
+    // executor=threaded 면 입력 구독은 자기 스레드에서 spin 하는 별도 노드에
+    let queue = Arc::new(FrameQueue::new(
+        config.input.queue_depth,
+        config.input.backpressure,
+    ));
+    let mut executor = Executor::new(config.input.executor, &shutdown, &ros_args);
+    let mut input = Input::new(
+        executor.node_for(context, &node, "input")?,
+        qos.profile(),
+        &config.input,
+        &queue,
+        &supervisor,
+        latest.as_ref().map(|latest| Arc::clone(&latest.raw)),
+    );
+    let report = Report::new(
+        Diagnostics::new(&node, "lidar_bev_publisher")?,
+        Arc::clone(input.bytes()),
+        history.clone(),
+    );
+    let worker = Worker {
+        config,
+        state,
+        output,
+        queue: Arc::clone(&queue),
+        recorder: Arc::clone(&recorder),
+        supervisor: Arc::clone(&supervisor),
+        report,
+        state_store,
+        output_options,
+    }
+    .spawn();
+
+    input.subscribe()?;
+    let mut supervisor_diagnostics = Diagnostics::new(&node, "lidar_bev_supervisor")?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/lidar_bev");
+    if latest_services.is_some() {
+        println!("서비스: ~/get_latest_cloud, ~/get_latest_raw_cloud");
+    }
+    println!("BEV 변환 시작...");
+
+    // 실제 설정을 livox/config 에도 발행 (transient local 이라 나중에 붙은 rosbag 기록에도 들어감)
+    let config_publisher = node.create_publisher::<StringMsg>(
+        "livox/config",
+        rclrs::QOS_PROFILE_DEFAULT.keep_last(1).transient_local(),
+    )?;
+    config_publisher.publish(StringMsg {
+        data: snapshot.to_yaml(),
+    })?;
+
+    let spin_result = shutdown.spin_with(&node, || {
+        if let Some(event) = supervisor.check_stall(node.count_publishers("livox/lidar")?) {
+            eprintln!("{}", event.describe());
+            input.subscribe()?;
+        }
+        // 재시작 후 잠시 경고 상태를 유지 (진단은 1초에 한 번만 발행)
+        let (level, message) = match supervisor.recent_event(SUPERVISOR_WARN_HOLD) {
+            Some(event) => (diagnostics::WARN, event.describe()),
+            None => (diagnostics::OK, "정상".to_string()),
+        };
+        let values = [("restarts", supervisor.restarts().to_string())];
+        if let Err(e) = supervisor_diagnostics.publish(level, &message, &values) {
+            eprintln!("진단 정보 발행 오류: {}", e);
+        }
+        Ok(())
+    });
+
+    // Ctrl-C: 구독을 끊고 큐에 남은 프레임을 처리한 뒤 누적 통계 출력
+    // spin 이 오류로 끝나도 (구독 재생성 실패 등) 아래 정리를 모두 마친 뒤 오류를 돌려줌
+    println!("종료 중...");
+    if let Err(e) = &spin_result {
+        eprintln!("spin 오류: {:#}", e);
+        shutdown.request();
+    }
+    // spin 스레드 오류가 있어도 큐 정리, 작업 스레드 종료, 상태 저장은 계속
+    if let Err(e) = executor.join() {
+        eprintln!("{}", e);
+    }
+    drop(motion_inputs);
+    drop(attention_inputs);
+    drop(latest_services);
+    drop(history_service);
+    // 창이 다 차지 않은 부분 프레임도 처리
+    input.close();
+    let worker_result = worker
+        .join()
+        .map_err(|_| anyhow!("BEV 작업 스레드가 비정상 종료되었습니다"));
+    if dump_on_exit {
+        recorder.dump_and_report("sigint");
+    }
+    match &worker_result {
+        Ok(totals) => totals.print(queue.dropped()),
+        Err(e) => eprintln!("{}", e),
+    }
+    if let Some(history) = &history {
+        history.write();
+    }
+    // ROS 1 master 에서 등록 해제
+    drop(ros1);
+    spin_result.and(worker_result.map(drop))
+}
@@ -0,0 +1,101 @@
+use super::frame::StageFrame;
+use crate::capture::{CaptureConfig, EventCapture};
+use crate::roi::{RoiAttention, RoiConfig};
+use crate::stamp;
+use anyhow::Error;
+use geometry_msgs::msg::PolygonStamped;
+use rclrs::{Node, Subscription};
+use std::sync::{Arc, Mutex};
+use std_msgs::msg::Bool;
+
+// 토픽으로 바뀌는 해상도 조절: 관심 영역 밖 다운샘플 (roi_topic), 이벤트 고해상도 구간 (capture_trigger_topic)
+pub struct Attention {
+    // roi_topic 구독 콜백이 갱신하는 관심 영역
+    roi: Option<Arc<Mutex<RoiAttention>>>,
+    // capture_trigger_topic 구독 콜백이 켜는 고해상도 구간
+    capture: Option<Arc<Mutex<EventCapture>>>,
+}
+
+// Attention 을 갱신하는 구독 (노드 쪽에 남아 종료 때 끊음)
+pub struct AttentionInputs {
+    _roi: Option<Arc<Subscription<PolygonStamped>>>,
+    _capture: Option<Arc<Subscription<Bool>>>,
+}
+
+impl Attention {
+    pub fn subscribe(
+        node: &Node,
+        roi_config: &Option<(String, RoiConfig)>,
+        capture_config: &Option<(String, CaptureConfig)>,
+    ) -> Result<(Self, AttentionInputs), Error> {
+        // 관심 영역 (가장 최근 다각형 하나, 꼭짓점이 3개 미만이면 해제)
+        let roi = roi_config
+            .as_ref()
+            .map(|(_, config)| Arc::new(Mutex::new(RoiAttention::new(*config))));
+        let mut roi_subscriber = None;
+        if let (Some(roi), Some((topic, _))) = (&roi, roi_config) {
+            let roi = Arc::clone(roi);
+            roi_subscriber = Some(node.create_subscription::<PolygonStamped, _>(
+                topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: PolygonStamped| {
+                    roi.lock().unwrap().set_msg(&msg);
+                },
+            )?);
+        }
+        let capture = capture_config
+            .as_ref()
+            .map(|(_, config)| Arc::new(Mutex::new(EventCapture::new(*config))));
+        let mut capture_subscriber = None;
+        if let (Some(capture), Some((topic, _))) = (&capture, capture_config) {
+            let capture = Arc::clone(capture);
+            capture_subscriber = Some(node.create_subscription::<Bool, _>(
+                topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: Bool| {
+                    capture.lock().unwrap().trigger(msg.data);
+                },
+            )?);
+        }
+        let inputs = AttentionInputs {
+            _roi: roi_subscriber,
+            _capture: capture_subscriber,
+        };
+        Ok((Attention { roi, capture }, inputs))
+    }
+
+    // 관심 영역 밖 포인트를 크게 다운샘플 (장착 보정 후 좌표)
+    pub fn roi(&self, frame: &mut impl StageFrame) -> Result<(), Error> {
+        if let Some(roi) = &self.roi {
+            let cloud = frame.cloud()?;
+            let keep = roi
+                .lock()
+                .unwrap()
+                .mask(&cloud.points, stamp::to_secs(&cloud.header.stamp));
+            frame.keep(&keep);
+        }
+        Ok(())
+    }
+
+    // 고해상도 구간이 아니면 capture_voxel 로 다운샘플
+    pub fn capture(&self, frame: &mut impl StageFrame) -> Result<(), Error> {
+        if let Some(capture) = &self.capture {
+            let cloud = frame.cloud()?;
+            let keep = capture
+                .lock()
+                .unwrap()
+                .mask(&cloud.points, stamp::to_secs(&cloud.header.stamp));
+            frame.keep(&keep);
+        }
+        Ok(())
+    }
+
+    pub fn report(&self, values: &mut Vec<(&str, String)>) {
+        if let Some(capture) = &self.capture {
+            values.push((
+                "capture_full_resolution",
+                capture.lock().unwrap().capturing().to_string(),
+            ));
+        }
+    }
+}
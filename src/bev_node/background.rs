@@ -0,0 +1,112 @@
+use super::frame::StageFrame;
+use super::record;
+use crate::background::{BackgroundConfig, BackgroundModel};
+use crate::params;
+use crate::persist::StateStore;
+use anyhow::{bail, Error};
+use rclrs::Node;
+use std::path::Path;
+
+// state_dir 안 파일 이름
+const STATE_BACKGROUND: &str = "background.txt";
+
+// 고정 설치용 배경 학습 설정
+// file 이 있으면 시작할 때 읽어 학습을 건너뛰고, 없으면 학습을 마친 뒤 저장
+#[derive(Debug, Clone)]
+pub struct BackgroundSettings {
+    pub model: BackgroundConfig,
+    pub file: String,
+}
+
+impl BackgroundSettings {
+    // background_filter=false 면 None
+    pub fn from_node(node: &Node) -> Result<Option<Self>, Error> {
+        let file = params::string(node, "background_file", "")?;
+        if !params::boolean(node, "background_filter", false)? {
+            return Ok(None);
+        }
+        let defaults = BackgroundConfig::default();
+        let model = BackgroundConfig {
+            voxel: params::float(node, "background_voxel", defaults.voxel as f64)? as f32,
+            learn_frames: params::int(
+                node,
+                "background_learn_frames",
+                defaults.learn_frames as i64,
+            )?
+            .max(1) as u32,
+            min_ratio: params::float(node, "background_min_ratio", defaults.min_ratio as f64)?
+                as f32,
+        };
+        if model.voxel <= 0.0 {
+            bail!("background_voxel 은 0 보다 커야 합니다");
+        }
+        Ok(Some(BackgroundSettings { model, file }))
+    }
+}
+
+// 학습한 배경 복셀에 드는 포인트를 버리고 전경만 남김
+pub struct Background {
+    model: BackgroundModel,
+    file: String,
+}
+
+impl Background {
+    // 저장한 배경 파일이 있으면 학습 없이 바로 전경 추출
+    pub fn new(settings: &BackgroundSettings, store: Option<&StateStore>) -> Result<Self, Error> {
+        let model = match record::saved_state(store, &settings.file, STATE_BACKGROUND) {
+            Some(path) => {
+                let model = BackgroundModel::load(settings.model, &path)?;
+                println!(
+                    "배경 불러옴: {} (배경 복셀 {})",
+                    path.display(),
+                    model.background_voxels()
+                );
+                model
+            }
+            None => BackgroundModel::new(settings.model),
+        };
+        Ok(Background {
+            model,
+            file: settings.file.clone(),
+        })
+    }
+
+    // 전경만 남기고, 학습이 끝난 프레임에 background_file 저장
+    pub fn filter(&mut self, frame: &mut impl StageFrame) -> Result<(), Error> {
+        let was_learning = self.model.learning();
+        let keep = self.model.mask(&frame.cloud()?.points);
+        frame.keep(&keep);
+        if was_learning && !self.model.learning() {
+            println!(
+                "배경 학습 완료: 배경 복셀 {}",
+                self.model.background_voxels()
+            );
+            if !self.file.is_empty() {
+                match self.model.save(Path::new(&self.file)) {
+                    Ok(()) => println!("배경 저장: {}", self.file),
+                    Err(e) => eprintln!("배경 저장 오류: {}", e),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn report(&self, values: &mut Vec<(&str, String)>) {
+        values.push((
+            "background_progress",
+            format!("{:.2}", self.model.progress()),
+        ));
+        values.push((
+            "background_voxels",
+            self.model.background_voxels().to_string(),
+        ));
+    }
+
+    // 아직 학습 중이면 저장하지 않음
+    pub fn save(&self, store: &StateStore) -> Result<(), Error> {
+        if !self.model.learning() {
+            store.save(STATE_BACKGROUND, |path| self.model.save(path))?;
+        }
+        Ok(())
+    }
+}
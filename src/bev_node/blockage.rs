@@ -0,0 +1,82 @@
+use crate::blockage::{BlockageConfig, BlockageDetector};
+use crate::cli::Verbosity;
+use crate::diagnostics;
+use crate::persist::{self, StateStore};
+use crate::point::LidarPoint;
+use anyhow::Error;
+
+// state_dir 안 파일 이름
+const STATE_BLOCKAGE: &str = "blockage.txt";
+
+// 센서 가림/덮개 오염 감지 (파싱 직후 전체 포인트로 갱신)
+pub struct Blockage {
+    detector: BlockageDetector,
+    // 상태가 바뀔 때만 알리기 위한 직전 판정
+    last_blocked: bool,
+}
+
+impl Blockage {
+    pub fn new(config: BlockageConfig) -> Self {
+        Blockage {
+            detector: BlockageDetector::new(config),
+            last_blocked: false,
+        }
+    }
+
+    pub fn update(&mut self, points: &[LidarPoint]) {
+        self.detector.update(points);
+    }
+
+    // 가림이 감지되면 WARN 과 함께 구간 (방위각, 도) 을 메시지로 알림
+    pub fn report(
+        &mut self,
+        verbosity: Verbosity,
+        level: &mut u8,
+        message: &mut String,
+        values: &mut Vec<(&str, String)>,
+    ) {
+        let near = self.detector.describe(&self.detector.near_sectors());
+        let missing = self.detector.describe(&self.detector.missing_sectors());
+        let blocked = self.detector.blocked();
+        if blocked {
+            *level = diagnostics::WARN;
+            *message = format!(
+                "센서 가림 의심 (근거리 반사: [{}], 데이터 없음: [{}]), 센서 창을 확인하세요",
+                near, missing
+            );
+        }
+        if blocked != self.last_blocked && verbosity > Verbosity::Quiet {
+            if blocked {
+                println!("{}", message);
+            } else {
+                println!("센서 가림 해제");
+            }
+        }
+        self.last_blocked = blocked;
+        values.push(("blockage", blocked.to_string()));
+        values.push(("blockage_learning", self.detector.learning().to_string()));
+        values.push(("blockage_near_sectors", near));
+        values.push(("blockage_missing_sectors", missing));
+    }
+
+    pub fn save(&self, store: &StateStore) -> Result<(), Error> {
+        if let Some(baseline) = self.detector.baseline() {
+            store.save(STATE_BLOCKAGE, |path| persist::save_values(&baseline, path))?;
+        }
+        Ok(())
+    }
+
+    pub fn restore(&mut self, store: &StateStore) -> Result<(), Error> {
+        if let Some(path) = store.existing(STATE_BLOCKAGE) {
+            if self.detector.restore(&persist::load_values(&path)?) {
+                println!("가림 감지 기준 불러옴: {}", path.display());
+            } else {
+                eprintln!(
+                    "{} 의 구간 수가 blockage_sectors 와 달라 다시 학습합니다",
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
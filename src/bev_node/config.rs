@@ -0,0 +1,327 @@
+use super::background::BackgroundSettings;
+use super::frame::StageFrame;
+use super::ground::GroundConfig;
+use super::input::InputConfig;
+use super::motion::MotionConfig;
+use super::outputs::OutputConfig;
+use super::record::RecordConfig;
+use super::visibility::VisibilityConfig;
+use super::worker::WorkerConfig;
+use crate::bev::{BevGrid, CellAggregation};
+use crate::blockage::BlockageConfig;
+use crate::capture::CaptureConfig;
+use crate::deskew::OdomMode;
+use crate::exclusion::{ExclusionZones, ZoneFrame};
+use crate::frame_id::FrameNaming;
+use crate::grid_map::GridMapLayers;
+use crate::ground::Plane;
+use crate::intensity::{IntensityTable, ReflectanceModel};
+use crate::invalid::InvalidPolicy;
+use crate::layout::{self, NamedLayout};
+use crate::line_timing::LineTimingTable;
+use crate::load::LoadConfig;
+use crate::params;
+use crate::reflection::{ReflectionConfig, ReflectionMode};
+use crate::roi::RoiConfig;
+use crate::scene_flow::SceneFlowConfig;
+use crate::supervisor::SupervisorConfig;
+use crate::temporal::{CellSmoothing, DustConfig};
+use crate::transform::{Convention, GravityAlign, Transform};
+use crate::visual::VisualSettings;
+use crate::weather::WeatherFilter;
+use anyhow::{bail, Error};
+use rclrs::Node;
+use std_msgs::msg::Header;
+
+// Z축 필터링 범위
+const Z_MIN: f32 = -0.1;
+const Z_MAX: f32 = 0.2;
+
+// BEV 노드 파라미터 (기능별 설정은 각 모듈의 XConfig 로 묶음)
+pub struct BevConfig {
+    pub input_layout: Option<&'static NamedLayout>,
+    pub output_layout: &'static NamedLayout,
+    // true 면 원본 버퍼/필드를 유지한 채 포인트만 골라냄
+    pub passthrough: bool,
+    // NaN/Inf 좌표 포인트 처리 (drop, zero, keep), 파싱 직후 적용하고 개수는 진단에 보고
+    pub invalid_points: InvalidPolicy,
+    // true 면 출력 메시지 두 개를 번갈아 쓰며 별도 스레드에서 발행
+    pub double_buffer: bool,
+    pub input: InputConfig,
+    pub worker: WorkerConfig,
+    // 처리 지연이 load_budget_ms 에 가까우면 품질을 단계적으로 낮춤 (복셀 다운샘플, 지면 normal
+    // 재추정 생략, BEV 셀 확대), 여유가 생기면 되돌림 (load_budget_ms 가 0 이면 None)
+    pub load: Option<LoadConfig>,
+    // 라인별 포인트 timestamp 보정 표, 파싱 직후 적용하고 순서가 바뀌면 시각순으로 정렬 (없으면 None)
+    pub line_timing: Option<LineTimingTable>,
+    // 라인별 (거리 구간별) intensity 배율 표, 파싱 직후 장착 보정 전에 적용 (없으면 None)
+    pub intensity: Option<IntensityTable>,
+    // 기준 타깃으로 맞춘 반사율 모델 (calibrate_reflectance), intensity 를 실제 반사율 % 로 바꿈
+    pub reflectance: Option<ReflectanceModel>,
+    // 처리 전에 모든 포인트에 적용할 센서 장착 자세 보정
+    pub mount: Transform,
+    // 항상 제거할 고정 영역 (상자/다각형, 센서 또는 장착 보정 후 좌표계), 자세 보정 전에 적용
+    pub exclusion: Option<ExclusionZones>,
+    // roi_topic (geometry_msgs/PolygonStamped, 장착 보정 후 x/y) 으로 받은 관심 영역 밖은 크게 다운샘플
+    pub roi: Option<(String, RoiConfig)>,
+    // capture_trigger_topic (std_msgs/Bool) 이 있으면 평소에는 capture_voxel 로 다운샘플해서 내보내고
+    // true 를 받으면 capture_hold 초 동안 원래 해상도로 (false 는 바로 종료)
+    pub capture: Option<(String, CaptureConfig)>,
+    pub motion: MotionConfig,
+    // 비/눈/안개 노이즈 제거 강도 (weather_filter 0..1, 0 이면 끔)
+    pub weather: WeatherFilter,
+    // 시간적으로 지속되지 않는 낮은 intensity 포인트(먼지) 제거 (dust_filter=false 면 None)
+    pub dust: Option<DustConfig>,
+    // 센서 가림/덮개 오염 감지, 진단 정보로 알림 (blockage_detection=false 면 None)
+    pub blockage: Option<BlockageConfig>,
+    // 고정 설치용 배경 학습 후 전경 포인트만 남김 (background_filter=false 면 None)
+    pub background: Option<BackgroundSettings>,
+    // 유리/거울 반사 허상 처리 (off, remove, flag)
+    pub reflection: ReflectionMode,
+    pub reflection_config: ReflectionConfig,
+    pub ground: GroundConfig,
+    // 발행하는 포인트 클라우드의 좌표축 규약 (flu, frd/ned, optical), frame_id 에 접미사를 붙임
+    pub convention: Convention,
+    // 출력 frame_id 템플릿과 입력 frame_id 별 이름 바꾸기 (기본 {frame}_bev, {frame}_overhead)
+    pub frame_naming: FrameNaming,
+    // rgb 출력 포인트 색과 층 이미지 컬러맵 (visual_* 파라미터)
+    pub visual: VisualSettings,
+    // BEV 셀 크기, x/y 범위, 센서 원점 위치
+    pub grid: BevGrid,
+    // 차량 통과 높이 (나무, 천장, 문형 구조물 등 이보다 높은 포인트는 장애물에서 제외)
+    // z_reference 와 같은 기준, 파라미터 0 이면 사용 안 함
+    pub clearance_height: Option<f32>,
+    pub outputs: OutputConfig,
+    pub visibility: Option<VisibilityConfig>,
+    // bev_mode=cells 면 셀마다 포인트 하나로 합침 (z 는 집계한 높이, 모든 포인트 그대로면 None)
+    pub cells: Option<CellAggregation>,
+    // bev_mode=density: cells 와 같고 셀별 원본 포인트 수를 density 필드로 추가
+    pub density: bool,
+    // bev_mode=cells/density 출력을 격자 전체의 정렬된 클라우드로 (height: x 셀, width: y 셀, 빈 셀은 NaN)
+    pub organized: bool,
+    // 포인트가 이 개수 미만인 셀은 비어 있는 것으로 보고 버림 (1 이면 끔, bev_cell_size > 0 필요)
+    pub min_points_per_cell: usize,
+    // bev_mode=cells/density 셀 점유 시간 평활 (bev_smoothing=off 면 None)
+    // decay: bev_smoothing_alpha, bev_smoothing_threshold, persistence: 최근 n 프레임 중 m 프레임
+    pub smoothing: Option<CellSmoothing>,
+    // 실험적 scene flow: 연속 프레임 격자 상관으로 셀별 속도를 추정해 radial_velocity 필드 추가 (None 이면 끔)
+    pub scene_flow: Option<SceneFlowConfig>,
+    pub record: RecordConfig,
+    // 구독 멈춤/반복 패닉 감시와 자동 재시작 (supervisor_stall_timeout 0 이면 멈춤 감시 끔,
+    // supervisor_max_panics 0 이면 패닉을 잡지 않음)
+    pub supervisor: SupervisorConfig,
+    // 날씨 필터 이웃 검사와 BEV 셀 집계 계산 경로
+    // (cpu, gpu: gpu 기능의 wgpu, cuda: cuda 기능의 CUDA 커널, 실패 시 CPU)
+    pub compute_backend: String,
+}
+
+impl BevConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        let bev_mode = params::string(node, "bev_mode", "points")?;
+        // 입력/출력 포인트 레이아웃 (layout::LAYOUTS 참고)
+        let config = BevConfig {
+            input_layout: layout::input_layout(&params::string(node, "input_layout", "auto")?)?,
+            output_layout: layout::lookup(&params::string(node, "output_layout", "livox_26")?)?,
+            passthrough: params::boolean(node, "passthrough", false)?,
+            invalid_points: InvalidPolicy::parse(&params::string(node, "invalid_points", "drop")?)?,
+            double_buffer: params::boolean(node, "double_buffer", false)?,
+            input: InputConfig::from_node(node)?,
+            worker: WorkerConfig::from_node(node)?,
+            load: LoadConfig::from_node(node)?,
+            line_timing: LineTimingTable::from_node(node)?,
+            intensity: IntensityTable::from_node(node)?,
+            reflectance: ReflectanceModel::from_node(node)?,
+            mount: Transform::from_node(node)?,
+            exclusion: ExclusionZones::from_node(node)?,
+            roi: RoiConfig::from_node(node)?,
+            capture: CaptureConfig::from_node(node)?,
+            motion: MotionConfig::from_node(node)?,
+            weather: WeatherFilter::new(params::float(node, "weather_filter", 0.0)? as f32),
+            dust: if params::boolean(node, "dust_filter", false)? {
+                Some(DustConfig {
+                    voxel: params::float(node, "dust_voxel", 0.5)? as f32,
+                    history: params::int(node, "dust_history", 5)?.clamp(1, 8) as u32,
+                    min_frames: params::int(node, "dust_min_frames", 3)?.max(1) as u32,
+                    max_intensity: params::float(node, "dust_max_intensity", 20.0)? as f32,
+                })
+            } else {
+                None
+            },
+            blockage: BlockageConfig::from_node(node)?,
+            background: BackgroundSettings::from_node(node)?,
+            reflection: ReflectionMode::parse(&params::string(node, "reflection_filter", "off")?)?,
+            reflection_config: ReflectionConfig {
+                max_intensity: params::float(node, "reflection_max_intensity", 30.0)? as f32,
+                ..ReflectionConfig::default()
+            },
+            ground: GroundConfig::from_node(node)?,
+            convention: Convention::from_node(node)?,
+            frame_naming: FrameNaming::from_node(node)?,
+            visual: VisualSettings::from_node(node)?,
+            grid: BevGrid::from_node(node)?,
+            clearance_height: Some(params::float(node, "clearance_height", 0.0)? as f32)
+                .filter(|h| *h > 0.0),
+            outputs: OutputConfig::from_node(node)?,
+            visibility: VisibilityConfig::from_node(node)?,
+            cells: match bev_mode.as_str() {
+                "points" => None,
+                "cells" | "density" => Some(CellAggregation::from_node(node)?),
+                other => bail!("알 수 없는 bev_mode '{}' (points, cells, density)", other),
+            },
+            density: bev_mode == "density",
+            organized: params::boolean(node, "bev_organized", false)?,
+            min_points_per_cell: params::int(node, "min_points_per_cell", 1)?.max(1) as usize,
+            smoothing: CellSmoothing::parse(
+                &params::string(node, "bev_smoothing", "off")?,
+                params::float(node, "bev_smoothing_alpha", 0.5)? as f32,
+                params::float(node, "bev_smoothing_threshold", 0.5)? as f32,
+                params::int(node, "bev_smoothing_m", 2)?.max(0) as u32,
+                params::int(node, "bev_smoothing_n", 3)?.max(0) as u32,
+            )?,
+            scene_flow: if params::boolean(node, "scene_flow", false)? {
+                let defaults = SceneFlowConfig::default();
+                Some(SceneFlowConfig {
+                    cell: params::float(node, "scene_flow_cell", defaults.cell as f64)? as f32,
+                    max_speed: params::float(
+                        node,
+                        "scene_flow_max_speed",
+                        defaults.max_speed as f64,
+                    )? as f32,
+                    patch: params::int(node, "scene_flow_patch", defaults.patch as i64)?.max(0)
+                        as i32,
+                    ..defaults
+                })
+            } else {
+                None
+            },
+            record: RecordConfig::from_node(node)?,
+            supervisor: SupervisorConfig {
+                stall_timeout: params::float(node, "supervisor_stall_timeout", 5.0)?,
+                max_panics: params::int(node, "supervisor_max_panics", 3)?.max(0) as usize,
+                panic_window: params::float(node, "supervisor_panic_window", 60.0)?,
+            },
+            compute_backend: params::string(node, "compute_backend", "cpu")?,
+        };
+
+        if config.cells.is_some() {
+            if config.grid.cell_size <= 0.0 {
+                bail!("bev_mode={} 는 bev_cell_size > 0 이 필요합니다", bev_mode);
+            }
+            if config.passthrough {
+                bail!(
+                    "bev_mode={} 는 passthrough 와 함께 쓸 수 없습니다",
+                    bev_mode
+                );
+            }
+        }
+        if config.min_points_per_cell > 1 && config.grid.cell_size <= 0.0 {
+            bail!("min_points_per_cell 은 bev_cell_size > 0 이 필요합니다");
+        }
+        if config.smoothing.is_some() && config.cells.is_none() {
+            bail!("bev_smoothing 은 bev_mode=cells/density 가 필요합니다");
+        }
+        if config.organized && (config.cells.is_none() || config.grid.dimensions().is_none()) {
+            bail!("bev_organized 는 bev_mode=cells/density 와 bev_extent_x/y > 0 이 필요합니다");
+        }
+        if config.outputs.grid_map {
+            GridMapLayers::new(&config.grid)?;
+            if config.passthrough {
+                bail!("publish_grid_map 은 passthrough 와 함께 쓸 수 없습니다");
+            }
+        }
+        if config.input.partial_fraction > 0.0 && config.worker.scan_split > 1 {
+            bail!("partial_fraction 과 scan_split 은 함께 쓸 수 없습니다");
+        }
+        if let Some(flow) = &config.scene_flow {
+            if flow.cell <= 0.0 {
+                bail!("scene_flow_cell 은 0 보다 커야 합니다");
+            }
+            if config.passthrough {
+                bail!("scene_flow 는 passthrough 와 함께 쓸 수 없습니다");
+            }
+        }
+        Ok(config)
+    }
+
+    // 출력 종류 (bev, overhead) 별 frame_id
+    pub fn frame_id(&self, frame: &str, output: &str) -> String {
+        self.frame_naming
+            .name(frame, output, self.convention.frame_suffix())
+    }
+
+    // 좌표축 규약을 적용하지 않는 BEV 격자/이미지 출력의 헤더 (convention 접미사 없음)
+    pub fn grid_header(&self, header: &Header) -> Header {
+        let mut grid_header = header.clone();
+        grid_header.frame_id = self.frame_naming.name(&header.frame_id, "bev", "");
+        grid_header
+    }
+
+    // 켜진 처리 단계 (process_and_publish_bev 순서, 설정 스냅샷용)
+    pub fn filter_chain(&self) -> Vec<&'static str> {
+        let mut chain = vec!["parse"];
+        let mut stage = |enabled: bool, name: &'static str| {
+            if enabled {
+                chain.push(name);
+            }
+        };
+        stage(self.invalid_points != InvalidPolicy::Keep, "invalid_points");
+        stage(self.line_timing.is_some(), "line_timing");
+        stage(self.intensity.is_some(), "intensity");
+        stage(self.reflectance.is_some(), "reflectance");
+        stage(self.load.is_some(), "load");
+        let zone = |frame| self.exclusion.as_ref().is_some_and(|z| z.frame == frame);
+        stage(zone(ZoneFrame::Sensor), "exclusion_sensor");
+        stage(true, "mount");
+        stage(zone(ZoneFrame::Base), "exclusion_base");
+        stage(self.roi.is_some(), "roi");
+        stage(self.motion.odom_mode != OdomMode::Off, "deskew");
+        stage(self.weather.aggressiveness > 0.0, "weather");
+        stage(self.dust.is_some(), "dust");
+        stage(self.background.is_some(), "background");
+        stage(self.reflection != ReflectionMode::Off, "reflection");
+        stage(self.motion.gravity_align != GravityAlign::Off, "level");
+        stage(true, "z_band");
+        stage(self.capture.is_some(), "capture");
+        stage(self.scene_flow.is_some(), "scene_flow");
+        stage(
+            true,
+            if self.cells.is_some() {
+                "cells"
+            } else {
+                "flatten"
+            },
+        );
+        stage(self.min_points_per_cell > 1, "min_points_per_cell");
+        stage(self.smoothing.is_some(), "smoothing");
+        chain
+    }
+
+    // 해당 좌표계 단계의 고정 제외 영역 제거
+    pub fn exclude(&self, zone_frame: ZoneFrame, frame: &mut impl StageFrame) -> Result<(), Error> {
+        if let Some(zones) = self.exclusion.as_ref().filter(|z| z.frame == zone_frame) {
+            let keep = zones.mask(&frame.cloud()?.points);
+            frame.keep(&keep);
+        }
+        Ok(())
+    }
+
+    // 필터 기준 높이 (지면 추정이 있으면 지면 위 높이, 없으면 z)
+    pub fn height(&self, plane: Option<&Plane>, xyz: [f32; 3]) -> f32 {
+        plane.map_or(xyz[2], |plane| plane.distance(xyz))
+    }
+
+    // 장애물 높이 범위에 드는지, 통과 높이 위(overhead)는 제외
+    pub fn in_band(&self, plane: Option<&Plane>, xyz: [f32; 3]) -> bool {
+        let h = self.height(plane, xyz);
+        let (min, max) = match plane {
+            Some(_) => (self.ground.z_min, self.ground.z_max),
+            None => (Z_MIN, Z_MAX),
+        };
+        h >= min && h <= max && !self.is_overhead(plane, xyz)
+    }
+
+    pub fn is_overhead(&self, plane: Option<&Plane>, xyz: [f32; 3]) -> bool {
+        self.clearance_height
+            .is_some_and(|clearance| self.height(plane, xyz) > clearance)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::cloud::{Point, PointCloud};
+use crate::layout::{self, NamedLayout};
+use crate::passthrough;
+use crate::point::{datatype, LidarPoint};
+use anyhow::Error;
+use sensor_msgs::msg::PointCloud2;
+use std_msgs::msg::Header;
+
+// 단계가 다시 써야 하는 포인트 필드 (패스스루 모드에서 원본 버퍼에 반영할 필드)
+#[derive(Clone, Copy)]
+pub enum EditedField {
+    Timestamp,
+    Intensity,
+    Xyz,
+    Tag,
+}
+
+// 처리 단계가 다루는 프레임: 일반 모드는 디코드한 포인트, 패스스루 모드는 원본 바이트 버퍼
+// 단계는 마스크(keep)와 좌표 변환(map_xyz), 필드 수정 뒤 commit 으로만 프레임을 바꾸므로
+// filter_stages/bev_stages 의 정의 하나가 두 모드에 같이 쓰임
+pub trait StageFrame {
+    fn header(&self) -> &Header;
+    // 디코드한 포인트 (패스스루 모드는 처음 필요할 때 디코드)
+    fn cloud(&mut self) -> Result<&mut PointCloud<LidarPoint>, Error>;
+    fn xyz_mask(&self, pred: impl Fn([f32; 3]) -> bool) -> Result<Vec<bool>, Error>;
+    // false 인 포인트를 버림
+    fn keep(&mut self, keep: &[bool]);
+    fn map_xyz(&mut self, f: impl Fn([f32; 3]) -> [f32; 3]) -> Result<(), Error>;
+    // cloud() 로 고친 필드를 프레임에 반영
+    fn commit(&mut self, field: EditedField) -> Result<(), Error>;
+    // 포인트를 시간 순으로 정렬 (순서에 의존하는 단계용)
+    fn sort_by_time(&mut self);
+}
+
+impl StageFrame for PointCloud<LidarPoint> {
+    fn header(&self) -> &Header {
+        &self.header
+    }
+
+    fn cloud(&mut self) -> Result<&mut PointCloud<LidarPoint>, Error> {
+        Ok(self)
+    }
+
+    fn xyz_mask(&self, pred: impl Fn([f32; 3]) -> bool) -> Result<Vec<bool>, Error> {
+        Ok(self.iter().map(|p| pred(p.xyz())).collect())
+    }
+
+    fn keep(&mut self, keep: &[bool]) {
+        let mut keep = keep.iter();
+        self.retain(|_| keep.next().copied().unwrap_or(true));
+    }
+
+    fn map_xyz(&mut self, f: impl Fn([f32; 3]) -> [f32; 3]) -> Result<(), Error> {
+        for p in self.points.iter_mut() {
+            p.set_xyz(f(p.xyz()));
+        }
+        Ok(())
+    }
+
+    fn commit(&mut self, _field: EditedField) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn sort_by_time(&mut self) {
+        self.points
+            .sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+    }
+}
+
+// 패스스루 모드 프레임: 원본 버퍼를 마스크로 제자리 압축하고 고친 필드만 다시 씀 (알 수 없는 필드 보존)
+pub struct RawFrame {
+    pub msg: PointCloud2,
+    layout: Option<&'static NamedLayout>,
+    // 디코드한 포인트 (msg 와 같은 순서, 한 번 디코드하면 msg 와 함께 갱신)
+    pub decoded: Option<PointCloud<LidarPoint>>,
+}
+
+impl RawFrame {
+    pub fn new(msg: PointCloud2, layout: Option<&'static NamedLayout>) -> Self {
+        RawFrame {
+            msg,
+            layout,
+            decoded: None,
+        }
+    }
+}
+
+fn to_f32([x, y, z]: [f64; 3]) -> [f32; 3] {
+    [x as f32, y as f32, z as f32]
+}
+
+fn to_f64([x, y, z]: [f32; 3]) -> [f64; 3] {
+    [x as f64, y as f64, z as f64]
+}
+
+impl StageFrame for RawFrame {
+    fn header(&self) -> &Header {
+        &self.msg.header
+    }
+
+    fn cloud(&mut self) -> Result<&mut PointCloud<LidarPoint>, Error> {
+        match self.decoded {
+            Some(ref mut cloud) => Ok(cloud),
+            None => {
+                let points = layout::parse(&self.msg, self.layout)?;
+                Ok(self
+                    .decoded
+                    .insert(PointCloud::new(self.msg.header.clone(), points)))
+            }
+        }
+    }
+
+    fn xyz_mask(&self, pred: impl Fn([f32; 3]) -> bool) -> Result<Vec<bool>, Error> {
+        passthrough::xyz_mask(&self.msg, |p| pred(to_f32(p)))
+    }
+
+    fn keep(&mut self, keep: &[bool]) {
+        passthrough::compact(&mut self.msg, keep);
+        if let Some(cloud) = &mut self.decoded {
+            cloud.keep(keep);
+        }
+    }
+
+    fn map_xyz(&mut self, f: impl Fn([f32; 3]) -> [f32; 3]) -> Result<(), Error> {
+        passthrough::map_xyz(&mut self.msg, |p| to_f64(f(to_f32(p))))?;
+        if let Some(cloud) = &mut self.decoded {
+            cloud.map_xyz(f)?;
+        }
+        Ok(())
+    }
+
+    fn commit(&mut self, field: EditedField) -> Result<(), Error> {
+        let Some(cloud) = &self.decoded else {
+            return Ok(());
+        };
+        let points = &cloud.points;
+        self.msg.header = cloud.header.clone();
+        let has = |name: &str| self.msg.fields.iter().any(|f| f.name == name);
+        let (has_timestamp, has_tag) = (has("timestamp"), has("tag"));
+        match field {
+            EditedField::Xyz => {
+                let mut i = 0;
+                passthrough::map_xyz(&mut self.msg, |_| {
+                    let p = &points[i];
+                    i += 1;
+                    to_f64(p.xyz())
+                })?;
+            }
+            // timestamp 필드가 없는 입력은 프레임 시각을 쓰므로 쓸 곳이 없음
+            EditedField::Timestamp if has_timestamp => {
+                passthrough::set_field(&mut self.msg, "timestamp", |i| points[i].timestamp)?;
+            }
+            EditedField::Timestamp => {}
+            EditedField::Intensity => {
+                passthrough::set_field(&mut self.msg, "intensity", |i| points[i].intensity as f64)?;
+            }
+            EditedField::Tag if has_tag => {
+                passthrough::set_field(&mut self.msg, "tag", |i| points[i].tag as f64)?;
+            }
+            // tag 필드가 없는 입력에는 반사 표시를 담을 tag 필드를 덧붙임
+            EditedField::Tag => {
+                passthrough::append_field(&mut self.msg, "tag", datatype::UINT8, |i| {
+                    points[i].tag as f64
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    // 원본 바이트 순서를 그대로 두므로 정렬하지 않음
+    fn sort_by_time(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(n: usize) -> Vec<LidarPoint> {
+        (0..n)
+            .map(|i| LidarPoint {
+                x: i as f32,
+                y: 1.0,
+                intensity: 10.0 + i as f32,
+                ..LidarPoint::default()
+            })
+            .collect()
+    }
+
+    fn raw(n: usize) -> RawFrame {
+        RawFrame::new(
+            layout::XYZI16.encode(&points(n), Header::default()),
+            Some(&layout::XYZI16),
+        )
+    }
+
+    // 원본 버퍼를 다시 디코드한 결과가 프레임이 들고 있는 포인트와 같아야 함
+    fn assert_consistent(frame: &RawFrame) {
+        let reparsed = layout::parse(&frame.msg, Some(&layout::XYZI16)).unwrap();
+        let decoded = &frame.decoded.as_ref().unwrap().points;
+        assert_eq!(reparsed.len(), decoded.len());
+        for (a, b) in reparsed.iter().zip(decoded) {
+            assert_eq!(a.xyz(), b.xyz());
+            assert_eq!(a.intensity, b.intensity);
+        }
+    }
+
+    #[test]
+    fn raw_frame_keep_and_map_match_point_cloud() {
+        let mut cloud = PointCloud::new(Header::default(), points(4));
+        let mut frame = raw(4);
+        frame.cloud().unwrap();
+        let keep = [true, false, true, true];
+        let shift = |[x, y, z]: [f32; 3]| [x + 0.5, y, z - 1.0];
+        cloud.keep(&keep);
+        cloud.map_xyz(shift).unwrap();
+        frame.keep(&keep);
+        frame.map_xyz(shift).unwrap();
+        assert_consistent(&frame);
+        let xs: Vec<f32> = cloud.points.iter().map(|p| p.x).collect();
+        assert_eq!(xs, vec![0.5, 2.5, 3.5]);
+        assert_eq!(frame.msg.width, 3);
+    }
+
+    #[test]
+    fn raw_frame_commit_writes_edited_fields() {
+        let mut frame = raw(3);
+        for p in frame.cloud().unwrap().points.iter_mut() {
+            p.x *= 2.0;
+            p.intensity = 99.0;
+        }
+        frame.commit(EditedField::Xyz).unwrap();
+        frame.commit(EditedField::Intensity).unwrap();
+        assert_consistent(&frame);
+
+        // tag 필드가 없는 입력에는 tag 필드를 덧붙임
+        frame.cloud().unwrap().points[1].tag = 0x40;
+        frame.commit(EditedField::Tag).unwrap();
+        let tags: Vec<u8> = layout::parse(&frame.msg, None)
+            .unwrap()
+            .iter()
+            .map(|p| p.tag)
+            .collect();
+        assert_eq!(tags, vec![0, 0x40, 0]);
+    }
+
+    #[test]
+    fn raw_frame_does_not_decode_until_needed() {
+        let mut frame = raw(3);
+        let keep = frame.xyz_mask(|[x, _, _]| x > 0.5).unwrap();
+        frame.keep(&keep);
+        assert!(frame.decoded.is_none());
+        assert_eq!(frame.cloud().unwrap().len(), 2);
+    }
+}
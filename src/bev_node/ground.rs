@@ -0,0 +1,194 @@
+use crate::cloud::{Point, PointCloud};
+use crate::ground::{GroundEstimator, GroundFit, GroundFitConfig, Plane};
+use crate::params;
+use crate::persist::{self, StateStore};
+use crate::pose::Pose;
+use anyhow::{bail, Error};
+use geometry_msgs::msg::PoseWithCovarianceStamped;
+use rclrs::{Node, Publisher};
+use std::sync::Arc;
+use std_msgs::msg::Header;
+
+// state_dir 안 파일 이름
+const STATE_GROUND: &str = "ground.txt";
+
+// 추정할 수 없는 축(x, y, yaw)의 분산
+const UNKNOWN_VARIANCE: f64 = 1e6;
+
+// 지면 추정과 지면 기준 높이 필터 설정
+#[derive(Debug, Clone, Copy)]
+pub struct GroundConfig {
+    // z_reference=ground 면 센서 z 대신 추정한 지면 위 높이 [z_min, z_max] 로 거름
+    pub reference: bool,
+    pub z_min: f32,
+    pub z_max: f32,
+    pub fit: GroundFitConfig,
+    pub alpha: f32,
+    // 지면 normal 로 추정한 roll/pitch/높이 (공분산 포함) 발행, IMU 장착 확인용
+    pub publish_attitude: bool,
+}
+
+impl GroundConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        Ok(GroundConfig {
+            reference: match params::string(node, "z_reference", "sensor")?.as_str() {
+                "sensor" => false,
+                "ground" => true,
+                other => bail!("알 수 없는 z_reference '{}' (sensor, ground)", other),
+            },
+            z_min: params::float(node, "ground_z_min", 0.2)? as f32,
+            z_max: params::float(node, "ground_z_max", 2.0)? as f32,
+            fit: GroundFitConfig {
+                threshold: params::float(node, "ground_threshold", 0.05)? as f32,
+                max_tilt: (params::float(node, "ground_max_tilt_deg", 15.0)? as f32).to_radians(),
+                ..GroundFitConfig::default()
+            },
+            alpha: params::float(node, "ground_alpha", 0.3)? as f32,
+            publish_attitude: params::boolean(node, "publish_ground_attitude", false)?,
+        })
+    }
+}
+
+// 지면 추정 상태 (지면 기준 높이, gravity_align=ground, 지면 자세 발행 중 하나라도 쓰면)
+pub struct Ground {
+    config: GroundConfig,
+    estimator: Option<GroundEstimator>,
+    // 지면 기준 roll/pitch (geometry_msgs/PoseWithCovarianceStamped)
+    attitude_publisher: Option<Arc<Publisher<PoseWithCovarianceStamped>>>,
+    // 마지막으로 처리에 성공한 프레임의 지면과 추정 품질 (진단용)
+    last_plane: Option<Plane>,
+    last_attitude: Option<(Plane, GroundFit)>,
+}
+
+impl Ground {
+    pub fn new(node: &Node, config: &GroundConfig, gravity_align: bool) -> Result<Self, Error> {
+        let attitude_publisher = if config.publish_attitude {
+            Some(node.create_publisher::<PoseWithCovarianceStamped>(
+                "livox/lidar_bev/ground_attitude",
+                rclrs::QOS_PROFILE_DEFAULT,
+            )?)
+        } else {
+            None
+        };
+        Ok(Ground {
+            config: *config,
+            estimator: (config.reference || gravity_align || config.publish_attitude)
+                .then(|| GroundEstimator::new(config.fit, config.alpha)),
+            attitude_publisher,
+            last_plane: None,
+            last_attitude: None,
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.estimator.is_some()
+    }
+
+    // 이번 프레임의 지면과 추정 품질, skip_normals 면 normal 을 다시 추정하지 않고 직전 평면 사용
+    pub fn update<P: Point>(
+        &mut self,
+        cloud: &PointCloud<P>,
+        skip_normals: bool,
+    ) -> (Option<Plane>, Option<(Plane, GroundFit)>) {
+        let Some(estimator) = &mut self.estimator else {
+            return (None, None);
+        };
+        if skip_normals {
+            return (estimator.plane(), None);
+        }
+        let plane = estimator.update(cloud);
+        (plane, plane.zip(estimator.last_fit()))
+    }
+
+    // 처리에 성공한 프레임의 지면 (ground: 지면 기준 모드의 지면, attitude: 정렬 전 지면과 품질)
+    // 지면 자세를 발행하고 진단용으로 보관
+    pub fn finish_frame(
+        &mut self,
+        header: &Header,
+        ground: Option<Plane>,
+        attitude: Option<(Plane, GroundFit)>,
+    ) {
+        if let (Some(publisher), Some((plane, fit))) = (&self.attitude_publisher, &attitude) {
+            if let Err(e) = publisher.publish(ground_attitude_msg(header, plane, fit)) {
+                eprintln!("지면 자세 발행 오류: {}", e);
+            }
+        }
+        self.last_plane = ground;
+        self.last_attitude = attitude;
+    }
+
+    pub fn report(&self, values: &mut Vec<(&str, String)>) {
+        if let Some(plane) = &self.last_plane {
+            values.push(("ground_height", format!("{:.3}", plane.height())));
+            values.push((
+                "ground_tilt_deg",
+                format!("{:.2}", plane.tilt().to_degrees()),
+            ));
+        }
+        if let Some((plane, fit)) = &self.last_attitude {
+            let (roll, pitch) = plane.roll_pitch();
+            values.push(("ground_roll_deg", format!("{:.2}", roll.to_degrees())));
+            values.push(("ground_pitch_deg", format!("{:.2}", pitch.to_degrees())));
+            values.push((
+                "ground_angle_std_deg",
+                format!("{:.3}", fit.angle_std.to_degrees()),
+            ));
+            values.push(("ground_inlier_ratio", format!("{:.2}", fit.inlier_ratio)));
+        }
+    }
+
+    // supervisor 재시작: 추정한 지면을 버리고 새로 추정
+    pub fn reset(&mut self) {
+        if self.estimator.is_some() {
+            self.estimator = Some(GroundEstimator::new(self.config.fit, self.config.alpha));
+        }
+    }
+
+    pub fn save(&self, store: &StateStore) -> Result<(), Error> {
+        if let Some(plane) = self.estimator.as_ref().and_then(GroundEstimator::plane) {
+            store.save(STATE_GROUND, |path| persist::save_plane(&plane, path))?;
+        }
+        Ok(())
+    }
+
+    pub fn restore(&mut self, store: &StateStore) -> Result<(), Error> {
+        if let (Some(estimator), Some(path)) = (&mut self.estimator, store.existing(STATE_GROUND)) {
+            let plane = persist::load_plane(&path)?;
+            estimator.restore(plane);
+            println!(
+                "지면 불러옴: {} (높이 {:.3} m)",
+                path.display(),
+                plane.height()
+            );
+        }
+        Ok(())
+    }
+}
+
+// 지면 normal 로 추정한 장착 보정 후 좌표계의 roll/pitch 와 지면 위 높이
+fn ground_attitude_msg(
+    header: &Header,
+    plane: &Plane,
+    fit: &GroundFit,
+) -> PoseWithCovarianceStamped {
+    let (roll, pitch) = plane.roll_pitch();
+    let mut msg = PoseWithCovarianceStamped {
+        header: header.clone(),
+        ..Default::default()
+    };
+    msg.pose.pose = Pose::from_rpy([0.0, 0.0, plane.height() as f64], roll, pitch, 0.0).to_msg();
+    let angle_var = (fit.angle_std as f64).powi(2);
+    let height_var = (fit.rmse as f64).powi(2) / fit.inliers.max(1) as f64;
+    let variances = [
+        UNKNOWN_VARIANCE,
+        UNKNOWN_VARIANCE,
+        height_var,
+        angle_var,
+        angle_var,
+        UNKNOWN_VARIANCE,
+    ];
+    for (i, var) in variances.into_iter().enumerate() {
+        msg.pose.covariance[i * 7] = var;
+    }
+    msg
+}
@@ -0,0 +1,142 @@
+use crate::executor::ExecutorModel;
+use crate::params;
+use crate::passthrough;
+use crate::pipeline::{Backpressure, FrameQueue, LatestFrame};
+use crate::split::PartialAssembler;
+use crate::stats;
+use crate::supervisor::Supervisor;
+use anyhow::{bail, Error};
+use rclrs::{Node, QoSProfile, Subscription};
+use sensor_msgs::msg::PointCloud2;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+// livox/lidar 수신 설정
+#[derive(Debug, Clone, Copy)]
+pub struct InputConfig {
+    // 처리 지연 시 동작 (drop_oldest, drop_newest, block) 과 대기 큐 길이
+    pub backpressure: Backpressure,
+    pub queue_depth: usize,
+    // 드라이버가 프레임을 패킷으로 나눠 보낼 때 포인트 timestamp 가 frame_period x partial_fraction
+    // 만큼 쌓이면 프레임 전체를 기다리지 않고 바로 처리 (0 이면 끔)
+    pub partial_fraction: f64,
+    pub frame_period: f64,
+    // 콜백 실행 방식 (single, threaded: livox/lidar 구독을 lidar_bev_publisher_input 노드로 옮겨 따로 spin)
+    pub executor: ExecutorModel,
+}
+
+impl InputConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        let config = InputConfig {
+            backpressure: Backpressure::parse(&params::string(
+                node,
+                "backpressure",
+                "drop_oldest",
+            )?)?,
+            queue_depth: params::int(node, "queue_depth", 2)?.max(1) as usize,
+            partial_fraction: params::float(node, "partial_fraction", 0.0)?.clamp(0.0, 1.0),
+            frame_period: params::float(node, "frame_period", 0.1)?,
+            executor: ExecutorModel::from_node(node)?,
+        };
+        // single 에서는 수신 콜백이 막히면 같은 스레드의 감시, 서비스, 진단도 함께 멈춤
+        if config.backpressure == Backpressure::Block && config.executor != ExecutorModel::Threaded
+        {
+            bail!("backpressure=block 은 executor=threaded 가 필요합니다");
+        }
+        Ok(config)
+    }
+}
+
+// 원본 LiDAR 구독: 수신 콜백은 큐에 넣기만 하고 처리는 작업 스레드에서
+// partial_fraction 이 있으면 수신 콜백에서 바로 부분 프레임으로 묶어 큐에 넣음
+pub struct Input {
+    node: Arc<Node>,
+    qos: QoSProfile,
+    queue: Arc<FrameQueue<PointCloud2>>,
+    supervisor: Arc<Supervisor>,
+    partial: Option<Arc<Mutex<PartialAssembler>>>,
+    latest: Option<Arc<LatestFrame>>,
+    // 수신한 입력 메시지 직렬화 크기 합 (큐에서 버린 프레임 포함)
+    bytes: Arc<AtomicU64>,
+    subscription: Option<Arc<Subscription<PointCloud2>>>,
+}
+
+impl Input {
+    // node: 구독을 만들 노드 (executor=threaded 면 자기 스레드에서 spin 하는 별도 노드)
+    pub fn new(
+        node: Arc<Node>,
+        qos: QoSProfile,
+        config: &InputConfig,
+        queue: &Arc<FrameQueue<PointCloud2>>,
+        supervisor: &Arc<Supervisor>,
+        latest: Option<Arc<LatestFrame>>,
+    ) -> Self {
+        let partial = (config.partial_fraction > 0.0).then(|| {
+            Arc::new(Mutex::new(PartialAssembler::new(
+                config.frame_period,
+                config.partial_fraction,
+            )))
+        });
+        Input {
+            node,
+            qos,
+            queue: Arc::clone(queue),
+            supervisor: Arc::clone(supervisor),
+            partial,
+            latest,
+            bytes: Arc::new(AtomicU64::new(0)),
+            subscription: None,
+        }
+    }
+
+    pub fn bytes(&self) -> &Arc<AtomicU64> {
+        &self.bytes
+    }
+
+    // 구독 생성 (구독이 멈추면 supervisor 판단에 따라 다시 만듦)
+    pub fn subscribe(&mut self) -> Result<(), Error> {
+        let queue = Arc::clone(&self.queue);
+        let supervisor = Arc::clone(&self.supervisor);
+        let partial = self.partial.clone();
+        let latest = self.latest.clone();
+        let bytes = Arc::clone(&self.bytes);
+        self.subscription = Some(self.node.create_subscription::<PointCloud2, _>(
+            "livox/lidar",
+            self.qos,
+            move |mut msg: PointCloud2| {
+                supervisor.alive();
+                bytes.fetch_add(stats::serialized_size(&msg) as u64, Ordering::Relaxed);
+                // 정렬된 입력의 행 끝 패딩을 먼저 제거 (이후 단계는 포인트가 연속이라고 가정)
+                if let Err(e) = passthrough::pack_rows(&mut msg) {
+                    eprintln!("PointCloud2 행 정리 실패: {}", e);
+                    return;
+                }
+                if let Some(latest) = &latest {
+                    latest.store(&msg);
+                }
+                let Some(partial) = &partial else {
+                    queue.push(msg);
+                    return;
+                };
+                match partial.lock().unwrap().push(msg) {
+                    Ok(parts) => parts.into_iter().for_each(|part| queue.push(part)),
+                    Err(e) => eprintln!("부분 프레임 묶기 오류: {}", e),
+                }
+            },
+        )?);
+        Ok(())
+    }
+
+    // 구독을 끊고 창이 다 차지 않은 부분 프레임까지 넣은 뒤 큐를 닫음 (작업 스레드가 남은 프레임 처리)
+    pub fn close(mut self) {
+        self.subscription = None;
+        if let Some(part) = self
+            .partial
+            .as_ref()
+            .and_then(|p| p.lock().unwrap().flush())
+        {
+            self.queue.push(part);
+        }
+        self.queue.close();
+    }
+}
@@ -0,0 +1,175 @@
+use super::frame::{EditedField, StageFrame};
+use crate::deskew::{self, OdomMode, PoseBuffer};
+use crate::ground::Plane;
+use crate::imu::{self, ImuSample, ImuTracker};
+use crate::params;
+use crate::stamp;
+use crate::transform::{GravityAlign, Transform};
+use anyhow::{bail, Error};
+use nav_msgs::msg::Odometry;
+use rclrs::{Node, Subscription};
+use sensor_msgs::msg::Imu;
+use std::sync::{Arc, Mutex};
+use std_msgs::msg::Header;
+
+// ego motion 왜곡 보정과 중력 방향 정렬 설정
+#[derive(Debug, Clone)]
+pub struct MotionConfig {
+    // 오도메트리 자세 보간으로 ego motion 왜곡 보정 (off, deskew, deskew_to_odom, imu)
+    // 오도메트리 child frame / IMU 좌표계는 장착 보정 후 좌표계(차량 기준)와 같아야 함
+    pub odom_mode: OdomMode,
+    pub odom_topic: String,
+    // odom_mode=imu / gravity_align=imu: IMU 토픽, 정지 초기화 샘플 수,
+    // 가속도 단위 배율 (Livox 는 g 단위라 9.80665)
+    pub imu_topic: String,
+    pub imu_accel_scale: f64,
+    pub imu_init_samples: usize,
+    // 출력 좌표계를 중력 방향에 맞춤 (off, imu, ground), 제동 시 센서가 숙여져도 z 가 높이가 되도록
+    pub gravity_align: GravityAlign,
+    // 오도메트리 기록 밖의 시각을 끝 자세로 대신할 허용 범위 (초)
+    pub odom_tolerance: f64,
+    // LiDAR 시각 + time_offset = 오도메트리/IMU 시각 (초, calibrate_time_offset 결과)
+    pub time_offset: f64,
+}
+
+impl MotionConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        Ok(MotionConfig {
+            odom_mode: OdomMode::parse(&params::string(node, "odom_mode", "off")?)?,
+            odom_topic: params::string(node, "odom_topic", "odom")?,
+            imu_topic: params::string(node, "imu_topic", "livox/imu")?,
+            imu_accel_scale: params::float(node, "imu_accel_scale", 1.0)?,
+            imu_init_samples: params::int(node, "imu_init_samples", 200)?.max(1) as usize,
+            gravity_align: GravityAlign::parse(&params::string(node, "gravity_align", "off")?)?,
+            odom_tolerance: params::float(node, "odom_tolerance", 0.05)?,
+            time_offset: params::float(node, "time_offset", 0.0)?,
+        })
+    }
+}
+
+// 오도메트리/IMU 자세 기록 (구독 콜백이 채우고 작업 스레드가 읽음)
+pub struct Motion {
+    config: MotionConfig,
+    // 왜곡 보정에 쓰는 자세 기록 (odom_mode=off 면 None)
+    odometry: Option<Arc<Mutex<PoseBuffer>>>,
+    // IMU 자세 기록 (odom_mode=imu 또는 gravity_align=imu 일 때)
+    attitude: Option<Arc<Mutex<PoseBuffer>>>,
+}
+
+// Motion 의 기록을 채우는 구독 (노드 쪽에 남아 종료 때 끊음)
+pub struct MotionInputs {
+    _odometry: Option<Arc<Subscription<Odometry>>>,
+    _imu: Option<Arc<Subscription<Imu>>>,
+}
+
+impl Motion {
+    // 오도메트리 자세 기록 (오도메트리 100Hz 기준 약 10초, IMU 200Hz 기준 약 5초)
+    pub fn subscribe(node: &Node, config: &MotionConfig) -> Result<(Self, MotionInputs), Error> {
+        let use_imu =
+            config.odom_mode == OdomMode::Imu || config.gravity_align == GravityAlign::Imu;
+        let attitude = use_imu.then(|| {
+            Arc::new(Mutex::new(
+                PoseBuffer::new(1000).with_time_offset(config.time_offset),
+            ))
+        });
+        let odometry = match config.odom_mode {
+            OdomMode::Off => None,
+            OdomMode::Imu => attitude.clone(),
+            _ => Some(Arc::new(Mutex::new(
+                PoseBuffer::new(1000).with_time_offset(config.time_offset),
+            ))),
+        };
+        let mut odom_subscriber = None;
+        if let Some(poses) = odometry
+            .as_ref()
+            .filter(|_| config.odom_mode != OdomMode::Imu)
+        {
+            let poses = Arc::clone(poses);
+            odom_subscriber = Some(node.create_subscription::<Odometry, _>(
+                &config.odom_topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: Odometry| {
+                    poses.lock().unwrap().push_odometry(&msg);
+                },
+            )?);
+        }
+        let mut imu_subscriber = None;
+        if let Some(poses) = &attitude {
+            let poses = Arc::clone(poses);
+            let mut tracker = ImuTracker::new(config.imu_init_samples);
+            let accel_scale = config.imu_accel_scale;
+            imu_subscriber = Some(node.create_subscription::<Imu, _>(
+                &config.imu_topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: Imu| {
+                    let sample = ImuSample::from_msg(&msg, accel_scale);
+                    let pose = tracker.push(sample);
+                    poses.lock().unwrap().push(sample.time, pose);
+                },
+            )?);
+        }
+        let motion = Motion {
+            config: config.clone(),
+            odometry,
+            attitude,
+        };
+        let inputs = MotionInputs {
+            _odometry: odom_subscriber,
+            _imu: imu_subscriber,
+        };
+        Ok((motion, inputs))
+    }
+
+    // 프레임 시각 기준으로 포인트 왜곡 보정, deskew_to_odom 이면 오도메트리 좌표계로 옮김
+    pub fn deskew(&self, frame: &mut impl StageFrame) -> Result<(), Error> {
+        let Some(odometry) = &self.odometry else {
+            return Ok(());
+        };
+        let cloud = frame.cloud()?;
+        let poses = odometry.lock().unwrap();
+        let to_odom = self.config.odom_mode == OdomMode::DeskewToOdom;
+        deskew::deskew(
+            &mut cloud.points,
+            &poses,
+            stamp::to_secs(&cloud.header.stamp),
+            to_odom,
+            self.config.odom_tolerance,
+        )?;
+        if to_odom {
+            cloud.header.frame_id = poses.frame_id.clone();
+        }
+        drop(poses);
+        frame.commit(EditedField::Xyz)
+    }
+
+    // 중력 방향 정렬 회전 (plane 은 정렬 전 좌표계의 지면 추정), 정렬하지 않으면 None
+    pub fn leveling(
+        &self,
+        header: &Header,
+        plane: Option<&Plane>,
+    ) -> Result<Option<Transform>, Error> {
+        let up = match self.config.gravity_align {
+            GravityAlign::Off => return Ok(None),
+            GravityAlign::Ground => match plane {
+                Some(plane) => plane.normal.map(|v| v as f64),
+                // 지면을 아직 못 찾았으면 정렬하지 않음
+                None => return Ok(None),
+            },
+            GravityAlign::Imu => {
+                let Some(attitude) = &self.attitude else {
+                    return Ok(None);
+                };
+                let time = stamp::to_secs(&header.stamp);
+                let Some(pose) = attitude
+                    .lock()
+                    .unwrap()
+                    .pose_at(time, self.config.odom_tolerance)
+                else {
+                    bail!("프레임 시각({:.3})의 IMU 자세가 없습니다", time);
+                };
+                imu::up_vector(&pose)
+            }
+        };
+        Ok(Some(Transform::leveling(up)))
+    }
+}
@@ -0,0 +1,424 @@
+use super::config::BevConfig;
+use super::frame::{RawFrame, StageFrame};
+use crate::bev::{self, Aggregation, BevGrid, Cell, CellAggregation};
+use crate::cloud::{Point, PointCloud};
+use crate::filter;
+use crate::grid_map::{GridMapLayers, GridMapOutput};
+use crate::ground::Plane;
+use crate::layers::{HeightLayers, LayerImage};
+use crate::params;
+use crate::passthrough;
+use crate::pipeline::{CloudOutput, LatestFrame};
+use crate::point::LidarPoint;
+use crate::ros1::{Ros1Bridge, Ros1Publisher};
+use crate::stamp;
+use crate::timing::StageTimer;
+use anyhow::{bail, Error};
+use diagnostic_msgs::msg::DiagnosticArray;
+use rclrs::{Node, Publisher, QoSProfile, Service};
+use sensor_msgs::msg::{Image, PointCloud2};
+use std::sync::Arc;
+use std_msgs::msg::Header;
+use std_srvs::srv::{Trigger, Trigger_Response};
+
+// livox/lidar_bev 외 보조 출력 설정
+#[derive(Debug, Clone)]
+pub struct OutputConfig {
+    // 통과 높이 위 포인트를 별도 토픽으로 발행
+    pub overhead: bool,
+    // 높이 구간별 BEV 층 (bev_layers 경계, z_reference 와 같은 기준), 층마다 토픽 또는 이미지 채널로 발행
+    // bev_layer_output: topics, image, both (image 는 4개 층까지, bev_cell_size/extent 필요)
+    pub layers: Option<HeightLayers>,
+    pub layer_topics: bool,
+    pub layer_image: bool,
+    // livox/lidar_bev/grid_map (grid_map_msgs/GridMap): elevation, intensity, density 레이어
+    // grid_map 기능과 bev_cell_size, bev_extent_x/y > 0 필요
+    pub grid_map: bool,
+    // 단계별 처리 시간 토픽 발행 여부 (기본 끔)
+    pub timing: bool,
+    // ~/get_latest_cloud, ~/get_latest_raw_cloud 서비스: 요청 시 최근 BEV/원본 프레임을
+    // latched 토픽 (~/latest_cloud, ~/latest_raw_cloud) 에 한 번 발행 (매 프레임 복사하므로 기본 끔)
+    pub latest_cloud_service: bool,
+}
+
+impl OutputConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        let layer_output = params::string(node, "bev_layer_output", "topics")?;
+        if !matches!(layer_output.as_str(), "topics" | "image" | "both") {
+            bail!(
+                "알 수 없는 bev_layer_output '{}' (topics, image, both)",
+                layer_output
+            );
+        }
+        Ok(OutputConfig {
+            overhead: params::boolean(node, "publish_overhead", false)?,
+            layers: HeightLayers::from_node(node)?,
+            layer_topics: layer_output != "image",
+            layer_image: layer_output != "topics",
+            grid_map: params::boolean(node, "publish_grid_map", false)?,
+            timing: params::boolean(node, "publish_timing", false)?,
+            latest_cloud_service: params::boolean(node, "latest_cloud_service", false)?,
+        })
+    }
+}
+
+// ros1_topics 에 있는 토픽이면 ROS 1 master 에도 발행
+pub fn ros1_publisher(
+    ros1: Option<&Ros1Bridge>,
+    topic: &str,
+) -> Result<Option<Arc<Ros1Publisher>>, Error> {
+    match ros1 {
+        Some(bridge) => bridge.publisher(topic),
+        None => Ok(None),
+    }
+}
+
+// 보조 출력 발행자 (작업 스레드 소유)
+pub struct Outputs {
+    // 통과 높이 위 포인트 (clearance_height 가 있을 때만)
+    overhead: Option<CloudOutput>,
+    // 높이 층별 출력 (bev_layers 가 없으면 비어 있음)
+    layers: Vec<CloudOutput>,
+    layer_image: Option<(LayerImage, Arc<Publisher<Image>>)>,
+    grid_map: Option<GridMapOutput>,
+    // 프레임별 단계 소요 시간 (parse/filter/serialize/publish/total, 마이크로초)
+    timing: Option<Arc<Publisher<DiagnosticArray>>>,
+}
+
+impl Outputs {
+    pub fn new(
+        node: &Node,
+        config: &BevConfig,
+        qos: QoSProfile,
+        ros1: Option<&Ros1Bridge>,
+    ) -> Result<Self, Error> {
+        let outputs = &config.outputs;
+        let overhead = if outputs.overhead && config.clearance_height.is_some() {
+            let publisher =
+                node.create_publisher::<PointCloud2>("livox/lidar_bev/overhead", qos)?;
+            Some(
+                CloudOutput::new(publisher, false)
+                    .with_ros1(ros1_publisher(ros1, "livox/lidar_bev/overhead")?),
+            )
+        } else {
+            None
+        };
+
+        // 높이 층별 BEV (livox/lidar_bev/layer_0 ..., 이미지는 livox/lidar_bev/layers)
+        let mut layers = Vec::new();
+        let mut layer_image = None;
+        if let Some(height_layers) = &outputs.layers {
+            if outputs.layer_topics {
+                for layer in 0..height_layers.len() {
+                    let topic = format!("livox/lidar_bev/layer_{}", layer);
+                    let publisher = node.create_publisher::<PointCloud2>(&topic, qos)?;
+                    println!(
+                        "높이 층 {} ({} m): {}",
+                        layer,
+                        height_layers.label(layer),
+                        topic
+                    );
+                    layers.push(
+                        CloudOutput::new(publisher, false).with_ros1(ros1_publisher(ros1, &topic)?),
+                    );
+                }
+            }
+            if outputs.layer_image {
+                layer_image = Some((
+                    LayerImage::new(&config.grid, height_layers.len())?,
+                    node.create_publisher::<Image>("livox/lidar_bev/layers", qos)?,
+                ));
+            }
+        }
+        let grid_map = if outputs.grid_map {
+            Some(GridMapOutput::new(node, "livox/lidar_bev/grid_map", qos)?)
+        } else {
+            None
+        };
+        let timing = if outputs.timing {
+            Some(node.create_publisher::<DiagnosticArray>(
+                "livox/lidar_bev/timing",
+                rclrs::QOS_PROFILE_DEFAULT,
+            )?)
+        } else {
+            None
+        };
+        Ok(Outputs {
+            overhead,
+            layers,
+            layer_image,
+            grid_map,
+            timing,
+        })
+    }
+
+    // 셀 집계가 필요한 출력이 있는지 (bev_mode=points 여도 grid_map 은 셀 집계)
+    pub fn needs_cells(&self) -> bool {
+        self.grid_map.is_some()
+    }
+
+    // 필터링 전 전체 포인트를 쓰는 이미지 출력이 있는지 (패스스루 모드는 이때만 디코드)
+    pub fn needs_cloud(&self) -> bool {
+        self.layer_image.is_some()
+    }
+
+    // 통과 높이 위 포인트는 투영하지 않고 3D 그대로
+    pub fn publish_overhead(
+        &self,
+        config: &BevConfig,
+        cloud: &PointCloud<LidarPoint>,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let Some(output) = &self.overhead else {
+            return Ok(());
+        };
+        let mut header = cloud.header.clone();
+        header.frame_id = config.frame_id(&cloud.header.frame_id, "overhead");
+        let mut overhead = PointCloud::new(header, Vec::new());
+        overhead
+            .points
+            .extend(cloud.iter().filter(|p| config.is_overhead(plane, p.xyz())));
+        config.convention.transform().apply(&mut overhead);
+        let mut overhead_msg = config
+            .output_layout
+            .encode(&overhead.points, overhead.header);
+        config
+            .visual
+            .colorize(&mut overhead_msg, &overhead.points)?;
+        output.publish(overhead_msg)
+    }
+
+    // 패스스루 모드용: 통과 높이 위 포인트를 원본 필드 그대로 따로 발행
+    pub fn publish_overhead_msg(
+        &self,
+        config: &BevConfig,
+        frame: &RawFrame,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let Some(output) = &self.overhead else {
+            return Ok(());
+        };
+        let overhead = frame.xyz_mask(|xyz| config.is_overhead(plane, xyz))?;
+        let mut overhead_msg = passthrough::select(&frame.msg, &overhead);
+        overhead_msg.header.frame_id = config.frame_id(&frame.msg.header.frame_id, "overhead");
+        config.convention.transform().apply_msg(&mut overhead_msg)?;
+        output.publish(overhead_msg)
+    }
+
+    // 층별로 포인트를 나눠 BEV 로 투영해 발행 (높이 범위 필터 전 전체 포인트 사용)
+    pub fn publish_layers(
+        &mut self,
+        config: &BevConfig,
+        cloud: &PointCloud<LidarPoint>,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let Some(layers) = &config.outputs.layers else {
+            return Ok(());
+        };
+        self.publish_layer_image(config, cloud, plane)?;
+        for (layer, output) in self.layers.iter().enumerate() {
+            let mut header = cloud.header.clone();
+            header.frame_id = config.frame_id(&cloud.header.frame_id, "bev");
+            let mut points: PointCloud<LidarPoint> = PointCloud::new(header, Vec::new());
+            points.points.extend(
+                cloud
+                    .iter()
+                    .filter(|p| layers.layer_of(config.height(plane, p.xyz())) == Some(layer)),
+            );
+            config.grid.apply(&mut points);
+            filter::flatten(&mut points, 0.0);
+            config.convention.transform().apply(&mut points);
+            let mut layer_msg = config.output_layout.encode(&points.points, points.header);
+            config.visual.colorize(&mut layer_msg, &points.points)?;
+            output.publish(layer_msg)?;
+        }
+        Ok(())
+    }
+
+    // 패스스루 모드용: 원본 필드를 그대로 둔 채 층별로 골라냄
+    pub fn publish_layers_msg(
+        &self,
+        config: &BevConfig,
+        msg: &PointCloud2,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let Some(layers) = &config.outputs.layers else {
+            return Ok(());
+        };
+        for (layer, output) in self.layers.iter().enumerate() {
+            let keep = passthrough::xyz_mask(msg, |[x, y, z]| {
+                let h = config.height(plane, [x as f32, y as f32, z as f32]);
+                layers.layer_of(h) == Some(layer)
+            })?;
+            let mut layer_msg = passthrough::select(msg, &keep);
+            config.grid.apply_msg(&mut layer_msg)?;
+            passthrough::set_field(&mut layer_msg, "z", |_| 0.0)?;
+            layer_msg.header.frame_id = config.frame_id(&msg.header.frame_id, "bev");
+            config.convention.transform().apply_msg(&mut layer_msg)?;
+            output.publish(layer_msg)?;
+        }
+        Ok(())
+    }
+
+    // 층마다 채널 하나인 이미지 (셀 값은 포인트 수)
+    pub fn publish_layer_image<P: Point>(
+        &mut self,
+        config: &BevConfig,
+        cloud: &PointCloud<P>,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let (Some(layers), Some((image, publisher))) =
+            (&config.outputs.layers, &mut self.layer_image)
+        else {
+            return Ok(());
+        };
+        image.clear();
+        for p in cloud.iter() {
+            let xyz = p.xyz();
+            if let Some(layer) = layers.layer_of(config.height(plane, xyz)) {
+                image.add(xyz[0], xyz[1], layer);
+            }
+        }
+        let header = config.grid_header(&cloud.header);
+        publisher.publish(match &config.visual.image_colormap {
+            Some(_) => image.to_color_msg(header, &config.visual),
+            None => image.to_msg(header),
+        })?;
+        Ok(())
+    }
+
+    // 셀 집계 결과를 GridMap 레이어로 (bev_mode=points 면 높이 max, intensity mean)
+    pub fn publish_grid_map(
+        &self,
+        config: &BevConfig,
+        grid: &BevGrid,
+        cells: &[Cell],
+        header: &Header,
+    ) -> Result<(), Error> {
+        let Some(output) = &self.grid_map else {
+            return Ok(());
+        };
+        let agg = config.cells.unwrap_or(CellAggregation {
+            height: Aggregation::Max,
+            intensity: Aggregation::Mean,
+        });
+        let points: Vec<LidarPoint> = cells
+            .iter()
+            .map(|cell| bev::cell_point(grid, cell, &agg))
+            .collect();
+        let mut layers = GridMapLayers::new(grid)?;
+        let indexed = |value: fn(&LidarPoint) -> f32| {
+            cells
+                .iter()
+                .zip(&points)
+                .map(move |(cell, p)| (cell.index, value(p)))
+        };
+        layers.add_layer("elevation", f32::NAN, indexed(|p| p.z));
+        layers.add_layer("intensity", f32::NAN, indexed(|p| p.intensity));
+        layers.add_layer(
+            "density",
+            0.0,
+            cells.iter().map(|cell| (cell.index, cell.count as f32)),
+        );
+        output.publish(&layers, config.grid_header(header), &["elevation"])
+    }
+
+    pub fn publish_timing(&self, timer: &StageTimer, header: &Header) {
+        if let Some(timing) = &self.timing {
+            if let Err(e) = timing.publish(timer.to_msg("lidar_bev_publisher", header)) {
+                eprintln!("타이밍 발행 오류: {}", e);
+            }
+        }
+    }
+
+    // 보조 출력 (overhead, 높이 층) 토픽별 발행 바이트
+    pub fn bytes(&self) -> Vec<(String, u64)> {
+        let mut bytes = Vec::new();
+        if let Some(overhead) = &self.overhead {
+            bytes.push((
+                "livox/lidar_bev/overhead".to_string(),
+                overhead.published_bytes(),
+            ));
+        }
+        for (layer, output) in self.layers.iter().enumerate() {
+            bytes.push((
+                format!("livox/lidar_bev/layer_{}", layer),
+                output.published_bytes(),
+            ));
+        }
+        bytes
+    }
+}
+
+// 요청 시에만 가져가는 소비자 (예: 도킹 루틴) 용 최근 BEV/원본 프레임
+#[derive(Default)]
+pub struct LatestClouds {
+    pub processed: Arc<LatestFrame>,
+    pub raw: Arc<LatestFrame>,
+}
+
+// ~/get_latest_cloud, ~/get_latest_raw_cloud (노드 쪽에 남아 종료 때 끊음)
+pub struct LatestServices {
+    _processed: Arc<Service<Trigger>>,
+    _raw: Arc<Service<Trigger>>,
+}
+
+impl LatestClouds {
+    pub fn serve(&self, node: &Node) -> Result<LatestServices, Error> {
+        Ok(LatestServices {
+            _processed: latest_service(
+                node,
+                "~/get_latest_cloud",
+                "~/latest_cloud",
+                &self.processed,
+            )?,
+            _raw: latest_service(
+                node,
+                "~/get_latest_raw_cloud",
+                "~/latest_raw_cloud",
+                &self.raw,
+            )?,
+        })
+    }
+}
+
+// std_srvs 만 쓸 수 있어 응답에 클라우드를 담지 못하므로 보관한 프레임을 latched 토픽에 발행하고
+// 응답에는 발행한 토픽과 프레임 시각, 포인트 수를 담음
+fn latest_service(
+    node: &Node,
+    service: &str,
+    topic: &str,
+    frame: &Arc<LatestFrame>,
+) -> Result<Arc<Service<Trigger>>, Error> {
+    let publisher = node.create_publisher::<PointCloud2>(
+        topic,
+        rclrs::QOS_PROFILE_DEFAULT.keep_last(1).transient_local(),
+    )?;
+    let frame = Arc::clone(frame);
+    let topic = topic.to_string();
+    let service = node.create_service::<Trigger, _>(service, move |_request_id, _request| {
+        let Some(msg) = frame.get() else {
+            return Trigger_Response {
+                success: false,
+                message: "아직 받은 프레임이 없습니다".to_string(),
+            };
+        };
+        let message = format!(
+            "{}: stamp {:.3}, 포인트 {}",
+            topic,
+            stamp::to_secs(&msg.header.stamp),
+            msg.width as usize * msg.height as usize
+        );
+        match publisher.publish(msg) {
+            Ok(()) => Trigger_Response {
+                success: true,
+                message,
+            },
+            Err(e) => Trigger_Response {
+                success: false,
+                message: format!("{} 발행 실패: {}", topic, e),
+            },
+        }
+    })?;
+    Ok(service)
+}
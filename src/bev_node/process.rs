@@ -0,0 +1,262 @@
+use super::config::BevConfig;
+use super::frame::{RawFrame, StageFrame};
+use super::stages::{self, StageGround};
+use super::state::BevState;
+use crate::bev;
+use crate::cloud::PointCloud;
+use crate::filter;
+use crate::ground::{GroundFit, Plane};
+use crate::invalid::{self, InvalidPolicy};
+use crate::layout::{self, NamedLayout};
+use crate::passthrough;
+use crate::pipeline::CloudOutput;
+use crate::point::{datatype, LidarPoint};
+use crate::timing::StageTimer;
+use anyhow::Error;
+use sensor_msgs::msg::PointCloud2;
+use std_msgs::msg::Header;
+
+// 프레임 하나를 처리한 결과 (진단/타이밍 발행용)
+pub struct FrameStats {
+    pub header: Header,
+    pub input_points: usize,
+    pub output_points: usize,
+    // 좌표가 NaN/Inf 인 입력 포인트 수와 출력에 남은 수 (0 이 아니면 출력 is_dense = false)
+    pub invalid_points: usize,
+    pub output_invalid_points: usize,
+    // 이번 프레임에 사용한 내부 버퍼 크기
+    pub buffer_bytes: usize,
+    // 지면 기준 모드에서 이번 프레임에 사용한 지면
+    pub ground: Option<Plane>,
+    // 중력 정렬 전 좌표계의 지면과 이번 프레임 추정 품질 (이번 프레임 추정에 실패했으면 None)
+    pub attitude: Option<(Plane, GroundFit)>,
+    pub timer: StageTimer,
+}
+
+fn create_bev_pointcloud2(
+    points: &[LidarPoint],
+    extras: &[(&str, &[f32])],
+    bev_header: Header,
+    layout: &NamedLayout,
+    out: &mut PointCloud2,
+) {
+    // 필드 offset 과 point_step 은 레이아웃 빌더가 계산, out 의 버퍼는 재사용
+    // 값이 빈 추가 필드는 넣지 않음 (density: 셀에 들어온 원본 포인트 수, radial_velocity: scene flow)
+    let extras: Vec<(&str, &[f32])> = extras
+        .iter()
+        .copied()
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+    if extras.is_empty() {
+        layout.encode_into(points, bev_header, out);
+    } else {
+        let fields: Vec<(&str, u8)> = extras
+            .iter()
+            .map(|&(name, _)| (name, datatype::FLOAT32))
+            .collect();
+        layout.encode_into_with(points, &fields, bev_header, out, |i, values| {
+            values.extend(extras.iter().map(|(_, field)| field[i] as f64))
+        });
+    }
+}
+
+pub fn process_and_publish_bev(
+    msg: PointCloud2,
+    output: &CloudOutput,
+    config: &BevConfig,
+    state: &mut BevState,
+) -> Result<FrameStats, Error> {
+    if config.passthrough {
+        return passthrough_bev(msg, output, config, state);
+    }
+    let mut timer = StageTimer::start();
+
+    // 1. 원본 3D 포인트 파싱
+    let mut cloud = PointCloud::new(
+        msg.header.clone(),
+        layout::parse(&msg, config.input_layout)?,
+    );
+    let original_count = cloud.len(); // 먼저 개수 저장
+    let invalid_points = invalid::apply(&mut cloud.points, config.invalid_points);
+    let StageGround { plane, attitude } =
+        stages::filter_stages(&mut cloud, config, state, &mut timer)?;
+
+    // 2. Z축 필터링 (지면 추정이 아직 없으면 센서 기준 범위) 후 BEV 평면으로 투영
+    state.publish_visibility(config, &cloud, plane.as_ref())?;
+    state
+        .outputs
+        .publish_layers(config, &cloud, plane.as_ref())?;
+    state
+        .outputs
+        .publish_overhead(config, &cloud, plane.as_ref())?;
+    let grid = stages::bev_stages(&mut cloud, config, state, plane.as_ref())?;
+    let mut density = Vec::new();
+    let cells = (config.cells.is_some() || state.outputs.needs_cells())
+        .then(|| state.compute.collect_cells(&grid, &cloud.points));
+    if let Some(cells) = &cells {
+        state
+            .outputs
+            .publish_grid_map(config, &grid, cells, &cloud.header)?;
+    }
+    match (&config.cells, cells) {
+        (Some(agg), Some(cells)) => {
+            let mut points: Vec<((i32, i32), (LidarPoint, f32))> = cells
+                .iter()
+                .map(|cell| {
+                    let point = bev::cell_point(&grid, cell, agg);
+                    (cell.index, (point, cell.count as f32))
+                })
+                .collect();
+            // 한 프레임만 보인 셀은 거르고, 잠깐 안 보인 셀은 마지막 값으로 유지
+            if let Some(smoothing) = &mut state.smoothing {
+                points = smoothing.update(grid.cell_size, points);
+            }
+            if config.density {
+                density = points.iter().map(|(_, (_, count))| *count).collect();
+            }
+            cloud.points = points.into_iter().map(|(_, (point, _))| point).collect();
+        }
+        _ => filter::flatten(&mut cloud, 0.0), // BEV에서는 Z=0
+    }
+    // 격자 위치는 출력 좌표계 변환 전 셀 인덱스로 정함 (부하 단계로 셀이 커지면 그 격자 기준)
+    let organized = config
+        .organized
+        .then(|| grid.organize(&cloud.points))
+        .flatten();
+    // 속도는 센서 위치(장착 보정 translation) 기준, 출력 좌표계 변환 전에 계산
+    let radial_velocity: Vec<f32> = state.scene_flow.as_ref().map_or_else(Vec::new, |flow| {
+        let [ox, oy, _] = config.mount.translation;
+        cloud
+            .iter()
+            .map(|p| flow.radial_velocity(p.x, p.y, [ox, oy]))
+            .collect()
+    });
+    config.convention.transform().apply(&mut cloud);
+    let output_invalid_points = invalid::count(&cloud.points);
+    let output_points = cloud.len();
+    if let Some(organized) = &organized {
+        let empty = LidarPoint {
+            x: f32::NAN,
+            y: f32::NAN,
+            z: f32::NAN,
+            ..LidarPoint::default()
+        };
+        cloud.points = organized.arrange(&cloud.points, empty);
+        if !density.is_empty() {
+            density = organized.arrange(&density, 0.0);
+        }
+    }
+    let radial_velocity = match &organized {
+        Some(organized) if !radial_velocity.is_empty() => {
+            organized.arrange(&radial_velocity, f32::NAN)
+        }
+        _ => radial_velocity,
+    };
+    timer.mark("filter");
+
+    // 3. 새로운 PointCloud2 메시지 생성 후 4. BEV 토픽으로 발행
+    let mut bev_header = cloud.header.clone();
+    bev_header.frame_id = config.frame_id(&cloud.header.frame_id, "bev");
+    output.publish_with(|out| {
+        create_bev_pointcloud2(
+            &cloud.points,
+            &[("density", &density), ("radial_velocity", &radial_velocity)],
+            bev_header,
+            config.output_layout,
+            out,
+        );
+        if let Err(e) = config.visual.colorize(out, &cloud.points) {
+            eprintln!("rgb 색 지정 오류: {}", e);
+        }
+        if let Some(organized) = &organized {
+            // 빈 셀 NaN 이 있으므로 is_dense 는 빌더가 false 로 둠
+            if let Err(e) = passthrough::set_height(out, organized.rows) {
+                eprintln!("정렬 출력 오류: {}", e);
+            }
+        }
+        timer.mark("serialize");
+    })?;
+    timer.mark("publish");
+
+    Ok(FrameStats {
+        header: msg.header.clone(),
+        input_points: original_count,
+        output_points,
+        invalid_points,
+        output_invalid_points,
+        buffer_bytes: msg.data.capacity()
+            + cloud.points.capacity() * std::mem::size_of::<LidarPoint>(),
+        ground: plane,
+        attitude,
+        timer,
+    })
+}
+
+// 재인코딩 없이 원본 바이트 버퍼를 마스크로 제자리 압축 (알 수 없는 필드 보존)
+// 처리 단계는 일반 모드와 같은 filter_stages/bev_stages 를 RawFrame 으로 실행
+fn passthrough_bev(
+    mut msg: PointCloud2,
+    output: &CloudOutput,
+    config: &BevConfig,
+    state: &mut BevState,
+) -> Result<FrameStats, Error> {
+    let mut timer = StageTimer::start();
+    let header = msg.header.clone();
+    let original_count = passthrough::point_count(&msg);
+    let invalid_points = invalid::apply_msg(&mut msg, config.invalid_points)?;
+    let mut frame = RawFrame::new(msg, config.input_layout);
+    let StageGround { plane, attitude } =
+        stages::filter_stages(&mut frame, config, state, &mut timer)?;
+
+    if state.visibility.is_some() || state.outputs.needs_cloud() {
+        let cloud = frame.cloud()?;
+        state.publish_visibility(config, cloud, plane.as_ref())?;
+        state
+            .outputs
+            .publish_layer_image(config, cloud, plane.as_ref())?;
+    }
+    state
+        .outputs
+        .publish_layers_msg(config, &frame.msg, plane.as_ref())?;
+    state
+        .outputs
+        .publish_overhead_msg(config, &frame, plane.as_ref())?;
+
+    stages::bev_stages(&mut frame, config, state, plane.as_ref())?;
+    timer.mark("filter");
+    let RawFrame {
+        mut msg, decoded, ..
+    } = frame;
+    passthrough::set_field(&mut msg, "z", |_| 0.0)?; // BEV에서는 Z=0
+    msg.header.frame_id = config.frame_id(&msg.header.frame_id, "bev");
+    config.convention.transform().apply_msg(&mut msg)?;
+    timer.mark("serialize");
+
+    // keep 일 때만 잘못된 포인트가 남을 수 있음 (입력 is_dense 대신 실제 개수로)
+    let output_invalid_points =
+        if invalid_points > 0 && config.invalid_points == InvalidPolicy::Keep {
+            invalid::count_msg(&msg)?
+        } else {
+            0
+        };
+    msg.is_dense = output_invalid_points == 0;
+    let output_points = msg.width as usize;
+    let buffer_bytes = msg.data.capacity()
+        + decoded.map_or(0, |cloud| {
+            cloud.points.capacity() * std::mem::size_of::<LidarPoint>()
+        });
+    output.publish(msg)?;
+    timer.mark("publish");
+
+    Ok(FrameStats {
+        header,
+        input_points: original_count,
+        output_points,
+        invalid_points,
+        output_invalid_points,
+        buffer_bytes,
+        ground: plane,
+        attitude,
+        timer,
+    })
+}
@@ -0,0 +1,174 @@
+use crate::history::StatsHistory;
+use crate::params;
+use crate::persist::StateStore;
+use crate::snapshot::ConfigSnapshot;
+use crate::stamp;
+use anyhow::Error;
+use rclrs::{Node, Service};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std_srvs::srv::{Trigger, Trigger_Response};
+
+// 실행 기록과 학습한 상태 저장 설정
+#[derive(Debug, Clone)]
+pub struct RecordConfig {
+    // 크래시 덤프용으로 보관할 최근 입력 프레임 수 (0 이면 끔)와 저장 위치, 패닉 때 저장하고
+    // crash_dump_on_exit 이면 Ctrl-C 종료 때도 저장
+    pub crash_dump_frames: usize,
+    pub crash_dump_dir: String,
+    pub crash_dump_on_exit: bool,
+    // 실행마다 실제 설정 스냅샷을 <dir>/lidar_bev_publisher_<unix초>.yaml 로 저장 (빈 문자열이면 저장 안 함)
+    pub config_snapshot_dir: String,
+    // 진단 값 시계열을 종료 시와 ~/export_stats 서비스 요청 시 저장 (.json 이면 JSON, 그 외 CSV,
+    // 빈 문자열이면 기록 안 함), 기록 간격 (초) 과 최대 행 수
+    pub stats_history_file: String,
+    pub stats_history_period: f64,
+    pub stats_history_max_rows: usize,
+    // 오래 학습하는 상태 (배경, 지면, 누적 점유 지도, 가림 감지 기준) 를 state_save_period 초마다
+    // 이 디렉터리에 저장하고 시작할 때 읽음 (빈 문자열이면 끔, background_file/occupancy_file 이 우선)
+    pub state_dir: String,
+    pub state_save_period: f64,
+}
+
+impl RecordConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        Ok(RecordConfig {
+            crash_dump_frames: params::int(node, "crash_dump_frames", 0)?.max(0) as usize,
+            crash_dump_dir: params::string(node, "crash_dump_dir", "crash_dumps")?,
+            crash_dump_on_exit: params::boolean(node, "crash_dump_on_exit", false)?,
+            config_snapshot_dir: params::string(node, "config_snapshot_dir", "config_snapshots")?,
+            stats_history_file: params::string(node, "stats_history_file", "")?,
+            stats_history_period: params::float(node, "stats_history_period", 1.0)?,
+            stats_history_max_rows: params::int(node, "stats_history_max_rows", 3600)?.max(0)
+                as usize,
+            state_dir: params::string(node, "state_dir", "")?,
+            state_save_period: params::float(node, "state_save_period", 60.0)?,
+        })
+    }
+
+    // 설정 스냅샷을 config_snapshot_dir 에 파일로 남김
+    pub fn write_snapshot(&self, snapshot: &ConfigSnapshot) {
+        if self.config_snapshot_dir.is_empty() {
+            return;
+        }
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path =
+            Path::new(&self.config_snapshot_dir).join(format!("lidar_bev_publisher_{}.yaml", secs));
+        match snapshot.write(&path) {
+            Ok(()) => println!("설정 스냅샷: {} ({})", path.display(), snapshot.hash()),
+            Err(e) => eprintln!("설정 스냅샷 저장 오류: {}", e),
+        }
+    }
+
+    // 학습한 상태 저장소 (state_dir 이 있을 때만)
+    pub fn state_store(&self) -> Result<Option<StateStore>, Error> {
+        if self.state_dir.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(StateStore::new(
+            &self.state_dir,
+            self.state_save_period,
+        )?))
+    }
+}
+
+// 명시한 파일이 없으면 state_dir 에 저장한 파일
+pub fn saved_state(store: Option<&StateStore>, file: &str, name: &str) -> Option<PathBuf> {
+    if file.is_empty() {
+        store.and_then(|store| store.existing(name))
+    } else {
+        Some(PathBuf::from(file)).filter(|path| path.exists())
+    }
+}
+
+// 진단 값 시계열 (stats_history_file 이 있을 때만), 작업 스레드와 서비스가 같이 씀
+#[derive(Clone)]
+pub struct History {
+    stats: Arc<Mutex<StatsHistory>>,
+    file: String,
+}
+
+impl History {
+    pub fn new(config: &RecordConfig) -> Option<Self> {
+        (!config.stats_history_file.is_empty()).then(|| History {
+            stats: Arc::new(Mutex::new(StatsHistory::new(
+                config.stats_history_period,
+                config.stats_history_max_rows,
+            ))),
+            file: config.stats_history_file.clone(),
+        })
+    }
+
+    // ~/export_stats: 요청 시 지금까지의 기록을 파일로 저장
+    pub fn serve(&self, node: &Node) -> Result<Arc<Service<Trigger>>, Error> {
+        let stats = Arc::clone(&self.stats);
+        let path = self.file.clone();
+        let service =
+            node.create_service::<Trigger, _>("~/export_stats", move |_request_id, _request| {
+                let stats = stats.lock().unwrap();
+                match stats.write(Path::new(&path)) {
+                    Ok(()) => Trigger_Response {
+                        success: true,
+                        message: format!("{} ({}행)", path, stats.len()),
+                    },
+                    Err(e) => Trigger_Response {
+                        success: false,
+                        message: e.to_string(),
+                    },
+                }
+            })?;
+        Ok(service)
+    }
+
+    pub fn record(&self, values: &[(&str, String)]) {
+        let now = stamp::to_secs(&stamp::now());
+        self.stats.lock().unwrap().record(now, values);
+    }
+
+    // 종료 때 저장
+    pub fn write(&self) {
+        let stats = self.stats.lock().unwrap();
+        match stats.write(Path::new(&self.file)) {
+            Ok(()) => println!("통계 기록 저장: {} ({}행)", self.file, stats.len()),
+            Err(e) => eprintln!("통계 기록 저장 오류: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn saved_state_prefers_explicit_file() {
+        let dir = std::env::temp_dir().join(format!("bev_saved_state_{}", std::process::id()));
+        let store = StateStore::new(dir.to_str().unwrap(), 60.0).unwrap();
+        store
+            .save("background.txt", |path| Ok(fs::write(path, "saved")?))
+            .unwrap();
+        let explicit = dir.join("explicit.txt");
+
+        // 파일을 지정하지 않으면 state_dir 의 파일
+        assert_eq!(
+            saved_state(Some(&store), "", "background.txt"),
+            Some(store.path("background.txt"))
+        );
+        assert_eq!(saved_state(None, "", "background.txt"), None);
+        // 지정한 파일이 아직 없으면 state_dir 에 있어도 새로 학습
+        let explicit_name = explicit.to_str().unwrap();
+        assert_eq!(
+            saved_state(Some(&store), explicit_name, "background.txt"),
+            None
+        );
+        fs::write(&explicit, "explicit").unwrap();
+        assert_eq!(
+            saved_state(Some(&store), explicit_name, "background.txt"),
+            Some(explicit.clone())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+use super::process::FrameStats;
+use super::record::History;
+use super::state::BevState;
+use crate::alloc_stats::{AllocSnapshot, BufferStats};
+use crate::cli::Verbosity;
+use crate::diagnostics::{self, Diagnostics};
+use crate::pipeline::CloudOutput;
+use crate::stats::{ByteRate, RunTotals};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// 프레임마다 발행하는 진단 정보 (lidar_bev_publisher), 기능별 값은 각 모듈의 report 가 채움
+pub struct Report {
+    diagnostics: Diagnostics,
+    // 수신한 입력 메시지 직렬화 크기 합 (입력 구독이 더함)
+    input_bytes: Arc<AtomicU64>,
+    history: Option<History>,
+    last_dropped: u64,
+    buffers: BufferStats,
+    input_rate: ByteRate,
+    output_rate: ByteRate,
+    last_invalid: (usize, usize),
+}
+
+impl Report {
+    pub fn new(
+        diagnostics: Diagnostics,
+        input_bytes: Arc<AtomicU64>,
+        history: Option<History>,
+    ) -> Self {
+        Report {
+            diagnostics,
+            input_bytes,
+            history,
+            last_dropped: 0,
+            buffers: BufferStats::default(),
+            input_rate: ByteRate::new(Duration::from_secs(1)),
+            output_rate: ByteRate::new(Duration::from_secs(1)),
+            last_invalid: (0, 0),
+        }
+    }
+
+    // 처리에 성공한 프레임
+    pub fn frame(&mut self, stats: &FrameStats) {
+        self.last_invalid = (stats.invalid_points, stats.output_invalid_points);
+        self.buffers.update(stats.buffer_bytes);
+    }
+
+    // 지난 진단 이후 큐에서 새로 버린 프레임이 있는지
+    pub fn dropping(&self, dropped: u64) -> bool {
+        dropped != self.last_dropped
+    }
+
+    pub fn input_bytes(&self) -> u64 {
+        self.input_bytes.load(Ordering::Relaxed)
+    }
+
+    // allocs: 처리 전후 힙 할당 (counting-alloc 기능이 꺼져 있으면 None)
+    pub fn publish(
+        &mut self,
+        dropped: u64,
+        totals: &RunTotals,
+        output: &CloudOutput,
+        state: &mut BevState,
+        allocs: (Option<AllocSnapshot>, Option<AllocSnapshot>),
+        verbosity: Verbosity,
+    ) {
+        let mut level = if self.dropping(dropped) {
+            if verbosity > Verbosity::Quiet {
+                println!("처리 지연으로 버린 프레임: {} (누적)", dropped);
+            }
+            self.last_dropped = dropped;
+            diagnostics::WARN
+        } else {
+            diagnostics::OK
+        };
+        let mut message = "BEV 처리 중".to_string();
+        let mut blockage_values = Vec::new();
+        if let Some(blockage) = &mut state.blockage {
+            blockage.report(verbosity, &mut level, &mut message, &mut blockage_values);
+        }
+
+        // 토픽별 전송량 (다운샘플/레이아웃 변경 효과 확인용)
+        let input_total = self.input_bytes();
+        let mut topic_bytes = vec![("livox/lidar_bev".to_string(), output.published_bytes())];
+        topic_bytes.extend(state.outputs.bytes());
+        let output_total: u64 = topic_bytes.iter().map(|(_, bytes)| bytes).sum();
+        let topic_keys: Vec<(String, u64)> = topic_bytes
+            .into_iter()
+            .map(|(topic, bytes)| (format!("{}/bytes_total", topic), bytes))
+            .collect();
+
+        // 내부 버퍼 사용량, counting-alloc 기능이 켜져 있으면 힙 할당 정보도 포함
+        let mut values = vec![
+            ("dropped_frames", dropped.to_string()),
+            ("buffer_bytes", self.buffers.current_bytes.to_string()),
+            ("buffer_peak_bytes", self.buffers.peak_bytes.to_string()),
+            ("invalid_points", self.last_invalid.0.to_string()),
+            ("output_invalid_points", self.last_invalid.1.to_string()),
+            ("invalid_points_total", totals.invalid_points.to_string()),
+            ("input_bytes_total", input_total.to_string()),
+            (
+                "input_kbps",
+                format!("{:.1}", self.input_rate.update(input_total) / 1e3),
+            ),
+            ("output_bytes_total", output_total.to_string()),
+            (
+                "output_kbps",
+                format!("{:.1}", self.output_rate.update(output_total) / 1e3),
+            ),
+        ];
+        values.extend(
+            topic_keys
+                .iter()
+                .map(|(key, bytes)| (key.as_str(), bytes.to_string())),
+        );
+        if let (Some(before), Some(after)) = allocs {
+            values.push((
+                "frame_allocations",
+                after.allocations_since(&before).to_string(),
+            ));
+            values.push(("heap_bytes", after.current_bytes.to_string()));
+            values.push(("heap_peak_bytes", after.peak_bytes.to_string()));
+        }
+        if let Some(background) = &state.background {
+            background.report(&mut values);
+        }
+        values.extend(blockage_values);
+        state.attention.report(&mut values);
+        if let Some(load) = &state.load {
+            values.push(("load_level", format!("{:?}", load.level())));
+            if let Some(us) = load.latency_us() {
+                values.push(("load_latency_us", format!("{:.0}", us)));
+            }
+        }
+        state.ground.report(&mut values);
+        if let Some(history) = &self.history {
+            history.record(&values);
+        }
+        if let Err(e) = self.diagnostics.publish(level, &message, &values) {
+            eprintln!("진단 정보 발행 오류: {}", e);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use super::config::BevConfig;
+use super::frame::{EditedField, StageFrame};
+use super::state::BevState;
+use crate::bev::BevGrid;
+use crate::exclusion::ZoneFrame;
+use crate::filter;
+use crate::ground::{GroundFit, Plane};
+use crate::load::LoadManager;
+use crate::reflection::{self, ReflectionMode};
+use crate::stamp;
+use crate::timing::StageTimer;
+use anyhow::Error;
+
+// filter_stages 가 추정한 지면
+pub struct StageGround {
+    // z_reference=ground 면 이번 프레임 지면 (정렬 후 좌표계)
+    pub plane: Option<Plane>,
+    // 정렬 전 좌표계의 지면과 추정 품질
+    pub attitude: Option<(Plane, GroundFit)>,
+}
+
+// 파싱 뒤 지면 정렬까지의 처리 단계 (일반/패스스루 모드 공통)
+pub fn filter_stages(
+    frame: &mut impl StageFrame,
+    config: &BevConfig,
+    state: &mut BevState,
+    timer: &mut StageTimer,
+) -> Result<StageGround, Error> {
+    if let Some(table) = &config.line_timing {
+        if table.apply(&mut frame.cloud()?.points) {
+            frame.sort_by_time();
+        }
+        frame.commit(EditedField::Timestamp)?;
+    }
+    if config.intensity.is_some() || config.reflectance.is_some() {
+        let points = &mut frame.cloud()?.points;
+        if let Some(table) = &config.intensity {
+            table.apply(points);
+        }
+        if let Some(model) = &config.reflectance {
+            model.apply(points);
+        }
+        frame.commit(EditedField::Intensity)?;
+    }
+    // 처리가 밀리면 부하 단계에 따라 복셀 다운샘플 (복셀마다 첫 포인트 유지)
+    if let Some(size) = state.load.as_ref().and_then(LoadManager::voxel) {
+        let keep = filter::voxel_mask(&frame.cloud()?.points, size);
+        frame.keep(&keep);
+    }
+    timer.mark("parse");
+    if let Some(blockage) = &mut state.blockage {
+        blockage.update(&frame.cloud()?.points);
+    }
+    config.exclude(ZoneFrame::Sensor, frame)?;
+    if !config.mount.is_identity() {
+        frame.map_xyz(|p| config.mount.apply_xyz(p))?;
+    }
+    config.exclude(ZoneFrame::Base, frame)?;
+    state.attention.roi(frame)?;
+    state.motion.deskew(frame)?;
+    timer.mark("transform");
+    if config.weather.enabled() {
+        let keep = config
+            .weather
+            .mask_with(state.compute.as_ref(), &frame.cloud()?.points);
+        frame.keep(&keep);
+    }
+    if let Some(dust) = &mut state.dust {
+        let keep = dust.mask(&frame.cloud()?.points);
+        frame.keep(&keep);
+    }
+    if let Some(background) = &mut state.background {
+        background.filter(frame)?;
+    }
+    timer.mark("weather");
+    if config.reflection != ReflectionMode::Off {
+        let cloud = frame.cloud()?;
+        let reflected = reflection::detect(&cloud.points, &config.reflection_config);
+        if config.reflection == ReflectionMode::Remove {
+            let keep: Vec<bool> = reflected.iter().map(|r| !r).collect();
+            frame.keep(&keep);
+        } else {
+            for (p, _) in cloud.points.iter_mut().zip(&reflected).filter(|(_, r)| **r) {
+                p.tag |= reflection::REFLECTION_TAG;
+            }
+            frame.commit(EditedField::Tag)?;
+        }
+    }
+    timer.mark("reflection");
+
+    // 지면 추정은 정렬 전 좌표계에서 하고, 정렬하면 지면도 같이 돌림
+    // 부하가 높으면 normal 을 다시 추정하지 않고 직전 평면 사용
+    let (mut plane, attitude) = if state.ground.enabled() {
+        let skip_normals = state.load.as_ref().is_some_and(LoadManager::skip_normals);
+        state.ground.update(frame.cloud()?, skip_normals)
+    } else {
+        (None, None)
+    };
+    if let Some(level) = state.motion.leveling(frame.header(), plane.as_ref())? {
+        frame.map_xyz(|p| level.apply_xyz(p))?;
+        plane = plane.map(|plane| level.apply_plane(&plane));
+        timer.mark("level");
+    }
+    Ok(StageGround {
+        plane: plane.filter(|_| config.ground.reference),
+        attitude,
+    })
+}
+
+// 높이 범위 필터부터 BEV 격자 투영까지 (일반/패스스루 모드 공통), 사용한 격자를 돌려줌
+pub fn bev_stages(
+    frame: &mut impl StageFrame,
+    config: &BevConfig,
+    state: &mut BevState,
+    plane: Option<&Plane>,
+) -> Result<BevGrid, Error> {
+    let keep = frame.xyz_mask(|xyz| config.in_band(plane, xyz))?;
+    frame.keep(&keep);
+    state.attention.capture(frame)?;
+    if let Some(flow) = &mut state.scene_flow {
+        let cloud = frame.cloud()?;
+        flow.update(stamp::to_secs(&cloud.header.stamp), &cloud.points);
+    }
+    let grid = state.output_grid(config);
+    let keep = frame.xyz_mask(|[x, y, _]| grid.contains(x, y))?;
+    frame.keep(&keep);
+    if grid.cell_size > 0.0 {
+        frame.map_xyz(|p| grid.snap(p))?;
+    }
+    if config.min_points_per_cell > 1 {
+        let keep = grid.dense_mask(&frame.cloud()?.points, config.min_points_per_cell);
+        frame.keep(&keep);
+    }
+    Ok(grid)
+}
@@ -0,0 +1,96 @@
+use super::attention::Attention;
+use super::background::Background;
+use super::blockage::Blockage;
+use super::config::BevConfig;
+use super::ground::Ground;
+use super::motion::Motion;
+use super::outputs::Outputs;
+use super::visibility::Visibility;
+use crate::bev::BevGrid;
+use crate::cloud::{Point, PointCloud};
+use crate::compute::ComputeBackend;
+use crate::ground::Plane;
+use crate::load::LoadManager;
+use crate::persist::StateStore;
+use crate::point::LidarPoint;
+use crate::scene_flow::SceneFlow;
+use crate::temporal::{CellSmoother, DustFilter};
+use anyhow::Error;
+
+// 프레임 사이에 유지되는 처리 상태 (작업 스레드 소유), 기능별 상태와 단계는 각 모듈에
+pub struct BevState {
+    pub motion: Motion,
+    pub attention: Attention,
+    pub ground: Ground,
+    pub dust: Option<DustFilter>,
+    pub blockage: Option<Blockage>,
+    pub background: Option<Background>,
+    pub visibility: Option<Visibility>,
+    pub outputs: Outputs,
+    pub compute: Box<dyn ComputeBackend>,
+    // load_budget_ms 가 있으면 처리 지연에 따라 품질 단계 조절
+    pub load: Option<LoadManager>,
+    pub scene_flow: Option<SceneFlow>,
+    // bev_smoothing: 셀별 (집계 포인트, 원본 포인트 수) 기록
+    pub smoothing: Option<CellSmoother<(LidarPoint, f32)>>,
+}
+
+impl BevState {
+    // 출력 BEV 격자 (부하가 높으면 셀 크기를 키움)
+    pub fn output_grid(&self, config: &BevConfig) -> BevGrid {
+        match &self.load {
+            Some(load) => load.grid(&config.grid),
+            None => config.grid,
+        }
+    }
+
+    // 필터링 전 전체 포인트로 가시성 격자를 만들어 발행 (광선 원점은 장착 위치)
+    pub fn publish_visibility<P: Point>(
+        &mut self,
+        config: &BevConfig,
+        cloud: &PointCloud<P>,
+        plane: Option<&Plane>,
+    ) -> Result<(), Error> {
+        let Some(visibility) = &mut self.visibility else {
+            return Ok(());
+        };
+        let origin = [config.mount.translation[0], config.mount.translation[1]];
+        visibility.publish(cloud, config.grid_header(&cloud.header), origin, |xyz| {
+            config.in_band(plane, xyz)
+        })
+    }
+
+    // supervisor 재시작: 프레임 사이에 쌓인 처리 상태를 새로 만듦
+    // (발행자, 구독 콜백이 채우는 자세 기록, 학습한 배경, 누적 점유 지도는 유지)
+    pub fn reset(&mut self, config: &BevConfig) {
+        self.ground.reset();
+        self.dust = config.dust.map(DustFilter::new);
+        self.load = config.load.map(LoadManager::new);
+        self.scene_flow = config.scene_flow.map(SceneFlow::new);
+        self.smoothing = config.smoothing.map(CellSmoother::new);
+    }
+
+    // state_dir 에 학습한 상태 저장 (아직 학습 중인 상태는 건너뜀)
+    pub fn save_state(&self, store: &StateStore) -> Result<(), Error> {
+        if let Some(background) = &self.background {
+            background.save(store)?;
+        }
+        self.ground.save(store)?;
+        if let Some(visibility) = &self.visibility {
+            visibility.save(store)?;
+        }
+        if let Some(blockage) = &self.blockage {
+            blockage.save(store)?;
+        }
+        Ok(())
+    }
+
+    // state_dir 에 저장한 지면, 가림 감지 기준을 읽음 (배경, 점유 지도는 만들 때 읽음)
+    pub fn restore_state(&mut self, store: &StateStore) -> Result<(), Error> {
+        self.ground.restore(store)?;
+        if let Some(blockage) = &mut self.blockage {
+            blockage.restore(store)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use super::record;
+use crate::bev::BevGrid;
+use crate::cloud::{Point, PointCloud};
+use crate::params;
+use crate::persist::StateStore;
+use crate::stamp;
+use crate::visibility::{TemporalOccupancy, VisibilityGrid};
+use anyhow::Error;
+use nav_msgs::msg::OccupancyGrid;
+use rclrs::{Node, Publisher};
+use std::path::Path;
+use std::sync::Arc;
+use std_msgs::msg::Header;
+
+// state_dir 안 파일 이름
+const STATE_OCCUPANCY: &str = "occupancy.bin";
+
+// 광선 투사로 free/occupied/unknown 을 구분한 격자 설정 (publish_visibility=false 면 None)
+#[derive(Debug, Clone)]
+pub struct VisibilityConfig {
+    // 가시성 격자를 프레임마다 새로 만들지 않고 log-odds 로 누적 (고정 설치, deskew_to_odom 용)
+    // occupancy_file 이 있으면 시작할 때 읽고 종료할 때 저장 (빈 문자열이면 저장 안 함)
+    pub temporal: bool,
+    pub occupancy_file: String,
+}
+
+impl VisibilityConfig {
+    pub fn from_node(node: &Node) -> Result<Option<Self>, Error> {
+        let publish = params::boolean(node, "publish_visibility", false)?;
+        let config = VisibilityConfig {
+            temporal: params::boolean(node, "visibility_temporal", false)?,
+            occupancy_file: params::string(node, "occupancy_file", "")?,
+        };
+        Ok(publish.then_some(config))
+    }
+}
+
+// 가시성 격자 (0: free, 100: occupied, -1: 관측 안 됨/가려짐)
+pub struct Visibility {
+    grid: VisibilityGrid,
+    publisher: Arc<Publisher<OccupancyGrid>>,
+    // visibility_temporal 이면 누적 점유 지도
+    occupancy: Option<TemporalOccupancy>,
+    occupancy_file: String,
+}
+
+impl Visibility {
+    // 같은 격자로 저장한 누적 점유 지도가 있으면 이어서 누적
+    pub fn new(
+        node: &Node,
+        config: &VisibilityConfig,
+        grid: &BevGrid,
+        store: Option<&StateStore>,
+    ) -> Result<Self, Error> {
+        let grid = VisibilityGrid::new(grid)?;
+        let publisher = node.create_publisher::<OccupancyGrid>(
+            "livox/lidar_bev/visibility",
+            rclrs::QOS_PROFILE_DEFAULT,
+        )?;
+        let occupancy = if !config.temporal {
+            None
+        } else if let Some(path) =
+            record::saved_state(store, &config.occupancy_file, STATE_OCCUPANCY)
+        {
+            let occupancy = TemporalOccupancy::load(&grid, &path)?;
+            println!(
+                "점유 지도 불러옴: {} (frame {}, 시각 {:.3})",
+                path.display(),
+                occupancy.frame_id,
+                occupancy.stamp
+            );
+            Some(occupancy)
+        } else {
+            Some(TemporalOccupancy::new(&grid))
+        };
+        Ok(Visibility {
+            grid,
+            publisher,
+            occupancy,
+            occupancy_file: config.occupancy_file.clone(),
+        })
+    }
+
+    // 필터링 전 전체 포인트로 격자를 만들어 발행
+    // in_band: 장애물 높이 범위에 드는 포인트, origin: 광선 원점 (장착 위치)
+    pub fn publish<P: Point>(
+        &mut self,
+        cloud: &PointCloud<P>,
+        header: Header,
+        origin: [f32; 2],
+        in_band: impl Fn([f32; 3]) -> bool,
+    ) -> Result<(), Error> {
+        self.grid.clear();
+        for p in cloud.iter() {
+            let xyz = p.xyz();
+            self.grid.add_hit(xyz[0], xyz[1], in_band(xyz));
+        }
+        self.grid.cast(origin);
+        match &mut self.occupancy {
+            Some(occupancy) => {
+                occupancy.update(&self.grid, stamp::to_secs(&header.stamp), &header.frame_id);
+                self.publisher
+                    .publish(occupancy.to_msg(&self.grid, header))?;
+            }
+            None => self.publisher.publish(self.grid.to_msg(header))?,
+        }
+        Ok(())
+    }
+
+    // 누적 점유 지도를 occupancy_file 에 저장
+    pub fn save_occupancy(&self) -> Result<(), Error> {
+        let Some(occupancy) = &self.occupancy else {
+            return Ok(());
+        };
+        if self.occupancy_file.is_empty() {
+            return Ok(());
+        }
+        occupancy.save(&self.grid, Path::new(&self.occupancy_file))?;
+        println!("점유 지도 저장: {}", self.occupancy_file);
+        Ok(())
+    }
+
+    pub fn save(&self, store: &StateStore) -> Result<(), Error> {
+        if let Some(occupancy) = &self.occupancy {
+            store.save(STATE_OCCUPANCY, |path| occupancy.save(&self.grid, path))?;
+        }
+        Ok(())
+    }
+}
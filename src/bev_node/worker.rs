@@ -0,0 +1,210 @@
+use super::config::BevConfig;
+use super::process::{self, FrameStats};
+use super::report::Report;
+use super::state::BevState;
+use crate::alloc_stats;
+use crate::cli::{OutputOptions, Verbosity};
+use crate::crash_dump::{self, CrashRecorder};
+use crate::params;
+use crate::persist::StateStore;
+use crate::pipeline::{CloudOutput, FrameQueue};
+use crate::rt;
+use crate::split;
+use crate::stats::RunTotals;
+use crate::supervisor::{self, Supervisor};
+use anyhow::{anyhow, Error};
+use rclrs::Node;
+use sensor_msgs::msg::PointCloud2;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// 작업 스레드 설정
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    // 프레임 하나를 포인트 timestamp 로 나눠 scan_split 배 빠르게 발행 (1 이면 나누지 않음)
+    // 조각마다 header stamp 를 구간 시작 시각으로 보간, 장애물 회피 반응 지연 감소용
+    pub scan_split: usize,
+    // 작업 스레드 CPU 고정 목록과 SCHED_FIFO 우선순위 (0 이면 일반 스케줄링)
+    pub cpus: Vec<usize>,
+    pub priority: i32,
+}
+
+impl WorkerConfig {
+    pub fn from_node(node: &Node) -> Result<Self, Error> {
+        Ok(WorkerConfig {
+            scan_split: params::int(node, "scan_split", 1)?.max(1) as usize,
+            cpus: params::int_array(node, "worker_cpus", &[])?
+                .into_iter()
+                .map(|cpu| cpu as usize)
+                .collect(),
+            priority: params::int(node, "worker_priority", 0)? as i32,
+        })
+    }
+}
+
+// 큐에서 프레임을 꺼내 처리하고 발행하는 작업 스레드
+pub struct Worker {
+    pub config: BevConfig,
+    pub state: BevState,
+    pub output: CloudOutput,
+    pub queue: Arc<FrameQueue<PointCloud2>>,
+    pub recorder: Arc<CrashRecorder>,
+    pub supervisor: Arc<Supervisor>,
+    pub report: Report,
+    pub state_store: Option<StateStore>,
+    pub output_options: OutputOptions,
+}
+
+impl Worker {
+    // 큐가 닫히고 남은 프레임까지 처리하면 누적 통계를 돌려줌
+    pub fn spawn(self) -> JoinHandle<RunTotals> {
+        thread::spawn(move || self.run())
+    }
+
+    fn run(mut self) -> RunTotals {
+        rt::apply_thread_options(
+            "bev_worker",
+            &self.config.worker.cpus,
+            self.config.worker.priority,
+        );
+
+        let mut totals = RunTotals::default();
+        while let Some(msg) = self.queue.pop() {
+            self.recorder.record(&msg);
+            let sub_frames = match split::split(msg, self.config.worker.scan_split) {
+                Ok(sub_frames) => sub_frames,
+                Err(e) => {
+                    totals.add_error();
+                    eprintln!("프레임 분할 오류: {}", e);
+                    continue;
+                }
+            };
+            // 조각은 원래 측정 간격대로 발행, 다음 프레임이 밀려 있으면 기다리지 않음
+            let frame_start = Instant::now();
+            for sub in sub_frames {
+                let due = frame_start + Duration::from_secs_f64(sub.offset);
+                if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                    if self.queue.is_empty() {
+                        thread::sleep(wait);
+                    }
+                }
+                self.frame(sub.msg, &mut totals);
+            }
+        }
+        self.finish(totals)
+    }
+
+    // 프레임 (또는 scan_split 조각) 하나를 처리하고 진단 발행
+    fn frame(&mut self, msg: PointCloud2, totals: &mut RunTotals) {
+        let before = alloc_stats::snapshot();
+        let mut frame_us = None;
+        match self.process(msg) {
+            Ok(stats) => {
+                frame_us = Some(stats.timer.total_us());
+                self.report.frame(&stats);
+                totals.add_invalid(stats.invalid_points);
+                totals.add_frame(
+                    stats.input_points,
+                    stats.output_points,
+                    stats.timer.total_us(),
+                );
+                if self.output_options.tick() {
+                    println!(
+                        "BEV 발행: 원본 {} 포인트 -> 필터링 후 {} 포인트",
+                        stats.input_points, stats.output_points
+                    );
+                    if self.output_options.verbose() {
+                        println!("  단계별 처리 시간(us): {}", stats.timer.summary());
+                    }
+                }
+                self.state
+                    .ground
+                    .finish_frame(&stats.header, stats.ground, stats.attitude);
+                self.state
+                    .outputs
+                    .publish_timing(&stats.timer, &stats.header);
+            }
+            Err(e) => {
+                totals.add_error();
+                eprintln!("BEV 처리 중 오류: {}", e);
+            }
+        }
+        let after = alloc_stats::snapshot();
+
+        let dropped = self.queue.dropped();
+        let verbosity = self.output_options.verbosity;
+        if let (Some(load), Some(us)) = (&mut self.state.load, frame_us) {
+            if let Some(next) = load.update(us, self.report.dropping(dropped)) {
+                if verbosity > Verbosity::Quiet {
+                    println!("처리 품질 단계 변경: {:?}", next);
+                }
+            }
+        }
+        self.report.publish(
+            dropped,
+            totals,
+            &self.output,
+            &mut self.state,
+            (before, after),
+            verbosity,
+        );
+        if let Some(store) = &mut self.state_store {
+            if store.due() {
+                if let Err(e) = self.state.save_state(store) {
+                    eprintln!("상태 저장 오류: {}", e);
+                }
+            }
+        }
+    }
+
+    // 패닉은 supervisor 가 세고, 반복되면 처리 상태를 초기화
+    // 복구하는 패닉은 덤프하지 않고, 잡지 않는 설정이면 전파 전에 덤프
+    fn process(&mut self, msg: PointCloud2) -> Result<FrameStats, Error> {
+        let (config, state, output) = (&self.config, &mut self.state, &self.output);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            crash_dump::recovering(|| process::process_and_publish_bev(msg, output, config, state))
+        }))
+        .unwrap_or_else(|payload| {
+            if !self.supervisor.catches_panics() {
+                if self.recorder.enabled() {
+                    self.recorder.dump_and_report("panic");
+                }
+                panic::resume_unwind(payload);
+            }
+            let message = supervisor::panic_message(payload.as_ref());
+            if let Some(event) = self.supervisor.record_panic(&message) {
+                eprintln!("{}", event.describe());
+                self.state.reset(&self.config);
+            }
+            Err(anyhow!("처리 중 패닉: {}", message))
+        })
+    }
+
+    // 더블 버퍼에 남은 메시지까지 발행하고 학습한 상태 저장
+    fn finish(self, mut totals: RunTotals) -> RunTotals {
+        totals.input_bytes = self.report.input_bytes();
+        totals.output_bytes = self.output.published_bytes()
+            + self
+                .state
+                .outputs
+                .bytes()
+                .iter()
+                .map(|(_, bytes)| bytes)
+                .sum::<u64>();
+        self.output.finish();
+        if let Some(visibility) = &self.state.visibility {
+            if let Err(e) = visibility.save_occupancy() {
+                eprintln!("점유 지도 저장 오류: {}", e);
+            }
+        }
+        if let Some(store) = &self.state_store {
+            match self.state.save_state(store) {
+                Ok(()) => println!("상태 저장: {}", self.config.record.state_dir),
+                Err(e) => eprintln!("상태 저장 오류: {}", e),
+            }
+        }
+        totals
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::{Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::autolabel::{self, AutoLabelConfig, AutoLabeler};
+use rust_lidar::classify::ClassRules;
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::cluster::{self, ClusterConfig};
+use rust_lidar::core::LidarPoint;
+use rust_lidar::dataset::{DatasetFormat, DatasetWriter};
+use rust_lidar::exclusion::{ExclusionZones, ZoneFrame};
+use rust_lidar::ground::{GroundEstimator, GroundFitConfig};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::tracking::{Tracker, TrackerConfig};
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// 자동 라벨링 도구: bag 재생 (ros2 bag play) 중 livox/lidar 를 모아 지면 분리, 클러스터 추적, 상자 맞춤으로
+// 초기 3D 상자 라벨을 만들고 종료 시 dataset_format 형식으로 dataset_dir 에 저장
+// 모든 상자에 신뢰도 (score) 가 붙고 review.json 에 트랙별 요약을 신뢰도 낮은 순으로 저장 (사람 검토용)
+// 트랙 전체를 본 뒤 라벨을 정하므로 프레임을 메모리에 모음, autolabel_max_frames 에 도달하면 종료
+fn main() -> Result<(), Error> {
+    println!("LiDAR Auto Labeling Tool");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "lidar_auto_labeler")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let exclusion = ExclusionZones::from_node(&node)?;
+    let min_height = params::float(&node, "object_min_height", 0.2)? as f32;
+    let max_height = params::float(&node, "object_max_height", 3.0)? as f32;
+    let cluster_config = ClusterConfig {
+        cell_size: params::float(&node, "cluster_cell_size", 0.3)? as f32,
+        min_points: params::int(&node, "cluster_min_points", 5)?.max(1) as usize,
+        ..ClusterConfig::default()
+    };
+    let mut tracker = Tracker::new(TrackerConfig {
+        gate: params::float(&node, "track_gate", 1.5)? as f32,
+        max_missed: params::int(&node, "track_max_missed", 3)?.max(0) as u32,
+        ..TrackerConfig::default()
+    });
+    let rules_path = params::string(&node, "classifier_rules", "")?;
+    let rules = if rules_path.is_empty() {
+        ClassRules::default()
+    } else {
+        ClassRules::load(Path::new(&rules_path))?
+    };
+    let format = DatasetFormat::parse(&params::string(&node, "dataset_format", "kitti")?)?;
+    let dataset_dir = params::string(&node, "dataset_dir", "autolabel")?;
+    let dataset_accumulate = params::int(&node, "dataset_accumulate", 1)?.max(1) as usize;
+    let max_frames = params::int(&node, "autolabel_max_frames", 600)?.max(1) as usize;
+    let config = AutoLabelConfig {
+        confident_frames: params::int(&node, "autolabel_confident_frames", 10)?.max(1) as usize,
+        confident_points: params::int(&node, "autolabel_confident_points", 50)?.max(1) as usize,
+        min_score: params::float(&node, "autolabel_min_score", 0.1)? as f32,
+        ..AutoLabelConfig::default()
+    };
+    let mut ground = GroundEstimator::new(GroundFitConfig::default(), 0.3);
+
+    let labeler = Arc::new(Mutex::new(AutoLabeler::new(config)));
+    let callback_labeler = Arc::clone(&labeler);
+    let callback_shutdown = Arc::clone(&shutdown);
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let mut labeler = callback_labeler.lock().unwrap();
+            if labeler.len() >= max_frames {
+                return;
+            }
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            let exclude = |frame, cloud: &mut PointCloud<_>| {
+                if let Some(zones) = exclusion.as_ref().filter(|z| z.frame == frame) {
+                    zones.apply(cloud);
+                }
+            };
+            exclude(ZoneFrame::Sensor, &mut cloud);
+            mount.apply(&mut cloud);
+            exclude(ZoneFrame::Base, &mut cloud);
+            let plane = ground.update(&cloud);
+
+            // 저장 포인트와 상자 모두 지면 위 높이 기준
+            let points: Vec<LidarPoint> = cloud
+                .iter()
+                .map(|p| LidarPoint {
+                    z: plane.map_or(p.z, |plane| plane.distance([p.x, p.y, p.z])),
+                    ..*p
+                })
+                .collect();
+            let above: Vec<[f32; 3]> = points
+                .iter()
+                .map(|p| [p.x, p.y, p.z])
+                .filter(|p| p[2] >= min_height && p[2] <= max_height)
+                .collect();
+            let clusters = cluster::euclidean(&above, &cluster_config);
+            let time = stamp::to_secs(&cloud.header.stamp);
+            tracker.update(time, &clusters);
+            labeler.push(time, points, &above, tracker.tracks(), &rules);
+            if labeler.len().is_multiple_of(100) {
+                println!("프레임 {}개 수집", labeler.len());
+            }
+            if labeler.len() >= max_frames {
+                callback_shutdown.request();
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar (최대 {} 프레임)", max_frames);
+    shutdown.spin(&node)?;
+    drop(subscriber);
+
+    let labeler = std::mem::replace(&mut *labeler.lock().unwrap(), AutoLabeler::new(config));
+    let (frames, review) = labeler.finish();
+    let mut writer = DatasetWriter::new(format, Path::new(&dataset_dir), dataset_accumulate)?;
+    let mut boxes = 0;
+    for frame in &frames {
+        writer.write_frame(frame.time, &frame.points, &frame.boxes)?;
+        boxes += frame.boxes.len();
+    }
+    writer.finish()?;
+    let review_path = Path::new(&dataset_dir).join("review.json");
+    fs::write(&review_path, autolabel::review_json(&review))?;
+    println!(
+        "라벨 저장: {} (프레임 {}, 상자 {}, 트랙 {})",
+        dataset_dir,
+        frames.len(),
+        boxes,
+        review.len()
+    );
+    println!("검토 목록: {}", review_path.display());
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::calibration::{self, MountCalibration};
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::ground::{self, GroundFitConfig, Plane};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::point::LidarPoint;
+use rust_lidar::shutdown::Shutdown;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// 차량을 평평한 바닥에 세워 두고 실행: N 프레임의 지면 평면에서 장착 높이/롤/피치를 추정해
+// 파라미터 파일의 mount_* 값을 갱신 (bev_pub 등은 --params-file 로 사용)
+fn main() -> Result<(), Error> {
+    println!("LiDAR Mount Calibration Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "lidar_mount_calibration")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let frames = params::int(&node, "calib_frames", 50)?.max(1) as usize;
+    let output = PathBuf::from(params::string(&node, "calib_output", "mount.yaml")?);
+    let fit_config = GroundFitConfig {
+        iterations: params::int(&node, "calib_iterations", 200)?.max(1) as usize,
+        threshold: params::float(&node, "calib_threshold", 0.03)? as f32,
+        max_tilt: (params::float(&node, "calib_max_tilt_deg", 30.0)? as f32).to_radians(),
+        max_range: params::float(&node, "calib_max_range", 20.0)? as f32,
+        ..GroundFitConfig::default()
+    };
+
+    let planes: Arc<Mutex<Vec<Plane>>> = Arc::new(Mutex::new(Vec::with_capacity(frames)));
+    let callback_planes = Arc::clone(&planes);
+    let callback_shutdown = Arc::clone(&shutdown);
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let cloud: PointCloud<LidarPoint> = PointCloud::new(msg.header, points);
+            let Some(fit) = ground::fit_ground(&cloud, &fit_config) else {
+                eprintln!("지면 평면을 찾지 못했습니다 (포인트 {})", cloud.len());
+                return;
+            };
+
+            let mut planes = callback_planes.lock().unwrap();
+            planes.push(fit.plane);
+            println!(
+                "[{}/{}] 높이 {:.3} m, 기울기 {:.2} deg, 인라이어 {}",
+                planes.len(),
+                frames,
+                fit.plane.height(),
+                fit.plane.tilt().to_degrees(),
+                fit.inliers
+            );
+            if planes.len() >= frames {
+                callback_shutdown.request();
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("{} 프레임 수집 중...", frames);
+    shutdown.spin(&node)?;
+    drop(subscriber);
+
+    let planes = planes.lock().unwrap();
+    let Some(calibration) = MountCalibration::from_planes(&planes) else {
+        bail!("지면 평면을 한 번도 찾지 못해 보정하지 않았습니다");
+    };
+    if calibration.frames < frames {
+        println!("중단됨: {} 프레임만으로 보정합니다", calibration.frames);
+    }
+
+    println!("=== 장착 보정 결과 ===");
+    for (key, value) in calibration.params() {
+        println!("{}: {:.4}", key, value);
+    }
+    calibration::update_params_file(&output, &calibration.params())?;
+    println!("저장: {}", output.display());
+    Ok(())
+}
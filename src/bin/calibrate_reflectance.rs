@@ -0,0 +1,113 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::calibration::{self, ReflectanceTarget};
+use rust_lidar::intensity::{IntensityTable, ReflectanceModel, ReflectanceSample};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::shutdown::Shutdown;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// 반사율을 아는 기준 타깃 (예: 10% / 50% / 90% 반사판) 을 서로 다른 거리에 세워 두고 실행:
+// N 프레임 동안 타깃 상자 안 포인트의 intensity/거리를 모아 반사율 모델을 맞추고
+// 파라미터 파일의 reflectance_* 값을 갱신 (bev_pub 에서 reflectance_model:=true 로 사용)
+// 라인별 보정 표 (intensity_calibration) 가 있으면 먼저 적용한 값으로 맞춤
+fn main() -> Result<(), Error> {
+    println!("LiDAR Reflectance Calibration Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "lidar_reflectance_calibration")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let frames = params::int(&node, "calib_frames", 50)?.max(1) as usize;
+    let output = PathBuf::from(params::string(&node, "calib_output", "reflectance.yaml")?);
+    let targets = params::string_array(&node, "calib_targets", &[])?
+        .iter()
+        .map(|t| ReflectanceTarget::parse(t))
+        .collect::<Result<Vec<_>>>()?;
+    if targets.len() < 2 {
+        bail!("calib_targets 에 반사율이 다른 타깃이 2개 이상 필요합니다");
+    }
+    let table = IntensityTable::from_node(&node)?;
+
+    let samples: Arc<Mutex<Vec<ReflectanceSample>>> = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::new(Mutex::new(0usize));
+    let callback_samples = Arc::clone(&samples);
+    let callback_received = Arc::clone(&received);
+    let callback_shutdown = Arc::clone(&shutdown);
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let mut points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            if let Some(table) = &table {
+                table.apply(&mut points);
+            }
+
+            let mut samples = callback_samples.lock().unwrap();
+            let mut seen = 0;
+            for target in &targets {
+                if let Some(sample) = target.observe(&points) {
+                    samples.push(sample);
+                    seen += 1;
+                }
+            }
+            let mut received = callback_received.lock().unwrap();
+            *received += 1;
+            println!(
+                "[{}/{}] 보인 타깃 {}/{}, 관측 {}",
+                received,
+                frames,
+                seen,
+                targets.len(),
+                samples.len()
+            );
+            if *received >= frames {
+                callback_shutdown.request();
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("{} 프레임 수집 중...", frames);
+    shutdown.spin(&node)?;
+    drop(subscriber);
+
+    let samples = samples.lock().unwrap();
+    let received = *received.lock().unwrap();
+    if received < frames {
+        println!("중단됨: {} 프레임만으로 보정합니다", received);
+    }
+    let model = ReflectanceModel::fit(&samples)?;
+    if model.range_exp == 0.0 {
+        println!("타깃 거리가 비슷해 거리 항은 맞추지 않았습니다");
+    }
+
+    println!("=== 반사율 보정 결과 ({} 관측) ===", samples.len());
+    for (key, value) in model.params() {
+        println!("{}: {:.6}", key, value);
+    }
+    // 알려진 반사율 대비 모델 값의 평균 상대 오차
+    let error = samples
+        .iter()
+        .map(|s| (model.reflectance(s.intensity, s.range) - s.reflectance).abs() / s.reflectance)
+        .sum::<f32>()
+        / samples.len() as f32;
+    println!("평균 상대 오차: {:.1}%", error * 100.0);
+    calibration::update_params_file(&output, &model.params())?;
+    println!(
+        "저장: {} (bev_pub 에서 reflectance_model:=true 로 사용)",
+        output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::calibration;
+use rust_lidar::imu::ImuSample;
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::registration::IcpConfig;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::time_offset::{self, MotionSignal, OffsetConfig, ScanMotion};
+use sensor_msgs::msg::{Imu, PointCloud2};
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+// 센서를 여러 축으로 흔들면서 실행: livox/lidar 와 기준 센서 (offset_reference=imu 면 imu_topic,
+// lidar 면 reference_topic) 의 회전 속도 신호를 상관 비교해 고정 시간 오프셋을 추정
+// 결과는 파라미터 파일의 time_offset 으로 저장 (LiDAR 시각 + time_offset = 기준 센서 시각, bev_pub 이 deskew 에 사용)
+fn main() -> Result<(), Error> {
+    println!("LiDAR Time Offset Calibration Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "lidar_time_offset_calibration")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let duration = params::float(&node, "calib_duration", 30.0)?;
+    let output = PathBuf::from(params::string(&node, "calib_output", "time_offset.yaml")?);
+    let config = OffsetConfig {
+        max_offset: params::float(&node, "calib_max_offset", 0.2)?,
+        step: params::float(&node, "calib_step", 0.002)?,
+        ..OffsetConfig::default()
+    };
+    let min_correlation = params::float(&node, "calib_min_correlation", 0.5)?;
+    let voxel = params::float(&node, "calib_voxel", 0.3)? as f32;
+    let icp = IcpConfig {
+        max_distance: params::float(&node, "calib_icp_max_distance", 0.5)? as f32,
+        ..IcpConfig::default()
+    };
+    let reference_kind = params::string(&node, "offset_reference", "imu")?;
+
+    let target = Arc::new(Mutex::new(ScanMotion::new(voxel, icp)));
+    let scan_callback = move |motion: Arc<Mutex<ScanMotion>>| {
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let xyz: Vec<[f32; 3]> = points.iter().map(|p| [p.x, p.y, p.z]).collect();
+            motion
+                .lock()
+                .unwrap()
+                .add(stamp::to_secs(&msg.header.stamp), &xyz);
+        }
+    };
+
+    // 기준 센서 신호 (IMU 자이로 또는 두 번째 LiDAR 스캔 정합)
+    let gyro = Arc::new(Mutex::new(MotionSignal::new()));
+    let reference_scans = Arc::new(Mutex::new(ScanMotion::new(voxel, icp)));
+    let (_imu_subscriber, _reference_subscriber, reference_topic) = match reference_kind.as_str() {
+        "imu" => {
+            let topic = params::string(&node, "imu_topic", "livox/imu")?;
+            let gyro = Arc::clone(&gyro);
+            let subscriber = node.create_subscription::<Imu, _>(
+                &topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: Imu| {
+                    let sample = ImuSample::from_msg(&msg, 1.0);
+                    gyro.lock().unwrap().push_gyro(sample.time, sample.gyro);
+                },
+            )?;
+            (Some(subscriber), None, topic)
+        }
+        "lidar" => {
+            let topic = params::string(&node, "reference_topic", "livox/lidar2")?;
+            let subscriber = node.create_subscription::<PointCloud2, _>(
+                &topic,
+                rclrs::QOS_PROFILE_DEFAULT,
+                scan_callback(Arc::clone(&reference_scans)),
+            )?;
+            (None, Some(subscriber), topic)
+        }
+        other => bail!("알 수 없는 offset_reference '{}' (imu, lidar)", other),
+    };
+
+    let callback_target = Arc::clone(&target);
+    let callback_shutdown = Arc::clone(&shutdown);
+    let on_scan = scan_callback(Arc::clone(&target));
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            on_scan(msg);
+            let span = callback_target
+                .lock()
+                .unwrap()
+                .signal
+                .span()
+                .map_or(0.0, |(start, end)| end - start);
+            if span >= duration {
+                callback_shutdown.request();
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar, {}", reference_topic);
+    println!("{:.0} 초 수집 중... (센서를 여러 방향으로 회전)", duration);
+    shutdown.spin(&node)?;
+    drop(subscriber);
+
+    let target = target.lock().unwrap();
+    let reference = match reference_kind.as_str() {
+        "imu" => gyro.lock().unwrap().clone(),
+        _ => reference_scans.lock().unwrap().signal.clone(),
+    };
+    println!(
+        "회전 속도 표본: LiDAR {}, 기준 {}",
+        target.signal.len(),
+        reference.len()
+    );
+    let Some(estimate) = time_offset::estimate(&reference, &target.signal, &config) else {
+        bail!("겹치는 표본이 부족해 오프셋을 추정하지 못했습니다");
+    };
+    println!("=== 시간 오프셋 추정 결과 ===");
+    println!("time_offset: {:.4} s", estimate.offset);
+    println!(
+        "상관: {:.3} (겹친 표본 {})",
+        estimate.correlation, estimate.samples
+    );
+    if estimate.correlation < min_correlation {
+        bail!(
+            "상관 {:.3} 이 calib_min_correlation {:.3} 보다 낮아 저장하지 않았습니다 (움직임 부족)",
+            estimate.correlation,
+            min_correlation
+        );
+    }
+    if (estimate.offset.abs() - config.max_offset).abs() < config.step {
+        println!("경고: 추정값이 탐색 범위 끝입니다, calib_max_offset 을 늘려 다시 실행하세요");
+    }
+    calibration::update_params_file(&output, &[("time_offset", estimate.offset)])?;
+    println!("저장: {}", output.display());
+    Ok(())
+}
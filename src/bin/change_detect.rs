@@ -0,0 +1,140 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::builder::PointCloud2Builder;
+use rust_lidar::change::{self, ChangeConfig};
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::diagnostics::{self, Diagnostics};
+use rust_lidar::layout;
+use rust_lidar::map_file;
+use rust_lidar::params;
+use rust_lidar::point::datatype;
+use rust_lidar::registration::IcpConfig;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path;
+use std_msgs::msg::Header;
+
+// 변화 검출 노드: change_frames 프레임을 모아 기준 지도 (change_reference, map_file 형식) 에 정합하고
+// 서로 change_threshold 안에 대응점이 없는 포인트를 발행
+// livox/changes: 기준 지도 좌표계 포인트 (change: 1 추가, 2 제거), 요약은 /diagnostics
+const ADDED: f64 = 1.0;
+const REMOVED: f64 = 2.0;
+
+fn main() -> Result<(), Error> {
+    println!("LiDAR Change Detection Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "change_detector")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let reference_path = params::string(&node, "change_reference", "")?;
+    if reference_path.is_empty() {
+        bail!("change_reference (기준 지도 파일) 가 필요합니다");
+    }
+    let (map_header, map_points) = map_file::load(Path::new(&reference_path))?;
+    let reference: Vec<[f32; 3]> = map_points.iter().map(|p| [p.x, p.y, p.z]).collect();
+    println!(
+        "기준 지도: {} ({} 포인트, frame {})",
+        reference_path,
+        reference.len(),
+        map_header.frame_id
+    );
+    let defaults = ChangeConfig::default();
+    let config = ChangeConfig {
+        voxel: params::float(&node, "change_voxel", defaults.voxel as f64)? as f32,
+        threshold: params::float(&node, "change_threshold", defaults.threshold as f64)? as f32,
+        max_range: params::float(&node, "change_max_range", defaults.max_range as f64)? as f32,
+        icp: IcpConfig {
+            max_distance: params::float(&node, "change_icp_max_distance", 1.0)? as f32,
+            ..IcpConfig::default()
+        },
+        min_fitness: params::float(&node, "change_min_fitness", defaults.min_fitness)?,
+    };
+    let frames = params::int(&node, "change_frames", 10)?.max(1) as usize;
+
+    let publisher =
+        node.create_publisher::<PointCloud2>("livox/changes", rclrs::QOS_PROFILE_DEFAULT)?;
+    let mut diagnostics = Diagnostics::new(&node, "change_detector")?;
+    let mut accumulated: Vec<[f32; 3]> = Vec::new();
+    let mut count = 0;
+    let _subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            mount.apply(&mut cloud);
+            accumulated.extend(cloud.iter().map(|p| [p.x, p.y, p.z]));
+            count += 1;
+            if count < frames {
+                return;
+            }
+
+            let result = change::detect(&reference, &accumulated, &config);
+            accumulated.clear();
+            count = 0;
+
+            let mut builder = PointCloud2Builder::new()
+                .add_field("x", datatype::FLOAT32)
+                .add_field("y", datatype::FLOAT32)
+                .add_field("z", datatype::FLOAT32)
+                .add_field("change", datatype::UINT8);
+            for (points, kind) in [(&result.added, ADDED), (&result.removed, REMOVED)] {
+                for p in points {
+                    builder.push_point(&[p[0] as f64, p[1] as f64, p[2] as f64, kind]);
+                }
+            }
+            let header = Header {
+                stamp: cloud.header.stamp,
+                frame_id: map_header.frame_id.clone(),
+            };
+            if let Err(e) = publisher.publish(builder.finish(header)) {
+                eprintln!("발행 오류: {}", e);
+            }
+
+            println!(
+                "변화: 추가 {} ({:.1}%), 제거 {} ({:.1}%), 정합 {}",
+                result.added.len(),
+                result.added_ratio() * 100.0,
+                result.removed.len(),
+                result.removed_ratio() * 100.0,
+                if result.aligned() { "성공" } else { "실패" }
+            );
+            let mut values = vec![
+                ("added_points", result.added.len().to_string()),
+                ("removed_points", result.removed.len().to_string()),
+                ("added_ratio", format!("{:.4}", result.added_ratio())),
+                ("removed_ratio", format!("{:.4}", result.removed_ratio())),
+                ("aligned", result.aligned().to_string()),
+            ];
+            if let Some(icp) = &result.icp {
+                values.push(("icp_fitness", format!("{:.3}", icp.fitness)));
+                values.push(("icp_rmse", format!("{:.3}", icp.rmse)));
+            }
+            let level = if result.aligned() {
+                diagnostics::OK
+            } else {
+                diagnostics::WARN
+            };
+            if let Err(e) = diagnostics.publish(level, "변화 검출", &values) {
+                eprintln!("진단 정보 발행 오류: {}", e);
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/changes");
+    shutdown.spin(&node)?;
+    Ok(())
+}
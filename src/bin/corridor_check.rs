@@ -0,0 +1,125 @@
+use anyhow::{bail, Error, Result};
+use nav_msgs::msg::{OccupancyGrid, Path};
+use rclrs::{self, Context};
+use rust_lidar::corridor;
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::visibility;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std_msgs::msg::Float32;
+
+// 통로가 비어 있을 때 발행하는 값
+const CLEAR: f32 = -1.0;
+
+// 경로(nav_msgs/Path)를 따라 폭 corridor_width 인 통로에서 처음 막히는 거리를 발행 (감시용)
+// TF 를 쓰지 않으므로 경로와 장애물은 같은 좌표계여야 함
+fn main() -> Result<(), Error> {
+    println!("Path Corridor Checker Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "corridor_checker")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let half_width = params::float(&node, "corridor_width", 1.2)? as f32 / 2.0;
+    let min_points = params::int(&node, "corridor_min_points", 3)?.max(1) as usize;
+    let path_topic = params::string(&node, "path_topic", "plan")?;
+    // cloud: BEV 포인트 클라우드, grid: 가시성 격자의 occupied 셀
+    let source = params::string(&node, "obstacle_source", "cloud")?;
+
+    let publisher =
+        node.create_publisher::<Float32>("corridor/blocking_distance", rclrs::QOS_PROFILE_DEFAULT)?;
+    let path: Arc<Mutex<Option<Path>>> = Arc::new(Mutex::new(None));
+
+    let path_store = Arc::clone(&path);
+    let _path_subscriber = node.create_subscription::<Path, _>(
+        &path_topic,
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: Path| {
+            *path_store.lock().unwrap() = Some(msg);
+        },
+    )?;
+
+    // 최신 경로와 장애물 좌표로 검사 후 발행
+    let check = move |frame_id: &str, obstacles: Vec<[f32; 2]>| {
+        let path = path.lock().unwrap();
+        let Some(path) = path.as_ref() else {
+            return;
+        };
+        if path.header.frame_id != frame_id {
+            eprintln!(
+                "경로({})와 장애물({}) 좌표계가 다릅니다",
+                path.header.frame_id, frame_id
+            );
+            return;
+        }
+        let polyline: Vec<[f32; 2]> = path
+            .poses
+            .iter()
+            .map(|p| [p.pose.position.x as f32, p.pose.position.y as f32])
+            .collect();
+        let distance = corridor::first_blocking(&polyline, obstacles, half_width, min_points);
+        let data = distance.unwrap_or(CLEAR);
+        if let Err(e) = publisher.publish(Float32 { data }) {
+            eprintln!("발행 오류: {}", e);
+        }
+    };
+
+    // 구독은 spin 이 끝날 때까지 살아 있어야 함
+    let mut _cloud_subscriber = None;
+    let mut _grid_subscriber = None;
+    match source.as_str() {
+        "cloud" => {
+            _cloud_subscriber = Some(node.create_subscription::<PointCloud2, _>(
+                "livox/lidar_bev",
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: PointCloud2| match layout::parse(&msg, input_layout) {
+                    Ok(points) => check(
+                        &msg.header.frame_id,
+                        points.iter().map(|p| [p.x, p.y]).collect(),
+                    ),
+                    Err(e) => eprintln!("PointCloud2 파싱 실패: {}", e),
+                },
+            )?);
+            println!("장애물 토픽: livox/lidar_bev");
+        }
+        "grid" => {
+            _grid_subscriber = Some(node.create_subscription::<OccupancyGrid, _>(
+                "livox/lidar_bev/visibility",
+                rclrs::QOS_PROFILE_DEFAULT,
+                move |msg: OccupancyGrid| {
+                    let info = &msg.info;
+                    let cell = info.resolution;
+                    let obstacles = msg
+                        .data
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, &v)| v == visibility::OCCUPIED)
+                        .map(|(i, _)| {
+                            let (col, row) = (i as u32 % info.width, i as u32 / info.width);
+                            [
+                                info.origin.position.x as f32 + (col as f32 + 0.5) * cell,
+                                info.origin.position.y as f32 + (row as f32 + 0.5) * cell,
+                            ]
+                        })
+                        .collect();
+                    check(&msg.header.frame_id, obstacles);
+                },
+            )?);
+            println!("장애물 토픽: livox/lidar_bev/visibility");
+        }
+        other => bail!("알 수 없는 obstacle_source '{}' (cloud, grid)", other),
+    }
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("경로 토픽: {}", path_topic);
+    println!(
+        "발행 토픽: corridor/blocking_distance (막힌 곳이 없으면 {})",
+        CLEAR
+    );
+    shutdown.spin(&node)?;
+    Ok(())
+}
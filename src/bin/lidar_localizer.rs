@@ -0,0 +1,396 @@
+use anyhow::{bail, Error, Result};
+use geometry_msgs::msg::PoseWithCovarianceStamped;
+use nav_msgs::msg::Odometry;
+use rclrs::{self, Context, Publisher};
+use rust_lidar::cli::OutputOptions;
+use rust_lidar::cloud::{PointCloud, PointXYZI};
+use rust_lidar::compute;
+use rust_lidar::diagnostics::{self, Diagnostics};
+use rust_lidar::layout::{self, NamedLayout};
+use rust_lidar::localization::{LocalizeResult, Localizer, LocalizerConfig};
+use rust_lidar::ndt::NdtConfig;
+use rust_lidar::params;
+use rust_lidar::pose::Pose;
+use rust_lidar::qos::QosPreset;
+use rust_lidar::registration::IcpConfig;
+use rust_lidar::relocalization::{Relocalization, RelocalizeConfig};
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::stats::RunTotals;
+use rust_lidar::tile_cache::TileCache;
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use std_msgs::msg::Header;
+use std_srvs::srv::{Trigger, Trigger_Response};
+
+// 정합 실패 시 공분산 대각값, EKF 가 사실상 무시하도록 큼
+const UNMATCHED_VARIANCE: f64 = 1e3;
+
+// 지도 좌표계 자세와 공분산 (child frame 은 입력 클라우드 frame)
+fn odometry_msg(header: &Header, map_frame: &str, result: &LocalizeResult) -> Odometry {
+    let mut msg = Odometry {
+        header: Header {
+            stamp: header.stamp.clone(),
+            frame_id: map_frame.to_string(),
+        },
+        child_frame_id: header.frame_id.clone(),
+        ..Default::default()
+    };
+    msg.pose.pose = result.pose.to_msg();
+    for i in 0..6 {
+        for j in 0..6 {
+            msg.pose.covariance[i * 6 + j] = match &result.covariance {
+                Some(c) => c.covariance[i][j],
+                None if i == j => UNMATCHED_VARIANCE,
+                None => 0.0,
+            };
+        }
+    }
+    msg
+}
+
+// 현재 위치 주변 부분 지도를 주기적으로 발행 (RViz 표시용, 전체 지도 대신)
+struct SubmapOutput {
+    publisher: Arc<Publisher<PointCloud2>>,
+    radius: f32,
+    voxel: f32,
+    // 발행 주기 (스캔 시각 기준 초, 0 이하면 발행 안 함)
+    interval: f64,
+    last: Option<f64>,
+}
+
+impl SubmapOutput {
+    fn update(&mut self, header: &Header, localizer: &Localizer, map_frame: &str) -> Result<()> {
+        if self.interval <= 0.0 {
+            return Ok(());
+        }
+        let time = stamp::to_secs(&header.stamp);
+        // 시간이 되돌아가면 (bag 반복 재생) 바로 다시 발행
+        if self
+            .last
+            .is_some_and(|last| time >= last && time - last < self.interval)
+        {
+            return Ok(());
+        }
+        self.last = Some(time);
+        let points = localizer
+            .submap(self.radius, self.voxel)
+            .into_iter()
+            .map(|[x, y, z]| PointXYZI {
+                x,
+                y,
+                z,
+                intensity: 0.0,
+            })
+            .collect();
+        let header = Header {
+            stamp: header.stamp.clone(),
+            frame_id: map_frame.to_string(),
+        };
+        self.publisher
+            .publish(PointCloud::new(header, points).to_msg())?;
+        Ok(())
+    }
+}
+
+// 재위치 추정 결과 출력 (reason: 요청 출처)
+fn report_relocalization(reason: &str, result: &Result<Option<Relocalization>>) -> String {
+    let message = match result {
+        Ok(Some(found)) => {
+            let [x, y, _] = found.pose.position;
+            format!(
+                "재위치 추정 성공 ({}): ({:.2}, {:.2}) yaw {:.1} deg, 점수 {:.2}",
+                reason,
+                x,
+                y,
+                found.pose.yaw().to_degrees(),
+                found.score
+            )
+        }
+        Ok(None) => format!("재위치 추정 실패 ({}): 일치하는 위치 없음", reason),
+        Err(e) => format!("재위치 추정 중 오류 ({}): {}", reason, e),
+    };
+    println!("{}", message);
+    message
+}
+
+fn process_scan(
+    msg: PointCloud2,
+    localizer: &mut Localizer,
+    publisher: &Arc<Publisher<Odometry>>,
+    input_layout: Option<&NamedLayout>,
+    mount: &Transform,
+    map_frame: &str,
+    submap: &mut SubmapOutput,
+) -> Result<(usize, LocalizeResult), Error> {
+    let mut cloud = PointCloud::new(msg.header.clone(), layout::parse(&msg, input_layout)?);
+    mount.apply(&mut cloud);
+    let points: Vec<[f32; 3]> = cloud.iter().map(|p| [p.x, p.y, p.z]).collect();
+    let result = localizer.localize(&points)?;
+    publisher.publish(odometry_msg(&cloud.header, map_frame, &result))?;
+    submap.update(&cloud.header, localizer, map_frame)?;
+    Ok((points.len(), result))
+}
+
+fn main() -> Result<(), Error> {
+    println!("LiDAR Localizer Node");
+    // --quiet / --verbose / --every N / --qos PRESET / --replay-config FILE
+    let mut output_options = OutputOptions::from_args(env::args())?;
+    // 포인트 클라우드 QoS (전송 설정은 Context 생성 전에 적용)
+    let qos = QosPreset::parse(&output_options.qos)?;
+    qos.apply_transport();
+    let context = Context::new(output_options.context_args(env::args()))?;
+    let node = rclrs::create_node(&context, "lidar_localizer")?;
+    let shutdown = Shutdown::install()?;
+
+    let map_path = params::string(&node, "map_path", "")?;
+    if map_path.is_empty() {
+        bail!("map_path 파라미터가 필요합니다 (map_file 형식 지도)");
+    }
+    // 현재 위치에서 load 반경 안의 타일만 읽고, unload 반경 밖으로 멀어진 타일은 메모리에서 버림
+    let tiles = TileCache::open(
+        Path::new(&map_path),
+        params::float(&node, "map_load_radius", 60.0)? as f32,
+        params::float(&node, "map_unload_radius", 80.0)? as f32,
+    )?;
+    let header = tiles.header();
+    println!(
+        "지도: {} ({} 포인트, {} 블록, 블록 크기 {:.1} m)",
+        map_path, header.points, header.blocks, header.block_size
+    );
+    // 지도 파일에 기록된 frame_id 를 기본값으로 사용
+    let map_frame = params::string(&node, "map_frame", &header.frame_id)?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let initial = Pose::from_rpy(
+        [
+            params::float(&node, "initial_x", 0.0)?,
+            params::float(&node, "initial_y", 0.0)?,
+            params::float(&node, "initial_z", 0.0)?,
+        ],
+        0.0,
+        0.0,
+        params::float(&node, "initial_yaw_deg", 0.0)?.to_radians(),
+    );
+    let config = LocalizerConfig {
+        voxel: params::float(&node, "registration_voxel", 0.3)? as f32,
+        min_fitness: params::float(&node, "localization_min_fitness", 0.5)?,
+        max_rmse: params::float(&node, "localization_max_rmse", 0.3)?,
+        degeneracy_threshold: params::float(&node, "degeneracy_threshold", 0.01)?,
+    };
+    let icp = IcpConfig {
+        max_iterations: params::int(&node, "icp_max_iterations", 30)?.max(1) as usize,
+        max_distance: params::float(&node, "icp_max_distance", 1.0)? as f32,
+        ..IcpConfig::default()
+    };
+    // compute_backend: 스캔 다운샘플과 ICP 대응점 검색 경로 ("cpu", "gpu", "cuda")
+    let compute = compute::backend(&params::string(&node, "compute_backend", "cpu")?)?;
+    // registration: "icp" 또는 "ndt" (거친 해상도부터 ndt_resolutions 순서로 정합, 넓은 수렴 범위)
+    let mut localizer = Localizer::new(config, icp, tiles, initial).with_compute(compute);
+    match params::string(&node, "registration", "icp")?.as_str() {
+        "icp" => {}
+        "ndt" => {
+            let resolutions = params::float_array(&node, "ndt_resolutions", &[4.0, 2.0, 1.0])?;
+            if resolutions.is_empty() || resolutions.iter().any(|&r| r <= 0.0) {
+                bail!(
+                    "ndt_resolutions 는 양수 목록이어야 합니다: {:?}",
+                    resolutions
+                );
+            }
+            localizer = localizer.with_ndt(NdtConfig {
+                resolutions: resolutions.iter().map(|&r| r as f32).collect(),
+                max_iterations: params::int(&node, "ndt_max_iterations", 20)?.max(1) as usize,
+                ..NdtConfig::default()
+            });
+        }
+        other => bail!("알 수 없는 registration '{}' (icp, ndt)", other),
+    }
+    let localizer = Arc::new(Mutex::new(localizer));
+
+    // 재위치 추정: 서비스는 현재 자세 주변 search_radius (0 이면 지도 전체),
+    // initialpose 는 클릭 위치 주변 initialpose_search_radius (0 이면 클릭 자세 그대로 사용)
+    let relocalize_config = RelocalizeConfig {
+        search_radius: params::float(&node, "relocalize_search_radius", 30.0)? as f32,
+        grid_step: params::float(&node, "relocalize_grid_step", 4.0)? as f32,
+        min_score: params::float(&node, "relocalize_min_score", 0.55)? as f32,
+        ..RelocalizeConfig::default()
+    };
+    let initialpose_radius = params::float(&node, "initialpose_search_radius", 5.0)? as f32;
+    // 연속 정합 실패가 이 횟수에 도달하면 자동으로 재위치 추정 (0 이면 안 함)
+    let relocalize_after = params::int(&node, "relocalize_after_failures", 10)?.max(0) as u32;
+
+    let service_localizer = Arc::clone(&localizer);
+    let service = node.create_service::<Trigger, _>(
+        "livox/localization/relocalize",
+        move |_request_id, _request| {
+            let mut localizer = service_localizer.lock().unwrap();
+            let hint = localizer.pose();
+            let result = localizer.relocalize(hint, &relocalize_config);
+            let message = report_relocalization("서비스 요청", &result);
+            Trigger_Response {
+                success: matches!(result, Ok(Some(_))),
+                message,
+            }
+        },
+    )?;
+
+    let initialpose_localizer = Arc::clone(&localizer);
+    let initialpose_frame = map_frame.clone();
+    let initialpose_subscriber = node.create_subscription::<PoseWithCovarianceStamped, _>(
+        "initialpose",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PoseWithCovarianceStamped| {
+            if msg.header.frame_id != initialpose_frame {
+                eprintln!(
+                    "initialpose frame '{}' 가 지도 frame '{}' 와 다릅니다, 무시",
+                    msg.header.frame_id, initialpose_frame
+                );
+                return;
+            }
+            let mut localizer = initialpose_localizer.lock().unwrap();
+            let pose = Pose::from_msg(&msg.pose.pose);
+            localizer.reset(pose);
+            if initialpose_radius > 0.0 {
+                let config = RelocalizeConfig {
+                    search_radius: initialpose_radius,
+                    grid_step: relocalize_config.grid_step.min(initialpose_radius),
+                    ..relocalize_config
+                };
+                let result = localizer.relocalize(pose, &config);
+                report_relocalization("initialpose", &result);
+            } else {
+                println!(
+                    "초기 자세 설정: ({:.2}, {:.2})",
+                    pose.position[0], pose.position[1]
+                );
+            }
+        },
+    )?;
+
+    let publisher = node
+        .create_publisher::<Odometry>("livox/localization/odometry", rclrs::QOS_PROFILE_DEFAULT)?;
+    // 부분 지도 반경(m), 다운샘플 크기(m), 발행 주기(초)
+    let mut submap = SubmapOutput {
+        publisher: node
+            .create_publisher::<PointCloud2>("livox/localization/submap", qos.profile())?,
+        radius: params::float(&node, "submap_radius", 40.0)? as f32,
+        voxel: params::float(&node, "submap_voxel", 0.5)? as f32,
+        interval: params::float(&node, "submap_interval", 1.0)?,
+        last: None,
+    };
+
+    let mut diagnostics = Diagnostics::new(&node, "lidar_localizer")?;
+
+    let totals = Arc::new(Mutex::new(RunTotals::default()));
+    let callback_totals = Arc::clone(&totals);
+    let callback_localizer = Arc::clone(&localizer);
+    let mut last_tiles = 0;
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        qos.profile(),
+        move |msg: PointCloud2| {
+            let start = Instant::now();
+            let mut localizer = callback_localizer.lock().unwrap();
+            let result = process_scan(
+                msg,
+                &mut localizer,
+                &publisher,
+                input_layout,
+                &mount,
+                &map_frame,
+                &mut submap,
+            );
+            let mut totals = callback_totals.lock().unwrap();
+            match result {
+                Ok((points, result)) => {
+                    totals.add_frame(points, points, start.elapsed().as_micros() as u64);
+                    // 타일이 바뀔 때 메모리에 유지 중인 지도 크기 알림
+                    if localizer.tiles.len() != last_tiles {
+                        last_tiles = localizer.tiles.len();
+                        println!(
+                            "지도 타일 {} 개 ({} 포인트)",
+                            last_tiles,
+                            localizer.tiles.point_count()
+                        );
+                    }
+                    if !result.matched && localizer.failures == 1 {
+                        eprintln!("지도 정합 실패, 등속 예측으로 진행");
+                    }
+                    if relocalize_after > 0 && localizer.failures == relocalize_after {
+                        let hint = localizer.pose();
+                        let result = localizer.relocalize(hint, &relocalize_config);
+                        report_relocalization("연속 정합 실패", &result);
+                    }
+                    // 정합 실패는 ERROR, 퇴화(한 방향 구속 부족)는 WARN
+                    let (level, message) = if !result.matched {
+                        (diagnostics::ERROR, "지도 정합 실패")
+                    } else if result.degenerate {
+                        (diagnostics::WARN, "정합 퇴화 (구속 부족 방향 있음)")
+                    } else {
+                        (diagnostics::OK, "")
+                    };
+                    let mut values = vec![
+                        ("matched", result.matched.to_string()),
+                        ("degenerate", result.degenerate.to_string()),
+                        ("failures", localizer.failures.to_string()),
+                        ("loaded_tiles", localizer.tiles.len().to_string()),
+                    ];
+                    if let Some(icp) = &result.icp {
+                        values.push(("fitness", format!("{:.3}", icp.fitness)));
+                        values.push(("rmse", format!("{:.3}", icp.rmse)));
+                    }
+                    if let Some(c) = &result.covariance {
+                        values.push((
+                            "translation_condition",
+                            format!("{:.4}", c.translation_condition),
+                        ));
+                        values.push(("rotation_condition", format!("{:.4}", c.rotation_condition)));
+                        values.push(("plane_residual", format!("{:.3}", c.residual)));
+                    }
+                    if let Err(e) = diagnostics.publish(level, message, &values) {
+                        eprintln!("진단 발행 실패: {}", e);
+                    }
+                    if output_options.tick() && output_options.verbose() {
+                        let [x, y, _] = result.pose.position;
+                        println!(
+                            "스캔 {} 포인트, 위치 ({:.2}, {:.2}), fitness {:.2}",
+                            points,
+                            x,
+                            y,
+                            result.icp.map_or(0.0, |icp| icp.fitness)
+                        );
+                    }
+                }
+                Err(e) => {
+                    totals.add_error();
+                    eprintln!("위치 추정 중 오류: {}", e);
+                }
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar, initialpose");
+    println!("서비스: livox/localization/relocalize");
+    println!("발행 토픽: livox/localization/odometry, livox/localization/submap");
+
+    shutdown.spin(&node)?;
+
+    // Ctrl-C: 구독을 끊고 누적 통계 출력
+    drop(subscriber);
+    drop(initialpose_subscriber);
+    drop(service);
+    totals.lock().unwrap().print(0);
+    let localizer = localizer.lock().unwrap();
+    println!(
+        "타일 읽기 {} 회, 해제 {} 회",
+        localizer.tiles.loaded, localizer.tiles.unloaded
+    );
+    Ok(())
+}
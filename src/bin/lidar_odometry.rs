@@ -0,0 +1,334 @@
+use anyhow::{Error, Result};
+use geometry_msgs::msg::PoseStamped;
+use nav_msgs::msg::{Odometry, Path};
+use rclrs::{self, Context, Publisher};
+use rust_lidar::cli::OutputOptions;
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::cloud::PointXYZI;
+use rust_lidar::fusion::{FusionConfig, FusionUpdate, OdometryFusion, PoseSource};
+use rust_lidar::layout::{self, NamedLayout};
+use rust_lidar::map_file;
+use rust_lidar::params;
+use rust_lidar::pose::Pose;
+use rust_lidar::qos::QosPreset;
+use rust_lidar::registration::{self, IcpConfig};
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::slam::{KeyframeConfig, LoopConfig, Slam};
+use rust_lidar::stamp;
+use rust_lidar::stats::RunTotals;
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path as StdPath;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use std_msgs::msg::Header;
+
+// 스캔 정합 + 휠 오도메트리 결과를 nav_msgs/Odometry 로 변환 (pose 는 frame 좌표계)
+fn odometry_msg(header: &Header, frame: &str, pose: &Pose, update: &FusionUpdate) -> Odometry {
+    let mut msg = Odometry {
+        header: Header {
+            stamp: header.stamp.clone(),
+            frame_id: frame.to_string(),
+        },
+        child_frame_id: header.frame_id.clone(),
+        ..Default::default()
+    };
+    msg.pose.pose = pose.to_msg();
+
+    // 속도는 직전 스캔 대비 변화량 / 시간 (차량 좌표계)
+    if update.dt > 0.0 {
+        let linear = update.delta.position.map(|v| v / update.dt);
+        let (roll, pitch, yaw) = update.delta.rpy();
+        let twist = &mut msg.twist.twist;
+        twist.linear.x = linear[0];
+        twist.linear.y = linear[1];
+        twist.linear.z = linear[2];
+        twist.angular.x = roll / update.dt;
+        twist.angular.y = pitch / update.dt;
+        twist.angular.z = yaw / update.dt;
+    }
+    msg
+}
+
+// 최적화된 키프레임 궤적
+fn trajectory_msg(slam: &Slam, map_frame: &str) -> Path {
+    let header = |time: f64| Header {
+        stamp: stamp::from_secs(time),
+        frame_id: map_frame.to_string(),
+    };
+    let poses = slam
+        .trajectory()
+        .map(|(time, pose)| PoseStamped {
+            header: header(time),
+            pose: pose.to_msg(),
+        })
+        .collect();
+    let last = slam.keyframes.last().map_or(0.0, |k| k.time);
+    Path {
+        header: header(last),
+        poses,
+    }
+}
+
+// slam 이 켜져 있을 때의 상태와 발행자
+struct SlamOutput {
+    slam: Slam,
+    map_frame: String,
+    // 루프 검증용 ICP 설정과 보정된 지도 다운샘플 크기
+    icp: IcpConfig,
+    map_voxel: f32,
+    odometry: Arc<Publisher<Odometry>>,
+    trajectory: Arc<Publisher<Path>>,
+    map: Arc<Publisher<PointCloud2>>,
+}
+
+impl SlamOutput {
+    // 키프레임 추가 후 map 좌표계 자세 발행, 새 키프레임이면 궤적도 발행
+    fn update(
+        &mut self,
+        header: &Header,
+        update: &FusionUpdate,
+        points: &[[f32; 3]],
+        voxel: f32,
+    ) -> Result<(), Error> {
+        let time = stamp::to_secs(&header.stamp);
+        // 키프레임이 아닐 때는 다운샘플하지 않도록 먼저 확인
+        if self.slam.is_keyframe(&update.pose) {
+            let scan = registration::voxel_downsample(points, voxel);
+            if let Some(index) = self.slam.add_scan(time, update.pose, scan) {
+                self.close_loop(header, index)?;
+            }
+            self.trajectory
+                .publish(trajectory_msg(&self.slam, &self.map_frame))?;
+        }
+        let corrected = self.slam.correct(&update.pose);
+        self.odometry
+            .publish(odometry_msg(header, &self.map_frame, &corrected, update))?;
+        Ok(())
+    }
+
+    // 루프가 검증되면 그래프를 다시 최적화하고 보정된 지도 발행
+    fn close_loop(&mut self, header: &Header, index: usize) -> Result<(), Error> {
+        let Some(closure) = self.slam.detect_loop(index, &self.icp) else {
+            return Ok(());
+        };
+        let result = self.slam.close_loop(&closure, 10);
+        println!(
+            "루프 검출: 키프레임 {} -> {} (거리 {:.3}, rmse {:.3}), 오차 {:.3} -> {:.3}",
+            closure.from,
+            closure.to,
+            closure.distance,
+            closure.icp.rmse,
+            result.initial_error,
+            result.final_error
+        );
+        let points = self
+            .slam
+            .map_points(self.map_voxel)
+            .into_iter()
+            .map(|[x, y, z]| PointXYZI {
+                x,
+                y,
+                z,
+                intensity: 0.0,
+            })
+            .collect();
+        let map_header = Header {
+            stamp: header.stamp.clone(),
+            frame_id: self.map_frame.clone(),
+        };
+        self.map
+            .publish(PointCloud::new(map_header, points).to_msg())?;
+        Ok(())
+    }
+}
+
+fn process_scan(
+    msg: PointCloud2,
+    fusion: &Mutex<OdometryFusion>,
+    publisher: &Arc<Publisher<Odometry>>,
+    input_layout: Option<&NamedLayout>,
+    mount: &Transform,
+    odom_frame: &str,
+    slam: Option<&mut SlamOutput>,
+) -> Result<(usize, PoseSource), Error> {
+    let mut cloud = PointCloud::new(msg.header.clone(), layout::parse(&msg, input_layout)?);
+    mount.apply(&mut cloud);
+    let points: Vec<[f32; 3]> = cloud.iter().map(|p| [p.x, p.y, p.z]).collect();
+
+    let (update, voxel) = {
+        let mut fusion = fusion.lock().unwrap();
+        let update = fusion.update(stamp::to_secs(&cloud.header.stamp), &points);
+        (update, fusion.config.voxel)
+    };
+    publisher.publish(odometry_msg(
+        &cloud.header,
+        odom_frame,
+        &update.pose,
+        &update,
+    ))?;
+    if let Some(slam) = slam {
+        slam.update(&cloud.header, &update, &points, voxel)?;
+    }
+    Ok((points.len(), update.source))
+}
+
+fn main() -> Result<(), Error> {
+    println!("LiDAR Odometry Node");
+    // --quiet / --verbose / --every N / --qos PRESET / --replay-config FILE
+    let mut output_options = OutputOptions::from_args(env::args())?;
+    // 포인트 클라우드 QoS (전송 설정은 Context 생성 전에 적용)
+    let qos = QosPreset::parse(&output_options.qos)?;
+    qos.apply_transport();
+    let context = Context::new(output_options.context_args(env::args()))?;
+    let node = rclrs::create_node(&context, "lidar_odometry")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let odom_frame = params::string(&node, "odom_frame", "odom")?;
+    // 휠 오도메트리 child frame 은 장착 보정 후 좌표계(차량 기준)와 같아야 함
+    let wheel_topic = params::string(&node, "wheel_odom_topic", "wheel/odom")?;
+    let fusion_config = FusionConfig {
+        wheel_timeout: params::float(&node, "fusion_wheel_timeout", 0.2)?,
+        min_fitness: params::float(&node, "fusion_min_fitness", 0.3)?,
+        max_rmse: params::float(&node, "fusion_max_rmse", 0.3)?,
+        max_correction: params::float(&node, "fusion_max_correction", 0.5)?,
+        voxel: params::float(&node, "registration_voxel", 0.3)? as f32,
+    };
+    let icp_config = IcpConfig {
+        max_iterations: params::int(&node, "icp_max_iterations", 30)?.max(1) as usize,
+        max_distance: params::float(&node, "icp_max_distance", 1.0)? as f32,
+        ..IcpConfig::default()
+    };
+    let fusion = Arc::new(Mutex::new(OdometryFusion::new(fusion_config, icp_config)));
+
+    let publisher =
+        node.create_publisher::<Odometry>("livox/odometry", rclrs::QOS_PROFILE_DEFAULT)?;
+
+    // slam=true 면 키프레임 자세 그래프로 보정한 map 좌표계 자세와 궤적도 발행
+    // loop_closure=true 면 Scan Context 후보를 ICP 로 검증해 루프 제약 추가, 보정된 지도 발행
+    let slam = if params::boolean(&node, "slam", false)? {
+        let mut slam = Slam::new(KeyframeConfig {
+            distance: params::float(&node, "keyframe_distance", 1.0)?,
+            angle: params::float(&node, "keyframe_angle_deg", 15.0)?.to_radians(),
+            ..KeyframeConfig::default()
+        });
+        if params::boolean(&node, "loop_closure", true)? {
+            slam = slam.with_loop_closure(LoopConfig {
+                threshold: params::float(&node, "loop_threshold", 0.3)? as f32,
+                exclude_recent: params::int(&node, "loop_exclude_recent", 30)?.max(1) as usize,
+                min_fitness: params::float(&node, "loop_min_fitness", 0.6)?,
+                max_rmse: params::float(&node, "loop_max_rmse", 0.15)?,
+                ..LoopConfig::default()
+            });
+        }
+        Some(SlamOutput {
+            slam,
+            map_frame: params::string(&node, "map_frame", "map")?,
+            icp: icp_config,
+            map_voxel: params::float(&node, "map_voxel", 0.2)? as f32,
+            odometry: node
+                .create_publisher::<Odometry>("livox/slam/odometry", rclrs::QOS_PROFILE_DEFAULT)?,
+            trajectory: node
+                .create_publisher::<Path>("livox/slam/trajectory", rclrs::QOS_PROFILE_DEFAULT)?,
+            map: node.create_publisher::<PointCloud2>("livox/slam/map", qos.profile())?,
+        })
+    } else {
+        None
+    };
+    let slam = Arc::new(Mutex::new(slam));
+    // 종료 시 보정된 지도를 저장할 경로 (비어 있으면 저장 안 함), 블록 크기와 좌표 양자화 단위
+    let map_save_path = params::string(&node, "map_save_path", "")?;
+    let map_block_size = params::float(&node, "map_block_size", 20.0)? as f32;
+    let map_resolution = params::float(&node, "map_resolution", 0.01)? as f32;
+
+    let wheel_fusion = Arc::clone(&fusion);
+    let wheel_subscriber = node.create_subscription::<Odometry, _>(
+        &wheel_topic,
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: Odometry| {
+            wheel_fusion.lock().unwrap().push_wheel(
+                stamp::to_secs(&msg.header.stamp),
+                Pose::from_msg(&msg.pose.pose),
+            );
+        },
+    )?;
+
+    let totals = Arc::new(Mutex::new(RunTotals::default()));
+    let callback_totals = Arc::clone(&totals);
+    let callback_slam = Arc::clone(&slam);
+    let mut last_source = None;
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        qos.profile(),
+        move |msg: PointCloud2| {
+            let start = Instant::now();
+            let result = process_scan(
+                msg,
+                &fusion,
+                &publisher,
+                input_layout,
+                &mount,
+                &odom_frame,
+                callback_slam.lock().unwrap().as_mut(),
+            );
+            let mut totals = callback_totals.lock().unwrap();
+            match result {
+                Ok((points, source)) => {
+                    totals.add_frame(points, points, start.elapsed().as_micros() as u64);
+                    // 자세 출처가 바뀔 때 (휠 끊김, 정합 실패 등) 알림
+                    if last_source != Some(source) {
+                        println!("자세 출처: {}", source.as_str());
+                        last_source = Some(source);
+                    }
+                    if output_options.tick() && output_options.verbose() {
+                        println!("스캔 {} 포인트 ({})", points, source.as_str());
+                    }
+                }
+                Err(e) => {
+                    totals.add_error();
+                    eprintln!("오도메트리 처리 중 오류: {}", e);
+                }
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar, {}", wheel_topic);
+    println!("발행 토픽: livox/odometry (slam: livox/slam/odometry, livox/slam/trajectory, livox/slam/map)");
+
+    shutdown.spin(&node)?;
+
+    // Ctrl-C: 구독을 끊고 누적 통계 출력
+    drop(subscriber);
+    drop(wheel_subscriber);
+    totals.lock().unwrap().print(0);
+    if let (Some(output), false) = (&*slam.lock().unwrap(), map_save_path.is_empty()) {
+        let points: Vec<PointXYZI> = output
+            .slam
+            .map_points(output.map_voxel)
+            .into_iter()
+            .map(|[x, y, z]| PointXYZI {
+                x,
+                y,
+                z,
+                intensity: 0.0,
+            })
+            .collect();
+        let header = map_file::save(
+            StdPath::new(&map_save_path),
+            &output.map_frame,
+            &points,
+            map_block_size,
+            map_resolution,
+        )?;
+        println!(
+            "지도 저장: {} ({} 포인트, {} 블록)",
+            map_save_path, header.points, header.blocks
+        );
+    }
+    Ok(())
+}
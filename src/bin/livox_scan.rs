@@ -8,7 +8,7 @@ fn main() -> Result<(), Error> {
     let context = Context::new(env::args())?;
     let node = rclrs::create_node(&context, "lidar_scanner")?;
     let _subscriber = node.create_subscription::<PointCloud2, _>(
-        "/livox/lidar",
+        "livox/lidar",
         rclrs::QOS_PROFILE_DEFAULT,
         move |msg: PointCloud2| {
             println!("{:?}", msg);
@@ -1,90 +1,28 @@
-use anyhow::{Error, Result};
+use anyhow::{bail, Error, Result};
 use rclrs::{self, Context};
+use rust_lidar::cli::OutputOptions;
+use rust_lidar::diagnostics::{self, Diagnostics};
+use rust_lidar::executor::{Executor, ExecutorModel};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::point::LidarPoint;
+use rust_lidar::qos::QosPreset;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::stats::{self, ChangeThresholds, FrameSummary, RunTotals, TopicWindow};
 use sensor_msgs::msg::PointCloud2;
 use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-#[derive(Debug)]
-struct LidarPoint {
-    x: f32,
-    y: f32,
-    z: f32,
-    intensity: f32,
-    tag: u8,
-    line: u8,
-    timestamp: f64,
-}
-
-impl LidarPoint {
-    fn from_bytes(data: &[u8], offset: usize) -> Option<Self> {
-        if offset + 26 > data.len() {
-            return None;
-        }
-
-        // 리틀 엔디안으로 바이트를 변환
-        let x = f32::from_le_bytes([
-            data[offset],
-            data[offset + 1],
-            data[offset + 2],
-            data[offset + 3],
-        ]);
-        let y = f32::from_le_bytes([
-            data[offset + 4],
-            data[offset + 5],
-            data[offset + 6],
-            data[offset + 7],
-        ]);
-        let z = f32::from_le_bytes([
-            data[offset + 8],
-            data[offset + 9],
-            data[offset + 10],
-            data[offset + 11],
-        ]);
-        let intensity = f32::from_le_bytes([
-            data[offset + 12],
-            data[offset + 13],
-            data[offset + 14],
-            data[offset + 15],
-        ]);
-        let tag = data[offset + 16];
-        let line = data[offset + 17];
-        let timestamp = f64::from_le_bytes([
-            data[offset + 18],
-            data[offset + 19],
-            data[offset + 20],
-            data[offset + 21],
-            data[offset + 22],
-            data[offset + 23],
-            data[offset + 24],
-            data[offset + 25],
-        ]);
-
-        Some(LidarPoint {
-            x,
-            y,
-            z,
-            intensity,
-            tag,
-            line,
-            timestamp,
-        })
-    }
-}
-
-fn parse_pointcloud2(msg: &PointCloud2) -> Vec<LidarPoint> {
-    let mut points = Vec::new();
-    let point_step = msg.point_step as usize;
-
-    for i in (0..msg.data.len()).step_by(point_step) {
-        if let Some(point) = LidarPoint::from_bytes(&msg.data, i) {
-            points.push(point);
-        }
-    }
-
-    points
-}
-
-fn print_point_cloud_summary(msg: &PointCloud2) {
-    let points = parse_pointcloud2(msg);
+fn print_point_cloud_summary(
+    msg: &PointCloud2,
+    points: &[LidarPoint],
+    summary: &FrameSummary,
+    verbose: bool,
+) {
+    // --verbose 면 포인트/라인을 더 많이 출력
+    let (max_points, max_lines) = if verbose { (20, usize::MAX) } else { (5, 10) };
 
     println!("=== LiDAR Point Cloud Data ===");
     println!("Frame ID: {}", msg.header.frame_id);
@@ -97,15 +35,15 @@ fn print_point_cloud_summary(msg: &PointCloud2) {
     println!();
 
     if !points.is_empty() {
-        // 첫 5개 포인트 상세 출력
-        println!("First 5 Points:");
+        // 첫 몇 개 포인트 상세 출력
+        println!("First {} Points:", max_points.min(points.len()));
         println!(
             "{:<6} {:<10} {:<10} {:<10} {:<10} {:<4} {:<4} {:<15}",
             "Index", "X(m)", "Y(m)", "Z(m)", "Intensity", "Tag", "Line", "Timestamp"
         );
         println!("{}", "-".repeat(80));
 
-        for (i, point) in points.iter().take(5).enumerate() {
+        for (i, point) in points.iter().take(max_points).enumerate() {
             println!(
                 "{:<6} {:<10.3} {:<10.3} {:<10.3} {:<10.1} {:<4} {:<4} {:<15.3}",
                 i,
@@ -119,56 +57,39 @@ fn print_point_cloud_summary(msg: &PointCloud2) {
             );
         }
 
-        if points.len() > 5 {
-            println!("... and {} more points", points.len() - 5);
+        if points.len() > max_points {
+            println!("... and {} more points", points.len() - max_points);
         }
 
         // 통계 정보
-        let x_values: Vec<f32> = points.iter().map(|p| p.x).collect();
-        let y_values: Vec<f32> = points.iter().map(|p| p.y).collect();
-        let z_values: Vec<f32> = points.iter().map(|p| p.z).collect();
-        let intensity_values: Vec<f32> = points.iter().map(|p| p.intensity).collect();
-
         println!("\n=== Statistics ===");
         println!(
             "X range: {:.3} ~ {:.3} m",
-            x_values.iter().fold(f32::INFINITY, |a, &b| a.min(b)),
-            x_values.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b))
+            summary.x_range.0, summary.x_range.1
         );
         println!(
             "Y range: {:.3} ~ {:.3} m",
-            y_values.iter().fold(f32::INFINITY, |a, &b| a.min(b)),
-            y_values.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b))
+            summary.y_range.0, summary.y_range.1
         );
         println!(
             "Z range: {:.3} ~ {:.3} m",
-            z_values.iter().fold(f32::INFINITY, |a, &b| a.min(b)),
-            z_values.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b))
+            summary.z_range.0, summary.z_range.1
         );
         println!(
             "Intensity range: {:.1} ~ {:.1}",
-            intensity_values
-                .iter()
-                .fold(f32::INFINITY, |a, &b| a.min(b)),
-            intensity_values
-                .iter()
-                .fold(f32::NEG_INFINITY, |a, &b| a.max(b))
+            summary.intensity_range.0, summary.intensity_range.1
         );
 
         // 라인별 포인트 개수
-        let mut line_counts = std::collections::HashMap::new();
-        for point in &points {
-            *line_counts.entry(point.line).or_insert(0) += 1;
-        }
-
         println!("\n=== Points per Line ===");
-        let mut lines: Vec<_> = line_counts.iter().collect();
-        lines.sort_by_key(|&(line, _)| line);
-        for (line, count) in lines.iter().take(10) {
+        for (line, count) in summary.line_counts.iter().take(max_lines) {
             println!("Line {}: {} points", line, count);
         }
-        if lines.len() > 10 {
-            println!("... and {} more lines", lines.len() - 10);
+        if summary.line_counts.len() > max_lines {
+            println!(
+                "... and {} more lines",
+                summary.line_counts.len() - max_lines
+            );
         }
     }
 
@@ -178,15 +99,191 @@ fn print_point_cloud_summary(msg: &PointCloud2) {
 
 fn main() -> Result<(), Error> {
     println!("This is LiDAR Scan node");
-    let context = Context::new(env::args())?;
+    // --quiet / --verbose / --every N / --qos PRESET / --replay-config FILE
+    let output_options = OutputOptions::from_args(env::args())?;
+    // 포인트 클라우드 QoS (전송 설정은 Context 생성 전에 적용)
+    let qos = QosPreset::parse(&output_options.qos)?;
+    qos.apply_transport();
+    let context = Context::new(output_options.context_args(env::args()))?;
     let node = rclrs::create_node(&context, "lidar_scanner")?;
-    let _subscriber = node.create_subscription::<PointCloud2, _>(
-        "/livox/lidar",
-        rclrs::QOS_PROFILE_DEFAULT,
-        move |msg: PointCloud2| {
-            print_point_cloud_summary(&msg);
-        },
-    )?;
-
-    rclrs::spin(node).map_err(|err| err.into())
+    let shutdown = Shutdown::install()?;
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+
+    // on_change: 통계가 임계값 이상 바뀐 프레임만 출력
+    let report_mode = params::string(&node, "report_mode", "every_frame")?;
+    let on_change = match report_mode.as_str() {
+        "every_frame" => false,
+        "on_change" => true,
+        other => bail!(
+            "알 수 없는 report_mode '{}' (every_frame, on_change)",
+            other
+        ),
+    };
+    let thresholds = ChangeThresholds {
+        count_ratio: params::float(&node, "change_count_ratio", 0.05)?,
+        range_m: params::float(&node, "change_range_m", 0.5)? as f32,
+    };
+
+    // scan_topics: 동시에 구독할 토픽 목록 (여러 센서 장착 시 토픽별 주기/포인트 수/지연 비교)
+    // 토픽이 여럿이면 프레임별 요약은 --verbose 에서만, 대신 compare_period 초마다 비교표 출력
+    let topics = params::string_array(&node, "scan_topics", &["livox/lidar"])?;
+    if topics.is_empty() {
+        bail!("scan_topics 가 비어 있습니다");
+    }
+    let compare = topics.len() > 1;
+    let compare_period =
+        Duration::from_secs_f64(params::float(&node, "compare_period", 1.0)?.max(0.1));
+
+    // executor=threaded 면 토픽마다 별도 노드 (lidar_scanner_topic<i>) 와 spin 스레드에서 파싱,
+    // 비교표/진단은 메인 노드에서
+    let mut executor = Executor::new(
+        ExecutorModel::from_node(&node)?,
+        &shutdown,
+        &output_options.ros_args,
+    );
+
+    let totals: Vec<Arc<Mutex<RunTotals>>> = topics
+        .iter()
+        .map(|_| Arc::new(Mutex::new(RunTotals::default())))
+        .collect();
+    let windows: Vec<Arc<Mutex<TopicWindow>>> = topics
+        .iter()
+        .map(|_| Arc::new(Mutex::new(TopicWindow::default())))
+        .collect();
+    let mut subscribers = Vec::with_capacity(topics.len());
+    for (i, topic) in topics.iter().enumerate() {
+        let callback_totals = Arc::clone(&totals[i]);
+        let callback_window = Arc::clone(&windows[i]);
+        let mut output_options = output_options.clone();
+        let mut last_reported: Option<FrameSummary> = None;
+        let topic_node = executor.node_for(&context, &node, &format!("topic{}", i))?;
+        let subscriber = topic_node.create_subscription::<PointCloud2, _>(
+            topic,
+            qos.profile(),
+            move |msg: PointCloud2| {
+                let start = Instant::now();
+                let latency_ms =
+                    (stamp::to_secs(&stamp::now()) - stamp::to_secs(&msg.header.stamp)) * 1e3;
+                let points = match layout::parse(&msg, input_layout) {
+                    Ok(points) => points,
+                    Err(e) => {
+                        callback_totals.lock().unwrap().add_error();
+                        eprintln!("PointCloud2 파싱 실패: {}", e);
+                        return;
+                    }
+                };
+                let summary = FrameSummary::from_points(&points);
+                callback_totals.lock().unwrap().add_frame(
+                    points.len(),
+                    points.len(),
+                    start.elapsed().as_micros() as u64,
+                );
+                callback_window.lock().unwrap().add(
+                    points.len(),
+                    stats::serialized_size(&msg),
+                    latency_ms,
+                );
+                if compare && !output_options.verbose() {
+                    return;
+                }
+
+                if on_change {
+                    if let Some(prev) = &last_reported {
+                        let changes = summary.changes_from(prev, &thresholds);
+                        if changes.is_empty() {
+                            return;
+                        }
+                        println!("=== Changed: {} ===", changes.join(", "));
+                    }
+                    last_reported = Some(summary.clone());
+                }
+
+                if output_options.tick() {
+                    print_point_cloud_summary(&msg, &points, &summary, output_options.verbose());
+                }
+            },
+        )?;
+        subscribers.push(subscriber);
+    }
+
+    // 토픽별 비교 통계는 진단 정보로도 발행 (키: <토픽>/rate_hz 등)
+    let mut diagnostics = Diagnostics::new(&node, "lidar_scanner")?;
+    let mut window_start = Instant::now();
+    shutdown.spin_with(&node, || {
+        let elapsed = window_start.elapsed();
+        if !compare || elapsed < compare_period {
+            return Ok(());
+        }
+        window_start = Instant::now();
+        let secs = elapsed.as_secs_f64();
+        let mut values = Vec::new();
+        let mut silent = Vec::new();
+        println!("=== 토픽 비교 ({:.1} s) ===", secs);
+        println!(
+            "{:<30} {:>8} {:>10} {:>10} {:>12} {:>12}",
+            "Topic", "Hz", "Points", "kbit/s", "Latency(ms)", "Max(ms)"
+        );
+        for (topic, window) in topics.iter().zip(&windows) {
+            let window = std::mem::take(&mut *window.lock().unwrap());
+            println!(
+                "{:<30} {:>8.2} {:>10.0} {:>10.1} {:>12.1} {:>12.1}",
+                topic,
+                window.rate(secs),
+                window.mean_points(),
+                window.kbps(secs),
+                window.mean_latency_ms(),
+                window.latency_ms_max
+            );
+            if window.frames == 0 {
+                silent.push(topic.as_str());
+            }
+            values.push((
+                format!("{}/rate_hz", topic),
+                format!("{:.2}", window.rate(secs)),
+            ));
+            values.push((
+                format!("{}/mean_points", topic),
+                format!("{:.0}", window.mean_points()),
+            ));
+            values.push((
+                format!("{}/kbps", topic),
+                format!("{:.1}", window.kbps(secs)),
+            ));
+            values.push((
+                format!("{}/latency_ms", topic),
+                format!("{:.1}", window.mean_latency_ms()),
+            ));
+        }
+        println!();
+        let (level, message) = if silent.is_empty() {
+            (diagnostics::OK, "모든 토픽 수신 중".to_string())
+        } else {
+            (
+                diagnostics::WARN,
+                format!("수신 없음: {}", silent.join(", ")),
+            )
+        };
+        let values: Vec<(&str, String)> = values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        if let Err(e) = diagnostics.publish(level, &message, &values) {
+            eprintln!("진단 정보 발행 오류: {}", e);
+        }
+        Ok(())
+    })?;
+
+    // Ctrl-C: 구독을 끊고 누적 통계 출력 (토픽이 여럿이면 토픽별로)
+    // spin 스레드 오류가 있어도 통계는 출력
+    if let Err(e) = executor.join() {
+        eprintln!("{}", e);
+    }
+    drop(subscribers);
+    for (topic, totals) in topics.iter().zip(&totals) {
+        if compare {
+            println!("[{}]", topic);
+        }
+        totals.lock().unwrap().print(0);
+    }
+    Ok(())
 }
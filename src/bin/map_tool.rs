@@ -0,0 +1,108 @@
+use anyhow::{anyhow, bail, Error, Result};
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::map_file::{self, MapReader};
+use rust_lidar::mesh::{self, MeshConfig};
+use rust_lidar::pcd;
+use rust_lidar::sidecar::{json_string, SidecarIndex};
+use rust_lidar::snapshot::ConfigSnapshot;
+use std::env;
+use std::path::Path;
+use std_msgs::msg::Header;
+
+// 지도 파일 확인/변환 도구 (ROS 없이 실행)
+//   map_tool info <map>             헤더와 블록 요약
+//   map_tool to-pcd <map> <out.pcd> PCD 로 변환 (PCL/CloudCompare 확인용), 옆에 <이름>.index.json 색인
+//   map_tool mesh <map> <out.obj|out.ply> [cell_m]
+//                                   높이장 격자 삼각화로 표면 메시 내보내기 (기본 셀 0.2 m)
+fn usage() -> Result<(), Error> {
+    bail!(
+        "사용법: map_tool info <map> | map_tool to-pcd <map> <out.pcd> | \
+         map_tool mesh <map> <out.obj|out.ply> [cell_m]"
+    )
+}
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["info", path] => {
+            let reader = MapReader::open(Path::new(path))?;
+            let header = reader.header();
+            println!("frame_id: {}", header.frame_id);
+            println!(
+                "포인트: {}, 블록: {} ({} m, 해상도 {} m)",
+                header.points, header.blocks, header.block_size, header.resolution
+            );
+            println!(
+                "범위: ({:.2}, {:.2}, {:.2}) ~ ({:.2}, {:.2}, {:.2})",
+                header.min[0],
+                header.min[1],
+                header.min[2],
+                header.max[0],
+                header.max[1],
+                header.max[2]
+            );
+            let largest = reader.blocks().map(|b| b.count).max().unwrap_or(0);
+            println!("블록당 최대 포인트: {}", largest);
+        }
+        ["to-pcd", path, out] => {
+            let (header, points) = map_file::load(Path::new(path))?;
+            let msg = PointCloud::new(
+                Header {
+                    frame_id: header.frame_id.clone(),
+                    ..Default::default()
+                },
+                points,
+            )
+            .to_msg();
+            pcd::write_pcd(Path::new(out), &msg)?;
+            // 변환 설정은 원본 지도뿐이므로 그 경로로 config_hash 를 만듦
+            let snapshot =
+                ConfigSnapshot::new("map_tool", vec![("map".to_string(), json_string(path))]);
+            let mut sidecar = SidecarIndex::new(&snapshot.hash());
+            let file = Path::new(out).file_name().unwrap_or_default();
+            sidecar.push(&file.to_string_lossy(), &msg);
+            let index = SidecarIndex::path_for(Path::new(out));
+            sidecar.write(&index)?;
+            println!(
+                "{} -> {} ({} 포인트, 색인 {})",
+                path,
+                out,
+                header.points,
+                index.display()
+            );
+        }
+        ["mesh", path, out, rest @ ..] => {
+            let mut config = MeshConfig::default();
+            match rest {
+                [] => {}
+                [cell] => {
+                    config.cell = cell
+                        .parse()
+                        .map_err(|_| anyhow!("셀 크기가 숫자가 아닙니다: '{}'", cell))?;
+                    // 셀이 커지면 이웃 높이 차 허용도 같이 키움
+                    config.max_step = config.max_step.max(config.cell * 2.5);
+                }
+                _ => usage()?,
+            }
+            let (_, points) = map_file::load(Path::new(path))?;
+            let xyz: Vec<[f32; 3]> = points.iter().map(|p| [p.x, p.y, p.z]).collect();
+            let mesh = mesh::height_field(&xyz, &config)?;
+            mesh.write(Path::new(out))?;
+            println!(
+                "{} -> {} (포인트 {}, 꼭짓점 {}, 삼각형 {})",
+                path,
+                out,
+                points.len(),
+                mesh.vertices.len(),
+                mesh.triangles.len()
+            );
+        }
+        _ => usage()?,
+    }
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use anyhow::{Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::builder::PointCloud2Builder;
+use rust_lidar::classify::ClassRules;
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::cluster::{self, ClusterConfig};
+use rust_lidar::core::LidarPoint;
+use rust_lidar::dataset::{DatasetFormat, DatasetWriter, LabelBox};
+use rust_lidar::exclusion::{ExclusionZones, ZoneFrame};
+use rust_lidar::ground::{GroundEstimator, GroundFitConfig};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::point::datatype;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::tracking::{Tracker, TrackerConfig};
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// 물체 검출 노드: 지면 위 포인트를 클러스터로 묶고 프레임 사이에 추적해 모양/속도 규칙으로 분류
+// 출력 포인트 하나 = 이번 프레임에 보인 물체 하나
+// (x/y/z: 중심, length/width/height: 상자 크기, vx/vy/speed: 속도, id: 트랙 번호,
+//  class: 0 other, 1 pedestrian, 2 vehicle)
+// livox/objects/predictions: 물체마다 prediction_horizons 초 뒤의 등속 예측 위치 (dt: 몇 초 뒤인지)
+// dataset_format (kitti, nuscenes) 을 지정하면 dataset_dir 에 프레임 포인트와 물체 상자를 학습용으로 저장
+fn main() -> Result<(), Error> {
+    println!("LiDAR Object Detection Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "object_detector")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let exclusion = ExclusionZones::from_node(&node)?;
+    // 지면 위 높이 범위 (m), 지면/천장 포인트 제외
+    let min_height = params::float(&node, "object_min_height", 0.2)? as f32;
+    let max_height = params::float(&node, "object_max_height", 3.0)? as f32;
+    let cluster_config = ClusterConfig {
+        cell_size: params::float(&node, "cluster_cell_size", 0.3)? as f32,
+        min_points: params::int(&node, "cluster_min_points", 5)?.max(1) as usize,
+        ..ClusterConfig::default()
+    };
+    let mut tracker = Tracker::new(TrackerConfig {
+        gate: params::float(&node, "track_gate", 1.5)? as f32,
+        max_missed: params::int(&node, "track_max_missed", 3)?.max(0) as u32,
+        ..TrackerConfig::default()
+    });
+    // 분류 임계값 YAML (빈 문자열이면 기본값)
+    let rules_path = params::string(&node, "classifier_rules", "")?;
+    let rules = if rules_path.is_empty() {
+        ClassRules::default()
+    } else {
+        ClassRules::load(Path::new(&rules_path))?
+    };
+    // 등속 예측 시점 (현재 프레임 기준 초), 비어 있으면 예측 발행 안 함
+    let horizons = params::float_array(&node, "prediction_horizons", &[0.5, 1.0, 2.0])?;
+    // 학습 데이터 내보내기 (빈 문자열이면 안 함), dataset_accumulate 프레임을 한 샘플로 합침
+    let dataset_format = params::string(&node, "dataset_format", "")?;
+    let dataset_dir = params::string(&node, "dataset_dir", "dataset")?;
+    let dataset_accumulate = params::int(&node, "dataset_accumulate", 1)?.max(1) as usize;
+    let dataset = if dataset_format.is_empty() {
+        None
+    } else {
+        let writer = DatasetWriter::new(
+            DatasetFormat::parse(&dataset_format)?,
+            Path::new(&dataset_dir),
+            dataset_accumulate,
+        )?;
+        println!("데이터셋 저장: {} ({})", dataset_dir, dataset_format);
+        Some(writer)
+    };
+    let dataset = Arc::new(Mutex::new(dataset));
+    let callback_dataset = Arc::clone(&dataset);
+    // 지면 추정이 실패하면 장착 보정 후 z = 0 을 지면으로 사용
+    let mut ground = GroundEstimator::new(GroundFitConfig::default(), 0.3);
+
+    let publisher =
+        node.create_publisher::<PointCloud2>("livox/objects", rclrs::QOS_PROFILE_DEFAULT)?;
+    let prediction_publisher = if horizons.is_empty() {
+        None
+    } else {
+        println!("예측 토픽: livox/objects/predictions ({:?} 초)", horizons);
+        Some(node.create_publisher::<PointCloud2>(
+            "livox/objects/predictions",
+            rclrs::QOS_PROFILE_DEFAULT,
+        )?)
+    };
+    let _subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            let exclude = |frame, cloud: &mut PointCloud<_>| {
+                if let Some(zones) = exclusion.as_ref().filter(|z| z.frame == frame) {
+                    zones.apply(cloud);
+                }
+            };
+            exclude(ZoneFrame::Sensor, &mut cloud);
+            mount.apply(&mut cloud);
+            exclude(ZoneFrame::Base, &mut cloud);
+            let plane = ground.update(&cloud);
+
+            let above: Vec<[f32; 3]> = cloud
+                .iter()
+                .map(|p| {
+                    let h = plane.map_or(p.z, |plane| plane.distance([p.x, p.y, p.z]));
+                    [p.x, p.y, h]
+                })
+                .filter(|p| p[2] >= min_height && p[2] <= max_height)
+                .collect();
+            let clusters = cluster::euclidean(&above, &cluster_config);
+            let time = stamp::to_secs(&cloud.header.stamp);
+            tracker.update(time, &clusters);
+            if let Some(writer) = callback_dataset.lock().unwrap().as_mut() {
+                // 상자와 같은 지면 위 높이 기준으로 저장
+                let points: Vec<LidarPoint> = cloud
+                    .iter()
+                    .map(|p| LidarPoint {
+                        z: plane.map_or(p.z, |plane| plane.distance([p.x, p.y, p.z])),
+                        ..*p
+                    })
+                    .collect();
+                let boxes: Vec<LabelBox> = tracker
+                    .tracks()
+                    .iter()
+                    .filter(|t| t.missed == 0)
+                    .map(|t| LabelBox::from_track(t, rules.classify(t)))
+                    .collect();
+                if let Err(e) = writer.write_frame(time, &points, &boxes) {
+                    eprintln!("데이터셋 저장 실패: {}", e);
+                }
+            }
+
+            let mut builder = PointCloud2Builder::new()
+                .add_field("x", datatype::FLOAT32)
+                .add_field("y", datatype::FLOAT32)
+                .add_field("z", datatype::FLOAT32)
+                .add_field("length", datatype::FLOAT32)
+                .add_field("width", datatype::FLOAT32)
+                .add_field("height", datatype::FLOAT32)
+                .add_field("vx", datatype::FLOAT32)
+                .add_field("vy", datatype::FLOAT32)
+                .add_field("speed", datatype::FLOAT32)
+                .add_field("id", datatype::UINT32)
+                .add_field("class", datatype::UINT8);
+            let mut counts = [0usize; 3];
+            for track in tracker.tracks().iter().filter(|t| t.missed == 0) {
+                let class = rules.classify(track);
+                counts[class.id() as usize] += 1;
+                let [length, width, height] = track.cluster.dimensions();
+                builder.push_point(&[
+                    track.position[0] as f64,
+                    track.position[1] as f64,
+                    track.cluster.centroid[2] as f64,
+                    length as f64,
+                    width as f64,
+                    height as f64,
+                    track.velocity[0] as f64,
+                    track.velocity[1] as f64,
+                    track.speed() as f64,
+                    track.id as f64,
+                    class.id() as f64,
+                ]);
+            }
+            println!(
+                "물체 {}개 (보행자 {}, 차량 {}, 기타 {})",
+                builder.len(),
+                counts[1],
+                counts[2],
+                counts[0]
+            );
+            if let Some(prediction_publisher) = &prediction_publisher {
+                // 속도를 아직 모르는 새 트랙 (한 번만 보인 물체) 은 제자리에 있는 것으로 예측
+                let mut predictions = PointCloud2Builder::new()
+                    .add_field("x", datatype::FLOAT32)
+                    .add_field("y", datatype::FLOAT32)
+                    .add_field("z", datatype::FLOAT32)
+                    .add_field("length", datatype::FLOAT32)
+                    .add_field("width", datatype::FLOAT32)
+                    .add_field("dt", datatype::FLOAT32)
+                    .add_field("id", datatype::UINT32)
+                    .add_field("class", datatype::UINT8);
+                for track in tracker.tracks().iter().filter(|t| t.missed == 0) {
+                    let class = rules.classify(track);
+                    let [length, width, _] = track.cluster.dimensions();
+                    for &dt in &horizons {
+                        let [x, y] = track.predict(time + dt);
+                        predictions.push_point(&[
+                            x as f64,
+                            y as f64,
+                            track.cluster.centroid[2] as f64,
+                            length as f64,
+                            width as f64,
+                            dt,
+                            track.id as f64,
+                            class.id() as f64,
+                        ]);
+                    }
+                }
+                if let Err(e) =
+                    prediction_publisher.publish(predictions.finish(cloud.header.clone()))
+                {
+                    eprintln!("예측 발행 오류: {}", e);
+                }
+            }
+            if let Err(e) = publisher.publish(builder.finish(cloud.header)) {
+                eprintln!("발행 오류: {}", e);
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/objects");
+    shutdown.spin(&node)?;
+    if let Some(writer) = dataset.lock().unwrap().as_ref() {
+        writer.finish()?;
+        println!("데이터셋 샘플 {}개 저장", writer.samples());
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::classify::{ClassRules, ObjectClass};
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::cluster::{self, ClusterConfig};
+use rust_lidar::diagnostics::{self, Diagnostics};
+use rust_lidar::exclusion::{ExclusionZones, ZoneFrame};
+use rust_lidar::ground::{GroundEstimator, GroundFitConfig};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::people::{CountLine, CountZone, PeopleCounter};
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::tracking::{Tracker, TrackerConfig};
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path;
+use std_msgs::msg::String as StringMsg;
+
+// 인원 계수 노드 (고정 설치 로비 센서): 지면 위 클러스터를 추적해 보행자 규칙에 맞는 트랙만
+// count_lines 계수선 통과 (in/out) 와 count_zones 구역 인원/누적 진입을 셈
+//   count_lines: ["entrance:0.0,-2.0,0.0,2.0"]          이름:x0,y0,x1,y1 (a -> b 왼쪽으로 건너면 in)
+//   count_zones: ["lobby:1.0,-3.0,6.0,-3.0,6.0,3.0"]    이름:x0,y0,x1,y1,x2,y2,... (장착 보정 후 좌표)
+// 익명 출력: 포인트/위치/트랙 번호는 발행하지 않고 livox/people/counts 에 숫자만 JSON 으로 발행
+fn main() -> Result<(), Error> {
+    println!("LiDAR People Counting Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "people_counter")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let exclusion = ExclusionZones::from_node(&node)?;
+    let min_height = params::float(&node, "object_min_height", 0.2)? as f32;
+    let max_height = params::float(&node, "object_max_height", 2.5)? as f32;
+    let cluster_config = ClusterConfig {
+        cell_size: params::float(&node, "cluster_cell_size", 0.2)? as f32,
+        min_points: params::int(&node, "cluster_min_points", 5)?.max(1) as usize,
+        ..ClusterConfig::default()
+    };
+    let mut tracker = Tracker::new(TrackerConfig {
+        gate: params::float(&node, "track_gate", 1.0)? as f32,
+        max_missed: params::int(&node, "track_max_missed", 3)?.max(0) as u32,
+        ..TrackerConfig::default()
+    });
+    let rules_path = params::string(&node, "classifier_rules", "")?;
+    let rules = if rules_path.is_empty() {
+        ClassRules::default()
+    } else {
+        ClassRules::load(Path::new(&rules_path))?
+    };
+    let lines = params::string_array(&node, "count_lines", &[])?
+        .iter()
+        .map(|s| CountLine::parse(s))
+        .collect::<Result<Vec<_>>>()?;
+    let zones = params::string_array(&node, "count_zones", &[])?
+        .iter()
+        .map(|s| CountZone::parse(s))
+        .collect::<Result<Vec<_>>>()?;
+    if lines.is_empty() && zones.is_empty() {
+        bail!("count_lines 또는 count_zones 를 하나 이상 설정해야 합니다");
+    }
+    // 이 횟수 이상 연속으로 보인 트랙만 셈
+    let min_hits = params::int(&node, "count_min_hits", 3)?.max(1) as u32;
+    let mut counter = PeopleCounter::new(lines, zones, min_hits);
+    let mut ground = GroundEstimator::new(GroundFitConfig::default(), 0.3);
+    let mut diagnostics = Diagnostics::new(&node, "lidar_people_counter")?;
+
+    let publisher =
+        node.create_publisher::<StringMsg>("livox/people/counts", rclrs::QOS_PROFILE_DEFAULT)?;
+    let _subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            let exclude = |frame, cloud: &mut PointCloud<_>| {
+                if let Some(zones) = exclusion.as_ref().filter(|z| z.frame == frame) {
+                    zones.apply(cloud);
+                }
+            };
+            exclude(ZoneFrame::Sensor, &mut cloud);
+            mount.apply(&mut cloud);
+            exclude(ZoneFrame::Base, &mut cloud);
+            let plane = ground.update(&cloud);
+
+            let above: Vec<[f32; 3]> = cloud
+                .iter()
+                .map(|p| {
+                    let h = plane.map_or(p.z, |plane| plane.distance([p.x, p.y, p.z]));
+                    [p.x, p.y, h]
+                })
+                .filter(|p| p[2] >= min_height && p[2] <= max_height)
+                .collect();
+            let clusters = cluster::euclidean(&above, &cluster_config);
+            let time = stamp::to_secs(&cloud.header.stamp);
+            tracker.update(time, &clusters);
+            counter.update(
+                tracker
+                    .tracks()
+                    .iter()
+                    .filter(|t| rules.classify(t) == ObjectClass::Pedestrian),
+            );
+
+            if let Err(e) = publisher.publish(StringMsg {
+                data: counter.to_json(time),
+            }) {
+                eprintln!("발행 오류: {}", e);
+            }
+            let mut values = vec![("people", counter.people().to_string())];
+            for line in &counter.lines {
+                values.push((
+                    line.name.as_str(),
+                    format!("in {} / out {}", line.entered, line.exited),
+                ));
+            }
+            for zone in &counter.zones {
+                values.push((
+                    zone.name.as_str(),
+                    format!("occupancy {} / entered {}", zone.occupancy, zone.entered),
+                ));
+            }
+            if let Err(e) = diagnostics.publish(diagnostics::OK, "counting", &values) {
+                eprintln!("진단 발행 오류: {}", e);
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/people/counts");
+    shutdown.spin(&node)?;
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anyhow::{bail, Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::cloud::{PointCloud, PointXYZI};
+use rust_lidar::ground::{self, GroundFitConfig, Plane};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::transform::Transform;
+use rust_lidar::volume::{VolumeConfig, VolumeReport};
+use sensor_msgs::msg::PointCloud2;
+use std::collections::VecDeque;
+use std::env;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std_msgs::msg::Header;
+use std_srvs::srv::{Trigger, Trigger_Response};
+
+// 적치물 부피 측정 노드: 최근 volume_frames 프레임을 모아 두었다가
+// livox/volume/measure (std_srvs/Trigger) 요청 때 다각형 안 기준 지면 위 부피를 계산
+// 결과는 서비스 응답 message 와 volume_json 파일 (빈 문자열이면 저장 안 함) 로 보고
+// volume_reference: fit 이면 다각형 밖 포인트로 지면 평면 추정, z 면 volume_ground_z 높이의 수평면
+
+// 쌓아 둔 프레임 (장착 보정 후 x/y/z)
+struct Accumulator {
+    frames: VecDeque<Vec<[f32; 3]>>,
+    capacity: usize,
+    header: Header,
+}
+
+impl Accumulator {
+    fn push(&mut self, header: Header, points: Vec<[f32; 3]>) {
+        if self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(points);
+        self.header = header;
+    }
+
+    fn points(&self) -> Vec<[f32; 3]> {
+        self.frames.iter().flatten().copied().collect()
+    }
+}
+
+enum Reference {
+    Fit(GroundFitConfig),
+    Fixed(Plane),
+}
+
+fn measure(
+    accumulator: &Accumulator,
+    config: &VolumeConfig,
+    reference: &Reference,
+) -> Result<VolumeReport> {
+    let points = accumulator.points();
+    if points.is_empty() {
+        bail!("아직 받은 프레임이 없습니다");
+    }
+    let plane = match reference {
+        Reference::Fixed(plane) => *plane,
+        Reference::Fit(fit_config) => {
+            let outside: Vec<PointXYZI> = config
+                .outside(&points)
+                .map(|[x, y, z]| PointXYZI {
+                    x,
+                    y,
+                    z,
+                    intensity: 0.0,
+                })
+                .collect();
+            let cloud = PointCloud::new(accumulator.header.clone(), outside);
+            let Some(fit) = ground::fit_ground(&cloud, fit_config) else {
+                bail!("다각형 밖에서 기준 지면을 찾지 못했습니다");
+            };
+            fit.plane
+        }
+    };
+    config.measure(
+        &points,
+        &plane,
+        &accumulator.header.frame_id,
+        stamp::to_secs(&accumulator.header.stamp),
+    )
+}
+
+fn main() -> Result<(), Error> {
+    println!("Pile Volume Measurement Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "pile_volume")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    // [x0, y0, x1, y1, ...] (m, 장착 보정 후 좌표계)
+    let flat = params::float_array(&node, "volume_polygon", &[])?;
+    if flat.len() < 6 || flat.len() % 2 != 0 {
+        bail!("volume_polygon 은 [x0, y0, x1, y1, ...] 꼭짓점 3개 이상이어야 합니다");
+    }
+    let config = VolumeConfig {
+        polygon: flat
+            .chunks_exact(2)
+            .map(|xy| [xy[0] as f32, xy[1] as f32])
+            .collect(),
+        cell: params::float(&node, "volume_cell", 0.1)? as f32,
+        min_points: params::int(&node, "volume_min_points", 3)?.max(1) as usize,
+    };
+    let reference = match params::string(&node, "volume_reference", "fit")?.as_str() {
+        "fit" => Reference::Fit(GroundFitConfig::default()),
+        "z" => Reference::Fixed(Plane {
+            normal: [0.0, 0.0, 1.0],
+            d: -params::float(&node, "volume_ground_z", 0.0)? as f32,
+        }),
+        other => bail!("알 수 없는 volume_reference '{}' (fit, z)", other),
+    };
+    let json_path = params::string(&node, "volume_json", "volume.json")?;
+    let accumulator = Arc::new(Mutex::new(Accumulator {
+        frames: VecDeque::new(),
+        capacity: params::int(&node, "volume_frames", 20)?.max(1) as usize,
+        header: Header::default(),
+    }));
+
+    let callback_accumulator = Arc::clone(&accumulator);
+    let _subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            mount.apply(&mut cloud);
+            let xyz = cloud.iter().map(|p| [p.x, p.y, p.z]).collect();
+            callback_accumulator.lock().unwrap().push(cloud.header, xyz);
+        },
+    )?;
+
+    let _service =
+        node.create_service::<Trigger, _>("livox/volume/measure", move |_request_id, _request| {
+            let result = measure(&accumulator.lock().unwrap(), &config, &reference);
+            let (success, message) = match result {
+                Ok(report) => {
+                    let mut message = report.summary();
+                    if !json_path.is_empty() {
+                        match report.write_json(Path::new(&json_path)) {
+                            Ok(()) => message.push_str(&format!(" ({} 저장)", json_path)),
+                            Err(e) => message.push_str(&format!(" (JSON 저장 실패: {})", e)),
+                        }
+                    }
+                    (true, message)
+                }
+                Err(e) => (false, format!("부피 측정 실패: {}", e)),
+            };
+            println!("{}", message);
+            Trigger_Response { success, message }
+        })?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("서비스: livox/volume/measure");
+    shutdown.spin(&node)?;
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use anyhow::{Error, Result};
+use nav_msgs::msg::Odometry;
+use rclrs::{self, Context};
+use rust_lidar::builder::PointCloud2Builder;
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::cluster::ClusterConfig;
+use rust_lidar::deskew::PoseBuffer;
+use rust_lidar::landmarks::{self, LandmarkMap, ReflectorConfig};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::point::datatype;
+use rust_lidar::pose::Pose;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// 재귀반사체 랜드마크 노드: 높은 intensity 덩어리를 찾아 ID 가 붙은 랜드마크 지도와 연결
+// livox/landmarks/observed: 이번 프레임 관측 하나 = 포인트 하나
+//   (x/y/z: 차량 좌표계 관측 중심, map_x/map_y/map_z: 연결된 랜드마크, id, distance: 둘 사이 거리)
+// livox/landmarks: 지도 전체 (x/y/z: 지도 좌표계 위치, id, observations: 누적 관측 수)
+// odom_topic 이 없으면 차량 좌표계를 지도 좌표계로 사용 (고정 설치)
+fn main() -> Result<(), Error> {
+    println!("Retroreflector Landmark Map Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "reflector_map")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let defaults = ReflectorConfig::default();
+    let config = ReflectorConfig {
+        min_intensity: params::float(&node, "reflector_min_intensity", 150.0)? as f32,
+        cluster: ClusterConfig {
+            cell_size: params::float(&node, "reflector_cell_size", 0.1)? as f32,
+            min_points: params::int(&node, "reflector_min_points", 3)?.max(1) as usize,
+            ..defaults.cluster
+        },
+        max_size: params::float(&node, "reflector_max_size", 0.5)? as f32,
+        gate: params::float(&node, "landmark_gate", 0.5)? as f32,
+    };
+    // true 면 새 관측을 지도에 추가하고 위치를 갱신, false 면 불러온 지도에 연결만
+    let mapping = params::boolean(&node, "landmark_mapping", true)?;
+    // 랜드마크 지도 파일: 있으면 불러오고 mapping 이면 종료 시 저장
+    let map_path = params::string(&node, "landmark_map", "")?;
+    let odom_topic = params::string(&node, "odom_topic", "")?;
+    let odom_tolerance = params::float(&node, "odom_tolerance", 0.05)?;
+
+    let map = if !map_path.is_empty() && Path::new(&map_path).exists() {
+        let map = LandmarkMap::load(Path::new(&map_path))?;
+        println!(
+            "랜드마크 지도 불러옴: {} ({}개)",
+            map_path,
+            map.landmarks.len()
+        );
+        map
+    } else {
+        LandmarkMap::new()
+    };
+    let map = Arc::new(Mutex::new(map));
+
+    let poses = Arc::new(Mutex::new(PoseBuffer::new(1000)));
+    let _odom_subscriber = if odom_topic.is_empty() {
+        None
+    } else {
+        let poses = Arc::clone(&poses);
+        Some(node.create_subscription::<Odometry, _>(
+            &odom_topic,
+            rclrs::QOS_PROFILE_DEFAULT,
+            move |msg: Odometry| {
+                poses.lock().unwrap().push_odometry(&msg);
+            },
+        )?)
+    };
+
+    let observed_publisher = node
+        .create_publisher::<PointCloud2>("livox/landmarks/observed", rclrs::QOS_PROFILE_DEFAULT)?;
+    let map_publisher =
+        node.create_publisher::<PointCloud2>("livox/landmarks", rclrs::QOS_PROFILE_DEFAULT)?;
+    let callback_map = Arc::clone(&map);
+    let subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            mount.apply(&mut cloud);
+
+            // 지도 좌표계로 옮길 차량 자세 (오도메트리가 아직 없으면 이번 프레임은 건너뜀)
+            let mut map_header = cloud.header.clone();
+            let pose = if odom_topic.is_empty() {
+                Pose::IDENTITY
+            } else {
+                let poses = poses.lock().unwrap();
+                let time = stamp::to_secs(&cloud.header.stamp);
+                let Some(pose) = poses.pose_at(time, odom_tolerance) else {
+                    eprintln!("프레임 시각({:.3})의 오도메트리 자세가 없습니다", time);
+                    return;
+                };
+                map_header.frame_id = poses.frame_id.clone();
+                pose
+            };
+
+            let observations = landmarks::extract(&cloud.points, &config);
+            let mut map = callback_map.lock().unwrap();
+            let associations = map.associate(&observations, &pose, config.gate, mapping);
+
+            let mut observed = PointCloud2Builder::new()
+                .add_field("x", datatype::FLOAT32)
+                .add_field("y", datatype::FLOAT32)
+                .add_field("z", datatype::FLOAT32)
+                .add_field("map_x", datatype::FLOAT32)
+                .add_field("map_y", datatype::FLOAT32)
+                .add_field("map_z", datatype::FLOAT32)
+                .add_field("id", datatype::UINT32)
+                .add_field("distance", datatype::FLOAT32);
+            for a in &associations {
+                let Some(landmark) = map.landmarks.iter().find(|l| l.id == a.id) else {
+                    continue;
+                };
+                let c = observations[a.observation].centroid;
+                observed.push_point(&[
+                    c[0] as f64,
+                    c[1] as f64,
+                    c[2] as f64,
+                    landmark.position[0],
+                    landmark.position[1],
+                    landmark.position[2],
+                    a.id as f64,
+                    a.distance,
+                ]);
+            }
+            println!(
+                "반사체 {}개 관측, {}개 연결 (지도 {}개)",
+                observations.len(),
+                associations.len(),
+                map.landmarks.len()
+            );
+
+            let mut landmark_cloud = PointCloud2Builder::new()
+                .add_field("x", datatype::FLOAT32)
+                .add_field("y", datatype::FLOAT32)
+                .add_field("z", datatype::FLOAT32)
+                .add_field("id", datatype::UINT32)
+                .add_field("observations", datatype::UINT32);
+            for l in &map.landmarks {
+                landmark_cloud.push_point(&[
+                    l.position[0],
+                    l.position[1],
+                    l.position[2],
+                    l.id as f64,
+                    l.observations as f64,
+                ]);
+            }
+            if let Err(e) = observed_publisher.publish(observed.finish(cloud.header)) {
+                eprintln!("관측 발행 오류: {}", e);
+            }
+            if let Err(e) = map_publisher.publish(landmark_cloud.finish(map_header)) {
+                eprintln!("지도 발행 오류: {}", e);
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/landmarks, livox/landmarks/observed");
+    shutdown.spin(&node)?;
+
+    // Ctrl-C: 구독을 끊고 지도 저장
+    drop(subscriber);
+    if mapping && !map_path.is_empty() {
+        let map = map.lock().unwrap();
+        map.save(Path::new(&map_path))?;
+        println!(
+            "랜드마크 지도 저장: {} ({}개)",
+            map_path,
+            map.landmarks.len()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::{Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::builder::PointCloud2Builder;
+use rust_lidar::cloud::PointCloud;
+use rust_lidar::ground::{GroundEstimator, GroundFitConfig};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::point::datatype;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::step::{self, StepConfig};
+use rust_lidar::transform::Transform;
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+
+// 과속방지턱/단차 검출 노드: 장애물은 아니지만 감속이 필요한 낮은 턱의 위치와 높이를 발행
+// 출력 포인트 하나 = 턱 하나 (x/y: 중심, z: 높이, length: 진행 방향 길이)
+fn main() -> Result<(), Error> {
+    println!("Speed Bump / Step Detection Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "step_detector")?;
+    let shutdown = Shutdown::install()?;
+
+    let input_layout = layout::input_layout(&params::string(&node, "input_layout", "auto")?)?;
+    let mount = Transform::from_node(&node)?;
+    let config = StepConfig {
+        max_range: params::float(&node, "step_max_range", 8.0)? as f32,
+        half_width: params::float(&node, "step_width", 2.0)? as f32 / 2.0,
+        slice: params::float(&node, "step_slice", 0.2)? as f32,
+        min_step: params::float(&node, "step_min_height", 0.03)? as f32,
+        max_step: params::float(&node, "step_max_height", 0.15)? as f32,
+        ..StepConfig::default()
+    };
+    // 지면 추정이 실패하면 장착 보정 후 z = 0 을 지면으로 사용
+    let mut ground = GroundEstimator::new(GroundFitConfig::default(), 0.3);
+
+    let publisher =
+        node.create_publisher::<PointCloud2>("livox/speed_bumps", rclrs::QOS_PROFILE_DEFAULT)?;
+    let _subscriber = node.create_subscription::<PointCloud2, _>(
+        "livox/lidar",
+        rclrs::QOS_PROFILE_DEFAULT,
+        move |msg: PointCloud2| {
+            let points = match layout::parse(&msg, input_layout) {
+                Ok(points) => points,
+                Err(e) => {
+                    eprintln!("PointCloud2 파싱 실패: {}", e);
+                    return;
+                }
+            };
+            let mut cloud = PointCloud::new(msg.header, points);
+            mount.apply(&mut cloud);
+            let plane = ground.update(&cloud);
+
+            let steps = step::detect_steps(
+                cloud.iter().map(|p| {
+                    let h = plane.map_or(p.z, |plane| plane.distance([p.x, p.y, p.z]));
+                    [p.x, p.y, h]
+                }),
+                &config,
+            );
+
+            let mut builder = PointCloud2Builder::new()
+                .add_field("x", datatype::FLOAT32)
+                .add_field("y", datatype::FLOAT32)
+                .add_field("z", datatype::FLOAT32)
+                .add_field("length", datatype::FLOAT32);
+            for s in &steps {
+                builder.push_point(&[
+                    ((s.x_start + s.x_end) / 2.0) as f64,
+                    s.y as f64,
+                    s.height as f64,
+                    (s.x_end - s.x_start) as f64,
+                ]);
+                println!(
+                    "턱 검출: {:.1}~{:.1} m, 높이 {:.0} cm",
+                    s.x_start,
+                    s.x_end,
+                    s.height * 100.0
+                );
+            }
+            if let Err(e) = publisher.publish(builder.finish(cloud.header)) {
+                eprintln!("발행 오류: {}", e);
+            }
+        },
+    )?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("구독 토픽: livox/lidar");
+    println!("발행 토픽: livox/speed_bumps");
+    shutdown.spin(&node)?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anyhow::{Error, Result};
+use rclrs::{self, Context};
+use rust_lidar::layout;
+use rust_lidar::params;
+use rust_lidar::shutdown::Shutdown;
+use rust_lidar::stamp;
+use rust_lidar::synthetic::{self, EgoMotion, SyntheticConfig};
+use sensor_msgs::msg::PointCloud2;
+use std::env;
+use std::thread;
+use std::time::Duration;
+use std_msgs::msg::Header;
+
+// 실제 센서 없이 파이프라인을 시험하기 위한 합성 Livox 프레임 발행 노드
+// livox/lidar: 센서 출력 (ego motion 이 있으면 왜곡됨), livox/lidar_truth: 프레임 시작 자세 기준 정답
+fn main() -> Result<(), Error> {
+    println!("Synthetic LiDAR Publisher Node");
+    let context = Context::new(env::args())?;
+    let node = rclrs::create_node(&context, "synthetic_lidar")?;
+    let shutdown = Shutdown::install()?;
+
+    let frame_id = params::string(&node, "frame_id", "livox_frame")?;
+    let config = SyntheticConfig {
+        lines: params::int(&node, "synthetic_lines", 4)?.clamp(1, 255) as u8,
+        points_per_line: params::int(&node, "synthetic_points_per_line", 2500)?.max(1) as usize,
+        frame_duration: params::float(&node, "synthetic_frame_duration", 0.1)? as f32,
+        sensor_height: params::float(&node, "synthetic_sensor_height", 1.5)? as f32,
+        half_size: params::float(&node, "synthetic_room_half_size", 10.0)? as f32,
+        motion: EgoMotion {
+            velocity: [
+                params::float(&node, "synthetic_velocity_x", 0.0)? as f32,
+                params::float(&node, "synthetic_velocity_y", 0.0)? as f32,
+                0.0,
+            ],
+            yaw_rate: params::float(&node, "synthetic_yaw_rate", 0.0)? as f32,
+        },
+        ..SyntheticConfig::default()
+    };
+
+    let publisher =
+        node.create_publisher::<PointCloud2>("livox/lidar", rclrs::QOS_PROFILE_DEFAULT)?;
+    let truth_publisher =
+        node.create_publisher::<PointCloud2>("livox/lidar_truth", rclrs::QOS_PROFILE_DEFAULT)?;
+
+    // 상대 토픽 이름은 노드 네임스페이스 아래로 (--ros-args -r __ns:=/front_lidar)
+    println!("네임스페이스: {}", node.namespace());
+    println!("발행 토픽: livox/lidar, livox/lidar_truth");
+    // rclrs 0.4 에는 타이머가 없으므로 프레임 주기만큼 잠자며 발행
+    let period = Duration::from_secs_f32(config.frame_duration.max(0.01));
+    while !shutdown.requested() {
+        let stamp = stamp::now();
+        let frame = synthetic::generate(&config, stamp::to_secs(&stamp) * 1e9);
+        let header = Header {
+            stamp,
+            frame_id: frame_id.clone(),
+        };
+        publisher.publish(layout::LIVOX_26.encode(&frame.distorted, header.clone()))?;
+        truth_publisher.publish(layout::LIVOX_26.encode(&frame.truth, header))?;
+        thread::sleep(period);
+    }
+    Ok(())
+}
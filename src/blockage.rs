@@ -0,0 +1,218 @@
+use crate::cloud::Point;
+#[cfg(feature = "ros")]
+use crate::params;
+#[cfg(feature = "ros")]
+use anyhow::Result;
+#[cfg(feature = "ros")]
+use rclrs::Node;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockageConfig {
+    // 방위각 구간 수 (360° 를 나눔)
+    pub sectors: usize,
+    // 이 거리 (m) 안의 반사는 덮개 오염/가림으로 봄
+    pub near_range: f32,
+    // 구간 포인트 중 근거리 반사 비율이 이 이상이면 가림 의심
+    pub near_ratio: f32,
+    // 구간 포인트 수가 학습한 평균의 이 비율 미만이면 데이터 없음
+    pub missing_ratio: f32,
+    // 시작 후 구간별 평균 포인트 수를 학습할 프레임 수
+    pub learn_frames: u32,
+    // 이 프레임 수 이상 연속으로 이상해야 알림 (지나가는 물체로 인한 오경보 방지)
+    pub persist_frames: u32,
+}
+
+impl Default for BlockageConfig {
+    fn default() -> Self {
+        BlockageConfig {
+            sectors: 12,
+            near_range: 0.3,
+            near_ratio: 0.5,
+            missing_ratio: 0.2,
+            learn_frames: 50,
+            persist_frames: 20,
+        }
+    }
+}
+
+impl BlockageConfig {
+    // blockage_detection=false 면 None, blockage_sectors, blockage_near_range (m),
+    // blockage_near_ratio, blockage_missing_ratio, blockage_learn_frames, blockage_persist_frames
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        if !params::boolean(node, "blockage_detection", false)? {
+            return Ok(None);
+        }
+        let defaults = BlockageConfig::default();
+        Ok(Some(BlockageConfig {
+            sectors: params::int(node, "blockage_sectors", defaults.sectors as i64)?.clamp(1, 360)
+                as usize,
+            near_range: params::float(node, "blockage_near_range", defaults.near_range as f64)?
+                as f32,
+            near_ratio: params::float(node, "blockage_near_ratio", defaults.near_ratio as f64)?
+                as f32,
+            missing_ratio: params::float(
+                node,
+                "blockage_missing_ratio",
+                defaults.missing_ratio as f64,
+            )? as f32,
+            learn_frames: params::int(node, "blockage_learn_frames", defaults.learn_frames as i64)?
+                .max(1) as u32,
+            persist_frames: params::int(
+                node,
+                "blockage_persist_frames",
+                defaults.persist_frames as i64,
+            )?
+            .max(1) as u32,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Sector {
+    // 학습 구간 포인트 수 합 (학습이 끝나면 프레임 평균)
+    baseline: f32,
+    near_streak: u32,
+    missing_streak: u32,
+}
+
+// 센서 가림/덮개 오염 감지 (센서 좌표계, 장착 보정 전 포인트 사용)
+//   근거리 반사: 덮개의 먼지/물방울/붙은 물체가 near_range 안의 반사로 보임
+//   데이터 없음: 시작 후 학습한 평균보다 포인트가 계속 적은 방위 구간 (가려진 창)
+// 학습 중에 이미 가려진 구간은 평균이 낮아 데이터 없음으로 잡히지 않음
+// FOV 밖 구간 (학습 평균이 1 미만) 은 데이터 없음 판단에서 제외
+#[derive(Debug, Clone)]
+pub struct BlockageDetector {
+    config: BlockageConfig,
+    sectors: Vec<Sector>,
+    learned: u32,
+}
+
+impl BlockageDetector {
+    pub fn new(config: BlockageConfig) -> Self {
+        BlockageDetector {
+            config,
+            sectors: vec![Sector::default(); config.sectors.max(1)],
+            learned: 0,
+        }
+    }
+
+    pub fn learning(&self) -> bool {
+        self.learned < self.config.learn_frames
+    }
+
+    // 학습을 마친 구간별 평균 포인트 수 (학습 중이면 None)
+    pub fn baseline(&self) -> Option<Vec<f32>> {
+        (!self.learning()).then(|| self.sectors.iter().map(|s| s.baseline).collect())
+    }
+
+    // 저장한 평균으로 학습을 건너뜀, 구간 수가 다르면 false
+    pub fn restore(&mut self, baseline: &[f32]) -> bool {
+        if baseline.len() != self.sectors.len() {
+            return false;
+        }
+        for (sector, value) in self.sectors.iter_mut().zip(baseline) {
+            sector.baseline = *value;
+        }
+        self.learned = self.config.learn_frames;
+        true
+    }
+
+    fn sector_of(&self, x: f32, y: f32) -> usize {
+        let n = self.sectors.len();
+        let t = (y.atan2(x).to_degrees() + 180.0) / 360.0;
+        ((t * n as f32) as usize).min(n - 1)
+    }
+
+    // 구간의 방위각 범위 (도, -180..180)
+    pub fn sector_range(&self, sector: usize) -> (f32, f32) {
+        let width = 360.0 / self.sectors.len() as f32;
+        let start = -180.0 + sector as f32 * width;
+        (start, start + width)
+    }
+
+    pub fn update<P: Point>(&mut self, points: &[P]) {
+        let mut counts = vec![(0u32, 0u32); self.sectors.len()];
+        let near_sq = self.config.near_range * self.config.near_range;
+        for p in points {
+            let [x, y, z] = p.xyz();
+            if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+                continue;
+            }
+            let count = &mut counts[self.sector_of(x, y)];
+            count.0 += 1;
+            if x * x + y * y + z * z < near_sq {
+                count.1 += 1;
+            }
+        }
+        let learning = self.learning();
+        if learning {
+            self.learned += 1;
+        }
+        let learned = self.learned as f32;
+        let finished = learning && !self.learning();
+        let config = self.config;
+        for (sector, (total, near)) in self.sectors.iter_mut().zip(counts) {
+            if learning {
+                sector.baseline += total as f32;
+                if finished {
+                    sector.baseline /= learned;
+                }
+            }
+            let near_blocked = total > 0 && near as f32 >= config.near_ratio * total as f32;
+            sector.near_streak = if near_blocked {
+                sector.near_streak + 1
+            } else {
+                0
+            };
+            let missing = !learning
+                && sector.baseline >= 1.0
+                && (total as f32) < config.missing_ratio * sector.baseline;
+            sector.missing_streak = if missing {
+                sector.missing_streak + 1
+            } else {
+                0
+            };
+        }
+    }
+
+    // 근거리 반사가 persist_frames 이상 이어진 구간
+    pub fn near_sectors(&self) -> Vec<usize> {
+        self.alerted(|s| s.near_streak)
+    }
+
+    // 데이터 없음이 persist_frames 이상 이어진 구간
+    pub fn missing_sectors(&self) -> Vec<usize> {
+        self.alerted(|s| s.missing_streak)
+    }
+
+    fn alerted(&self, streak: impl Fn(&Sector) -> u32) -> Vec<usize> {
+        self.sectors
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| streak(s) >= self.config.persist_frames)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn blocked(&self) -> bool {
+        !self.near_sectors().is_empty() || !self.missing_sectors().is_empty()
+    }
+
+    // "-180~-150, 30~60" 형식 (연속 구간은 합침), 없으면 빈 문자열
+    pub fn describe(&self, sectors: &[usize]) -> String {
+        let mut ranges: Vec<(f32, f32)> = Vec::new();
+        for &sector in sectors {
+            let (start, end) = self.sector_range(sector);
+            match ranges.last_mut() {
+                Some(last) if last.1 == start => last.1 = end,
+                _ => ranges.push((start, end)),
+            }
+        }
+        ranges
+            .iter()
+            .map(|(start, end)| format!("{:.0}~{:.0}", start, end))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -0,0 +1,95 @@
+use crate::msg::{Header, PointCloud2, PointField};
+use crate::point::datatype;
+
+// 필드를 순서대로 추가하면 offset/point_step/row_step 을 자동으로 계산
+#[derive(Debug, Default)]
+pub struct PointCloud2Builder {
+    fields: Vec<PointField>,
+    point_step: usize,
+    data: Vec<u8>,
+    width: usize,
+    // x/y/z 중 NaN/Inf 가 있는 포인트 수 (0 이 아니면 is_dense = false)
+    invalid: usize,
+}
+
+impl PointCloud2Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 기존 data 버퍼의 용량을 재사용 (내용은 비움)
+    pub fn with_buffer(mut self, mut data: Vec<u8>) -> Self {
+        data.clear();
+        self.data = data;
+        self.width = 0;
+        self.invalid = 0;
+        self
+    }
+
+    pub fn add_field(mut self, name: &str, datatype: u8) -> Self {
+        let size = datatype::size(datatype)
+            .unwrap_or_else(|| panic!("필드 '{}' 의 datatype({}) 이 잘못됨", name, datatype));
+        self.fields.push(PointField {
+            name: name.to_string(),
+            offset: self.point_step as u32,
+            datatype,
+            count: 1,
+        });
+        self.point_step += size;
+        self
+    }
+
+    pub fn point_step(&self) -> usize {
+        self.point_step
+    }
+
+    pub fn reserve(&mut self, points: usize) {
+        self.data.reserve(points * self.point_step);
+    }
+
+    // values 는 add_field 순서와 같아야 함
+    pub fn push_point(&mut self, values: &[f64]) {
+        assert_eq!(
+            values.len(),
+            self.fields.len(),
+            "push_point 값 개수가 필드 개수와 다릅니다"
+        );
+        let mut valid = true;
+        for (field, &value) in self.fields.iter().zip(values) {
+            if matches!(field.name.as_str(), "x" | "y" | "z") {
+                valid &= value.is_finite();
+            }
+            datatype::write(&mut self.data, field.datatype, value);
+        }
+        self.width += 1;
+        if !valid {
+            self.invalid += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0
+    }
+
+    pub fn invalid(&self) -> usize {
+        self.invalid
+    }
+
+    pub fn finish(self, header: Header) -> PointCloud2 {
+        PointCloud2 {
+            header,
+            height: 1,
+            width: self.width as u32,
+            fields: self.fields,
+            is_bigendian: false,
+            point_step: self.point_step as u32,
+            row_step: (self.width * self.point_step) as u32,
+            data: self.data,
+            is_dense: self.invalid == 0,
+        }
+    }
+}
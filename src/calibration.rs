@@ -0,0 +1,148 @@
+use crate::ground::{self, Plane};
+use crate::intensity::ReflectanceSample;
+use crate::point::LidarPoint;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+// 지면 평면으로부터 추정한 장착 높이/롤/피치 (요와 x/y 는 지면만으로 알 수 없음)
+#[derive(Debug, Clone, Copy)]
+pub struct MountCalibration {
+    pub roll: f64,
+    pub pitch: f64,
+    pub height: f64,
+    pub frames: usize,
+}
+
+impl MountCalibration {
+    // 프레임별 평면을 평균: normal 은 더한 뒤 정규화, 높이는 산술 평균
+    pub fn from_planes(planes: &[Plane]) -> Option<Self> {
+        if planes.is_empty() {
+            return None;
+        }
+        let mut normal = [0f64; 3];
+        let mut height = 0.0;
+        for plane in planes {
+            for (sum, v) in normal.iter_mut().zip(plane.normal) {
+                *sum += v as f64;
+            }
+            height += plane.d as f64;
+        }
+        let len = normal.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if len < 1e-9 {
+            return None;
+        }
+        let (roll, pitch) = ground::level_angles(normal.map(|v| v / len));
+        Some(MountCalibration {
+            roll,
+            pitch,
+            height: height / planes.len() as f64,
+            frames: planes.len(),
+        })
+    }
+
+    // Transform::from_node 가 읽는 파라미터 이름과 값
+    pub fn params(&self) -> [(&'static str, f64); 3] {
+        [
+            ("mount_roll_deg", self.roll.to_degrees()),
+            ("mount_pitch_deg", self.pitch.to_degrees()),
+            ("mount_z", self.height),
+        ]
+    }
+}
+
+// 반사율을 알고 있는 기준 타깃 (센서 좌표계 상자 안의 포인트를 타깃으로 봄)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectanceTarget {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+    // 알려진 반사율 (%)
+    pub reflectance: f32,
+}
+
+impl ReflectanceTarget {
+    // 관측 하나로 인정할 최소 포인트 수
+    pub const MIN_POINTS: usize = 5;
+
+    // "x_min,y_min,z_min,x_max,y_max,z_max,reflectance" (reflectance 는 %)
+    pub fn parse(text: &str) -> Result<Self> {
+        let values = text
+            .split(',')
+            .map(|v| {
+                v.trim()
+                    .parse::<f32>()
+                    .with_context(|| format!("타깃 '{}' 의 숫자가 아닌 값 '{}'", text, v))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if values.len() != 7 {
+            bail!(
+                "타깃 '{}': 값 7개 (x_min,y_min,z_min,x_max,y_max,z_max,reflectance) 가 필요합니다",
+                text
+            );
+        }
+        let target = ReflectanceTarget {
+            min: [values[0], values[1], values[2]],
+            max: [values[3], values[4], values[5]],
+            reflectance: values[6],
+        };
+        if (0..3).any(|i| target.min[i] >= target.max[i]) || target.reflectance <= 0.0 {
+            bail!("타깃 '{}': min < max, reflectance > 0 이어야 합니다", text);
+        }
+        Ok(target)
+    }
+
+    // 상자 안 포인트의 intensity/거리 평균, 포인트가 MIN_POINTS 보다 적으면 None
+    pub fn observe(&self, points: &[LidarPoint]) -> Option<ReflectanceSample> {
+        let (mut count, mut intensity, mut range) = (0usize, 0f64, 0f64);
+        for p in points {
+            let xyz = [p.x, p.y, p.z];
+            if (0..3).all(|i| xyz[i] >= self.min[i] && xyz[i] <= self.max[i]) {
+                count += 1;
+                intensity += p.intensity as f64;
+                range += ((p.x * p.x + p.y * p.y + p.z * p.z) as f64).sqrt();
+            }
+        }
+        (count >= Self::MIN_POINTS).then(|| ReflectanceSample {
+            intensity: (intensity / count as f64) as f32,
+            range: (range / count as f64) as f32,
+            reflectance: self.reflectance,
+        })
+    }
+}
+
+// ROS 2 파라미터 YAML(--params-file) 에서 주어진 키만 갱신, 없으면 ros__parameters 아래에 추가
+// 파일이 없으면 모든 노드(/**)에 적용되는 파일을 새로 만듦
+pub fn update_params_file(path: &Path, values: &[(&str, f64)]) -> Result<()> {
+    let text = if path.exists() {
+        fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?
+    } else {
+        "/**:\n  ros__parameters:\n".to_string()
+    };
+
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let Some(section) = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("ros__parameters:"))
+    else {
+        anyhow::bail!("{} 에 ros__parameters 섹션이 없습니다", path.display());
+    };
+    let indent = lines[section].len() - lines[section].trim_start().len() + 2;
+
+    for (key, value) in values {
+        let entry = format!("{}{}: {:.6}", " ".repeat(indent), key, value);
+        let prefix = format!("{}:", key);
+        match lines
+            .iter()
+            .skip(section + 1)
+            .position(|l| l.trim_start().starts_with(&prefix))
+        {
+            Some(i) => lines[section + 1 + i] = entry,
+            None => lines.insert(section + 1, entry),
+        }
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    fs::write(path, out).with_context(|| format!("{} 쓰기 실패", path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::cloud::{Point, PointCloud};
+#[cfg(feature = "ros")]
+use crate::params;
+#[cfg(feature = "ros")]
+use anyhow::Result;
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureConfig {
+    // 평소 출력 해상도 (복셀 크기 m, 복셀마다 첫 포인트만 남김)
+    pub voxel: f32,
+    // 트리거 후 원래 해상도로 내보내는 시간 (초, 프레임 시각 기준)
+    pub hold: f64,
+}
+
+impl CaptureConfig {
+    // capture_trigger_topic 이 비어 있으면 None, capture_voxel (m), capture_hold (초)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<(String, Self)>> {
+        let topic = params::string(node, "capture_trigger_topic", "")?;
+        if topic.is_empty() {
+            return Ok(None);
+        }
+        let config = CaptureConfig {
+            voxel: params::float(node, "capture_voxel", 0.2)? as f32,
+            hold: params::float(node, "capture_hold", 5.0)?,
+        };
+        Ok(Some((topic, config)))
+    }
+}
+
+// 이벤트 트리거 고해상도 캡처: 평소에는 다운샘플해서 대역폭을 줄이고
+// 외부 트리거 (물체 감지 등) 가 오면 hold 초 동안 원래 해상도로 내보냄
+// 트리거 시각은 수신 후 처음 처리하는 프레임 시각으로 정함 (bag 재생에서도 같은 구간)
+#[derive(Debug, Clone)]
+pub struct EventCapture {
+    config: CaptureConfig,
+    pending: bool,
+    // 원래 해상도 구간 끝 (초, 프레임 시각), 없으면 평소 해상도
+    until: Option<f64>,
+    // 마지막 프레임을 원래 해상도로 내보냈는지 (진단용)
+    capturing: bool,
+}
+
+impl EventCapture {
+    pub fn new(config: CaptureConfig) -> Self {
+        EventCapture {
+            config,
+            pending: false,
+            until: None,
+            capturing: false,
+        }
+    }
+
+    // true: 다음 프레임부터 hold 초 (진행 중이면 연장), false: 바로 평소 해상도로
+    pub fn trigger(&mut self, start: bool) {
+        self.pending = start;
+        if !start {
+            self.until = None;
+        }
+    }
+
+    // frame_time 프레임을 원래 해상도로 내보낼지
+    pub fn full_resolution(&mut self, frame_time: f64) -> bool {
+        if std::mem::take(&mut self.pending) {
+            self.until = Some(frame_time + self.config.hold);
+        }
+        self.capturing = self.until.is_some_and(|until| frame_time <= until);
+        self.capturing
+    }
+
+    pub fn capturing(&self) -> bool {
+        self.capturing
+    }
+
+    // 남길 포인트 마스크 (원래 해상도 구간이면 모두 남김)
+    pub fn mask<P: Point>(&mut self, points: &[P], frame_time: f64) -> Vec<bool> {
+        if self.full_resolution(frame_time) || self.config.voxel <= 0.0 {
+            return vec![true; points.len()];
+        }
+        let size = self.config.voxel;
+        let mut seen = HashSet::new();
+        points
+            .iter()
+            .map(|p| seen.insert(p.xyz().map(|v| (v / size).floor() as i32)))
+            .collect()
+    }
+
+    pub fn apply<P: Point>(&mut self, cloud: &mut PointCloud<P>, frame_time: f64) {
+        let mut keep = self.mask(&cloud.points, frame_time).into_iter();
+        cloud.retain(|_| keep.next().unwrap_or(true));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::pose::Pose;
+use crate::registration::{self, IcpConfig, IcpResult, VoxelIndex};
+
+// 두 클라우드/지도 사이 변화 검출 (공사 진척, 보안 감시용)
+// current 를 reference 에 ICP 로 맞춘 뒤 서로 threshold 안에 대응점이 없는 포인트를
+// 추가(current 에만 있음) / 제거(reference 에만 있음) 로 표시
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeConfig {
+    // 비교 전 다운샘플 복셀 (m)
+    pub voxel: f32,
+    // 이 거리 안에 상대 포인트가 없으면 변화로 봄 (m)
+    pub threshold: f32,
+    // current 관측 위치에서 이 거리 밖의 reference 포인트는 보이지 않았을 수 있으므로 제거로 보지 않음
+    // (m, 0 이면 제한 없음)
+    pub max_range: f32,
+    pub icp: IcpConfig,
+    // 정합 대응 비율이 이보다 낮으면 정합하지 않고 (identity) 비교
+    pub min_fitness: f64,
+}
+
+impl Default for ChangeConfig {
+    fn default() -> Self {
+        ChangeConfig {
+            voxel: 0.1,
+            threshold: 0.3,
+            max_range: 40.0,
+            icp: IcpConfig::default(),
+            min_fitness: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChangeResult {
+    // current -> reference 자세 (정합 실패 시 identity)
+    pub pose: Pose,
+    pub icp: Option<IcpResult>,
+    // reference 좌표계 포인트
+    pub added: Vec<[f32; 3]>,
+    pub removed: Vec<[f32; 3]>,
+    // 비교에 쓴 (다운샘플 후) 포인트 수
+    pub reference_points: usize,
+    pub current_points: usize,
+}
+
+impl ChangeResult {
+    pub fn aligned(&self) -> bool {
+        self.icp.is_some()
+    }
+
+    // 추가/제거 포인트 비율 (다운샘플 후 포인트 수 기준)
+    pub fn added_ratio(&self) -> f64 {
+        self.added.len() as f64 / self.current_points.max(1) as f64
+    }
+
+    pub fn removed_ratio(&self) -> f64 {
+        self.removed.len() as f64 / self.reference_points.max(1) as f64
+    }
+}
+
+pub fn detect(reference: &[[f32; 3]], current: &[[f32; 3]], config: &ChangeConfig) -> ChangeResult {
+    // 최근접 검색 셀 크기 = 검색 거리 (ICP 대응 거리와 변화 기준 거리가 달라 색인을 따로 만듦)
+    let reference = registration::voxel_downsample(reference, config.voxel);
+    let icp_target = VoxelIndex::new(reference.clone(), config.icp.max_distance);
+    let reference = VoxelIndex::new(reference, config.threshold.max(config.voxel));
+    let current = registration::voxel_downsample(current, config.voxel);
+
+    let icp = registration::icp(&current, &icp_target, Pose::IDENTITY, &config.icp)
+        .filter(|result| result.fitness >= config.min_fitness);
+    let pose = icp.map_or(Pose::IDENTITY, |result| result.pose);
+    let moved: Vec<[f32; 3]> = current
+        .iter()
+        .map(|&p| pose.transform_point(p.map(|v| v as f64)).map(|v| v as f32))
+        .collect();
+    let moved_index = VoxelIndex::new(moved, config.threshold.max(config.voxel));
+
+    let added = moved_index
+        .points()
+        .iter()
+        .copied()
+        .filter(|&p| reference.nearest(p, config.threshold).is_none())
+        .collect();
+    let origin = pose.position.map(|v| v as f32);
+    let range_sq = config.max_range * config.max_range;
+    let removed = reference
+        .points()
+        .iter()
+        .copied()
+        .filter(|p| {
+            let d = [p[0] - origin[0], p[1] - origin[1], p[2] - origin[2]];
+            config.max_range <= 0.0 || d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= range_sq
+        })
+        .filter(|&p| moved_index.nearest(p, config.threshold).is_none())
+        .collect();
+
+    ChangeResult {
+        pose,
+        icp,
+        added,
+        removed,
+        reference_points: reference.len(),
+        current_points: moved_index.len(),
+    }
+}
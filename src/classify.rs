@@ -0,0 +1,184 @@
+use crate::tracking::Track;
+use crate::yaml;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+// 클러스터 모양/속도 규칙으로 보행자/차량/기타 구분 (학습 검출기 전까지 쓰는 간단한 분류)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectClass {
+    Other,
+    Pedestrian,
+    Vehicle,
+}
+
+impl ObjectClass {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectClass::Other => "other",
+            ObjectClass::Pedestrian => "pedestrian",
+            ObjectClass::Vehicle => "vehicle",
+        }
+    }
+
+    // PointCloud2 class 필드 값
+    pub fn id(&self) -> u8 {
+        *self as u8
+    }
+}
+
+// 특징별 [min, max] 범위, 모두 만족해야 해당 클래스
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassRule {
+    // 긴 변, 짧은 변, 높이 (m)
+    pub length: [f32; 2],
+    pub width: [f32; 2],
+    pub height: [f32; 2],
+    // 속도 (m/s)
+    pub speed: [f32; 2],
+}
+
+impl ClassRule {
+    const ANY: [f32; 2] = [0.0, f32::INFINITY];
+
+    pub fn matches(&self, track: &Track) -> bool {
+        let [length, width, height] = track.cluster.dimensions();
+        let within = |[min, max]: [f32; 2], value: f32| value >= min && value <= max;
+        within(self.length, length)
+            && within(self.width, width)
+            && within(self.height, height)
+            && within(self.speed, track.speed())
+    }
+
+    fn set(&mut self, key: &str, value: f32) -> Result<()> {
+        let (bound, feature) = key
+            .split_once('_')
+            .with_context(|| format!("알 수 없는 임계값 '{}'", key))?;
+        let range = match feature {
+            "length" => &mut self.length,
+            "width" => &mut self.width,
+            "height" => &mut self.height,
+            "speed" => &mut self.speed,
+            _ => bail!(
+                "알 수 없는 특징 '{}' (length, width, height, speed)",
+                feature
+            ),
+        };
+        match bound {
+            "min" => range[0] = value,
+            "max" => range[1] = value,
+            _ => bail!("임계값은 min_* / max_* 여야 합니다: '{}'", key),
+        }
+        Ok(())
+    }
+}
+
+// 보행자를 먼저 검사하고, 어느 규칙에도 맞지 않으면 other
+//
+// YAML 형식 (적은 값만 기본값을 덮어씀):
+//   pedestrian:
+//     max_length: 1.2
+//     min_height: 0.9
+//     max_speed: 3.0
+//   vehicle:
+//     min_length: 2.5
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassRules {
+    pub pedestrian: ClassRule,
+    pub vehicle: ClassRule,
+}
+
+impl Default for ClassRules {
+    fn default() -> Self {
+        ClassRules {
+            pedestrian: ClassRule {
+                length: [0.1, 1.2],
+                width: [0.1, 1.0],
+                height: [0.9, 2.2],
+                speed: [0.0, 3.5],
+            },
+            vehicle: ClassRule {
+                length: [2.5, 15.0],
+                width: [1.2, 3.5],
+                height: [1.0, 4.5],
+                speed: ClassRule::ANY,
+            },
+        }
+    }
+}
+
+impl ClassRules {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        Self::parse(&text).with_context(|| format!("{} 해석 실패", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut rules = ClassRules::default();
+        yaml::for_each_entry(text, |entry| {
+            let rule = match entry.section.unwrap_or(entry.key) {
+                "pedestrian" => &mut rules.pedestrian,
+                "vehicle" => &mut rules.vehicle,
+                other => bail!("알 수 없는 클래스 '{}' (pedestrian, vehicle)", other),
+            };
+            if entry.section.is_some() {
+                let value: f32 = entry
+                    .value
+                    .parse()
+                    .with_context(|| format!("숫자가 아닌 값 '{}'", entry.value))?;
+                rule.set(entry.key, value)?;
+            }
+            Ok(())
+        })?;
+        Ok(rules)
+    }
+
+    pub fn classify(&self, track: &Track) -> ObjectClass {
+        if self.pedestrian.matches(track) {
+            ObjectClass::Pedestrian
+        } else if self.vehicle.matches(track) {
+            ObjectClass::Vehicle
+        } else {
+            ObjectClass::Other
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_overrides_only_given_thresholds() {
+        let rules = ClassRules::parse(
+            "pedestrian:\n  max_length: 1.5\n  min_height: 0.8\nvehicle:\n  max_speed: 40\n",
+        )
+        .unwrap();
+        let defaults = ClassRules::default();
+        assert_eq!(
+            rules.pedestrian.length,
+            [defaults.pedestrian.length[0], 1.5]
+        );
+        assert_eq!(
+            rules.pedestrian.height,
+            [0.8, defaults.pedestrian.height[1]]
+        );
+        assert_eq!(rules.pedestrian.speed, defaults.pedestrian.speed);
+        assert_eq!(rules.vehicle.speed, [0.0, 40.0]);
+        assert_eq!(rules.vehicle.length, defaults.vehicle.length);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_classes_and_thresholds() {
+        for text in [
+            "cyclist:\n  max_length: 2.0\n",
+            "vehicle:\n  max_mass: 2.0\n",
+            "vehicle:\n  avg_length: 2.0\n",
+            "vehicle:\n  length: 2.0\n",
+            "vehicle:\n  min_length: long\n",
+        ] {
+            assert!(ClassRules::parse(text).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::snapshot;
+use anyhow::{anyhow, bail, Result};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+// 노드 공통 출력 옵션: --quiet, --verbose, --every N, --qos PRESET, --replay-config FILE
+// (--ros-args 뒤의 인자는 rcl 이 처리하므로 무시)
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    pub verbosity: Verbosity,
+    pub every: u64,
+    // 포인트 클라우드 QoS/전송 프리셋 이름 (qos::QosPreset)
+    pub qos: String,
+    // 이전 실행의 설정 스냅샷 (snapshot::ConfigSnapshot) 을 params 파일로 다시 적용
+    pub replay_config: Option<String>,
+    // 첫 --ros-args 부터의 인자 (rcl 이 처리, executor 가 노드 지정 이름 변경을 검사)
+    pub ros_args: Vec<String>,
+    frame: u64,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        OutputOptions {
+            verbosity: Verbosity::Normal,
+            every: 1,
+            qos: "default".to_string(),
+            replay_config: None,
+            ros_args: Vec::new(),
+            frame: 0,
+        }
+    }
+}
+
+impl OutputOptions {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = OutputOptions::default();
+        let mut args: Vec<String> = args.into_iter().skip(1).collect();
+        if let Some(start) = args.iter().position(|arg| arg == "--ros-args") {
+            options.ros_args = args.split_off(start);
+        }
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+                "--every" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--every 뒤에 프레임 수가 필요합니다"))?;
+                    options.every = value
+                        .parse::<u64>()
+                        .map_err(|_| anyhow!("--every 값이 잘못됨: {}", value))?
+                        .max(1);
+                }
+                "--qos" => {
+                    options.qos = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--qos 뒤에 프리셋 이름이 필요합니다"))?;
+                }
+                "--replay-config" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--replay-config 뒤에 스냅샷 파일이 필요합니다"))?;
+                    if !Path::new(&path).exists() {
+                        bail!("설정 스냅샷 파일이 없습니다: {}", path);
+                    }
+                    options.replay_config = Some(path);
+                }
+                other => bail!(
+                    "알 수 없는 옵션 '{}' (--quiet, --verbose, --every N, --qos PRESET, --replay-config FILE)",
+                    other
+                ),
+            }
+        }
+
+        Ok(options)
+    }
+
+    // rclrs Context 에 넘길 인자 (--replay-config 가 있으면 스냅샷을 마지막 params 파일로 추가)
+    pub fn context_args(&self, args: impl IntoIterator<Item = String>) -> Vec<String> {
+        match &self.replay_config {
+            Some(path) => snapshot::replay_args(args, path),
+            None => args.into_iter().collect(),
+        }
+    }
+
+    // 프레임마다 호출, 이번 프레임 요약을 출력해야 하면 true
+    pub fn tick(&mut self) -> bool {
+        let frame = self.frame;
+        self.frame += 1;
+        self.verbosity > Verbosity::Quiet && frame.is_multiple_of(self.every)
+    }
+
+    pub fn verbose(&self) -> bool {
+        self.verbosity == Verbosity::Verbose
+    }
+}
@@ -0,0 +1,188 @@
+use crate::msg::{Header, PointCloud2, PointField};
+use crate::point::{self, FieldSpec, LidarPoint};
+use anyhow::{bail, Result};
+
+// 포인트 구조체의 바이트 레이아웃, 보통 #[derive(PointLayout)] 으로 생성
+pub use rust_lidar_derive::PointLayout;
+
+pub trait PointLayout: Sized {
+    // (필드 이름, offset, PointField datatype)
+    const FIELDS: &'static [(&'static str, usize, u8)];
+    const POINT_STEP: usize;
+
+    // data 는 최소 POINT_STEP 바이트 (리틀 엔디안)
+    fn read(data: &[u8]) -> Self;
+    fn write(&self, out: &mut Vec<u8>);
+
+    // FIELDS 순서의 값으로 변환/복원
+    fn values(&self, out: &mut Vec<f64>);
+    fn from_values(values: &[f64]) -> Self;
+
+    fn point_fields() -> Vec<PointField> {
+        Self::FIELDS
+            .iter()
+            .map(|&(name, offset, datatype)| PointField {
+                name: name.to_string(),
+                offset: offset as u32,
+                datatype,
+                count: 1,
+            })
+            .collect()
+    }
+
+    // 메시지 레이아웃이 그대로 일치하면 read/write 를 바로 사용할 수 있음
+    fn matches(msg: &PointCloud2) -> bool {
+        !msg.is_bigendian
+            && msg.point_step as usize == Self::POINT_STEP
+            && msg.fields.len() == Self::FIELDS.len()
+            && msg
+                .fields
+                .iter()
+                .zip(Self::FIELDS)
+                .all(|(f, &(name, offset, dt))| {
+                    f.name == name && f.offset as usize == offset && f.datatype == dt
+                })
+    }
+}
+
+// PCL 처럼 포인트 타입마다 필드 메타데이터를 가지는 트레이트
+pub trait Point: PointLayout + Copy + Default {
+    fn xyz(&self) -> [f32; 3];
+    fn set_xyz(&mut self, xyz: [f32; 3]);
+}
+
+#[derive(Debug, Clone, Copy, Default, PointLayout)]
+pub struct PointXYZI {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub intensity: f32,
+}
+
+impl Point for PointXYZI {
+    fn xyz(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn set_xyz(&mut self, [x, y, z]: [f32; 3]) {
+        self.x = x;
+        self.y = y;
+        self.z = z;
+    }
+}
+
+impl Point for LidarPoint {
+    fn xyz(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn set_xyz(&mut self, [x, y, z]: [f32; 3]) {
+        self.x = x;
+        self.y = y;
+        self.z = z;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PointCloud<P: Point> {
+    pub header: Header,
+    pub points: Vec<P>,
+}
+
+impl<P: Point> PointCloud<P> {
+    pub fn new(header: Header, points: Vec<P>) -> Self {
+        PointCloud { header, points }
+    }
+
+    // 메시지에 없는 필드는 0 으로 채움, x/y/z 는 필수
+    pub fn from_msg(msg: &PointCloud2) -> Result<Self> {
+        let header = msg.header.clone();
+        let data = point::packed_data(msg)?;
+        if P::matches(msg) {
+            let points = data
+                .chunks_exact(P::POINT_STEP)
+                .map(|chunk| P::read(chunk))
+                .collect();
+            return Ok(PointCloud { header, points });
+        }
+
+        let specs: Vec<Option<FieldSpec>> = P::FIELDS
+            .iter()
+            .map(|(name, _, _)| {
+                msg.fields
+                    .iter()
+                    .find(|f| f.name == *name)
+                    .map(|f| FieldSpec::new(f.offset as usize, f.datatype))
+            })
+            .collect();
+        if ["x", "y", "z"].iter().any(|axis| {
+            !P::FIELDS
+                .iter()
+                .zip(&specs)
+                .any(|(&(name, _, _), spec)| name == *axis && spec.is_some())
+        }) {
+            bail!("PointCloud2 에 x/y/z 필드가 없습니다");
+        }
+
+        let point_step = msg.point_step as usize;
+        let extent = specs.iter().flatten().map(|s| s.end()).max().unwrap_or(0);
+        if point_step < extent {
+            bail!(
+                "point_step({}) 이 필드 범위({} bytes)보다 작습니다",
+                point_step,
+                extent
+            );
+        }
+
+        let mut values = vec![0.0; specs.len()];
+        let points = (0..data.len() / point_step)
+            .map(|i| {
+                let base = i * point_step;
+                for (value, spec) in values.iter_mut().zip(&specs) {
+                    *value = spec.map_or(0.0, |s| s.read(&data, base, msg.is_bigendian));
+                }
+                P::from_values(&values)
+            })
+            .collect();
+
+        Ok(PointCloud { header, points })
+    }
+
+    pub fn to_msg(&self) -> PointCloud2 {
+        let mut data = Vec::with_capacity(self.points.len() * P::POINT_STEP);
+        for point in &self.points {
+            point.write(&mut data);
+        }
+
+        PointCloud2 {
+            header: self.header.clone(),
+            height: 1,
+            width: self.points.len() as u32,
+            fields: P::point_fields(),
+            is_bigendian: false,
+            point_step: P::POINT_STEP as u32,
+            row_step: (self.points.len() * P::POINT_STEP) as u32,
+            data,
+            is_dense: self
+                .points
+                .iter()
+                .all(|p| p.xyz().iter().all(|v| v.is_finite())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, P> {
+        self.points.iter()
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&P) -> bool) {
+        self.points.retain(f);
+    }
+}
@@ -0,0 +1,110 @@
+// 지면 위 포인트를 BEV 격자 연결 성분으로 묶어 물체 후보(클러스터)와 모양 특징을 구함
+// 같은 셀이나 이웃 8셀에 포인트가 있으면 같은 클러스터
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClusterConfig {
+    // 연결 판단 셀 크기 (m), 이보다 멀리 떨어진 물체는 분리됨
+    pub cell_size: f32,
+    // 클러스터로 인정할 포인트 수 범위
+    pub min_points: usize,
+    pub max_points: usize,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        ClusterConfig {
+            cell_size: 0.3,
+            min_points: 5,
+            max_points: 50_000,
+        }
+    }
+}
+
+// 클러스터 하나의 모양 특징 (z 는 지면 위 높이 기준)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cluster {
+    pub centroid: [f32; 3],
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+    pub points: usize,
+}
+
+impl Cluster {
+    // x/y 축 정렬 상자의 긴 변, 짧은 변, 높이 (m)
+    pub fn dimensions(&self) -> [f32; 3] {
+        let dx = self.max[0] - self.min[0];
+        let dy = self.max[1] - self.min[1];
+        [dx.max(dy), dx.min(dy), self.max[2] - self.min[2]]
+    }
+
+    // 지면 위 최고 높이
+    pub fn top(&self) -> f32 {
+        self.max[2]
+    }
+}
+
+// points: (x, y, 지면 위 높이), 지면/높이 범위 필터는 호출하는 쪽에서
+pub fn euclidean(points: &[[f32; 3]], config: &ClusterConfig) -> Vec<Cluster> {
+    if config.cell_size <= 0.0 {
+        return Vec::new();
+    }
+    let key = |p: &[f32; 3]| {
+        (
+            (p[0] / config.cell_size).floor() as i32,
+            (p[1] / config.cell_size).floor() as i32,
+        )
+    };
+    let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        cells.entry(key(p)).or_default().push(i);
+    }
+
+    let mut visited: HashSet<(i32, i32)> = HashSet::with_capacity(cells.len());
+    let mut clusters = Vec::new();
+    let mut stack = Vec::new();
+    for &start in cells.keys() {
+        if !visited.insert(start) {
+            continue;
+        }
+        stack.push(start);
+        let mut members: Vec<usize> = Vec::new();
+        while let Some((cx, cy)) = stack.pop() {
+            members.extend(&cells[&(cx, cy)]);
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let next = (cx + dx, cy + dy);
+                    if cells.contains_key(&next) && visited.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        clusters.push(members);
+    }
+
+    clusters
+        .into_iter()
+        .filter(|m| m.len() >= config.min_points && m.len() <= config.max_points)
+        .map(|members| {
+            let mut min = [f32::MAX; 3];
+            let mut max = [f32::MIN; 3];
+            let mut sum = [0f64; 3];
+            for &i in &members {
+                for k in 0..3 {
+                    min[k] = min[k].min(points[i][k]);
+                    max[k] = max[k].max(points[i][k]);
+                    sum[k] += points[i][k] as f64;
+                }
+            }
+            let n = members.len() as f64;
+            Cluster {
+                centroid: sum.map(|s| (s / n) as f32),
+                min,
+                max,
+                points: members.len(),
+            }
+        })
+        .collect()
+}
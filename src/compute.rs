@@ -0,0 +1,176 @@
+use crate::bev::{self, BevGrid, Cell};
+use crate::point::LidarPoint;
+use crate::registration::{self, VoxelIndex};
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+// 무거운 기하 연산의 계산 경로 (CPU, gpu 기능의 wgpu 컴퓨트 셰이더, cuda 기능의 CUDA 커널)
+// 모든 구현은 CPU 경로와 같은 결과를 같은 순서로 내야 함 (voxel_downsample 만 예외)
+pub trait ComputeBackend: Send {
+    fn name(&self) -> &'static str;
+
+    // registration::voxel_downsample 와 같음 (복셀마다 평균 하나)
+    // CPU 경로도 해시 순서라 출력 순서는 정해지지 않음, 호출하는 쪽은 집합으로만 사용
+    fn voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Vec<[f32; 3]>;
+
+    // bev::collect_cells 와 같음 (셀은 처음 들어온 순서)
+    fn collect_cells(&self, grid: &BevGrid, points: &[LidarPoint]) -> Vec<Cell>;
+
+    // 각 query 의 max_distance 안 최근접 target 포인트 (인덱스, 거리 제곱)
+    // CPU 는 색인으로 찾고, GPU/CUDA 는 target.points() 전체를 훑음
+    fn nearest(
+        &self,
+        queries: &[[f32; 3]],
+        target: &VoxelIndex,
+        max_distance: f32,
+    ) -> Vec<Option<(usize, f32)>>;
+
+    // 각 포인트에서 radii[i] 안의 다른 포인트 수 (limits[i] 에 도달하면 더 세지 않음)
+    // radii 는 cell 이하여야 함 (주변 27 셀만 봄)
+    fn neighbor_counts(
+        &self,
+        points: &[[f32; 3]],
+        radii: &[f32],
+        limits: &[usize],
+        cell: f32,
+    ) -> Vec<usize> {
+        neighbor_counts(points, radii, limits, cell)
+    }
+}
+
+pub(crate) type CellKey = (i32, i32, i32);
+
+pub(crate) fn cell_key(p: [f32; 3], cell: f32) -> CellKey {
+    (
+        (p[0] / cell).floor() as i32,
+        (p[1] / cell).floor() as i32,
+        (p[2] / cell).floor() as i32,
+    )
+}
+
+// ComputeBackend::neighbor_counts 의 CPU 구현
+pub fn neighbor_counts(
+    points: &[[f32; 3]],
+    radii: &[f32],
+    limits: &[usize],
+    cell: f32,
+) -> Vec<usize> {
+    let mut cells: HashMap<CellKey, Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        cells.entry(cell_key(*p, cell)).or_default().push(i);
+    }
+    points
+        .iter()
+        .zip(radii.iter().zip(limits))
+        .enumerate()
+        .map(|(i, (p, (&radius, &limit)))| {
+            let (cx, cy, cz) = cell_key(*p, cell);
+            let r_sq = radius * radius;
+            let mut count = 0;
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(cell) = cells.get(&(cx + dx, cy + dy, cz + dz)) else {
+                            continue;
+                        };
+                        for &j in cell {
+                            if count >= limit {
+                                return count;
+                            }
+                            if j == i {
+                                continue;
+                            }
+                            let q = points[j];
+                            let d = (q[0] - p[0]).powi(2)
+                                + (q[1] - p[1]).powi(2)
+                                + (q[2] - p[2]).powi(2);
+                            if d <= r_sq {
+                                count += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            count
+        })
+        .collect()
+}
+
+pub struct Cpu;
+
+impl ComputeBackend for Cpu {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Vec<[f32; 3]> {
+        registration::voxel_downsample(points, size)
+    }
+
+    fn collect_cells(&self, grid: &BevGrid, points: &[LidarPoint]) -> Vec<Cell> {
+        bev::collect_cells(grid, points)
+    }
+
+    fn nearest(
+        &self,
+        queries: &[[f32; 3]],
+        target: &VoxelIndex,
+        max_distance: f32,
+    ) -> Vec<Option<(usize, f32)>> {
+        queries
+            .iter()
+            .map(|q| target.nearest(*q, max_distance))
+            .collect()
+    }
+}
+
+// compute_backend 파라미터: "cpu", "gpu" 또는 "cuda"
+// 해당 기능 없이 빌드했거나 장치를 못 찾으면 CPU 로 대체
+pub fn backend(name: &str) -> Result<Box<dyn ComputeBackend>> {
+    match name {
+        "cpu" => Ok(Box::new(Cpu)),
+        "gpu" => Ok(gpu_or_cpu()),
+        "cuda" => Ok(cuda_or_cpu()),
+        _ => bail!("알 수 없는 compute_backend '{}' (cpu, gpu, cuda)", name),
+    }
+}
+
+#[cfg(feature = "gpu")]
+fn gpu_or_cpu() -> Box<dyn ComputeBackend> {
+    match crate::gpu::Gpu::new() {
+        Ok(gpu) => {
+            println!("GPU 계산 경로 사용: {}", gpu.adapter_name());
+            Box::new(gpu)
+        }
+        Err(e) => {
+            eprintln!("GPU 초기화 실패, CPU 로 대체: {}", e);
+            Box::new(Cpu)
+        }
+    }
+}
+
+#[cfg(not(feature = "gpu"))]
+fn gpu_or_cpu() -> Box<dyn ComputeBackend> {
+    eprintln!("gpu 기능 없이 빌드되어 CPU 로 대체 (cargo build --features gpu)");
+    Box::new(Cpu)
+}
+
+#[cfg(feature = "cuda")]
+fn cuda_or_cpu() -> Box<dyn ComputeBackend> {
+    match crate::cuda::Cuda::new() {
+        Ok(cuda) => {
+            println!("CUDA 계산 경로 사용: {}", cuda.device_name());
+            Box::new(cuda)
+        }
+        Err(e) => {
+            eprintln!("CUDA 초기화 실패, CPU 로 대체: {}", e);
+            Box::new(Cpu)
+        }
+    }
+}
+
+#[cfg(not(feature = "cuda"))]
+fn cuda_or_cpu() -> Box<dyn ComputeBackend> {
+    eprintln!("cuda 기능 없이 빌드되어 CPU 로 대체 (cargo build --features cuda)");
+    Box::new(Cpu)
+}
@@ -0,0 +1,446 @@
+// 포인트 코덱 (바이트 <-> LidarPoint), no_std + alloc 으로 빌드 가능
+// std 기능 없이 빌드하면 이 모듈만 남음 (임베디드 게이트웨이 MCU 용)
+// cargo build --lib --no-default-features --target thumbv7em-none-eabihf
+use alloc::vec::Vec;
+use anyhow::{bail, Result};
+
+#[cfg(feature = "std")]
+use crate::cloud::PointLayout;
+
+// sensor_msgs/PointField datatype 상수
+pub mod datatype {
+    use alloc::vec::Vec;
+
+    pub const INT8: u8 = 1;
+    pub const UINT8: u8 = 2;
+    pub const INT16: u8 = 3;
+    pub const UINT16: u8 = 4;
+    pub const INT32: u8 = 5;
+    pub const UINT32: u8 = 6;
+    pub const FLOAT32: u8 = 7;
+    pub const FLOAT64: u8 = 8;
+
+    pub fn size(datatype: u8) -> Option<usize> {
+        match datatype {
+            INT8 | UINT8 => Some(1),
+            INT16 | UINT16 => Some(2),
+            INT32 | UINT32 | FLOAT32 => Some(4),
+            FLOAT64 => Some(8),
+            _ => None,
+        }
+    }
+
+    // f64 값을 datatype 에 맞춰 리틀 엔디안으로 기록
+    pub fn write(buf: &mut Vec<u8>, datatype: u8, value: f64) {
+        match datatype {
+            INT8 => buf.push(value as i8 as u8),
+            UINT8 => buf.push(value as u8),
+            INT16 => buf.extend_from_slice(&(value as i16).to_le_bytes()),
+            UINT16 => buf.extend_from_slice(&(value as u16).to_le_bytes()),
+            INT32 => buf.extend_from_slice(&(value as i32).to_le_bytes()),
+            UINT32 => buf.extend_from_slice(&(value as u32).to_le_bytes()),
+            FLOAT32 => buf.extend_from_slice(&(value as f32).to_le_bytes()),
+            FLOAT64 => buf.extend_from_slice(&value.to_le_bytes()),
+            _ => {}
+        }
+    }
+}
+
+// C 구조체와 같은 메모리 배치 (FFI 에서 복사 없이 배열로 넘김, 바이트 레이아웃과는 무관)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "std", derive(PointLayout))]
+pub struct LidarPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub intensity: f32,
+    pub tag: u8,
+    pub line: u8,
+    pub timestamp: f64,
+}
+
+// 포인트 내 필드 하나의 위치와 타입
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSpec {
+    pub offset: usize,
+    pub datatype: u8,
+}
+
+impl FieldSpec {
+    pub const fn new(offset: usize, datatype: u8) -> Self {
+        FieldSpec { offset, datatype }
+    }
+
+    pub fn end(&self) -> usize {
+        self.offset + datatype::size(self.datatype).unwrap_or(0)
+    }
+
+    // 어떤 타입이든 f64 로 변환해서 읽음 (범위 검사는 호출자 책임)
+    pub fn read(&self, data: &[u8], base: usize, big_endian: bool) -> f64 {
+        let start = base + self.offset;
+        macro_rules! read {
+            ($t:ty, $n:expr) => {{
+                let mut bytes = [0u8; $n];
+                bytes.copy_from_slice(&data[start..start + $n]);
+                if big_endian {
+                    <$t>::from_be_bytes(bytes) as f64
+                } else {
+                    <$t>::from_le_bytes(bytes) as f64
+                }
+            }};
+        }
+
+        match self.datatype {
+            datatype::INT8 => data[start] as i8 as f64,
+            datatype::UINT8 => data[start] as f64,
+            datatype::INT16 => read!(i16, 2),
+            datatype::UINT16 => read!(u16, 2),
+            datatype::INT32 => read!(i32, 4),
+            datatype::UINT32 => read!(u32, 4),
+            datatype::FLOAT32 => read!(f32, 4),
+            datatype::FLOAT64 => read!(f64, 8),
+            _ => 0.0,
+        }
+    }
+
+    // read 의 반대: 값을 datatype 으로 변환해서 제자리에 기록
+    pub fn write(&self, data: &mut [u8], base: usize, big_endian: bool, value: f64) {
+        let start = base + self.offset;
+        macro_rules! write {
+            ($t:ty, $n:expr) => {{
+                let v = value as $t;
+                let bytes = if big_endian {
+                    v.to_be_bytes()
+                } else {
+                    v.to_le_bytes()
+                };
+                data[start..start + $n].copy_from_slice(&bytes);
+            }};
+        }
+
+        match self.datatype {
+            datatype::INT8 => data[start] = value as i8 as u8,
+            datatype::UINT8 => data[start] = value as u8,
+            datatype::INT16 => write!(i16, 2),
+            datatype::UINT16 => write!(u16, 2),
+            datatype::INT32 => write!(i32, 4),
+            datatype::UINT32 => write!(u32, 4),
+            datatype::FLOAT32 => write!(f32, 4),
+            datatype::FLOAT64 => write!(f64, 8),
+            _ => {}
+        }
+    }
+}
+
+// 메시지의 PointField 에서 찾은 필드별 위치/타입
+#[derive(Debug, Clone, Copy)]
+pub struct FieldOffsets {
+    pub x: FieldSpec,
+    pub y: FieldSpec,
+    pub z: FieldSpec,
+    pub intensity: Option<FieldSpec>,
+    pub tag: Option<FieldSpec>,
+    pub line: Option<FieldSpec>,
+    pub timestamp: Option<FieldSpec>,
+}
+
+impl FieldOffsets {
+    pub const LIVOX: FieldOffsets = FieldOffsets {
+        x: FieldSpec::new(0, datatype::FLOAT32),
+        y: FieldSpec::new(4, datatype::FLOAT32),
+        z: FieldSpec::new(8, datatype::FLOAT32),
+        intensity: Some(FieldSpec::new(12, datatype::FLOAT32)),
+        tag: Some(FieldSpec::new(16, datatype::UINT8)),
+        line: Some(FieldSpec::new(17, datatype::UINT8)),
+        timestamp: Some(FieldSpec::new(18, datatype::FLOAT64)),
+    };
+
+    // (이름, offset, datatype) 목록에서 필드 찾기, 비어있으면 Livox 기본 레이아웃
+    pub fn from_descriptors(fields: &[(&str, usize, u8)]) -> Result<Self> {
+        if fields.is_empty() {
+            return Ok(Self::LIVOX);
+        }
+
+        for &(name, _, dt) in fields {
+            if datatype::size(dt).is_none() {
+                bail!("필드 '{}' 의 datatype({}) 을 지원하지 않습니다", name, dt);
+            }
+        }
+
+        // Velodyne/Ouster 는 라인 번호를 ring 으로 부름
+        let find = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                fields
+                    .iter()
+                    .find(|f| f.0 == *name)
+                    .map(|&(_, offset, dt)| FieldSpec::new(offset, dt))
+            })
+        };
+
+        let (Some(x), Some(y), Some(z)) = (find(&["x"]), find(&["y"]), find(&["z"])) else {
+            bail!("PointCloud2 에 x/y/z 필드가 없습니다");
+        };
+
+        Ok(FieldOffsets {
+            x,
+            y,
+            z,
+            intensity: find(&["intensity"]),
+            tag: find(&["tag"]),
+            line: find(&["line", "ring"]),
+            timestamp: find(&["timestamp"]),
+        })
+    }
+
+    // 한 포인트를 읽는 데 필요한 최소 바이트 수 (패딩 제외)
+    pub fn extent(&self) -> usize {
+        [
+            Some(self.x),
+            Some(self.y),
+            Some(self.z),
+            self.intensity,
+            self.tag,
+            self.line,
+            self.timestamp,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|f| f.end())
+        .max()
+        .unwrap_or(0)
+    }
+}
+
+impl LidarPoint {
+    // offset 은 포인트 시작 위치, 각 필드는 layout 의 위치/타입을 따라 내부 타입으로 변환
+    pub fn from_bytes(
+        data: &[u8],
+        offset: usize,
+        layout: &FieldOffsets,
+        big_endian: bool,
+    ) -> Option<Self> {
+        if offset + layout.extent() > data.len() {
+            return None;
+        }
+
+        let read = |field: Option<FieldSpec>| field.map(|f| f.read(data, offset, big_endian));
+
+        Some(LidarPoint {
+            x: layout.x.read(data, offset, big_endian) as f32,
+            y: layout.y.read(data, offset, big_endian) as f32,
+            z: layout.z.read(data, offset, big_endian) as f32,
+            intensity: read(layout.intensity).unwrap_or(0.0) as f32,
+            tag: read(layout.tag).unwrap_or(0.0) as u8,
+            line: read(layout.line).unwrap_or(0.0) as u8,
+            timestamp: read(layout.timestamp).unwrap_or(0.0),
+        })
+    }
+
+    // 출력 레이아웃 필드 이름에 해당하는 값 (없는 필드는 0)
+    pub fn field_value(&self, name: &str) -> f64 {
+        match name {
+            "x" => self.x as f64,
+            "y" => self.y as f64,
+            "z" => self.z as f64,
+            "intensity" => self.intensity as f64,
+            "tag" => self.tag as f64,
+            "line" => self.line as f64,
+            "timestamp" => self.timestamp,
+            // Livox tag 를 라벨로 사용
+            "label" => self.tag as f64,
+            // intensity 를 회색조로 표현
+            "rgb" => {
+                let v = self.intensity.clamp(0.0, 255.0) as u32;
+                f32::from_bits((v << 16) | (v << 8) | v) as f64
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+// point_step 간격으로 저장된 포인트 버퍼 디코드 (끝의 잘린 포인트는 버림)
+pub fn decode(
+    data: &[u8],
+    point_step: usize,
+    layout: &FieldOffsets,
+    big_endian: bool,
+) -> Result<Vec<LidarPoint>> {
+    // point_step 은 패딩을 포함할 수 있으므로 필드 범위보다 작을 때만 거부
+    if point_step < layout.extent() {
+        bail!(
+            "point_step({}) 이 필드 범위({} bytes)보다 작습니다",
+            point_step,
+            layout.extent()
+        );
+    }
+
+    let mut points = Vec::with_capacity(data.len() / point_step);
+    for i in (0..data.len()).step_by(point_step) {
+        if let Some(point) = LidarPoint::from_bytes(data, i, layout, big_endian) {
+            points.push(point);
+        }
+    }
+    Ok(points)
+}
+
+// (이름, datatype) 필드 순서대로 패딩 없이 리틀 엔디안 인코딩해서 out 뒤에 추가
+pub fn encode(points: &[LidarPoint], fields: &[(&str, u8)], out: &mut Vec<u8>) {
+    let point_step: usize = fields
+        .iter()
+        .map(|&(_, dt)| datatype::size(dt).unwrap_or(0))
+        .sum();
+    out.reserve(points.len() * point_step);
+    for point in points {
+        for &(name, dt) in fields {
+            datatype::write(out, dt, point.field_value(name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn sample(i: usize) -> LidarPoint {
+        LidarPoint {
+            x: 1.5 + i as f32,
+            y: -2.25,
+            z: 0.125 * i as f32,
+            intensity: 40.0 + i as f32,
+            tag: 0x10,
+            line: i as u8,
+            timestamp: 1.0e9 + i as f64 * 1.0e5,
+        }
+    }
+
+    // LIVOX 레이아웃으로 point_step 간격 기록 (26바이트 뒤는 0 패딩)
+    fn livox_bytes(points: &[LidarPoint], point_step: usize, big_endian: bool) -> Vec<u8> {
+        let l = FieldOffsets::LIVOX;
+        let mut data = vec![0u8; points.len() * point_step];
+        for (i, p) in points.iter().enumerate() {
+            let base = i * point_step;
+            for (spec, name) in [
+                (Some(l.x), "x"),
+                (Some(l.y), "y"),
+                (Some(l.z), "z"),
+                (l.intensity, "intensity"),
+                (l.tag, "tag"),
+                (l.line, "line"),
+                (l.timestamp, "timestamp"),
+            ] {
+                spec.unwrap()
+                    .write(&mut data, base, big_endian, p.field_value(name));
+            }
+        }
+        data
+    }
+
+    fn assert_same(a: &LidarPoint, b: &LidarPoint) {
+        assert_eq!(
+            (a.x, a.y, a.z, a.intensity, a.tag, a.line, a.timestamp),
+            (b.x, b.y, b.z, b.intensity, b.tag, b.line, b.timestamp)
+        );
+    }
+
+    #[test]
+    fn decode_round_trips_encode() {
+        let points = [sample(0), sample(1)];
+        let fields = [
+            ("x", datatype::FLOAT32),
+            ("y", datatype::FLOAT32),
+            ("z", datatype::FLOAT32),
+            ("intensity", datatype::FLOAT32),
+            ("tag", datatype::UINT8),
+            ("line", datatype::UINT8),
+            ("timestamp", datatype::FLOAT64),
+        ];
+        let mut data = Vec::new();
+        encode(&points, &fields, &mut data);
+        assert_eq!(data.len(), 2 * 26);
+
+        let decoded = decode(&data, 26, &FieldOffsets::LIVOX, false).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_same(&decoded[0], &points[0]);
+        assert_same(&decoded[1], &points[1]);
+    }
+
+    #[test]
+    fn decode_skips_point_step_padding() {
+        let points = [sample(0), sample(1), sample(2)];
+        let data = livox_bytes(&points, 32, false);
+        let decoded = decode(&data, 32, &FieldOffsets::LIVOX, false).unwrap();
+        assert_eq!(decoded.len(), 3);
+        for (a, b) in decoded.iter().zip(&points) {
+            assert_same(a, b);
+        }
+    }
+
+    #[test]
+    fn decode_drops_truncated_tail() {
+        let mut data = livox_bytes(&[sample(0), sample(1)], 26, false);
+        data.truncate(26 + 20);
+        let decoded = decode(&data, 26, &FieldOffsets::LIVOX, false).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_same(&decoded[0], &sample(0));
+    }
+
+    #[test]
+    fn decode_rejects_point_step_shorter_than_fields() {
+        let data = livox_bytes(&[sample(0)], 26, false);
+        assert!(decode(&data, 24, &FieldOffsets::LIVOX, false).is_err());
+    }
+
+    #[test]
+    fn decode_reads_big_endian() {
+        let points = [sample(3)];
+        let data = livox_bytes(&points, 26, true);
+        assert_ne!(data, livox_bytes(&points, 26, false));
+        let decoded = decode(&data, 26, &FieldOffsets::LIVOX, true).unwrap();
+        assert_same(&decoded[0], &points[0]);
+    }
+
+    #[test]
+    fn from_descriptors_defaults_to_livox() {
+        let layout = FieldOffsets::from_descriptors(&[]).unwrap();
+        assert_eq!(layout.extent(), 26);
+        assert_eq!(layout.timestamp.unwrap().offset, 18);
+    }
+
+    #[test]
+    fn from_descriptors_fills_missing_fields_with_zero() {
+        // x/y/z 와 ring 만 있는 16바이트 레이아웃 (ring 은 line 으로 읽음)
+        let layout = FieldOffsets::from_descriptors(&[
+            ("x", 0, datatype::FLOAT32),
+            ("y", 4, datatype::FLOAT32),
+            ("z", 8, datatype::FLOAT32),
+            ("ring", 12, datatype::UINT16),
+        ])
+        .unwrap();
+        assert!(layout.intensity.is_none() && layout.tag.is_none());
+        assert_eq!(layout.extent(), 14);
+
+        let mut data = vec![0u8; 16];
+        layout.x.write(&mut data, 0, false, 1.0);
+        layout.z.write(&mut data, 0, false, -3.0);
+        layout.line.unwrap().write(&mut data, 0, false, 7.0);
+        let p = &decode(&data, 16, &layout, false).unwrap()[0];
+        assert_eq!((p.x, p.y, p.z, p.line), (1.0, 0.0, -3.0, 7));
+        assert_eq!((p.intensity, p.tag, p.timestamp), (0.0, 0, 0.0));
+    }
+
+    #[test]
+    fn from_descriptors_rejects_missing_xyz_and_unknown_datatype() {
+        let no_z = [("x", 0, datatype::FLOAT32), ("y", 4, datatype::FLOAT32)];
+        assert!(FieldOffsets::from_descriptors(&no_z).is_err());
+
+        let bad_type = [
+            ("x", 0, datatype::FLOAT32),
+            ("y", 4, datatype::FLOAT32),
+            ("z", 8, datatype::FLOAT32),
+            ("intensity", 12, 9),
+        ];
+        assert!(FieldOffsets::from_descriptors(&bad_type).is_err());
+    }
+}
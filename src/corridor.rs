@@ -0,0 +1,67 @@
+// 경로(폴리라인) 양옆 half_width 안에 든 장애물 중 경로를 따라 가장 가까운 거리
+
+// 경로 시작점부터 잰 누적 길이
+fn arc_lengths(path: &[[f32; 2]]) -> Vec<f32> {
+    let mut total = 0.0;
+    let mut lengths = Vec::with_capacity(path.len());
+    lengths.push(0.0);
+    for w in path.windows(2) {
+        total += (w[1][0] - w[0][0]).hypot(w[1][1] - w[0][1]);
+        lengths.push(total);
+    }
+    lengths
+}
+
+pub fn path_length(path: &[[f32; 2]]) -> f32 {
+    arc_lengths(path).last().copied().unwrap_or(0.0)
+}
+
+// 포인트가 통로 안에 있으면 경로상의 위치(누적 길이) 반환
+fn corridor_position(
+    path: &[[f32; 2]],
+    lengths: &[f32],
+    p: [f32; 2],
+    half_width: f32,
+) -> Option<f32> {
+    let mut best: Option<(f32, f32)> = None; // (경로까지 거리, 누적 길이)
+    for (i, w) in path.windows(2).enumerate() {
+        let (a, b) = (w[0], w[1]);
+        let d = [b[0] - a[0], b[1] - a[1]];
+        let len_sq = d[0] * d[0] + d[1] * d[1];
+        let t = if len_sq > 0.0 {
+            (((p[0] - a[0]) * d[0] + (p[1] - a[1]) * d[1]) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let q = [a[0] + t * d[0], a[1] + t * d[1]];
+        let dist = (p[0] - q[0]).hypot(p[1] - q[1]);
+        if dist <= half_width && best.is_none_or(|(bd, _)| dist < bd) {
+            best = Some((dist, lengths[i] + t * len_sq.sqrt()));
+        }
+    }
+    best.map(|(_, s)| s)
+}
+
+// min_points 개 이상의 장애물 포인트가 통로 안에 있을 때 min_points 번째로 가까운 위치
+// (포인트 하나짜리 노이즈로 멈추지 않도록), 막힌 곳이 없으면 None
+pub fn first_blocking(
+    path: &[[f32; 2]],
+    obstacles: impl IntoIterator<Item = [f32; 2]>,
+    half_width: f32,
+    min_points: usize,
+) -> Option<f32> {
+    if path.len() < 2 {
+        return None;
+    }
+    let lengths = arc_lengths(path);
+    let mut hits: Vec<f32> = obstacles
+        .into_iter()
+        .filter_map(|p| corridor_position(path, &lengths, p, half_width))
+        .collect();
+    let k = min_points.max(1);
+    if hits.len() < k {
+        return None;
+    }
+    hits.select_nth_unstable_by(k - 1, f32::total_cmp);
+    Some(hits[k - 1])
+}
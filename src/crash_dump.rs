@@ -0,0 +1,136 @@
+use crate::msg::PointCloud2;
+use crate::pcd;
+use crate::sidecar::SidecarIndex;
+use crate::snapshot::ConfigSnapshot;
+use anyhow::Result;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Once, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 패닉 훅이 덤프할 recorder (노드마다 하나, 훅은 프로세스에 한 번만 설치)
+static RECORDERS: Mutex<Vec<Weak<CrashRecorder>>> = Mutex::new(Vec::new());
+static HOOK: Once = Once::new();
+
+thread_local! {
+    // 호출자가 catch_unwind 로 잡아 복구하는 구간이면 true (패닉 훅에서 덤프하지 않음)
+    static RECOVERING: Cell<bool> = const { Cell::new(false) };
+}
+
+// 최근 N 프레임을 메모리에 보관하다가 패닉 (또는 요청 시 SIGINT) 때 설정과 함께 디스크에 저장
+pub struct CrashRecorder {
+    frames: Mutex<VecDeque<PointCloud2>>,
+    capacity: usize,
+    dir: PathBuf,
+    // 설정 스냅샷 YAML 과 그 해시 (index.json 의 config_hash, 스냅샷 파일의 config_hash 와 같음)
+    config: String,
+    config_hash: String,
+}
+
+impl CrashRecorder {
+    // capacity 가 0 보다 크면 install_panic_hook 으로 설치한 훅의 덤프 대상에 등록
+    pub fn new(capacity: usize, dir: impl Into<PathBuf>, config: &ConfigSnapshot) -> Arc<Self> {
+        let recorder = Arc::new(CrashRecorder {
+            frames: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            dir: dir.into(),
+            config: config.to_yaml(),
+            config_hash: config.hash(),
+        });
+        if capacity > 0 {
+            let mut recorders = RECORDERS.lock().unwrap_or_else(|e| e.into_inner());
+            recorders.retain(|r| r.strong_count() > 0);
+            recorders.push(Arc::downgrade(&recorder));
+        }
+        recorder
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn record(&self, msg: &PointCloud2) {
+        if !self.enabled() {
+            return;
+        }
+        // 패닉 중에도 덤프할 수 있도록 poison 은 무시
+        let mut frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        if frames.len() >= self.capacity {
+            frames.pop_front();
+        }
+        frames.push_back(msg.clone());
+    }
+
+    // <dir>/crash_<unix초>_<reason>/ 아래에 frame_XX.pcd, frames.txt, index.json, config.txt 저장
+    pub fn dump(&self, reason: &str) -> Result<PathBuf> {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let dir = self.dir.join(format!("crash_{}_{}", secs, reason));
+        fs::create_dir_all(&dir)?;
+
+        fs::write(dir.join("config.txt"), &self.config)?;
+
+        let frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = String::from("# file frame_id stamp points\n");
+        let mut sidecar = SidecarIndex::new(&self.config_hash);
+        for (i, frame) in frames.iter().enumerate() {
+            let name = format!("frame_{:02}.pcd", i);
+            pcd::write_pcd(&dir.join(&name), frame)?;
+            sidecar.push(&name, frame);
+            index.push_str(&format!(
+                "{} {} {}.{:09} {}\n",
+                name,
+                frame.header.frame_id,
+                frame.header.stamp.sec,
+                frame.header.stamp.nanosec,
+                frame.width * frame.height
+            ));
+        }
+        fs::write(dir.join("frames.txt"), index)?;
+        sidecar.write(&dir.join("index.json"))?;
+
+        Ok(dir)
+    }
+
+    // 실패해도 종료 경로를 막지 않도록 결과만 출력
+    pub fn dump_and_report(&self, reason: &str) {
+        match self.dump(reason) {
+            Ok(dir) => eprintln!("크래시 덤프 저장: {}", dir.display()),
+            Err(e) => eprintln!("크래시 덤프 실패: {}", e),
+        }
+    }
+}
+
+// 패닉 시 살아 있는 모든 recorder 를 덤프하는 훅 (바이너리 main 에서 호출, 여러 번 불러도 한 번만 설치)
+// recovering 구간의 패닉과 SIGINT 덤프는 호출하는 쪽에서 처리
+pub fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            default_hook(info);
+            if RECOVERING.with(Cell::get) {
+                return;
+            }
+            let recorders = RECORDERS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            for recorder in recorders.iter().filter_map(Weak::upgrade) {
+                recorder.dump_and_report("panic");
+            }
+        }));
+    });
+}
+
+// f 안의 패닉은 호출자가 잡아 복구하므로 패닉 훅에서 덤프하지 않음
+pub fn recovering<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RECOVERING.with(|r| r.set(self.0));
+        }
+    }
+    let _restore = Restore(RECOVERING.with(|r| r.replace(true)));
+    f()
+}
@@ -0,0 +1,566 @@
+use crate::bev::{BevGrid, Cell};
+use crate::compute::{self, CellKey, ComputeBackend, Cpu};
+use crate::point::LidarPoint;
+use crate::registration::VoxelIndex;
+use anyhow::{bail, Result};
+use cudarc::driver::{CudaContext, CudaFunction, CudaStream, LaunchConfig, PushKernelArg};
+use std::sync::Arc;
+
+// 커널은 NVRTC 로 실행 시 컴파일 (빌드에 CUDA 툴킷 불필요)
+// 합계는 부호 있는 64비트 고정소수점 atomicAdd: llrint(v * FIXED_SCALE) 를 2의 보수 그대로 u64 로 더함
+// (값은 ±FIXED_LIMIT 로 제한, UINT16 intensity 도 그대로 들어가고 셀당 2^19 포인트까지 넘치지 않음)
+// 최소값은 크기 순서가 같은 u32 를 비트 반전해서 atomicMax (0 으로 초기화된 버퍼 그대로 사용)
+const KERNELS: &str = r#"
+#define FIXED_LIMIT 1048576.0
+#define FIXED_SCALE 16777216.0
+
+__device__ unsigned long long fixed(float v) {
+    return (unsigned long long)llrint(fmin(fmax((double)v, -FIXED_LIMIT), FIXED_LIMIT) * FIXED_SCALE);
+}
+
+__device__ unsigned int ordered(float v) {
+    unsigned int b = __float_as_uint(v);
+    return (b & 0x80000000u) ? ~b : (b | 0x80000000u);
+}
+
+__device__ unsigned int hash(int x, int y, int z, unsigned int mask) {
+    return (((unsigned int)x * 73856093u) ^ ((unsigned int)y * 19349663u) ^ ((unsigned int)z * 83492791u)) & mask;
+}
+
+// 셀 통계 (u32 7 개): count, ~min_z, max_z, ~min_i, max_i, ~first, last / 합계 (u64 2 개): z, intensity
+extern "C" __global__ void bev(
+    const float* points, unsigned int n, int origin_x, int origin_y,
+    unsigned int width, unsigned int height, float cell_size,
+    unsigned int* stats, unsigned long long* sums)
+{
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= n) return;
+    const float* p = points + i * 4;
+    int cx = (int)floorf(p[0] / cell_size) - origin_x;
+    int cy = (int)floorf(p[1] / cell_size) - origin_y;
+    if (cx < 0 || cy < 0 || (unsigned int)cx >= width || (unsigned int)cy >= height) return;
+    unsigned int cell = (unsigned int)cy * width + (unsigned int)cx;
+    unsigned int* s = stats + cell * 7;
+    atomicAdd(&s[0], 1u);
+    atomicMax(&s[1], ~ordered(p[2]));
+    atomicMax(&s[2], ordered(p[2]));
+    atomicMax(&s[3], ~ordered(p[3]));
+    atomicMax(&s[4], ordered(p[3]));
+    atomicMax(&s[5], ~i);
+    atomicMax(&s[6], i);
+    atomicAdd(&sums[cell * 2], fixed(p[2]));
+    atomicAdd(&sums[cell * 2 + 1], fixed(p[3]));
+}
+
+// 선형 탐사 해시: 슬롯마다 대표 포인트 인덱스+1, count, 복셀 내 x/y/z 오프셋 합
+extern "C" __global__ void voxel(
+    const float* points, unsigned int n, float size, unsigned int mask,
+    unsigned int* reps, unsigned int* counts, unsigned long long* sums)
+{
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= n) return;
+    const float* p = points + i * 3;
+    int k[3];
+    for (int a = 0; a < 3; a++) k[a] = (int)floorf(p[a] / size);
+    unsigned int slot = hash(k[0], k[1], k[2], mask);
+    for (unsigned int probe = 0; probe <= mask; probe++) {
+        unsigned int rep = atomicCAS(&reps[slot], 0u, i + 1u);
+        if (rep == 0u) rep = i + 1u;
+        const float* r = points + (rep - 1u) * 3;
+        if ((int)floorf(r[0] / size) == k[0] && (int)floorf(r[1] / size) == k[1]
+            && (int)floorf(r[2] / size) == k[2]) {
+            atomicAdd(&counts[slot], 1u);
+            for (int a = 0; a < 3; a++) atomicAdd(&sums[slot * 3 + a], fixed(p[a] / size - k[a]));
+            return;
+        }
+        slot = (slot + 1u) & mask;
+    }
+}
+
+// query 하나당 스레드 하나가 target 전체를 훑음 (인덱스+1, 거리 제곱)
+extern "C" __global__ void nearest(
+    const float* queries, unsigned int nq, const float* targets, unsigned int nt,
+    float max_d2, unsigned int* index, float* distance)
+{
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= nq) return;
+    const float* q = queries + i * 3;
+    float best = max_d2;
+    unsigned int best_index = 0u;
+    for (unsigned int j = 0; j < nt; j++) {
+        const float* t = targets + j * 3;
+        float dx = t[0] - q[0], dy = t[1] - q[1], dz = t[2] - q[2];
+        float d2 = dx * dx + dy * dy + dz * dz;
+        if (d2 < best || (best_index == 0u && d2 <= best)) {
+            best = d2;
+            best_index = j + 1u;
+        }
+    }
+    index[i] = best_index;
+    distance[i] = best;
+}
+
+// 셀 해시 (슬롯마다 셀 키 3 개, 셀 순으로 정렬한 order 의 시작과 길이) 로 주변 27 셀의 이웃 수
+extern "C" __global__ void neighbors(
+    const float* points, unsigned int n, const float* radii, const unsigned int* limits,
+    float cell, const int* keys, const unsigned int* starts, const unsigned int* lens,
+    unsigned int mask, const unsigned int* order, unsigned int* counts)
+{
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i >= n) return;
+    const float* p = points + i * 3;
+    float r2 = radii[i] * radii[i];
+    unsigned int limit = limits[i];
+    int c[3];
+    for (int a = 0; a < 3; a++) c[a] = (int)floorf(p[a] / cell);
+    unsigned int count = 0;
+    for (int dx = -1; dx <= 1 && count < limit; dx++)
+    for (int dy = -1; dy <= 1 && count < limit; dy++)
+    for (int dz = -1; dz <= 1 && count < limit; dz++) {
+        int x = c[0] + dx, y = c[1] + dy, z = c[2] + dz;
+        unsigned int slot = hash(x, y, z, mask);
+        while (lens[slot] != 0u) {
+            const int* k = keys + slot * 3;
+            if (k[0] == x && k[1] == y && k[2] == z) {
+                for (unsigned int s = starts[slot]; s < starts[slot] + lens[slot] && count < limit; s++) {
+                    unsigned int j = order[s];
+                    if (j == i) continue;
+                    const float* q = points + j * 3;
+                    float ex = q[0] - p[0], ey = q[1] - p[1], ez = q[2] - p[2];
+                    if (ex * ex + ey * ey + ez * ez <= r2) count++;
+                }
+                break;
+            }
+            slot = (slot + 1u) & mask;
+        }
+    }
+    counts[i] = count;
+}
+"#;
+
+const FIXED_SCALE: f64 = 16777216.0;
+// 밀집 BEV 격자가 이보다 크면 (범위 제한 없음 + 작은 셀) CPU 경로 사용
+const MAX_BEV_CELLS: usize = 1 << 22;
+
+fn ordered_to_f32(v: u32) -> f32 {
+    if v & 0x8000_0000 != 0 {
+        f32::from_bits(v & 0x7fff_ffff)
+    } else {
+        f32::from_bits(!v)
+    }
+}
+
+fn fixed_sum(sum: u64) -> f64 {
+    sum as i64 as f64 / FIXED_SCALE
+}
+
+// 커널의 hash 와 같은 식
+fn hash((x, y, z): CellKey, mask: u32) -> u32 {
+    ((x as u32).wrapping_mul(73856093)
+        ^ (y as u32).wrapping_mul(19349663)
+        ^ (z as u32).wrapping_mul(83492791))
+        & mask
+}
+
+fn flatten(points: &[[f32; 3]]) -> Vec<f32> {
+    points.iter().flatten().copied().collect()
+}
+
+fn launch_config(n: usize) -> Result<LaunchConfig> {
+    if n > u32::MAX as usize {
+        bail!("CUDA 작업 크기 초과 ({} 스레드)", n);
+    }
+    Ok(LaunchConfig::for_num_elems((n as u32).max(1)))
+}
+
+pub struct Cuda {
+    stream: Arc<CudaStream>,
+    device_name: String,
+    bev: CudaFunction,
+    voxel: CudaFunction,
+    nearest: CudaFunction,
+    neighbors: CudaFunction,
+}
+
+impl Cuda {
+    pub fn new() -> Result<Self> {
+        // 라이브러리가 없으면 cudarc 가 패닉하므로 먼저 확인
+        let present = unsafe {
+            cudarc::driver::sys::is_culib_present() && cudarc::nvrtc::sys::is_culib_present()
+        };
+        if !present {
+            bail!("CUDA 드라이버 또는 NVRTC 라이브러리를 찾을 수 없습니다");
+        }
+        let context = CudaContext::new(0)?;
+        let ptx = cudarc::nvrtc::compile_ptx(KERNELS)?;
+        let module = context.load_module(ptx)?;
+        Ok(Cuda {
+            stream: context.default_stream(),
+            device_name: context.name()?,
+            bev: module.load_function("bev")?,
+            voxel: module.load_function("voxel")?,
+            nearest: module.load_function("nearest")?,
+            neighbors: module.load_function("neighbors")?,
+        })
+    }
+
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    fn try_voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Result<Vec<[f32; 3]>> {
+        let capacity = (points.len() * 2).next_power_of_two().max(2);
+        let input = self.stream.clone_htod(&flatten(points))?;
+        let mut reps = self.stream.alloc_zeros::<u32>(capacity)?;
+        let mut counts = self.stream.alloc_zeros::<u32>(capacity)?;
+        let mut sums = self.stream.alloc_zeros::<u64>(capacity * 3)?;
+        let (n, mask) = (points.len() as u32, (capacity - 1) as u32);
+        let mut launch = self.stream.launch_builder(&self.voxel);
+        launch
+            .arg(&input)
+            .arg(&n)
+            .arg(&size)
+            .arg(&mask)
+            .arg(&mut reps)
+            .arg(&mut counts)
+            .arg(&mut sums);
+        unsafe { launch.launch(launch_config(points.len())?) }?;
+        let reps = self.stream.clone_dtoh(&reps)?;
+        let counts = self.stream.clone_dtoh(&counts)?;
+        let sums = self.stream.clone_dtoh(&sums)?;
+
+        Ok(reps
+            .iter()
+            .zip(&counts)
+            .zip(sums.chunks_exact(3))
+            .filter(|((&rep, &count), _)| rep != 0 && count != 0)
+            .map(|((&rep, &count), sum)| {
+                let rep = points[rep as usize - 1];
+                std::array::from_fn(|k| {
+                    let voxel = (rep[k] / size).floor() as f64;
+                    let offset = fixed_sum(sum[k]) / count as f64;
+                    ((voxel + offset.clamp(0.0, 1.0)) * size as f64) as f32
+                })
+            })
+            .collect())
+    }
+
+    fn try_collect_cells(
+        &self,
+        grid: &BevGrid,
+        points: &[LidarPoint],
+    ) -> Result<Option<Vec<Cell>>> {
+        let cells: Vec<(i32, i32)> = points
+            .iter()
+            .filter_map(|p| grid.cell_of(p.x, p.y))
+            .collect();
+        let (Some(min_x), Some(max_x)) = (
+            cells.iter().map(|c| c.0).min(),
+            cells.iter().map(|c| c.0).max(),
+        ) else {
+            return Ok(Some(Vec::new()));
+        };
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        let max_y = cells.iter().map(|c| c.1).max().unwrap_or(0);
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        if width * height > MAX_BEV_CELLS {
+            return Ok(None);
+        }
+
+        let input: Vec<f32> = points
+            .iter()
+            .flat_map(|p| [p.x, p.y, p.z, p.intensity])
+            .collect();
+        let input = self.stream.clone_htod(&input)?;
+        let mut stats = self.stream.alloc_zeros::<u32>(width * height * 7)?;
+        let mut sums = self.stream.alloc_zeros::<u64>(width * height * 2)?;
+        let n = points.len() as u32;
+        let (w, h) = (width as u32, height as u32);
+        let mut launch = self.stream.launch_builder(&self.bev);
+        launch
+            .arg(&input)
+            .arg(&n)
+            .arg(&min_x)
+            .arg(&min_y)
+            .arg(&w)
+            .arg(&h)
+            .arg(&grid.cell_size)
+            .arg(&mut stats)
+            .arg(&mut sums);
+        unsafe { launch.launch(launch_config(points.len())?) }?;
+        let stats = self.stream.clone_dtoh(&stats)?;
+        let sums = self.stream.clone_dtoh(&sums)?;
+
+        let mut found: Vec<(u32, Cell)> = stats
+            .chunks_exact(7)
+            .zip(sums.chunks_exact(2))
+            .enumerate()
+            .filter(|(_, (s, _))| s[0] > 0)
+            .map(|(i, (s, sum))| {
+                let index = (min_x + (i % width) as i32, min_y + (i / width) as i32);
+                let count = s[0];
+                let cell = Cell::from_stats(
+                    index,
+                    count as usize,
+                    (
+                        [ordered_to_f32(!s[1]), ordered_to_f32(s[2])],
+                        fixed_sum(sum[0]),
+                    ),
+                    (
+                        [ordered_to_f32(!s[3]), ordered_to_f32(s[4])],
+                        fixed_sum(sum[1]),
+                    ),
+                    points[s[6] as usize],
+                );
+                (!s[5], cell)
+            })
+            .collect();
+        // CPU 경로와 같이 셀이 처음 들어온 순서
+        found.sort_by_key(|(first, _)| *first);
+        Ok(Some(found.into_iter().map(|(_, cell)| cell).collect()))
+    }
+
+    fn try_nearest(
+        &self,
+        queries: &[[f32; 3]],
+        targets: &[[f32; 3]],
+        max_distance: f32,
+    ) -> Result<Vec<Option<(usize, f32)>>> {
+        let query_buffer = self.stream.clone_htod(&flatten(queries))?;
+        let target_buffer = self.stream.clone_htod(&flatten(targets))?;
+        let mut index = self.stream.alloc_zeros::<u32>(queries.len())?;
+        let mut distance = self.stream.alloc_zeros::<f32>(queries.len())?;
+        let (nq, nt) = (queries.len() as u32, targets.len() as u32);
+        let max_d2 = max_distance * max_distance;
+        let mut launch = self.stream.launch_builder(&self.nearest);
+        launch
+            .arg(&query_buffer)
+            .arg(&nq)
+            .arg(&target_buffer)
+            .arg(&nt)
+            .arg(&max_d2)
+            .arg(&mut index)
+            .arg(&mut distance);
+        unsafe { launch.launch(launch_config(queries.len())?) }?;
+        let index = self.stream.clone_dtoh(&index)?;
+        let distance = self.stream.clone_dtoh(&distance)?;
+        Ok(index
+            .iter()
+            .zip(distance)
+            .map(|(&i, d)| (i > 0).then(|| (i as usize - 1, d)))
+            .collect())
+    }
+
+    fn try_neighbor_counts(
+        &self,
+        points: &[[f32; 3]],
+        radii: &[f32],
+        limits: &[usize],
+        cell: f32,
+    ) -> Result<Vec<usize>> {
+        // 셀 정렬과 해시 표는 CPU 에서 (O(n)), 이웃 거리 검사만 GPU 에서
+        let keys: Vec<CellKey> = points.iter().map(|p| compute::cell_key(*p, cell)).collect();
+        let mut order: Vec<u32> = (0..points.len() as u32).collect();
+        order.sort_unstable_by_key(|&i| keys[i as usize]);
+        let mut runs: Vec<(CellKey, u32, u32)> = Vec::new();
+        for (s, &i) in order.iter().enumerate() {
+            match runs.last_mut() {
+                Some((key, _, len)) if *key == keys[i as usize] => *len += 1,
+                _ => runs.push((keys[i as usize], s as u32, 1)),
+            }
+        }
+        let capacity = (runs.len() * 2).next_power_of_two().max(2);
+        let mask = (capacity - 1) as u32;
+        let mut table_keys = vec![0i32; capacity * 3];
+        let mut starts = vec![0u32; capacity];
+        let mut lens = vec![0u32; capacity];
+        for &(key, start, len) in &runs {
+            let mut slot = hash(key, mask) as usize;
+            while lens[slot] != 0 {
+                slot = (slot + 1) & mask as usize;
+            }
+            table_keys[slot * 3..slot * 3 + 3].copy_from_slice(&[key.0, key.1, key.2]);
+            starts[slot] = start;
+            lens[slot] = len;
+        }
+
+        let limits: Vec<u32> = limits
+            .iter()
+            .map(|&l| l.min(u32::MAX as usize) as u32)
+            .collect();
+        let input = self.stream.clone_htod(&flatten(points))?;
+        let radii = self.stream.clone_htod(radii)?;
+        let limits = self.stream.clone_htod(&limits)?;
+        let table_keys = self.stream.clone_htod(&table_keys)?;
+        let starts = self.stream.clone_htod(&starts)?;
+        let lens = self.stream.clone_htod(&lens)?;
+        let order = self.stream.clone_htod(&order)?;
+        let mut counts = self.stream.alloc_zeros::<u32>(points.len())?;
+        let n = points.len() as u32;
+        let mut launch = self.stream.launch_builder(&self.neighbors);
+        launch
+            .arg(&input)
+            .arg(&n)
+            .arg(&radii)
+            .arg(&limits)
+            .arg(&cell)
+            .arg(&table_keys)
+            .arg(&starts)
+            .arg(&lens)
+            .arg(&mask)
+            .arg(&order)
+            .arg(&mut counts);
+        unsafe { launch.launch(launch_config(points.len())?) }?;
+        Ok(self
+            .stream
+            .clone_dtoh(&counts)?
+            .into_iter()
+            .map(|c| c as usize)
+            .collect())
+    }
+}
+
+// CUDA 실행이 실패하면 그 호출만 CPU 로 처리
+fn fallback<T>(name: &str, result: Result<T>, cpu: impl FnOnce() -> T) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("CUDA {} 실패, CPU 로 처리: {}", name, e);
+        cpu()
+    })
+}
+
+impl ComputeBackend for Cuda {
+    fn name(&self) -> &'static str {
+        "cuda"
+    }
+
+    fn voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Vec<[f32; 3]> {
+        if size <= 0.0 || points.is_empty() {
+            return Cpu.voxel_downsample(points, size);
+        }
+        fallback("복셀화", self.try_voxel_downsample(points, size), || {
+            Cpu.voxel_downsample(points, size)
+        })
+    }
+
+    fn collect_cells(&self, grid: &BevGrid, points: &[LidarPoint]) -> Vec<Cell> {
+        if grid.cell_size <= 0.0 || points.is_empty() {
+            return Cpu.collect_cells(grid, points);
+        }
+        match self.try_collect_cells(grid, points) {
+            Ok(Some(cells)) => cells,
+            Ok(None) => Cpu.collect_cells(grid, points),
+            Err(e) => fallback("BEV 셀 집계", Err(e), || Cpu.collect_cells(grid, points)),
+        }
+    }
+
+    fn nearest(
+        &self,
+        queries: &[[f32; 3]],
+        target: &VoxelIndex,
+        max_distance: f32,
+    ) -> Vec<Option<(usize, f32)>> {
+        if queries.is_empty() || target.is_empty() {
+            return vec![None; queries.len()];
+        }
+        fallback(
+            "최근접 검색",
+            self.try_nearest(queries, target.points(), max_distance),
+            || Cpu.nearest(queries, target, max_distance),
+        )
+    }
+
+    fn neighbor_counts(
+        &self,
+        points: &[[f32; 3]],
+        radii: &[f32],
+        limits: &[usize],
+        cell: f32,
+    ) -> Vec<usize> {
+        if cell <= 0.0 || points.is_empty() {
+            return compute::neighbor_counts(points, radii, limits, cell);
+        }
+        fallback(
+            "이웃 수 계산",
+            self.try_neighbor_counts(points, radii, limits, cell),
+            || compute::neighbor_counts(points, radii, limits, cell),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bev::{self, Aggregation, CellAggregation, Origin};
+
+    // CUDA 장치가 없는 환경에서는 건너뜀
+    fn device() -> Option<Cuda> {
+        Cuda::new()
+            .map_err(|e| eprintln!("CUDA 없음, 건너뜀: {}", e))
+            .ok()
+    }
+
+    #[test]
+    fn collect_cells_matches_cpu_for_wide_values() {
+        let Some(cuda) = device() else {
+            return;
+        };
+        let grid = BevGrid {
+            cell_size: 1.0,
+            extent_x: 0.0,
+            extent_y: 0.0,
+            origin: Origin::Center,
+        };
+        // UINT16 intensity 와 예전 고정소수점 범위 (±1024) 밖의 높이
+        let values = [
+            (0.0, 65535.0),
+            (-1500.0, 40000.0),
+            (3000.0, 65535.0),
+            (-0.25, 0.0),
+            (12.5, 1.5),
+        ];
+        let points: Vec<LidarPoint> = (0..200)
+            .map(|i| {
+                let (z, intensity) = values[i % values.len()];
+                LidarPoint {
+                    x: (i % 3) as f32 + 0.5,
+                    y: -((i % 2) as f32) - 0.5,
+                    z,
+                    intensity,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let cpu = Cpu.collect_cells(&grid, &points);
+        let gpu = cuda.collect_cells(&grid, &points);
+        assert_eq!(cpu.len(), gpu.len());
+        for aggregation in [Aggregation::Mean, Aggregation::Min, Aggregation::Max] {
+            let agg = CellAggregation {
+                height: aggregation,
+                intensity: aggregation,
+            };
+            for (c, g) in cpu.iter().zip(&gpu) {
+                assert_eq!((c.index, c.count), (g.index, g.count));
+                let (c, g) = (
+                    bev::cell_point(&grid, c, &agg),
+                    bev::cell_point(&grid, g, &agg),
+                );
+                assert!(
+                    (c.z - g.z).abs() < 1e-3,
+                    "{:?}: {} != {}",
+                    aggregation,
+                    c.z,
+                    g.z
+                );
+                assert!(
+                    (c.intensity - g.intensity).abs() < 1e-3,
+                    "{:?}: {} != {}",
+                    aggregation,
+                    c.intensity,
+                    g.intensity
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::classify::ObjectClass;
+use crate::core::LidarPoint;
+use crate::sidecar::json_string;
+use crate::tracking::Track;
+use anyhow::{bail, Context, Result};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 학습용 데이터셋 내보내기: 누적 프레임 포인트와 검출 상자를 기존 도구가 읽는 형식으로 저장
+//   kitti:    velodyne/000000.bin (f32 x,y,z,reflectance 0..1), label_2/000000.txt, calib/000000.txt
+//             카메라가 없으므로 calib 는 LiDAR 축만 카메라 축으로 바꾸는 행렬 (P2 = 단위 투영)
+//   nuscenes: samples/LIDAR_TOP/<token>.pcd.bin (f32 x,y,z,intensity,ring), 끝낼 때
+//             sample.json, sample_annotation.json (nuScenes 표 구조 일부, devkit 로더가 쓰는 필드만)
+// 좌표는 장착 보정 후 기준 좌표계, 포인트와 상자의 z 는 모두 지면 위 높이 기준 (호출하는 쪽에서 맞춤)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetFormat {
+    Kitti,
+    NuScenes,
+}
+
+impl DatasetFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "kitti" => Ok(DatasetFormat::Kitti),
+            "nuscenes" => Ok(DatasetFormat::NuScenes),
+            other => bail!("알 수 없는 dataset_format '{}' (kitti, nuscenes)", other),
+        }
+    }
+
+    fn category(&self, class: ObjectClass) -> &'static str {
+        match (self, class) {
+            (DatasetFormat::Kitti, ObjectClass::Pedestrian) => "Pedestrian",
+            (DatasetFormat::Kitti, ObjectClass::Vehicle) => "Car",
+            (DatasetFormat::Kitti, ObjectClass::Other) => "Misc",
+            (DatasetFormat::NuScenes, ObjectClass::Pedestrian) => "human.pedestrian.adult",
+            (DatasetFormat::NuScenes, ObjectClass::Vehicle) => "vehicle.car",
+            (DatasetFormat::NuScenes, ObjectClass::Other) => "movable_object.debris",
+        }
+    }
+}
+
+// 3D 상자 라벨 하나
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelBox {
+    pub class: ObjectClass,
+    pub track_id: u32,
+    // 상자 중심 (m)
+    pub center: [f32; 3],
+    // 길이(heading 방향), 너비, 높이 (m)
+    pub size: [f32; 3],
+    // z 축 회전 (rad, x 축에서 반시계)
+    pub yaw: f32,
+    pub velocity: [f32; 2],
+    pub points: usize,
+    // 검출 신뢰도 (0..1), None 이면 확정 라벨로 저장 (KITTI score 열 생략)
+    pub score: Option<f32>,
+}
+
+impl LabelBox {
+    // 트랙의 x/y 축 정렬 상자 (긴 변 방향을 heading 으로)
+    pub fn from_track(track: &Track, class: ObjectClass) -> Self {
+        let c = &track.cluster;
+        let dx = c.max[0] - c.min[0];
+        let dy = c.max[1] - c.min[1];
+        LabelBox {
+            class,
+            track_id: track.id,
+            center: [
+                (c.min[0] + c.max[0]) / 2.0,
+                (c.min[1] + c.max[1]) / 2.0,
+                (c.min[2] + c.max[2]) / 2.0,
+            ],
+            size: c.dimensions(),
+            yaw: if dx >= dy {
+                0.0
+            } else {
+                std::f32::consts::FRAC_PI_2
+            },
+            velocity: track.velocity,
+            points: c.points,
+            score: None,
+        }
+    }
+
+    // KITTI label_2 한 줄 (카메라 좌표: x = -y_lidar, y = -z_lidar, z = x_lidar, 위치는 바닥 중심)
+    fn kitti_line(&self, category: &str) -> String {
+        let [l, w, h] = self.size;
+        let location = [-self.center[1], -(self.center[2] - h / 2.0), self.center[0]];
+        let rotation_y = wrap_angle(-self.yaw - std::f32::consts::FRAC_PI_2);
+        let alpha = wrap_angle(rotation_y - location[0].atan2(location[2]));
+        let mut line = format!(
+            "{} 0.00 0 {:.2} 0.00 0.00 0.00 0.00 {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} {:.2}",
+            category, alpha, h, w, l, location[0], location[1], location[2], rotation_y
+        );
+        if let Some(score) = self.score {
+            line.push_str(&format!(" {:.4}", score));
+        }
+        line
+    }
+}
+
+fn wrap_angle(a: f32) -> f32 {
+    let pi = std::f32::consts::PI;
+    (a + pi).rem_euclid(2.0 * pi) - pi
+}
+
+// LiDAR (x 앞, y 왼쪽, z 위) -> KITTI 카메라 (x 오른쪽, y 아래, z 앞)
+const KITTI_CALIB: &str = "\
+P0: 1 0 0 0 0 1 0 0 0 0 1 0
+P1: 1 0 0 0 0 1 0 0 0 0 1 0
+P2: 1 0 0 0 0 1 0 0 0 0 1 0
+P3: 1 0 0 0 0 1 0 0 0 0 1 0
+R0_rect: 1 0 0 0 1 0 0 0 1
+Tr_velo_to_cam: 0 -1 0 0 0 0 -1 0 1 0 0 0
+Tr_imu_to_velo: 1 0 0 0 0 1 0 0 0 0 1 0
+";
+
+struct NuScenesSample {
+    token: String,
+    timestamp: i64,
+    file: String,
+    annotations: Vec<LabelBox>,
+}
+
+pub struct DatasetWriter {
+    pub format: DatasetFormat,
+    dir: PathBuf,
+    // 한 샘플에 합칠 연속 프레임 수 (Livox 비반복 스캔 밀도 보충)
+    accumulate: usize,
+    frames: VecDeque<Vec<LidarPoint>>,
+    samples: usize,
+    nuscenes: Vec<NuScenesSample>,
+}
+
+impl DatasetWriter {
+    pub fn new(format: DatasetFormat, dir: &Path, accumulate: usize) -> Result<Self> {
+        let subdirs: &[&str] = match format {
+            DatasetFormat::Kitti => &["velodyne", "label_2", "calib"],
+            DatasetFormat::NuScenes => &["samples/LIDAR_TOP"],
+        };
+        for sub in subdirs {
+            let path = dir.join(sub);
+            fs::create_dir_all(&path).with_context(|| format!("{} 만들기 실패", path.display()))?;
+        }
+        Ok(DatasetWriter {
+            format,
+            dir: dir.to_path_buf(),
+            accumulate: accumulate.max(1),
+            frames: VecDeque::new(),
+            samples: 0,
+            nuscenes: Vec::new(),
+        })
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    // 프레임 하나 추가, 최근 accumulate 프레임 포인트와 이번 프레임 상자로 샘플 하나 저장
+    pub fn write_frame(
+        &mut self,
+        time: f64,
+        points: &[LidarPoint],
+        boxes: &[LabelBox],
+    ) -> Result<()> {
+        self.frames.push_back(points.to_vec());
+        while self.frames.len() > self.accumulate {
+            self.frames.pop_front();
+        }
+        let index = self.samples;
+        match self.format {
+            DatasetFormat::Kitti => {
+                let name = format!("{:06}", index);
+                let mut bin = Vec::new();
+                for p in self.frames.iter().flatten() {
+                    for v in [p.x, p.y, p.z, (p.intensity / 255.0).clamp(0.0, 1.0)] {
+                        bin.extend_from_slice(&v.to_le_bytes());
+                    }
+                }
+                write(
+                    &self.dir.join("velodyne").join(format!("{}.bin", name)),
+                    &bin,
+                )?;
+                let labels: String = boxes
+                    .iter()
+                    .map(|b| b.kitti_line(self.format.category(b.class)) + "\n")
+                    .collect();
+                write(
+                    &self.dir.join("label_2").join(format!("{}.txt", name)),
+                    labels.as_bytes(),
+                )?;
+                write(
+                    &self.dir.join("calib").join(format!("{}.txt", name)),
+                    KITTI_CALIB.as_bytes(),
+                )?;
+            }
+            DatasetFormat::NuScenes => {
+                let token = format!("{:032x}", index);
+                let file = format!("samples/LIDAR_TOP/{}.pcd.bin", token);
+                let mut bin = Vec::new();
+                for p in self.frames.iter().flatten() {
+                    for v in [p.x, p.y, p.z, p.intensity, p.line as f32] {
+                        bin.extend_from_slice(&v.to_le_bytes());
+                    }
+                }
+                write(&self.dir.join(&file), &bin)?;
+                self.nuscenes.push(NuScenesSample {
+                    token,
+                    timestamp: (time * 1e6).round() as i64,
+                    file,
+                    annotations: boxes.to_vec(),
+                });
+            }
+        }
+        self.samples += 1;
+        Ok(())
+    }
+
+    // nuScenes 표 파일 저장 (KITTI 는 프레임마다 이미 저장됨)
+    pub fn finish(&self) -> Result<()> {
+        if self.format != DatasetFormat::NuScenes {
+            return Ok(());
+        }
+        let mut samples = Vec::new();
+        let mut annotations = Vec::new();
+        for (i, s) in self.nuscenes.iter().enumerate() {
+            let prev = i
+                .checked_sub(1)
+                .map_or(String::new(), |j| self.nuscenes[j].token.clone());
+            let next = self
+                .nuscenes
+                .get(i + 1)
+                .map_or(String::new(), |n| n.token.clone());
+            samples.push(format!(
+                "{{\"token\": {}, \"timestamp\": {}, \"prev\": {}, \"next\": {}, \"lidar_file\": {}}}",
+                json_string(&s.token),
+                s.timestamp,
+                json_string(&prev),
+                json_string(&next),
+                json_string(&s.file)
+            ));
+            for (k, b) in s.annotations.iter().enumerate() {
+                // nuScenes size 는 너비, 길이, 높이 / rotation 은 w,x,y,z 쿼터니언
+                let [l, w, h] = b.size;
+                let (qz, qw) = ((b.yaw / 2.0).sin(), (b.yaw / 2.0).cos());
+                annotations.push(format!(
+                    "{{\"token\": {}, \"sample_token\": {}, \"instance_token\": {}, \"category_name\": {}, \
+                     \"translation\": [{:.3}, {:.3}, {:.3}], \"size\": [{:.3}, {:.3}, {:.3}], \
+                     \"rotation\": [{:.6}, 0.0, 0.0, {:.6}], \"velocity\": [{:.3}, {:.3}], \
+                     \"num_lidar_pts\": {}, \"score\": {}}}",
+                    json_string(&format!("{}_{}", s.token, k)),
+                    json_string(&s.token),
+                    json_string(&format!("{:032x}", b.track_id)),
+                    json_string(self.format.category(b.class)),
+                    b.center[0],
+                    b.center[1],
+                    b.center[2],
+                    w,
+                    l,
+                    h,
+                    qw,
+                    qz,
+                    b.velocity[0],
+                    b.velocity[1],
+                    b.points,
+                    b.score.map_or("null".to_string(), |s| format!("{:.4}", s))
+                ));
+            }
+        }
+        write(
+            &self.dir.join("sample.json"),
+            format!("[\n  {}\n]\n", samples.join(",\n  ")).as_bytes(),
+        )?;
+        write(
+            &self.dir.join("sample_annotation.json"),
+            format!("[\n  {}\n]\n", annotations.join(",\n  ")).as_bytes(),
+        )
+    }
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<()> {
+    fs::write(path, bytes).with_context(|| format!("{} 쓰기 실패", path.display()))
+}
@@ -0,0 +1,201 @@
+use crate::point::LidarPoint;
+use crate::pose::Pose;
+#[cfg(feature = "ros")]
+use crate::stamp;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use nav_msgs::msg::Odometry;
+use std::collections::VecDeque;
+
+// 오도메트리 자세 기록 (시간순), 포인트 측정 시각의 자세를 보간해서 제공
+pub struct PoseBuffer {
+    poses: VecDeque<(f64, Pose)>,
+    capacity: usize,
+    // 기록된 자세의 좌표계 (Odometry header.frame_id)
+    pub frame_id: String,
+    // 질의 시각(LiDAR 시계) + time_offset = 기록 시각 (calibrate_time_offset 으로 추정, 초)
+    pub time_offset: f64,
+}
+
+impl PoseBuffer {
+    pub fn new(capacity: usize) -> Self {
+        PoseBuffer {
+            poses: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(2),
+            frame_id: String::new(),
+            time_offset: 0.0,
+        }
+    }
+
+    pub fn with_time_offset(mut self, offset: f64) -> Self {
+        self.time_offset = offset;
+        self
+    }
+
+    pub fn push(&mut self, time: f64, pose: Pose) {
+        // 시간이 되돌아가면 (bag 재생 반복 등) 기록을 비움
+        if self.poses.back().is_some_and(|(t, _)| time < *t) {
+            self.poses.clear();
+        }
+        if self.poses.len() >= self.capacity {
+            self.poses.pop_front();
+        }
+        self.poses.push_back((time, pose));
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn push_odometry(&mut self, msg: &Odometry) {
+        self.frame_id = msg.header.frame_id.clone();
+        self.push(
+            stamp::to_secs(&msg.header.stamp),
+            Pose::from_msg(&msg.pose.pose),
+        );
+    }
+
+    pub fn latest(&self) -> Option<(f64, Pose)> {
+        self.poses.back().copied()
+    }
+
+    // 기록 범위 밖이면 tolerance(초) 안에서만 끝 자세를 그대로 사용
+    pub fn pose_at(&self, time: f64, tolerance: f64) -> Option<Pose> {
+        let time = time + self.time_offset;
+        let (first_t, first) = *self.poses.front()?;
+        let (last_t, last) = *self.poses.back()?;
+        if time <= first_t {
+            return (first_t - time <= tolerance).then_some(first);
+        }
+        if time >= last_t {
+            return (time - last_t <= tolerance).then_some(last);
+        }
+        let i = self.poses.partition_point(|(t, _)| *t <= time);
+        let (t0, p0) = self.poses[i - 1];
+        let (t1, p1) = self.poses[i];
+        let alpha = if t1 > t0 {
+            (time - t0) / (t1 - t0)
+        } else {
+            0.0
+        };
+        Some(p0.interpolate(&p1, alpha))
+    }
+}
+
+// 포인트 측정 시각 (초), timestamp 가 없으면 프레임 시각
+pub fn point_time(point: &LidarPoint, frame_time: f64) -> f64 {
+    if point.timestamp > 0.0 {
+        point.timestamp * 1e-9
+    } else {
+        frame_time
+    }
+}
+
+// 각 포인트를 측정 시점 자세에서 reference 시각 자세 기준으로 옮김 (ego motion 왜곡 보정)
+// to_world 면 reference 자세까지 적용해서 오도메트리 좌표계로 표현
+pub fn deskew(
+    points: &mut [LidarPoint],
+    poses: &PoseBuffer,
+    reference_time: f64,
+    to_world: bool,
+    tolerance: f64,
+) -> Result<()> {
+    let Some(reference) = poses.pose_at(reference_time, tolerance) else {
+        bail!("프레임 시각({:.3})의 오도메트리가 없습니다", reference_time);
+    };
+    let reference_inv = reference.inverse();
+
+    // 같은 시각 포인트가 많으므로 직전 자세를 재사용
+    let mut cached: Option<(f64, Pose)> = None;
+    for p in points.iter_mut() {
+        let t = point_time(p, reference_time);
+        let correction = match cached {
+            Some((ct, pose)) if ct == t => pose,
+            _ => {
+                let Some(pose) = poses.pose_at(t, tolerance) else {
+                    bail!("포인트 시각({:.3})의 오도메트리가 없습니다", t);
+                };
+                let correction = if to_world {
+                    pose
+                } else {
+                    reference_inv.compose(&pose)
+                };
+                cached = Some((t, correction));
+                correction
+            }
+        };
+        let q = correction.transform_point([p.x as f64, p.y as f64, p.z as f64]);
+        p.x = q[0] as f32;
+        p.y = q[1] as f32;
+        p.z = q[2] as f32;
+    }
+    Ok(())
+}
+
+// bev_pub 의 odom_mode 파라미터
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OdomMode {
+    Off,
+    // 프레임 시각 자세 기준으로 왜곡 보정 (좌표계는 그대로)
+    Deskew,
+    // 왜곡 보정 후 오도메트리 좌표계로 변환
+    DeskewToOdom,
+    // 오도메트리 대신 IMU 자이로 적분 자세로 회전만 보정
+    Imu,
+}
+
+impl OdomMode {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "off" => Ok(OdomMode::Off),
+            "deskew" => Ok(OdomMode::Deskew),
+            "deskew_to_odom" => Ok(OdomMode::DeskewToOdom),
+            "imu" => Ok(OdomMode::Imu),
+            _ => bail!(
+                "알 수 없는 odom_mode '{}' (off, deskew, deskew_to_odom, imu)",
+                name
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthetic::{self, EgoMotion, SyntheticConfig};
+
+    fn max_error(a: &[LidarPoint], b: &[LidarPoint]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(p, q)| ((p.x - q.x).powi(2) + (p.y - q.y).powi(2) + (p.z - q.z).powi(2)).sqrt())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn deskew_recovers_synthetic_truth() {
+        let config = SyntheticConfig {
+            points_per_line: 1000,
+            motion: EgoMotion {
+                velocity: [3.0, 1.0, 0.2],
+                yaw_rate: 1.5,
+            },
+            ..SyntheticConfig::default()
+        };
+        let stamp = 100.0;
+        let frame = synthetic::generate(&config, stamp * 1e9);
+
+        // 합성 운동을 1 ms 간격 오도메트리로 기록 (프레임 앞뒤 여유 포함)
+        let mut poses = PoseBuffer::new(1000);
+        for i in -10..=(config.frame_duration * 1000.0) as i32 + 10 {
+            let t = i as f32 * 1e-3;
+            let (position, yaw) = config.motion.pose_at(t);
+            poses.push(
+                stamp + t as f64,
+                Pose::from_rpy(position.map(|v| v as f64), 0.0, 0.0, yaw as f64),
+            );
+        }
+
+        let mut points = frame.distorted.clone();
+        assert!(max_error(&points, &frame.truth) > 1.0);
+        deskew(&mut points, &poses, stamp, false, 0.0).unwrap();
+        let error = max_error(&points, &frame.truth);
+        assert!(error < 1e-3, "최대 오차 {} m", error);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::stamp;
+use anyhow::Result;
+use diagnostic_msgs::msg::{DiagnosticArray, DiagnosticStatus, KeyValue};
+use rclrs::{Node, Publisher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std_msgs::msg::Header;
+
+pub const OK: u8 = 0;
+pub const WARN: u8 = 1;
+pub const ERROR: u8 = 2;
+
+// /diagnostics 로 노드 상태를 주기적으로 발행 (period 보다 자주 호출하면 무시)
+pub struct Diagnostics {
+    publisher: Arc<Publisher<DiagnosticArray>>,
+    name: String,
+    period: Duration,
+    last_publish: Option<Instant>,
+}
+
+impl Diagnostics {
+    // /diagnostics 는 관례대로 전역 토픽, 센서별 인스턴스는 상태 이름에 네임스페이스를 붙여 구분
+    pub fn new(node: &Node, name: &str) -> Result<Self> {
+        let name = match node.namespace().as_str() {
+            "" | "/" => name.to_string(),
+            namespace => format!("{}/{}", namespace, name),
+        };
+        Ok(Diagnostics {
+            publisher: node.create_publisher("/diagnostics", rclrs::QOS_PROFILE_DEFAULT)?,
+            name,
+            period: Duration::from_secs(1),
+            last_publish: None,
+        })
+    }
+
+    pub fn publish(&mut self, level: u8, message: &str, values: &[(&str, String)]) -> Result<()> {
+        if let Some(last) = self.last_publish {
+            if last.elapsed() < self.period {
+                return Ok(());
+            }
+        }
+        self.last_publish = Some(Instant::now());
+
+        let status = DiagnosticStatus {
+            level,
+            name: self.name.clone(),
+            message: message.to_string(),
+            hardware_id: String::new(),
+            values: values
+                .iter()
+                .map(|(key, value)| KeyValue {
+                    key: key.to_string(),
+                    value: value.clone(),
+                })
+                .collect(),
+        };
+        self.publisher.publish(DiagnosticArray {
+            header: Header {
+                stamp: stamp::now(),
+                frame_id: String::new(),
+            },
+            status: vec![status],
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::cloud::{Point, PointCloud};
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::yaml;
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::fs;
+use std::path::Path;
+
+// 항상 제거하는 고정 영역 (크레인 팔, 차체 일부처럼 시야를 계속 가리는 구조물)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Zone {
+    Box {
+        min: [f32; 3],
+        max: [f32; 3],
+    },
+    // x/y 다각형 기둥, z 범위가 없으면 높이와 무관하게 제거
+    Polygon {
+        vertices: Vec<[f32; 2]>,
+        z_min: f32,
+        z_max: f32,
+    },
+}
+
+impl Zone {
+    pub fn contains(&self, [x, y, z]: [f32; 3]) -> bool {
+        match self {
+            Zone::Box { min, max } => {
+                let xyz = [x, y, z];
+                (0..3).all(|i| xyz[i] >= min[i] && xyz[i] <= max[i])
+            }
+            Zone::Polygon {
+                vertices,
+                z_min,
+                z_max,
+            } => z >= *z_min && z <= *z_max && point_in_polygon(vertices, x, y),
+        }
+    }
+}
+
+// 영역 좌표계: sensor 는 센서 원본 좌표 (장착 보정 전), base 는 장착 보정 후 차량 좌표
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneFrame {
+    Sensor,
+    Base,
+}
+
+// 설정 파일 형식 (YAML 일부):
+//   frame: sensor                                  # sensor 또는 base (기본 sensor)
+//   zones:
+//     - box: [0.2, -0.5, -0.3, 1.5, 0.5, 0.8]      # x_min, y_min, z_min, x_max, y_max, z_max
+//     - polygon: [[1.0, 0.0], [3.0, 1.0], [3.0, -1.0]]
+//       z: [-1.0, 2.5]                             # 선택, 없으면 모든 높이
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExclusionZones {
+    pub frame: ZoneFrame,
+    pub zones: Vec<Zone>,
+}
+
+impl ExclusionZones {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        Self::parse(&text).with_context(|| format!("{} 해석 실패", path.display()))
+    }
+
+    // exclusion_zones 파라미터: 영역 설정 파일 경로 (빈 문자열이면 사용 안 함)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let path = params::string(node, "exclusion_zones", "")?;
+        if path.is_empty() {
+            return Ok(None);
+        }
+        let zones = Self::load(Path::new(&path))?;
+        println!(
+            "제외 영역: {} ({}개, {:?} 좌표계)",
+            path,
+            zones.zones.len(),
+            zones.frame
+        );
+        Ok(Some(zones))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut frame = ZoneFrame::Sensor;
+        let mut zones = Vec::new();
+        yaml::for_each_entry(text, |entry| {
+            let value = entry.value;
+            match (entry.section, entry.item, entry.key) {
+                (None, _, "frame") => {
+                    frame = match value {
+                        "sensor" => ZoneFrame::Sensor,
+                        "base" => ZoneFrame::Base,
+                        other => bail!("알 수 없는 frame '{}' (sensor, base)", other),
+                    }
+                }
+                (None, _, "zones") if value.is_empty() => {}
+                (None, _, other) => bail!("알 수 없는 키 '{}'", other),
+                (Some("zones"), true, "box") => {
+                    let v = parse_list(value)?;
+                    if v.len() != 6 {
+                        bail!("box 는 값 6개가 필요합니다");
+                    }
+                    let (min, max) = ([v[0], v[1], v[2]], [v[3], v[4], v[5]]);
+                    if (0..3).any(|i| min[i] >= max[i]) {
+                        bail!("box 의 min 이 max 보다 작아야 합니다");
+                    }
+                    zones.push(Zone::Box { min, max });
+                }
+                (Some("zones"), true, "polygon") => {
+                    let vertices = parse_vertices(value)?;
+                    if vertices.len() < 3 {
+                        bail!("polygon 은 꼭짓점 3개 이상이 필요합니다");
+                    }
+                    zones.push(Zone::Polygon {
+                        vertices,
+                        z_min: f32::NEG_INFINITY,
+                        z_max: f32::INFINITY,
+                    });
+                }
+                (Some("zones"), false, "z") => {
+                    let Some(Zone::Polygon { z_min, z_max, .. }) = zones.last_mut() else {
+                        bail!("z 는 polygon 바로 아래에만 쓸 수 있습니다");
+                    };
+                    let v = parse_list(value)?;
+                    if v.len() != 2 || v[0] >= v[1] {
+                        bail!("z 는 [min, max] 여야 합니다");
+                    }
+                    (*z_min, *z_max) = (v[0], v[1]);
+                }
+                (Some("zones"), _, other) => bail!("알 수 없는 영역 항목 '{}'", other),
+                (Some(_), _, _) => bail!("zones 밖의 항목입니다"),
+            }
+            Ok(())
+        })?;
+        Ok(ExclusionZones { frame, zones })
+    }
+
+    pub fn contains(&self, xyz: [f32; 3]) -> bool {
+        self.zones.iter().any(|zone| zone.contains(xyz))
+    }
+
+    pub fn apply<P: Point>(&self, cloud: &mut PointCloud<P>) {
+        cloud.retain(|p| !self.contains(p.xyz()));
+    }
+
+    // 패스스루 모드용 남길 포인트 마스크 (passthrough::compact)
+    pub fn mask<P: Point>(&self, points: &[P]) -> Vec<bool> {
+        points.iter().map(|p| !self.contains(p.xyz())).collect()
+    }
+}
+
+// 짝수-홀수 규칙 (경계 위 포인트는 어느 쪽이든 될 수 있음)
+pub fn point_in_polygon(vertices: &[[f32; 2]], x: f32, y: f32) -> bool {
+    let mut inside = false;
+    let mut j = vertices.len() - 1;
+    for i in 0..vertices.len() {
+        let ([xi, yi], [xj, yj]) = (vertices[i], vertices[j]);
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+// "[1.0, 2.0, 3.0]"
+fn parse_list(value: &str) -> Result<Vec<f32>> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .with_context(|| format!("'[...]' 목록이 아닙니다: '{}'", value))?;
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<f32>()
+                .with_context(|| format!("숫자가 아닌 값 '{}'", v))
+        })
+        .collect()
+}
+
+// "[[x, y], [x, y], ...]"
+fn parse_vertices(value: &str) -> Result<Vec<[f32; 2]>> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .with_context(|| format!("'[[x, y], ...]' 목록이 아닙니다: '{}'", value))?;
+    inner
+        .split(']')
+        .map(|v| v.trim().trim_start_matches(',').trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let xy = parse_list(&format!("{}]", v))?;
+            match xy[..] {
+                [x, y] => Ok([x, y]),
+                _ => bail!("꼭짓점은 [x, y] 여야 합니다: '{}]'", v),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_boxes_and_polygons() {
+        let zones = ExclusionZones::parse(
+            "\
+frame: base
+zones:
+  - box: [0.2, -0.5, -0.3, 1.5, 0.5, 0.8]
+  - polygon: [[1.0, 0.0], [3.0, 1.0], [3.0, -1.0]]
+    z: [-1.0, 2.5]
+  - polygon: [[-1.0, -1.0], [-2.0, -1.0], [-2.0, -2.0]]
+",
+        )
+        .unwrap();
+        assert_eq!(zones.frame, ZoneFrame::Base);
+        assert_eq!(zones.zones.len(), 3);
+        assert!(zones.contains([1.0, 0.0, 0.0]));
+        assert!(zones.contains([2.5, 0.0, 2.0]));
+        assert!(!zones.contains([2.5, 0.0, 3.0]));
+        // z 범위가 없는 다각형은 높이와 무관
+        assert!(zones.contains([-1.8, -1.5, 100.0]));
+        assert!(!zones.contains([5.0, 5.0, 0.0]));
+    }
+
+    #[test]
+    fn parse_defaults_to_sensor_frame() {
+        let zones = ExclusionZones::parse("zones:\n  - box: [0, 0, 0, 1, 1, 1]\n").unwrap();
+        assert_eq!(zones.frame, ZoneFrame::Sensor);
+    }
+
+    #[test]
+    fn parse_rejects_bad_zones() {
+        for text in [
+            "frame: world\n",
+            "zones:\n  - box: [0, 0, 0, 1, 1]\n",
+            "zones:\n  - box: [1, 0, 0, 0, 1, 1]\n",
+            "zones:\n  - polygon: [[0, 0], [1, 1]]\n",
+            "zones:\n  - polygon: [[0, 0], [1, 1, 2], [2, 0]]\n",
+            "zones:\n  - box: [0, 0, 0, 1, 1, 1]\n    z: [0, 1]\n",
+            "zones:\n  - sphere: [0, 0, 0, 1]\n",
+            "lines:\n  0: 1\n",
+        ] {
+            assert!(ExclusionZones::parse(text).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::params;
+use crate::shutdown::Shutdown;
+use anyhow::{anyhow, bail, Result};
+use rclrs::{Context, Node};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+// 콜백 실행 방식
+//   single: 노드 하나를 메인 스레드에서 spin (모든 콜백이 차례로 실행)
+//   threaded: 무거운 입력 구독을 별도 노드 (<노드 이름>_<역할>) 로 옮겨 자기 스레드에서 spin
+//     서비스, 진단, 보조 구독은 메인 노드에 남으므로 입력 콜백이 밀려도 서로 막지 않음
+// rclrs 0.4 에는 다중 스레드 executor 가 없어 노드마다 spin 스레드를 둠
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorModel {
+    Single,
+    Threaded,
+}
+
+impl ExecutorModel {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "single" => Ok(ExecutorModel::Single),
+            "threaded" => Ok(ExecutorModel::Threaded),
+            other => bail!("알 수 없는 executor '{}' (single, threaded)", other),
+        }
+    }
+
+    pub fn from_node(node: &Node) -> Result<Self> {
+        ExecutorModel::parse(&params::string(node, "executor", "single")?)
+    }
+}
+
+// ros_args 의 노드를 지정한 이름 변경 (-r <노드>:<이름>:=<새 이름>) 중 __node 가 아닌 것
+// 노드 지정 규칙은 원래 노드에만 적용되어 threaded 의 추가 노드에는 적용되지 않음
+pub fn node_scoped_remaps(ros_args: &[String]) -> Vec<String> {
+    let mut remaps = Vec::new();
+    let mut in_ros_args = false;
+    let mut args = ros_args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ros-args" => in_ros_args = true,
+            "--" => in_ros_args = false,
+            "-r" | "--remap" if in_ros_args => {
+                let Some(rule) = args.next() else {
+                    break;
+                };
+                let from = rule
+                    .split_once(":=")
+                    .map_or(rule.as_str(), |(from, _)| from);
+                if from
+                    .split_once(':')
+                    .is_some_and(|(_, name)| name != "__node")
+                {
+                    remaps.push(rule.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    remaps
+}
+
+// threaded 모드에서 추가 노드와 spin 스레드 관리
+pub struct Executor {
+    model: ExecutorModel,
+    shutdown: Arc<Shutdown>,
+    // Context 를 만든 --ros-args 인자 (cli::OutputOptions::ros_args)
+    ros_args: Vec<String>,
+    threads: Vec<(String, JoinHandle<Result<()>>)>,
+}
+
+impl Executor {
+    pub fn new(model: ExecutorModel, shutdown: &Arc<Shutdown>, ros_args: &[String]) -> Self {
+        Executor {
+            model,
+            shutdown: Arc::clone(shutdown),
+            ros_args: ros_args.to_vec(),
+            threads: Vec::new(),
+        }
+    }
+
+    // 구독을 만들 노드: single 이면 main 그대로, threaded 면 새 노드를 만들고 spin 스레드 시작
+    // 상대 토픽 이름은 같은 네임스페이스로 풀리므로 토픽 이름은 바뀌지 않음 (~/ 이름은 새 노드 기준)
+    pub fn node_for(
+        &mut self,
+        context: &Context,
+        main: &Arc<Node>,
+        role: &str,
+    ) -> Result<Arc<Node>> {
+        if self.model == ExecutorModel::Single {
+            return Ok(Arc::clone(main));
+        }
+        // 메인 노드에만 적용되는 토픽/네임스페이스 변경이면 두 모드가 다른 토픽을 구독하게 됨
+        let scoped = node_scoped_remaps(&self.ros_args);
+        if !scoped.is_empty() {
+            bail!(
+                "executor=threaded 는 노드를 지정한 이름 변경과 함께 쓸 수 없습니다 ({}), \
+                 노드를 지정하지 않은 -r <이름>:=<새 이름> 을 쓰세요",
+                scoped.join(", ")
+            );
+        }
+        let name = format!("{}_{}", main.name(), role);
+        let node = rclrs::create_node(context, &name)?;
+        // -r __node:=이름 은 Context 의 모든 노드에 적용되어 두 노드 이름이 같아짐
+        // threaded 에서는 <원래 노드 이름>:__node:=이름 처럼 노드를 지정해서 바꿔야 함
+        if node.name() == main.name() {
+            bail!(
+                "executor=threaded 는 노드를 지정하지 않은 __node 이름 변경과 함께 쓸 수 없습니다 \
+                 ({} 와 {} 노드 이름이 같아짐, <노드>:__node:=<이름> 으로 지정하세요)",
+                main.name(),
+                name
+            );
+        }
+        let spin_node = Arc::clone(&node);
+        let shutdown = Arc::clone(&self.shutdown);
+        let handle = thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || shutdown.spin(&spin_node))?;
+        self.threads.push((name, handle));
+        Ok(node)
+    }
+
+    // 종료 요청 후 spin 스레드가 끝날 때까지 기다림
+    pub fn join(mut self) -> Result<()> {
+        for (name, handle) in std::mem::take(&mut self.threads) {
+            handle
+                .join()
+                .map_err(|_| anyhow!("{} spin 스레드가 비정상 종료되었습니다", name))??;
+        }
+        Ok(())
+    }
+}
+
+// join 전에 오류로 빠져나가도 spin 스레드를 남기지 않도록 종료를 요청하고 기다림
+impl Drop for Executor {
+    fn drop(&mut self) {
+        if self.threads.is_empty() {
+            return;
+        }
+        self.shutdown.request();
+        for (name, handle) in self.threads.drain(..) {
+            match handle.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("{} spin 오류: {}", name, e),
+                Err(_) => eprintln!("{} spin 스레드가 비정상 종료되었습니다", name),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn node_scoped_remaps_skip_global_and_node_name_rules() {
+        let ros_args = args(&[
+            "--ros-args",
+            "-r",
+            "__ns:=/front",
+            "-r",
+            "lidar_bev_publisher:__node:=front_bev",
+            "--remap",
+            "livox/lidar:=/front/points",
+            "-p",
+            "a:=1:2",
+        ]);
+        assert!(node_scoped_remaps(&ros_args).is_empty());
+    }
+
+    #[test]
+    fn node_scoped_remaps_report_topic_and_namespace_rules() {
+        let ros_args = args(&[
+            "--ros-args",
+            "-r",
+            "lidar_bev_publisher:livox/lidar:=/front/points",
+            "--",
+            "-r",
+            "other:x:=y",
+            "--ros-args",
+            "--remap",
+            "lidar_bev_publisher:__ns:=/front",
+        ]);
+        assert_eq!(
+            node_scoped_remaps(&ros_args),
+            args(&[
+                "lidar_bev_publisher:livox/lidar:=/front/points",
+                "lidar_bev_publisher:__ns:=/front",
+            ])
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::cloud::{Point, PointCloud};
+use crate::ground::Plane;
+use std::collections::HashSet;
+
+// 포인트 타입에 무관한 기본 필터들
+
+pub fn z_band<P: Point>(cloud: &mut PointCloud<P>, min_z: f32, max_z: f32) {
+    cloud.retain(|p| {
+        let z = p.xyz()[2];
+        z >= min_z && z <= max_z
+    });
+}
+
+pub fn crop_box<P: Point>(cloud: &mut PointCloud<P>, min: [f32; 3], max: [f32; 3]) {
+    cloud.retain(|p| {
+        let xyz = p.xyz();
+        (0..3).all(|i| xyz[i] >= min[i] && xyz[i] <= max[i])
+    });
+}
+
+pub fn range<P: Point>(cloud: &mut PointCloud<P>, min_range: f32, max_range: f32) {
+    let (min_sq, max_sq) = (min_range * min_range, max_range * max_range);
+    cloud.retain(|p| {
+        let [x, y, z] = p.xyz();
+        let d = x * x + y * y + z * z;
+        d >= min_sq && d <= max_sq
+    });
+}
+
+// 복셀마다 처음 들어온 포인트 하나만 남김 (평균을 내지 않아 다른 필드가 그대로 유지됨)
+pub fn voxel<P: Point>(cloud: &mut PointCloud<P>, size: f32) {
+    let mut keep = voxel_mask(&cloud.points, size).into_iter();
+    cloud.retain(|_| keep.next().unwrap_or(true));
+}
+
+// voxel 과 같은 기준으로 남길 포인트 마스크 (패스스루 모드용)
+pub fn voxel_mask<P: Point>(points: &[P], size: f32) -> Vec<bool> {
+    if size <= 0.0 {
+        return vec![true; points.len()];
+    }
+    let mut seen = HashSet::with_capacity(points.len());
+    points
+        .iter()
+        .map(|p| seen.insert(p.xyz().map(|v| (v / size).floor() as i32)))
+        .collect()
+}
+
+// BEV 처럼 모든 포인트를 z 평면으로 투영
+pub fn flatten<P: Point>(cloud: &mut PointCloud<P>, z: f32) {
+    for p in cloud.points.iter_mut() {
+        let [x, y, _] = p.xyz();
+        p.set_xyz([x, y, z]);
+    }
+}
+
+// 평면 위 높이(부호 있는 거리)가 [min_h, max_h] 인 포인트만 남김 (지면 기준 z 필터)
+pub fn height_band<P: Point>(cloud: &mut PointCloud<P>, plane: &Plane, min_h: f32, max_h: f32) {
+    cloud.retain(|p| {
+        let h = plane.distance(p.xyz());
+        h >= min_h && h <= max_h
+    });
+}
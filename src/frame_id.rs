@@ -0,0 +1,66 @@
+#[cfg(feature = "ros")]
+use crate::params;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashMap;
+
+// 출력 frame_id 규칙: TF 트리(로봇 description)에 있는 이름을 그대로 쓰도록 설정
+//   output_frame_id: 템플릿 (기본 "{frame}_{output}{convention}", "keep" 은 "{frame}" 과 같음)
+//     {frame}      입력 frame_id (frame_id_map 에 있으면 바꾼 이름)
+//     {output}     출력 종류 (bev, overhead)
+//     {convention} 좌표축 규약 접미사 (flu: "", frd: "_frd", optical: "_optical")
+//   frame_id_map: ["livox_frame=front_lidar_link", ...] 입력 frame_id 별 이름 바꾸기
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameNaming {
+    pub template: String,
+    pub map: HashMap<String, String>,
+}
+
+impl Default for FrameNaming {
+    fn default() -> Self {
+        FrameNaming {
+            template: "{frame}_{output}{convention}".to_string(),
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl FrameNaming {
+    pub fn new(template: &str, entries: &[String]) -> Result<Self> {
+        let template = match template {
+            "keep" => "{frame}".to_string(),
+            "" => bail!("output_frame_id 가 비어 있습니다"),
+            other => other.to_string(),
+        };
+        let mut map = HashMap::new();
+        for entry in entries {
+            let Some((from, to)) = entry.split_once('=') else {
+                bail!("frame_id_map '{}': 'from=to' 형식이 아닙니다", entry);
+            };
+            let (from, to) = (from.trim(), to.trim());
+            if from.is_empty() || to.is_empty() {
+                bail!("frame_id_map '{}': 빈 frame_id", entry);
+            }
+            map.insert(from.to_string(), to.to_string());
+        }
+        Ok(FrameNaming { template, map })
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        let defaults = FrameNaming::default();
+        Self::new(
+            &params::string(node, "output_frame_id", &defaults.template)?,
+            &params::string_array(node, "frame_id_map", &[])?,
+        )
+    }
+
+    pub fn name(&self, frame: &str, output: &str, convention: &str) -> String {
+        let frame = self.map.get(frame).map_or(frame, String::as_str);
+        self.template
+            .replace("{frame}", frame)
+            .replace("{output}", output)
+            .replace("{convention}", convention)
+    }
+}
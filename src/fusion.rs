@@ -0,0 +1,173 @@
+use crate::deskew::PoseBuffer;
+use crate::pose::Pose;
+use crate::registration::{self, IcpConfig, IcpResult, VoxelIndex};
+
+#[derive(Debug, Clone, Copy)]
+pub struct FusionConfig {
+    // 스캔 시각에서 이 시간(초) 안에 휠 오도메트리가 없으면 끊긴 것으로 봄
+    pub wheel_timeout: f64,
+    // 정합 결과를 받아들일 최소 fitness 와 최대 rmse (m)
+    pub min_fitness: f64,
+    pub max_rmse: f64,
+    // 휠 추정과 정합 결과의 차이가 이보다 크면 (m) 정합 실패로 보고 휠을 씀
+    pub max_correction: f64,
+    // 정합 전 다운샘플 복셀 크기 (m)
+    pub voxel: f32,
+}
+
+impl Default for FusionConfig {
+    fn default() -> Self {
+        FusionConfig {
+            wheel_timeout: 0.2,
+            min_fitness: 0.3,
+            max_rmse: 0.3,
+            max_correction: 0.5,
+            voxel: 0.3,
+        }
+    }
+}
+
+// 이번 스캔 자세를 어디서 얻었는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoseSource {
+    // 스캔 정합 (초기값은 휠 또는 등속 예측)
+    Lidar,
+    // 정합 실패, 휠 오도메트리 변화량 사용
+    Wheel,
+    // 정합 실패 + 휠 끊김, 직전 속도 유지
+    ConstantVelocity,
+    // 아무 정보 없음 (첫 스캔 등), 자세 유지
+    Hold,
+}
+
+impl PoseSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PoseSource::Lidar => "lidar",
+            PoseSource::Wheel => "wheel",
+            PoseSource::ConstantVelocity => "constant_velocity",
+            PoseSource::Hold => "hold",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FusionUpdate {
+    pub pose: Pose,
+    // 직전 스캔 대비 움직임과 시간 간격 (초)
+    pub delta: Pose,
+    pub dt: f64,
+    pub source: PoseSource,
+    pub icp: Option<IcpResult>,
+}
+
+struct LastScan {
+    time: f64,
+    target: VoxelIndex,
+}
+
+// 휠 오도메트리를 스캔 정합 초기값으로 쓰고, 한쪽이 끊기면 다른 쪽으로 넘어가는 오도메트리
+pub struct OdometryFusion {
+    pub config: FusionConfig,
+    pub icp: IcpConfig,
+    wheel: PoseBuffer,
+    pose: Pose,
+    last: Option<LastScan>,
+    // 직전 스캔 간 움직임 (등속 예측용)
+    velocity: Option<(Pose, f64)>,
+}
+
+impl OdometryFusion {
+    pub fn new(config: FusionConfig, icp: IcpConfig) -> Self {
+        OdometryFusion {
+            config,
+            icp,
+            wheel: PoseBuffer::new(1000),
+            pose: Pose::IDENTITY,
+            last: None,
+            velocity: None,
+        }
+    }
+
+    pub fn pose(&self) -> Pose {
+        self.pose
+    }
+
+    pub fn push_wheel(&mut self, time: f64, pose: Pose) {
+        self.wheel.push(time, pose);
+    }
+
+    // 두 시각 사이의 휠 오도메트리 변화량 (어느 한쪽이라도 없으면 None)
+    fn wheel_delta(&self, from: f64, to: f64) -> Option<Pose> {
+        let tolerance = self.config.wheel_timeout;
+        let a = self.wheel.pose_at(from, tolerance)?;
+        let b = self.wheel.pose_at(to, tolerance)?;
+        Some(a.inverse().compose(&b))
+    }
+
+    fn constant_velocity(&self, dt: f64) -> Option<Pose> {
+        let (delta, last_dt) = self.velocity?;
+        let ratio = if last_dt > 0.0 {
+            (dt / last_dt).clamp(0.0, 2.0)
+        } else {
+            1.0
+        };
+        Some(Pose::IDENTITY.interpolate(&delta, ratio))
+    }
+
+    // 스캔 (차량 좌표계 x/y/z) 하나로 자세 갱신
+    pub fn update(&mut self, time: f64, points: &[[f32; 3]]) -> FusionUpdate {
+        let scan = registration::voxel_downsample(points, self.config.voxel);
+        let Some(last) = self.last.take() else {
+            self.last = Some(LastScan {
+                time,
+                target: VoxelIndex::new(scan, self.icp.max_distance),
+            });
+            return FusionUpdate {
+                pose: self.pose,
+                delta: Pose::IDENTITY,
+                dt: 0.0,
+                source: PoseSource::Hold,
+                icp: None,
+            };
+        };
+        let dt = time - last.time;
+
+        // 초기값: 휠 > 등속 > 정지
+        let wheel = self.wheel_delta(last.time, time);
+        let (guess, guess_source) = match (wheel, self.constant_velocity(dt)) {
+            (Some(delta), _) => (delta, PoseSource::Wheel),
+            (None, Some(delta)) => (delta, PoseSource::ConstantVelocity),
+            (None, None) => (Pose::IDENTITY, PoseSource::Hold),
+        };
+
+        // 새 스캔 -> 직전 스캔 정합 결과가 곧 직전 대비 움직임
+        let icp = registration::icp(&scan, &last.target, guess, &self.icp);
+        let accepted = icp.filter(|result| {
+            let diff = guess.inverse().compose(&result.pose).translation_norm();
+            result.fitness >= self.config.min_fitness
+                && result.rmse <= self.config.max_rmse
+                && (wheel.is_none() || diff <= self.config.max_correction)
+        });
+        let (delta, source) = match accepted {
+            Some(result) => (result.pose, PoseSource::Lidar),
+            None => (guess, guess_source),
+        };
+
+        self.pose = self.pose.compose(&delta);
+        if source != PoseSource::Hold {
+            self.velocity = Some((delta, dt));
+        }
+        self.last = Some(LastScan {
+            time,
+            target: VoxelIndex::new(scan, self.icp.max_distance),
+        });
+        FusionUpdate {
+            pose: self.pose,
+            delta,
+            dt,
+            source,
+            icp,
+        }
+    }
+}
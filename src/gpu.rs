@@ -0,0 +1,515 @@
+use crate::bev::{BevGrid, Cell};
+use crate::compute::{ComputeBackend, Cpu};
+use crate::point::LidarPoint;
+use crate::registration::VoxelIndex;
+use anyhow::{anyhow, bail, Result};
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+// 셰이더 공통: 워크그룹 크기와 64비트 고정소수점 누적 (u32 두 개, 올림수 처리)
+// 값은 (v + FIXED_OFFSET) * FIXED_SCALE 로 부호 없는 정수가 되도록 옮겨서 더함
+const COMMON: &str = r#"
+const WORKGROUP: u32 = 256u;
+const FIXED_OFFSET: f32 = 1024.0;
+const FIXED_SCALE: f32 = 65536.0;
+
+fn fixed(v: f32) -> u32 {
+    return u32(clamp(v + FIXED_OFFSET, 0.0, 65000.0) * FIXED_SCALE);
+}
+
+// 부호 있는 f32 를 크기 순서가 같은 u32 로 (atomicMax 로 최대값 비교)
+fn ordered(v: f32) -> u32 {
+    let b = bitcast<u32>(v);
+    if ((b & 0x80000000u) != 0u) {
+        return ~b;
+    }
+    return b | 0x80000000u;
+}
+"#;
+
+// 셀 통계 (u32 11 개): count, ~min_z, max_z, ~min_i, max_i, ~first, last, z 합 lo/hi, i 합 lo/hi
+// 최소값은 비트 반전해서 atomicMax 로 구하므로 0 으로 초기화된 버퍼를 그대로 사용
+const BEV_SHADER: &str = r#"
+struct Params {
+    origin_x: i32,
+    origin_y: i32,
+    width: u32,
+    height: u32,
+    cell_size: f32,
+    count: u32,
+    pad0: u32,
+    pad1: u32,
+}
+
+const STRIDE: u32 = 11u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> points: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> cells: array<atomic<u32>>;
+
+fn add64(i: u32, v: u32) {
+    let old = atomicAdd(&cells[i], v);
+    if (old + v < old) {
+        atomicAdd(&cells[i + 1u], 1u);
+    }
+}
+
+@compute @workgroup_size(256)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.count) {
+        return;
+    }
+    let p = points[i];
+    let cx = i32(floor(p.x / params.cell_size)) - params.origin_x;
+    let cy = i32(floor(p.y / params.cell_size)) - params.origin_y;
+    if (cx < 0 || cy < 0 || u32(cx) >= params.width || u32(cy) >= params.height) {
+        return;
+    }
+    let base = (u32(cy) * params.width + u32(cx)) * STRIDE;
+    atomicAdd(&cells[base], 1u);
+    atomicMax(&cells[base + 1u], ~ordered(p.z));
+    atomicMax(&cells[base + 2u], ordered(p.z));
+    atomicMax(&cells[base + 3u], ~ordered(p.w));
+    atomicMax(&cells[base + 4u], ordered(p.w));
+    atomicMax(&cells[base + 5u], ~i);
+    atomicMax(&cells[base + 6u], i);
+    add64(base + 7u, fixed(p.z));
+    add64(base + 9u, fixed(p.w));
+}
+"#;
+
+// 선형 탐사 해시 (슬롯당 u32 8 개): 대표 포인트 인덱스+1, count, 복셀 내 x/y/z 오프셋 합 lo/hi
+const VOXEL_SHADER: &str = r#"
+struct Params {
+    size: f32,
+    count: u32,
+    mask: u32,
+    pad: u32,
+}
+
+const STRIDE: u32 = 8u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> points: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> table: array<atomic<u32>>;
+
+fn add64(i: u32, v: u32) {
+    let old = atomicAdd(&table[i], v);
+    if (old + v < old) {
+        atomicAdd(&table[i + 1u], 1u);
+    }
+}
+
+fn voxel(p: vec4<f32>) -> vec3<i32> {
+    return vec3<i32>(floor(p.xyz / params.size));
+}
+
+fn hash(k: vec3<i32>) -> u32 {
+    return ((u32(k.x) * 73856093u) ^ (u32(k.y) * 19349663u) ^ (u32(k.z) * 83492791u)) & params.mask;
+}
+
+@compute @workgroup_size(256)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.count) {
+        return;
+    }
+    let p = points[i];
+    let k = voxel(p);
+    var slot = hash(k);
+    for (var probe = 0u; probe <= params.mask; probe = probe + 1u) {
+        let base = slot * STRIDE;
+        var rep = atomicLoad(&table[base]);
+        if (rep == 0u) {
+            let r = atomicCompareExchangeWeak(&table[base], 0u, i + 1u);
+            if (r.exchanged) {
+                rep = i + 1u;
+            } else {
+                rep = r.old_value;
+            }
+        }
+        // 약한 CAS 의 가짜 실패면 rep 이 0 이므로 같은 슬롯을 다시 봄
+        if (rep == 0u) {
+            continue;
+        }
+        if (all(voxel(points[rep - 1u]) == k)) {
+            let offset = p.xyz / params.size - vec3<f32>(k);
+            atomicAdd(&table[base + 1u], 1u);
+            add64(base + 2u, fixed(offset.x));
+            add64(base + 4u, fixed(offset.y));
+            add64(base + 6u, fixed(offset.z));
+            return;
+        }
+        slot = (slot + 1u) & params.mask;
+    }
+}
+"#;
+
+// query 하나당 스레드 하나가 target 전체를 훑음, 결과는 (인덱스+1, 거리 제곱 비트)
+const NEAREST_SHADER: &str = r#"
+struct Params {
+    queries: u32,
+    targets: u32,
+    max_d2: f32,
+    pad: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> queries: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read> targets: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read_write> result: array<vec2<u32>>;
+
+@compute @workgroup_size(256)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.queries) {
+        return;
+    }
+    let q = queries[i].xyz;
+    var best = params.max_d2;
+    var best_index = 0u;
+    for (var j = 0u; j < params.targets; j = j + 1u) {
+        let d = targets[j].xyz - q;
+        let d2 = dot(d, d);
+        if (d2 < best || (best_index == 0u && d2 <= best)) {
+            best = d2;
+            best_index = j + 1u;
+        }
+    }
+    result[i] = vec2<u32>(best_index, bitcast<u32>(best));
+}
+"#;
+
+const WORKGROUP: u32 = 256;
+const FIXED_OFFSET: f64 = 1024.0;
+const FIXED_SCALE: f64 = 65536.0;
+// 밀집 BEV 격자가 이보다 크면 (범위 제한 없음 + 작은 셀) CPU 경로 사용
+const MAX_BEV_CELLS: usize = 1 << 21;
+
+fn to_vec4(points: impl Iterator<Item = [f32; 4]>) -> Vec<[f32; 4]> {
+    points.collect()
+}
+
+fn ordered_to_f32(v: u32) -> f32 {
+    if v & 0x8000_0000 != 0 {
+        f32::from_bits(v & 0x7fff_ffff)
+    } else {
+        f32::from_bits(!v)
+    }
+}
+
+fn fixed_sum(lo: u32, hi: u32, count: u32) -> f64 {
+    ((hi as u64) << 32 | lo as u64) as f64 / FIXED_SCALE - FIXED_OFFSET * count as f64
+}
+
+pub struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    adapter_name: String,
+    bev: wgpu::ComputePipeline,
+    voxel: wgpu::ComputePipeline,
+    nearest: wgpu::ComputePipeline,
+}
+
+impl Gpu {
+    pub fn new() -> Result<Self> {
+        let instance =
+            wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle_from_env());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+                label: Some("rust_lidar"),
+                required_limits: adapter.limits(),
+                ..Default::default()
+            }))?;
+        let pipeline = |name: &str, source: &str| {
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some(name),
+                source: wgpu::ShaderSource::Wgsl(format!("{}{}", COMMON, source).into()),
+            });
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(name),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        Ok(Gpu {
+            adapter_name: adapter.get_info().name,
+            bev: pipeline("bev", BEV_SHADER),
+            voxel: pipeline("voxel", VOXEL_SHADER),
+            nearest: pipeline("nearest", NEAREST_SHADER),
+            device,
+            queue,
+        })
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    fn input(&self, contents: &[u8], usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents,
+                usage,
+            })
+    }
+
+    // 0 으로 초기화된 출력 버퍼
+    fn output(&self, size: usize) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: size.max(4) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        })
+    }
+
+    // 바인딩 0..n 에 버퍼를 묶어 invocations 개 스레드로 실행하고 마지막 버퍼를 읽어옴
+    fn run(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        buffers: &[&wgpu::Buffer],
+        invocations: usize,
+    ) -> Result<Vec<u32>> {
+        let groups = invocations.div_ceil(WORKGROUP as usize);
+        let max_groups = self.device.limits().max_compute_workgroups_per_dimension as usize;
+        if groups > max_groups {
+            bail!("GPU 작업 크기 초과 ({} 워크그룹)", groups);
+        }
+        let layout = pipeline.get_bind_group_layout(0);
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .enumerate()
+            .map(|(i, buffer)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &entries,
+        });
+
+        let output = buffers
+            .last()
+            .ok_or_else(|| anyhow!("출력 버퍼가 없습니다"))?;
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups.max(1) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(output, 0, &readback, 0, output.size());
+        self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        rx.recv()??;
+        let data = bytemuck::cast_slice::<u8, u32>(&slice.get_mapped_range()?).to_vec();
+        readback.unmap();
+        Ok(data)
+    }
+
+    fn try_voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Result<Vec<[f32; 3]>> {
+        let capacity = (points.len() * 2).next_power_of_two().max(2);
+        let params = [
+            size.to_bits(),
+            points.len() as u32,
+            (capacity - 1) as u32,
+            0,
+        ];
+        let params = self.input(bytemuck::cast_slice(&params), wgpu::BufferUsages::UNIFORM);
+        let input = to_vec4(points.iter().map(|p| [p[0], p[1], p[2], 0.0]));
+        let input = self.input(bytemuck::cast_slice(&input), wgpu::BufferUsages::STORAGE);
+        let table = self.output(capacity * 8 * 4);
+        let table = self.run(&self.voxel, &[&params, &input, &table], points.len())?;
+
+        Ok(table
+            .chunks_exact(8)
+            .filter(|slot| slot[0] != 0 && slot[1] != 0)
+            .map(|slot| {
+                let rep = points[slot[0] as usize - 1];
+                let count = slot[1];
+                std::array::from_fn(|k| {
+                    let voxel = (rep[k] / size).floor() as f64;
+                    let offset = fixed_sum(slot[2 + k * 2], slot[3 + k * 2], count) / count as f64;
+                    ((voxel + offset.clamp(0.0, 1.0)) * size as f64) as f32
+                })
+            })
+            .collect())
+    }
+
+    fn try_collect_cells(
+        &self,
+        grid: &BevGrid,
+        points: &[LidarPoint],
+    ) -> Result<Option<Vec<Cell>>> {
+        let cells: Vec<(i32, i32)> = points
+            .iter()
+            .filter_map(|p| grid.cell_of(p.x, p.y))
+            .collect();
+        let (Some(min_x), Some(max_x)) = (
+            cells.iter().map(|c| c.0).min(),
+            cells.iter().map(|c| c.0).max(),
+        ) else {
+            return Ok(Some(Vec::new()));
+        };
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        let max_y = cells.iter().map(|c| c.1).max().unwrap_or(0);
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        if width * height > MAX_BEV_CELLS {
+            return Ok(None);
+        }
+
+        let params = [
+            min_x as u32,
+            min_y as u32,
+            width as u32,
+            height as u32,
+            grid.cell_size.to_bits(),
+            points.len() as u32,
+            0,
+            0,
+        ];
+        let params = self.input(bytemuck::cast_slice(&params), wgpu::BufferUsages::UNIFORM);
+        let input = to_vec4(points.iter().map(|p| [p.x, p.y, p.z, p.intensity]));
+        let input = self.input(bytemuck::cast_slice(&input), wgpu::BufferUsages::STORAGE);
+        let stats = self.output(width * height * 11 * 4);
+        let stats = self.run(&self.bev, &[&params, &input, &stats], points.len())?;
+
+        let mut found: Vec<(u32, Cell)> = stats
+            .chunks_exact(11)
+            .enumerate()
+            .filter(|(_, s)| s[0] > 0)
+            .map(|(i, s)| {
+                let index = (min_x + (i % width) as i32, min_y + (i / width) as i32);
+                let count = s[0];
+                let cell = Cell::from_stats(
+                    index,
+                    count as usize,
+                    (
+                        [ordered_to_f32(!s[1]), ordered_to_f32(s[2])],
+                        fixed_sum(s[7], s[8], count),
+                    ),
+                    (
+                        [ordered_to_f32(!s[3]), ordered_to_f32(s[4])],
+                        fixed_sum(s[9], s[10], count),
+                    ),
+                    points[s[6] as usize],
+                );
+                (!s[5], cell)
+            })
+            .collect();
+        // CPU 경로와 같이 셀이 처음 들어온 순서
+        found.sort_by_key(|(first, _)| *first);
+        Ok(Some(found.into_iter().map(|(_, cell)| cell).collect()))
+    }
+
+    fn try_nearest(
+        &self,
+        queries: &[[f32; 3]],
+        targets: &[[f32; 3]],
+        max_distance: f32,
+    ) -> Result<Vec<Option<(usize, f32)>>> {
+        let params = [
+            queries.len() as u32,
+            targets.len() as u32,
+            (max_distance * max_distance).to_bits(),
+            0,
+        ];
+        let params = self.input(bytemuck::cast_slice(&params), wgpu::BufferUsages::UNIFORM);
+        let vec4 = |points: &[[f32; 3]]| to_vec4(points.iter().map(|p| [p[0], p[1], p[2], 0.0]));
+        let query_buffer = self.input(
+            bytemuck::cast_slice(&vec4(queries)),
+            wgpu::BufferUsages::STORAGE,
+        );
+        let target_buffer = self.input(
+            bytemuck::cast_slice(&vec4(targets)),
+            wgpu::BufferUsages::STORAGE,
+        );
+        let result = self.output(queries.len() * 8);
+        let result = self.run(
+            &self.nearest,
+            &[&params, &query_buffer, &target_buffer, &result],
+            queries.len(),
+        )?;
+        Ok(result
+            .chunks_exact(2)
+            .map(|r| (r[0] > 0).then(|| (r[0] as usize - 1, f32::from_bits(r[1]))))
+            .collect())
+    }
+}
+
+// GPU 실행이 실패하면 (장치 분실, 버퍼 한도 초과 등) 그 호출만 CPU 로 처리
+fn fallback<T>(name: &str, result: Result<T>, cpu: impl FnOnce() -> T) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("GPU {} 실패, CPU 로 처리: {}", name, e);
+        cpu()
+    })
+}
+
+impl ComputeBackend for Gpu {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn voxel_downsample(&self, points: &[[f32; 3]], size: f32) -> Vec<[f32; 3]> {
+        if size <= 0.0 || points.is_empty() {
+            return Cpu.voxel_downsample(points, size);
+        }
+        fallback("복셀화", self.try_voxel_downsample(points, size), || {
+            Cpu.voxel_downsample(points, size)
+        })
+    }
+
+    fn collect_cells(&self, grid: &BevGrid, points: &[LidarPoint]) -> Vec<Cell> {
+        if grid.cell_size <= 0.0 || points.is_empty() {
+            return Cpu.collect_cells(grid, points);
+        }
+        match self.try_collect_cells(grid, points) {
+            Ok(Some(cells)) => cells,
+            Ok(None) => Cpu.collect_cells(grid, points),
+            Err(e) => fallback("BEV 셀 집계", Err(e), || Cpu.collect_cells(grid, points)),
+        }
+    }
+
+    fn nearest(
+        &self,
+        queries: &[[f32; 3]],
+        target: &VoxelIndex,
+        max_distance: f32,
+    ) -> Vec<Option<(usize, f32)>> {
+        if queries.is_empty() || target.is_empty() {
+            return vec![None; queries.len()];
+        }
+        fallback(
+            "최근접 검색",
+            self.try_nearest(queries, target.points(), max_distance),
+            || Cpu.nearest(queries, target, max_distance),
+        )
+    }
+}
@@ -0,0 +1,172 @@
+use crate::bev::BevGrid;
+use anyhow::{bail, Result};
+#[cfg(feature = "grid_map")]
+use geometry_msgs::msg::{Point, Pose, Quaternion};
+#[cfg(feature = "grid_map")]
+use grid_map_msgs::msg::{GridMap, GridMapInfo};
+#[cfg(feature = "grid_map")]
+use rclrs::Publisher;
+#[cfg(feature = "ros")]
+use rclrs::{Node, QoSProfile};
+#[cfg(feature = "grid_map")]
+use std::sync::Arc;
+#[cfg(feature = "ros")]
+use std_msgs::msg::Header;
+#[cfg(feature = "grid_map")]
+use std_msgs::msg::{Float32MultiArray, MultiArrayDimension, MultiArrayLayout};
+
+// grid_map_msgs/GridMap 레이어 모음 (grid_map 필터/시각화에서 바로 사용)
+// grid_map 규약: 인덱스 (0, 0) 은 x, y 가 가장 큰 모서리, 행은 -x, 열은 -y 방향
+// 레이어 값은 열 우선 (Eigen 기본) 으로 저장
+#[derive(Debug, Clone)]
+pub struct GridMapLayers {
+    resolution: f32,
+    // x 셀 수, y 셀 수, 첫 셀 인덱스 (BevGrid::dimensions)
+    rows: usize,
+    cols: usize,
+    first: (i32, i32),
+    layers: Vec<(String, Vec<f32>)>,
+}
+
+impl GridMapLayers {
+    // bev_cell_size, bev_extent_x, bev_extent_y 가 모두 양수여야 함
+    pub fn new(grid: &BevGrid) -> Result<Self> {
+        let Some((rows, cols, first)) = grid.dimensions().filter(|_| grid.cell_size > 0.0) else {
+            bail!("GridMap 출력은 bev_cell_size, bev_extent_x, bev_extent_y 가 필요합니다");
+        };
+        Ok(GridMapLayers {
+            resolution: grid.cell_size,
+            rows,
+            cols,
+            first,
+            layers: Vec::new(),
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|(name, _)| name.as_str())
+    }
+
+    // (셀 인덱스, 값) 으로 레이어 추가, 값이 없는 셀은 empty (grid_map 은 보통 NaN)
+    pub fn add_layer(
+        &mut self,
+        name: &str,
+        empty: f32,
+        cells: impl IntoIterator<Item = ((i32, i32), f32)>,
+    ) {
+        let mut data = vec![empty; self.rows * self.cols];
+        for ((ix, iy), value) in cells {
+            let (lx, ly) = (ix - self.first.0, iy - self.first.1);
+            if lx < 0 || ly < 0 || lx as usize >= self.rows || ly as usize >= self.cols {
+                continue;
+            }
+            let row = self.rows - 1 - lx as usize;
+            let col = self.cols - 1 - ly as usize;
+            data[col * self.rows + row] = value;
+        }
+        self.layers.push((name.to_string(), data));
+    }
+
+    // 지도 중심 (센서 원점 기준 x, y)
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.first.0 as f32 + self.rows as f32 / 2.0) * self.resolution,
+            (self.first.1 as f32 + self.cols as f32 / 2.0) * self.resolution,
+        ]
+    }
+
+    // basic_layers: 이 레이어 값이 NaN 인 셀을 grid_map 이 빈 셀로 취급
+    #[cfg(feature = "grid_map")]
+    pub fn to_msg(&self, header: Header, basic_layers: &[&str]) -> GridMap {
+        let [x, y] = self.center();
+        let layout = MultiArrayLayout {
+            dim: vec![
+                MultiArrayDimension {
+                    label: "column_index".to_string(),
+                    size: self.cols as u32,
+                    stride: (self.rows * self.cols) as u32,
+                },
+                MultiArrayDimension {
+                    label: "row_index".to_string(),
+                    size: self.rows as u32,
+                    stride: self.rows as u32,
+                },
+            ],
+            data_offset: 0,
+        };
+        GridMap {
+            header,
+            info: GridMapInfo {
+                resolution: self.resolution as f64,
+                length_x: self.rows as f64 * self.resolution as f64,
+                length_y: self.cols as f64 * self.resolution as f64,
+                pose: Pose {
+                    position: Point {
+                        x: x as f64,
+                        y: y as f64,
+                        z: 0.0,
+                    },
+                    orientation: Quaternion {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                        w: 1.0,
+                    },
+                },
+            },
+            layers: self.names().map(str::to_string).collect(),
+            basic_layers: basic_layers
+                .iter()
+                .filter(|name| self.names().any(|n| n == **name))
+                .map(|name| name.to_string())
+                .collect(),
+            data: self
+                .layers
+                .iter()
+                .map(|(_, data)| Float32MultiArray {
+                    layout: layout.clone(),
+                    data: data.clone(),
+                })
+                .collect(),
+            outer_start_index: 0,
+            inner_start_index: 0,
+        }
+    }
+}
+
+// GridMap 발행자, grid_map 기능 없이 빌드했으면 만들 때 오류
+#[cfg(feature = "ros")]
+pub struct GridMapOutput {
+    #[cfg(feature = "grid_map")]
+    publisher: Arc<Publisher<GridMap>>,
+}
+
+#[cfg(feature = "ros")]
+impl GridMapOutput {
+    #[cfg(feature = "grid_map")]
+    pub fn new(node: &Node, topic: &str, qos: QoSProfile) -> Result<Self> {
+        Ok(GridMapOutput {
+            publisher: node.create_publisher::<GridMap>(topic, qos)?,
+        })
+    }
+
+    #[cfg(not(feature = "grid_map"))]
+    pub fn new(_node: &Node, _topic: &str, _qos: QoSProfile) -> Result<Self> {
+        bail!("GridMap 출력은 grid_map 기능으로 빌드해야 합니다 (cargo build --features grid_map)")
+    }
+
+    #[cfg(feature = "grid_map")]
+    pub fn publish(&self, layers: &GridMapLayers, header: Header, basic: &[&str]) -> Result<()> {
+        self.publisher.publish(layers.to_msg(header, basic))?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "grid_map"))]
+    pub fn publish(&self, _layers: &GridMapLayers, _header: Header, _basic: &[&str]) -> Result<()> {
+        Ok(())
+    }
+}
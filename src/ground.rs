@@ -0,0 +1,325 @@
+use crate::cloud::{Point, PointCloud};
+
+// 평면 n·p + d = 0, normal 은 단위 벡터이고 센서 원점이 평면 위쪽(d > 0)이 되도록 방향을 맞춤
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: [f32; 3],
+    pub d: f32,
+}
+
+impl Plane {
+    fn from_points(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> Option<Self> {
+        let u = sub(b, a);
+        let v = sub(c, a);
+        let n = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        Plane::from_normal(n, a)
+    }
+
+    // normal 방향과 점 하나로 평면 생성 (퇴화된 경우 None)
+    fn from_normal(n: [f32; 3], point: [f32; 3]) -> Option<Self> {
+        let len = dot(n, n).sqrt();
+        if len < 1e-6 {
+            return None;
+        }
+        let mut normal = n.map(|v| v / len);
+        let mut d = -dot(normal, point);
+        if d < 0.0 {
+            normal = normal.map(|v| -v);
+            d = -d;
+        }
+        Some(Plane { normal, d })
+    }
+
+    // 부호 있는 거리 (평면 위쪽이 양수)
+    pub fn distance(&self, p: [f32; 3]) -> f32 {
+        dot(self.normal, p) + self.d
+    }
+
+    // 센서 원점에서 평면까지 높이
+    pub fn height(&self) -> f32 {
+        self.d
+    }
+
+    // 평면 normal 이 위쪽이 되는 자세의 (roll, pitch), IMU 자세의 roll/pitch 와 같은 정의
+    pub fn roll_pitch(&self) -> (f64, f64) {
+        level_angles(self.normal.map(|v| v as f64))
+    }
+
+    // 센서 z 축과 normal 사이 각도 (라디안, 뒤집힌 장착도 0 에 가깝게)
+    pub fn tilt(&self) -> f32 {
+        self.normal[2].abs().min(1.0).acos()
+    }
+}
+
+// Rx(roll) 다음 Ry(pitch) 로 위쪽 방향 normal 이 +z 가 되는 각도 (Transform::from_rpy 의 3행 = normal)
+pub fn level_angles([nx, ny, nz]: [f64; 3]) -> (f64, f64) {
+    (ny.atan2(nz), (-nx).clamp(-1.0, 1.0).asin())
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GroundFitConfig {
+    // RANSAC 반복 횟수와 인라이어 거리 기준 (m)
+    pub iterations: usize,
+    pub threshold: f32,
+    // 센서 z 축 대비 허용 기울기 (라디안), 벽 같은 수직 평면을 배제
+    pub max_tilt: f32,
+    // 이 거리보다 먼 포인트는 사용하지 않음 (m, 0 이면 제한 없음)
+    pub max_range: f32,
+    // 인라이어가 이보다 적으면 지면으로 인정하지 않음
+    pub min_inliers: usize,
+}
+
+impl Default for GroundFitConfig {
+    fn default() -> Self {
+        GroundFitConfig {
+            iterations: 100,
+            threshold: 0.05,
+            max_tilt: 30f32.to_radians(),
+            max_range: 30.0,
+            min_inliers: 100,
+        }
+    }
+}
+
+// 지면 평면 추정 결과
+#[derive(Debug, Clone, Copy)]
+pub struct GroundFit {
+    pub plane: Plane,
+    pub inliers: usize,
+    // 사용한 포인트 중 인라이어 비율
+    pub inlier_ratio: f32,
+    // 인라이어의 평면 거리 RMS (m)
+    pub rmse: f32,
+    // normal 방향(roll/pitch) 표준편차 추정 (라디안), 잔차가 작고 넓게 퍼질수록 작음
+    pub angle_std: f32,
+}
+
+impl GroundFit {
+    fn new(plane: Plane, inliers: &[[f32; 3]], total: usize) -> Self {
+        let n = inliers.len().max(1) as f32;
+        let mut mean = [0f32; 3];
+        for p in inliers {
+            for i in 0..3 {
+                mean[i] += p[i] / n;
+            }
+        }
+        let mut residual = 0.0;
+        let mut spread = 0.0;
+        for &p in inliers {
+            let d = plane.distance(p);
+            let q = sub(p, mean);
+            residual += d * d;
+            // 평면 방향 퍼짐 (중심에서의 거리 제곱 - 평면 수직 성분)
+            spread += dot(q, q) - dot(plane.normal, q).powi(2);
+        }
+        let rmse = (residual / n).sqrt();
+        let spread = (spread / n).sqrt();
+        GroundFit {
+            plane,
+            inliers: inliers.len(),
+            inlier_ratio: inliers.len() as f32 / total.max(1) as f32,
+            rmse,
+            angle_std: if spread > 1e-6 {
+                rmse / (spread * n.sqrt())
+            } else {
+                std::f32::consts::PI
+            },
+        }
+    }
+}
+
+// 재현 가능한 결과를 위해 고정 시드 xorshift 로 샘플링
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+// RANSAC 으로 가장 큰 (기울기 제한 내) 평면을 찾고 인라이어로 최소제곱 보정
+pub fn fit_ground<P: Point>(cloud: &PointCloud<P>, config: &GroundFitConfig) -> Option<GroundFit> {
+    let max_range_sq = config.max_range * config.max_range;
+    let points: Vec<[f32; 3]> = cloud
+        .iter()
+        .map(|p| p.xyz())
+        .filter(|p| p.iter().all(|v| v.is_finite()))
+        .filter(|&p| config.max_range <= 0.0 || dot(p, p) <= max_range_sq)
+        .collect();
+    let (plane, inliers) = fit_plane(&points, config.iterations, config.threshold, |plane| {
+        plane.tilt() <= config.max_tilt
+    })?;
+    if inliers.len() < config.min_inliers.max(3) {
+        return None;
+    }
+    let inlier_points: Vec<[f32; 3]> = inliers.iter().map(|&i| points[i]).collect();
+    Some(GroundFit::new(plane, &inlier_points, points.len()))
+}
+
+// 방향 조건(accept)을 만족하는 가장 큰 평면과 그 인라이어 인덱스 (지면 외 벽/유리면 검출에도 사용)
+pub fn fit_plane(
+    points: &[[f32; 3]],
+    iterations: usize,
+    threshold: f32,
+    accept: impl Fn(&Plane) -> bool,
+) -> Option<(Plane, Vec<usize>)> {
+    if points.len() < 3 {
+        return None;
+    }
+
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let mut best: Option<(Plane, usize)> = None;
+    for _ in 0..iterations {
+        let sample = [
+            points[rng.next(points.len())],
+            points[rng.next(points.len())],
+            points[rng.next(points.len())],
+        ];
+        let Some(plane) = Plane::from_points(sample[0], sample[1], sample[2]) else {
+            continue;
+        };
+        if !accept(&plane) {
+            continue;
+        }
+        let inliers = points
+            .iter()
+            .filter(|&&p| plane.distance(p).abs() <= threshold)
+            .count();
+        if best.is_none_or(|(_, b)| inliers > b) {
+            best = Some((plane, inliers));
+        }
+    }
+
+    let (plane, _) = best?;
+    let inliers: Vec<usize> = (0..points.len())
+        .filter(|&i| plane.distance(points[i]).abs() <= threshold)
+        .collect();
+    let inlier_points: Vec<[f32; 3]> = inliers.iter().map(|&i| points[i]).collect();
+    let plane = refine(&inlier_points, plane)
+        .filter(|refined| accept(refined))
+        .unwrap_or(plane);
+    Some((plane, inliers))
+}
+
+// 인라이어 공분산의 최소 고유벡터를 normal 로 사용 (초기 normal 에서 역반복)
+fn refine(points: &[[f32; 3]], initial: Plane) -> Option<Plane> {
+    let n = points.len() as f64;
+    let mut mean = [0f64; 3];
+    for p in points {
+        for i in 0..3 {
+            mean[i] += p[i] as f64 / n;
+        }
+    }
+    let mut cov = [[0f64; 3]; 3];
+    for p in points {
+        let q = [
+            p[0] as f64 - mean[0],
+            p[1] as f64 - mean[1],
+            p[2] as f64 - mean[2],
+        ];
+        for i in 0..3 {
+            for j in 0..3 {
+                cov[i][j] += q[i] * q[j];
+            }
+        }
+    }
+
+    // cov^-1 을 반복 적용하면 가장 작은 고유값의 고유벡터로 수렴
+    let inv = invert(cov)?;
+    let mut normal = initial.normal.map(|v| v as f64);
+    for _ in 0..10 {
+        let next = [0, 1, 2].map(|i| (0..3).map(|j| inv[i][j] * normal[j]).sum::<f64>());
+        let len = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if len < 1e-12 {
+            return None;
+        }
+        normal = next.map(|v| v / len);
+    }
+
+    Plane::from_normal(normal.map(|v| v as f32), mean.map(|v| v as f32))
+}
+
+fn invert(m: [[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let c =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let cof = [
+        [c(1, 2, 1, 2), -c(1, 2, 0, 2), c(1, 2, 0, 1)],
+        [-c(0, 2, 1, 2), c(0, 2, 0, 2), -c(0, 2, 0, 1)],
+        [c(0, 1, 1, 2), -c(0, 1, 0, 2), c(0, 1, 0, 1)],
+    ];
+    let det = m[0][0] * cof[0][0] + m[0][1] * cof[0][1] + m[0][2] * cof[0][2];
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    // 대칭 행렬이므로 여인수 행렬의 전치 = 여인수 행렬
+    Some(cof.map(|row| row.map(|v| v / det)))
+}
+
+// 프레임마다 지면을 다시 추정하되 이전 추정과 섞어서 흔들림을 줄임
+// (추정 실패 시 마지막 평면 유지)
+pub struct GroundEstimator {
+    config: GroundFitConfig,
+    // 새 추정의 반영 비율 (1.0 이면 이전 값 무시)
+    alpha: f32,
+    plane: Option<Plane>,
+    // 이번 프레임의 추정 (실패했으면 None)
+    last_fit: Option<GroundFit>,
+}
+
+impl GroundEstimator {
+    pub fn new(config: GroundFitConfig, alpha: f32) -> Self {
+        GroundEstimator {
+            config,
+            alpha: alpha.clamp(0.0, 1.0),
+            plane: None,
+            last_fit: None,
+        }
+    }
+
+    pub fn update<P: Point>(&mut self, cloud: &PointCloud<P>) -> Option<Plane> {
+        self.last_fit = fit_ground(cloud, &self.config);
+        if let Some(fit) = self.last_fit {
+            self.plane = match self.plane {
+                Some(prev) => {
+                    let a = self.alpha;
+                    let n = [0, 1, 2].map(|i| prev.normal[i] * (1.0 - a) + fit.plane.normal[i] * a);
+                    let len = dot(n, n).sqrt();
+                    Some(Plane {
+                        normal: n.map(|v| v / len),
+                        d: prev.d * (1.0 - a) + fit.plane.d * a,
+                    })
+                }
+                None => Some(fit.plane),
+            };
+        }
+        self.plane
+    }
+
+    pub fn plane(&self) -> Option<Plane> {
+        self.plane
+    }
+
+    // 저장한 지면으로 시작 (다음 추정부터 alpha 로 섞음)
+    pub fn restore(&mut self, plane: Plane) {
+        self.plane = Some(plane);
+    }
+
+    pub fn last_fit(&self) -> Option<GroundFit> {
+        self.last_fit
+    }
+}
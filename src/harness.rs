@@ -0,0 +1,234 @@
+use crate::layout;
+use crate::point::LidarPoint;
+use crate::shutdown::Shutdown;
+use crate::stamp;
+use crate::synthetic::{self, SyntheticConfig};
+use anyhow::{bail, Result};
+use rclrs::{Context, Node, Publisher, RclReturnCode, RclrsError, Subscription};
+use sensor_msgs::msg::PointCloud2;
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use std_msgs::msg::Header;
+
+// 같은 프로세스의 시험끼리 토픽이 섞이지 않도록 그래프마다 다른 네임스페이스
+static GRAPH_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// 통합 시험용 그래프 (시험 하나에 Context 하나): 합성 입력 발행자와 출력 수집 구독자는 시험 노드에,
+// 시험 대상 노드 (bev_node::run 등) 는 같은 Context 에 자기 파라미터 인자로 만들어 스레드에서 실행
+// 네임스페이스는 Context 인자라 시험 대상 노드가 만드는 추가 노드 (executor=threaded) 에도 적용
+// 파라미터 기록 (params::take_recorded) 은 노드 전체 이름별이라 그래프끼리 섞이지 않음
+// 그래프가 없어지면 종료를 요청하고 노드 스레드가 끝날 때까지 기다림
+pub struct TestGraph {
+    context: Arc<Context>,
+    node: Arc<Node>,
+    namespace: String,
+    shutdown: Arc<Shutdown>,
+    nodes: Vec<(String, JoinHandle<Result<()>>)>,
+}
+
+impl TestGraph {
+    pub fn new() -> Result<Self> {
+        let namespace = format!(
+            "/rust_lidar_test_{}_{}",
+            std::process::id(),
+            GRAPH_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let context = Context::new([
+            "test".to_string(),
+            "--ros-args".to_string(),
+            "-r".to_string(),
+            format!("__ns:={}", namespace),
+        ])?;
+        let node = rclrs::create_node(&context, "test_graph")?;
+        Ok(TestGraph {
+            context: Arc::new(context),
+            node,
+            namespace,
+            shutdown: Shutdown::new(),
+            nodes: Vec::new(),
+        })
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    // 시험 대상 노드를 스레드에서 실행 (params: 이름, 값 쌍, 값은 ROS 파라미터 문법 그대로)
+    // run 은 shutdown 이 요청될 때까지 spin 하고 정리 후 돌아와야 함
+    pub fn launch(
+        &mut self,
+        name: &str,
+        params: &[(&str, &str)],
+        run: impl FnOnce(&Context, Arc<Node>, Arc<Shutdown>) -> Result<()> + Send + 'static,
+    ) -> Result<()> {
+        // 설정 스냅샷은 기본으로 켜져 있으므로 작업 디렉터리 대신 임시 디렉터리에 (params 로 덮어쓸 수 있음)
+        let snapshot_dir = std::env::temp_dir().join(self.namespace.trim_start_matches('/'));
+        let mut args = vec![
+            "--ros-args".to_string(),
+            "-p".to_string(),
+            format!("config_snapshot_dir:={}", snapshot_dir.display()),
+        ];
+        for (param, value) in params {
+            args.push("-p".to_string());
+            args.push(format!("{}:={}", param, value));
+        }
+        let node = Node::builder(&self.context, name).arguments(args).build()?;
+        let context = Arc::clone(&self.context);
+        let shutdown = Arc::clone(&self.shutdown);
+        let handle = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || run(&context, node, shutdown))?;
+        self.nodes.push((name.to_string(), handle));
+        Ok(())
+    }
+
+    pub fn publisher(&self, topic: &str) -> Result<Arc<Publisher<PointCloud2>>> {
+        Ok(self
+            .node
+            .create_publisher::<PointCloud2>(topic, rclrs::QOS_PROFILE_DEFAULT)?)
+    }
+
+    pub fn capture(&self, topic: &str) -> Result<Capture> {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let callback_messages = Arc::clone(&messages);
+        let subscription = self.node.create_subscription::<PointCloud2, _>(
+            topic,
+            rclrs::QOS_PROFILE_DEFAULT,
+            move |msg: PointCloud2| callback_messages.lock().unwrap().push(msg),
+        )?;
+        Ok(Capture {
+            messages,
+            _subscription: subscription,
+        })
+    }
+
+    // 시험 대상 노드가 subscribes 를 구독하고 publishes 를 발행할 때까지 대기 (준비 확인)
+    pub fn wait_ready(
+        &mut self,
+        timeout: Duration,
+        subscribes: &[&str],
+        publishes: &[&str],
+    ) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            self.check_nodes()?;
+            let mut ready = true;
+            for topic in subscribes {
+                ready &= self.node.count_subscriptions(topic)? > 0;
+            }
+            for topic in publishes {
+                ready &= self.node.count_publishers(topic)? > 0;
+            }
+            if ready {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                bail!(
+                    "{} 초 안에 시험 대상 노드가 준비되지 않음 (구독 {:?}, 발행 {:?})",
+                    timeout.as_secs_f64(),
+                    subscribes,
+                    publishes
+                );
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    // done 이 true 가 되거나 timeout 이 지날 때까지 콜백 처리, 사이마다 tick 호출 (입력 발행 등)
+    // 시험 대상 노드가 먼저 끝나면 그 오류 (패닉이면 메시지) 로 바로 실패
+    pub fn spin_until(
+        &mut self,
+        timeout: Duration,
+        mut tick: impl FnMut() -> Result<()>,
+        mut done: impl FnMut() -> bool,
+    ) -> Result<bool> {
+        let start = Instant::now();
+        while start.elapsed() < timeout {
+            if done() {
+                return Ok(true);
+            }
+            self.check_nodes()?;
+            tick()?;
+            match rclrs::spin_once(Arc::clone(&self.node), Some(Duration::from_millis(50))) {
+                Ok(())
+                | Err(RclrsError::RclError {
+                    code: RclReturnCode::Timeout,
+                    ..
+                }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(done())
+    }
+
+    // 종료 요청 전에 끝난 노드 스레드가 있으면 오류
+    fn check_nodes(&mut self) -> Result<()> {
+        let Some(i) = self.nodes.iter().position(|(_, h)| h.is_finished()) else {
+            return Ok(());
+        };
+        let (name, handle) = self.nodes.remove(i);
+        match handle.join() {
+            Ok(Ok(())) => bail!("시험 대상 노드 {} 가 먼저 종료됨", name),
+            Ok(Err(e)) => Err(e.context(format!("시험 대상 노드 {} 오류", name))),
+            Err(panic) => bail!("시험 대상 노드 {} 패닉: {}", name, panic_message(&*panic)),
+        }
+    }
+}
+
+impl Drop for TestGraph {
+    fn drop(&mut self) {
+        self.shutdown.request();
+        for (name, handle) in self.nodes.drain(..) {
+            match handle.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("시험 대상 노드 {} 종료 오류: {}", name, e),
+                Err(panic) => eprintln!("시험 대상 노드 {} 패닉: {}", name, panic_message(&*panic)),
+            }
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "알 수 없는 패닉".to_string())
+}
+
+// 토픽에서 받은 메시지 모음
+pub struct Capture {
+    messages: Arc<Mutex<Vec<PointCloud2>>>,
+    _subscription: Arc<Subscription<PointCloud2>>,
+}
+
+impl Capture {
+    pub fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn take(&self) -> Vec<PointCloud2> {
+        std::mem::take(&mut *self.messages.lock().unwrap())
+    }
+}
+
+// 합성 Livox 프레임 (livox_26, 현재 시각)
+pub fn synthetic_frame(config: &SyntheticConfig, frame_id: &str) -> (PointCloud2, Vec<LidarPoint>) {
+    let stamp = stamp::now();
+    let frame = synthetic::generate(config, stamp::to_secs(&stamp) * 1e9);
+    let header = Header {
+        stamp,
+        frame_id: frame_id.to_string(),
+    };
+    (
+        layout::LIVOX_26.encode(&frame.distorted, header),
+        frame.distorted,
+    )
+}
@@ -0,0 +1,125 @@
+use crate::sidecar::json_string;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+
+// 진단 값 (주기, 지연, 품질 지표) 의 시계열 기록, 종료 시나 서비스 요청 시 파일로 저장
+// bag 없이 실행 후 분석용, 확장자가 .json 이면 JSON 배열, 그 외는 CSV
+// 키는 처음 나온 순서대로 열이 되고 그 시점에 없던 값은 빈 칸 (JSON 에서는 생략)
+#[derive(Debug, Clone)]
+pub struct StatsHistory {
+    // 기록 간격 (초, 기록 시각 기준)
+    period: f64,
+    // 이보다 오래된 기록은 버림 (0 이면 제한 없음)
+    max_rows: usize,
+    columns: Vec<String>,
+    index: HashMap<String, usize>,
+    rows: VecDeque<(f64, Vec<Option<String>>)>,
+    last: Option<f64>,
+}
+
+impl StatsHistory {
+    pub fn new(period: f64, max_rows: usize) -> Self {
+        StatsHistory {
+            period,
+            max_rows,
+            columns: Vec::new(),
+            index: HashMap::new(),
+            rows: VecDeque::new(),
+            last: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    // time: 초 (유닉스 시각), period 보다 자주 호출하면 무시
+    pub fn record(&mut self, time: f64, values: &[(&str, String)]) {
+        if self.last.is_some_and(|last| time - last < self.period) {
+            return;
+        }
+        self.last = Some(time);
+        let mut row = vec![None; self.columns.len()];
+        for (key, value) in values {
+            let column = match self.index.get(*key) {
+                Some(&column) => column,
+                None => {
+                    self.index.insert(key.to_string(), self.columns.len());
+                    self.columns.push(key.to_string());
+                    row.push(None);
+                    self.columns.len() - 1
+                }
+            };
+            row[column] = Some(value.clone());
+        }
+        self.rows.push_back((time, row));
+        if self.max_rows > 0 && self.rows.len() > self.max_rows {
+            self.rows.pop_front();
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut text = String::from("time");
+        for column in &self.columns {
+            text.push(',');
+            text.push_str(column);
+        }
+        text.push('\n');
+        for (time, row) in &self.rows {
+            text.push_str(&format!("{:.3}", time));
+            for column in 0..self.columns.len() {
+                text.push(',');
+                if let Some(Some(value)) = row.get(column) {
+                    // 쉼표나 따옴표가 있으면 따옴표로 감쌈
+                    if value.contains([',', '"', '\n']) {
+                        text.push_str(&format!("\"{}\"", value.replace('"', "\"\"")));
+                    } else {
+                        text.push_str(value);
+                    }
+                }
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    // 값은 숫자로 읽히면 숫자, 아니면 문자열
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|(time, row)| {
+                let mut fields = vec![format!("\"time\": {:.3}", time)];
+                for (column, value) in self.columns.iter().zip(row) {
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    let value = match value.parse::<f64>() {
+                        Ok(v) if v.is_finite() => v.to_string(),
+                        _ => json_string(value),
+                    };
+                    fields.push(format!("{}: {}", json_string(column), value));
+                }
+                format!("{{{}}}", fields.join(", "))
+            })
+            .collect();
+        format!("[\n  {}\n]\n", rows.join(",\n  "))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let text = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => self.to_json(),
+            _ => self.to_csv(),
+        };
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("{} 생성 실패", dir.display()))?;
+        }
+        fs::write(path, text).with_context(|| format!("{} 저장 실패", path.display()))
+    }
+}
@@ -0,0 +1,327 @@
+use crate::pose::Pose;
+#[cfg(feature = "ros")]
+use crate::stamp;
+#[cfg(feature = "ros")]
+use sensor_msgs::msg::Imu;
+
+pub const GRAVITY: f64 = 9.80665;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ImuSample {
+    pub time: f64,
+    // 각속도 (rad/s), 가속도 (m/s^2, 정지 시 위쪽으로 +g)
+    pub gyro: [f64; 3],
+    pub accel: [f64; 3],
+}
+
+impl ImuSample {
+    // Livox 드라이버는 가속도를 g 단위로 보내므로 accel_scale=9.80665 로 맞춤
+    #[cfg(feature = "ros")]
+    pub fn from_msg(msg: &Imu, accel_scale: f64) -> Self {
+        let w = &msg.angular_velocity;
+        let a = &msg.linear_acceleration;
+        ImuSample {
+            time: stamp::to_secs(&msg.header.stamp),
+            gyro: [w.x, w.y, w.z],
+            accel: [a.x * accel_scale, a.y * accel_scale, a.z * accel_scale],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ImuBias {
+    pub gyro: [f64; 3],
+    pub accel: [f64; 3],
+}
+
+impl ImuBias {
+    fn correct(&self, a: &ImuSample, b: &ImuSample) -> ([f64; 3], [f64; 3]) {
+        // 구간 양 끝 측정의 평균 (중점 적분)
+        let gyro = [0, 1, 2].map(|k| (a.gyro[k] + b.gyro[k]) / 2.0 - self.gyro[k]);
+        let accel = [0, 1, 2].map(|k| (a.accel[k] + b.accel[k]) / 2.0 - self.accel[k]);
+        (gyro, accel)
+    }
+}
+
+// 회전 벡터 -> 쿼터니언 [x, y, z, w]
+fn exp_rotation(w: [f64; 3]) -> [f64; 4] {
+    Pose::from_rotation_vector([0.0; 3], w).orientation
+}
+
+fn rotate_by(orientation: [f64; 4], delta: [f64; 4]) -> [f64; 4] {
+    let a = Pose {
+        position: [0.0; 3],
+        orientation,
+    };
+    let b = Pose {
+        position: [0.0; 3],
+        orientation: delta,
+    };
+    a.compose(&b).orientation
+}
+
+// 자세 + 속도 (월드 좌표계)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NavState {
+    pub pose: Pose,
+    pub velocity: [f64; 3],
+}
+
+// 두 시각 사이 IMU 측정을 시작 자세 기준으로 적분한 결과 (중력 제외)
+// 바이어스가 바뀌면 보관한 측정으로 다시 적분
+#[derive(Debug, Clone)]
+pub struct Preintegration {
+    pub bias: ImuBias,
+    pub dt: f64,
+    // 회전과 위치 변화량
+    pub delta: Pose,
+    pub delta_velocity: [f64; 3],
+    samples: Vec<ImuSample>,
+}
+
+impl Preintegration {
+    pub fn new(bias: ImuBias) -> Self {
+        Preintegration {
+            bias,
+            dt: 0.0,
+            delta: Pose::IDENTITY,
+            delta_velocity: [0.0; 3],
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn integrate(&mut self, sample: ImuSample) {
+        if let Some(prev) = self.samples.last().copied() {
+            self.step(&prev, &sample);
+        }
+        self.samples.push(sample);
+    }
+
+    fn step(&mut self, prev: &ImuSample, sample: &ImuSample) {
+        let dt = sample.time - prev.time;
+        if dt <= 0.0 {
+            return;
+        }
+        let (gyro, accel) = self.bias.correct(prev, sample);
+        let a = self.delta.rotate(accel);
+        for (k, a) in a.iter().enumerate() {
+            self.delta.position[k] += self.delta_velocity[k] * dt + 0.5 * a * dt * dt;
+            self.delta_velocity[k] += a * dt;
+        }
+        self.delta.orientation =
+            rotate_by(self.delta.orientation, exp_rotation(gyro.map(|v| v * dt)));
+        self.dt += dt;
+    }
+
+    // 측정은 그대로 두고 바이어스만 바꿔서 다시 적분
+    pub fn set_bias(&mut self, bias: ImuBias) {
+        let samples = std::mem::take(&mut self.samples);
+        *self = Preintegration::new(bias);
+        for sample in samples {
+            self.integrate(sample);
+        }
+    }
+
+    // 마지막 측정부터 새로 시작 (다음 구간의 첫 측정으로 이어감)
+    pub fn reset(&mut self, bias: ImuBias) {
+        let last = self.samples.last().copied();
+        *self = Preintegration::new(bias);
+        self.samples.extend(last);
+    }
+
+    // 시작 상태에 적분 결과를 적용한 끝 상태 (gravity 는 월드 좌표계, 보통 [0, 0, -g])
+    pub fn predict(&self, start: &NavState, gravity: [f64; 3]) -> NavState {
+        let dt = self.dt;
+        let dv = start.pose.rotate(self.delta_velocity);
+        let dp = start.pose.rotate(self.delta.position);
+        let mut end = NavState {
+            pose: Pose {
+                position: [0.0; 3],
+                orientation: rotate_by(start.pose.orientation, self.delta.orientation),
+            },
+            velocity: [0.0; 3],
+        };
+        for k in 0..3 {
+            end.velocity[k] = start.velocity[k] + gravity[k] * dt + dv[k];
+            end.pose.position[k] = start.pose.position[k]
+                + start.velocity[k] * dt
+                + 0.5 * gravity[k] * dt * dt
+                + dp[k];
+        }
+        end
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GravityEstimate {
+    // IMU 좌표계에서 본 중력 반대 방향 가속도 (정지 시 측정 평균)
+    pub gravity: [f64; 3],
+    pub bias: ImuBias,
+    pub roll: f64,
+    pub pitch: f64,
+}
+
+// 정지 구간 측정 평균으로 중력 방향, 자이로 바이어스, 가속도 크기 오차 추정
+#[derive(Debug, Clone)]
+pub struct StaticInitializer {
+    // 정지로 볼 최대 각속도 (rad/s) 와 |가속도| - g 허용 오차 (m/s^2)
+    pub gyro_threshold: f64,
+    pub accel_tolerance: f64,
+    pub min_samples: usize,
+    gyro_sum: [f64; 3],
+    accel_sum: [f64; 3],
+    count: usize,
+}
+
+impl StaticInitializer {
+    pub fn new(min_samples: usize) -> Self {
+        StaticInitializer {
+            gyro_threshold: 0.05,
+            accel_tolerance: 0.5,
+            min_samples: min_samples.max(1),
+            gyro_sum: [0.0; 3],
+            accel_sum: [0.0; 3],
+            count: 0,
+        }
+    }
+
+    // 움직임이 감지되면 지금까지 모은 구간을 버림
+    pub fn add(&mut self, sample: &ImuSample) -> bool {
+        let norm = |v: [f64; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        let still = norm(sample.gyro) < self.gyro_threshold
+            && (norm(sample.accel) - GRAVITY).abs() < self.accel_tolerance;
+        if !still {
+            self.count = 0;
+            self.gyro_sum = [0.0; 3];
+            self.accel_sum = [0.0; 3];
+            return false;
+        }
+        for k in 0..3 {
+            self.gyro_sum[k] += sample.gyro[k];
+            self.accel_sum[k] += sample.accel[k];
+        }
+        self.count += 1;
+        true
+    }
+
+    pub fn estimate(&self) -> Option<GravityEstimate> {
+        if self.count < self.min_samples {
+            return None;
+        }
+        let n = self.count as f64;
+        let gravity = self.accel_sum.map(|v| v / n);
+        let norm =
+            (gravity[0] * gravity[0] + gravity[1] * gravity[1] + gravity[2] * gravity[2]).sqrt();
+        // 방향은 측정을 믿고 크기 차이만 가속도 바이어스로 봄
+        let accel_bias = gravity.map(|v| v - v / norm * GRAVITY);
+        Some(GravityEstimate {
+            gravity,
+            bias: ImuBias {
+                gyro: self.gyro_sum.map(|v| v / n),
+                accel: accel_bias,
+            },
+            roll: gravity[1].atan2(gravity[2]),
+            pitch: (-gravity[0]).atan2((gravity[1] * gravity[1] + gravity[2] * gravity[2]).sqrt()),
+        })
+    }
+}
+
+// 자이로 적분으로 IMU 자세 추적 (deskew 용), 시작 시 정지 구간으로 바이어스/기울기 초기화
+pub struct ImuTracker {
+    pub initializer: StaticInitializer,
+    // 가속도가 g 에 가까울 때 roll/pitch 를 가속도 방향으로 당기는 비율 (샘플당, 0 이면 자이로만)
+    pub gravity_gain: f64,
+    estimate: Option<GravityEstimate>,
+    orientation: [f64; 4],
+    last: Option<ImuSample>,
+}
+
+impl ImuTracker {
+    pub fn new(init_samples: usize) -> Self {
+        ImuTracker {
+            initializer: StaticInitializer::new(init_samples),
+            gravity_gain: 0.005,
+            estimate: None,
+            orientation: [0.0, 0.0, 0.0, 1.0],
+            last: None,
+        }
+    }
+
+    pub fn estimate(&self) -> Option<&GravityEstimate> {
+        self.estimate.as_ref()
+    }
+
+    pub fn bias(&self) -> ImuBias {
+        self.estimate.map(|e| e.bias).unwrap_or_default()
+    }
+
+    // 측정 하나를 적분하고 그 시각의 자세 반환 (yaw 는 시작 기준 상대값)
+    pub fn push(&mut self, sample: ImuSample) -> Pose {
+        if self.estimate.is_none() {
+            self.initializer.add(&sample);
+            if let Some(estimate) = self.initializer.estimate() {
+                let yaw = Pose {
+                    position: [0.0; 3],
+                    orientation: self.orientation,
+                }
+                .yaw();
+                self.orientation =
+                    Pose::from_rpy([0.0; 3], estimate.roll, estimate.pitch, yaw).orientation;
+                self.estimate = Some(estimate);
+            }
+        }
+        if let Some(prev) = self.last {
+            let dt = sample.time - prev.time;
+            if dt > 0.0 {
+                let (gyro, _) = self.bias().correct(&prev, &sample);
+                self.orientation = rotate_by(self.orientation, exp_rotation(gyro.map(|v| v * dt)));
+            }
+        }
+        if self.estimate.is_some() {
+            self.correct_gravity(&sample);
+        }
+        self.last = Some(sample);
+        Pose {
+            position: [0.0; 3],
+            orientation: self.orientation,
+        }
+    }
+
+    // 자이로 적분의 roll/pitch 누적 오차를 측정 가속도 방향으로 조금씩 보정 (상보 필터)
+    fn correct_gravity(&mut self, sample: &ImuSample) {
+        let bias = self.bias();
+        let accel = [0, 1, 2].map(|k| sample.accel[k] - bias.accel[k]);
+        let norm = (accel[0] * accel[0] + accel[1] * accel[1] + accel[2] * accel[2]).sqrt();
+        // 가감속/충격 중에는 가속도가 중력 방향이 아니므로 보정하지 않음
+        if (norm - GRAVITY).abs() > self.initializer.accel_tolerance {
+            return;
+        }
+        let measured = accel.map(|v| v / norm);
+        let predicted = up_vector(&Pose {
+            position: [0.0; 3],
+            orientation: self.orientation,
+        });
+        let axis = [
+            measured[1] * predicted[2] - measured[2] * predicted[1],
+            measured[2] * predicted[0] - measured[0] * predicted[2],
+            measured[0] * predicted[1] - measured[1] * predicted[0],
+        ];
+        self.orientation = rotate_by(
+            self.orientation,
+            exp_rotation(axis.map(|v| v * self.gravity_gain)),
+        );
+    }
+}
+
+// 자세(센서 -> 월드) 기준으로 센서 좌표계에서 본 위쪽 방향
+pub fn up_vector(attitude: &Pose) -> [f64; 3] {
+    attitude.inverse().rotate([0.0, 0.0, 1.0])
+}
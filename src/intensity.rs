@@ -0,0 +1,298 @@
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::point::LidarPoint;
+use crate::yaml;
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 스캔 라인별 (선택적으로 거리 구간별) intensity 배율 표
+// 센서마다 라인별 반사율이 체계적으로 달라서 파싱 직후 곱해 맞춤
+//
+// YAML 형식 (range_bins 가 없으면 라인마다 배율 하나):
+//   range_bins: [10.0, 30.0]     # 거리 구간 경계 (m), 구간은 경계 수 + 1 개
+//   lines:
+//     0: 1.05                    # 모든 거리에 같은 배율
+//     1: [1.10, 1.00, 0.95]      # <10m, 10..30m, >=30m
+// 표에 없는 라인은 배율 1
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntensityTable {
+    pub range_bins: Vec<f32>,
+    pub gains: HashMap<u8, Vec<f32>>,
+}
+
+impl IntensityTable {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        Self::parse(&text).with_context(|| format!("{} 해석 실패", path.display()))
+    }
+
+    // intensity_calibration 파라미터: 보정 표 경로 (빈 문자열이면 보정 안 함)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let path = params::string(node, "intensity_calibration", "")?;
+        if path.is_empty() {
+            return Ok(None);
+        }
+        let table = Self::load(Path::new(&path))?;
+        println!(
+            "intensity 보정 표: {} ({}개 라인, 거리 구간 {}개)",
+            path,
+            table.gains.len(),
+            table.range_bins.len() + 1
+        );
+        Ok(Some(table))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut table = IntensityTable::default();
+        yaml::for_each_entry(text, |entry| {
+            match (entry.section, entry.key) {
+                (Some("lines"), key) => {
+                    let line_no: u8 = key
+                        .parse()
+                        .with_context(|| format!("라인 번호 '{}'", key))?;
+                    table.gains.insert(line_no, parse_values(entry.value)?);
+                }
+                (None, "range_bins") => table.range_bins = parse_values(entry.value)?,
+                (None, "lines") if entry.value.is_empty() => {}
+                (_, other) => bail!("알 수 없는 키 '{}'", other),
+            }
+            Ok(())
+        })?;
+
+        if table.range_bins.windows(2).any(|w| w[0] >= w[1]) {
+            bail!(
+                "range_bins 는 오름차순이어야 합니다: {:?}",
+                table.range_bins
+            );
+        }
+        let bins = table.range_bins.len() + 1;
+        for (line, gains) in &table.gains {
+            if gains.len() != 1 && gains.len() != bins {
+                bail!(
+                    "라인 {} 의 배율 {}개 (1개 또는 거리 구간 수 {}개여야 함)",
+                    line,
+                    gains.len(),
+                    bins
+                );
+            }
+        }
+        Ok(table)
+    }
+
+    // 라인/센서 원점 거리에 해당하는 배율
+    pub fn gain(&self, line: u8, range: f32) -> f32 {
+        match self.gains.get(&line) {
+            None => 1.0,
+            Some(gains) if gains.len() == 1 => gains[0],
+            Some(gains) => gains[self.range_bins.partition_point(|edge| *edge <= range)],
+        }
+    }
+
+    fn calibrate(&self, p: &LidarPoint) -> f32 {
+        let range = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+        p.intensity * self.gain(p.line, range)
+    }
+
+    // 거리는 센서 원점 기준이므로 장착 보정 전에 적용
+    pub fn apply(&self, points: &mut [LidarPoint]) {
+        for p in points.iter_mut() {
+            p.intensity = self.calibrate(p);
+        }
+    }
+}
+
+// "1.05" 또는 "[1.1, 1.0, 0.95]"
+fn parse_values(value: &str) -> Result<Vec<f32>> {
+    let inner = match value.strip_prefix('[') {
+        Some(rest) => rest.strip_suffix(']').context("']' 로 끝나지 않는 목록")?,
+        None => value,
+    };
+    let values = inner
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.parse::<f32>()
+                .with_context(|| format!("숫자가 아닌 값 '{}'", v))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if values.is_empty() {
+        bail!("값이 없습니다");
+    }
+    Ok(values)
+}
+
+// 기준 타깃 한 번의 관측: 보정 전 intensity 평균, 센서 원점 거리, 알려진 반사율 (%)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectanceSample {
+    pub intensity: f32,
+    pub range: f32,
+    pub reflectance: f32,
+}
+
+// intensity/거리 -> 실제 반사율 (%) 모델: reflectance = scale * intensity^intensity_exp * range^range_exp
+// 로그 공간 최소제곱으로 맞추므로 장비/거리가 달라도 같은 반사율이 같은 값으로 나오도록 함
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectanceModel {
+    pub scale: f64,
+    pub intensity_exp: f64,
+    pub range_exp: f64,
+}
+
+impl ReflectanceModel {
+    // 거리 차이가 이보다 작으면 (ln 기준, 약 10%) 거리 항은 0 으로 두고 맞춤
+    const MIN_LOG_RANGE_SPAN: f64 = 0.1;
+
+    // 반사율이 다른 타깃 2개 이상 필요, 거리 항까지 맞추려면 서로 다른 거리에서 관측해야 함
+    pub fn fit(samples: &[ReflectanceSample]) -> Result<Self> {
+        let rows: Vec<[f64; 3]> = samples
+            .iter()
+            .filter(|s| s.intensity > 0.0 && s.range > 0.0 && s.reflectance > 0.0)
+            .map(|s| {
+                [
+                    (s.intensity as f64).ln(),
+                    (s.range as f64).ln(),
+                    (s.reflectance as f64).ln(),
+                ]
+            })
+            .collect();
+        let span = |k: usize| {
+            let (min, max) = rows.iter().fold((f64::MAX, f64::MIN), |(lo, hi), r| {
+                (lo.min(r[k]), hi.max(r[k]))
+            });
+            max - min
+        };
+        if rows.len() < 2 || span(0) < 1e-3 {
+            bail!(
+                "유효한 관측 {}개: intensity 가 다른 관측이 2개 이상 필요합니다",
+                rows.len()
+            );
+        }
+        let with_range = rows.len() >= 3 && span(1) >= Self::MIN_LOG_RANGE_SPAN;
+
+        // 정규 방정식 A^T A x = A^T b, 열은 [1, ln I, (ln R)]
+        let n = if with_range { 3 } else { 2 };
+        let mut ata = [[0f64; 3]; 3];
+        let mut atb = [0f64; 3];
+        for r in &rows {
+            let a = [1.0, r[0], r[1]];
+            for i in 0..n {
+                for j in 0..n {
+                    ata[i][j] += a[i] * a[j];
+                }
+                atb[i] += a[i] * r[2];
+            }
+        }
+        let x =
+            solve(&mut ata, &mut atb, n).context("관측이 한쪽으로 몰려 모델을 풀 수 없습니다")?;
+        Ok(ReflectanceModel {
+            scale: x[0].exp(),
+            intensity_exp: x[1],
+            range_exp: x[2],
+        })
+    }
+
+    // reflectance_scale/intensity_exp/range_exp 파라미터 (reflectance_model=true 일 때만 사용)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        if !params::boolean(node, "reflectance_model", false)? {
+            return Ok(None);
+        }
+        Ok(Some(ReflectanceModel {
+            scale: params::float(node, "reflectance_scale", 1.0)?,
+            intensity_exp: params::float(node, "reflectance_intensity_exp", 1.0)?,
+            range_exp: params::float(node, "reflectance_range_exp", 0.0)?,
+        }))
+    }
+
+    // from_node 가 읽는 파라미터 이름과 값 (calibration::update_params_file 로 저장)
+    pub fn params(&self) -> [(&'static str, f64); 3] {
+        [
+            ("reflectance_scale", self.scale),
+            ("reflectance_intensity_exp", self.intensity_exp),
+            ("reflectance_range_exp", self.range_exp),
+        ]
+    }
+
+    pub fn reflectance(&self, intensity: f32, range: f32) -> f32 {
+        if intensity <= 0.0 {
+            return 0.0;
+        }
+        let range = (range as f64).max(0.1);
+        (self.scale * (intensity as f64).powf(self.intensity_exp) * range.powf(self.range_exp))
+            as f32
+    }
+
+    // 라인별 보정 표 다음, 장착 보정 전에 적용 (intensity 가 반사율 % 로 바뀜)
+    pub fn apply(&self, points: &mut [LidarPoint]) {
+        for p in points.iter_mut() {
+            let range = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+            p.intensity = self.reflectance(p.intensity, range);
+        }
+    }
+}
+
+// n x n (n <= 3) 가우스 소거, 특이 행렬이면 None
+fn solve(a: &mut [[f64; 3]; 3], b: &mut [f64; 3], n: usize) -> Option<[f64; 3]> {
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col];
+        for row in col + 1..n {
+            let f = a[row][col] / pivot_row[col];
+            for (v, p) in a[row][col..n].iter_mut().zip(&pivot_row[col..n]) {
+                *v -= f * p;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = [0f64; 3];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_range_bins_and_gains() {
+        let table = IntensityTable::parse(
+            "range_bins: [10.0, 30.0]\nlines:\n  0: 1.05\n  1: [1.10, 1.00, 0.95]\n",
+        )
+        .unwrap();
+        assert_eq!(table.range_bins, [10.0, 30.0]);
+        assert_eq!(table.gain(0, 50.0), 1.05);
+        assert_eq!(table.gain(1, 5.0), 1.10);
+        assert_eq!(table.gain(1, 10.0), 1.00);
+        assert_eq!(table.gain(1, 30.0), 0.95);
+        assert_eq!(table.gain(2, 5.0), 1.0);
+    }
+
+    #[test]
+    fn parse_rejects_bad_tables() {
+        for text in [
+            "range_bins: [30.0, 10.0]\n",
+            "range_bins: [10.0]\nlines:\n  0: [1.0, 1.0, 1.0]\n",
+            "lines:\n  300: 1.0\n",
+            "lines:\n  0: [1.0, 1.0\n",
+            "lines:\n  0: []\n",
+            "gain: 1.0\n",
+        ] {
+            assert!(IntensityTable::parse(text).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::msg::PointCloud2;
+use crate::passthrough;
+use crate::point::LidarPoint;
+use anyhow::{bail, Result};
+
+// NaN/Inf 좌표 포인트 처리 방식 (일부 재발행 노드가 잘못된 포인트를 그대로 내보냄)
+//   drop: 제거, zero: x/y/z 를 0 으로 (포인트 수/순서 유지), keep: 그대로 두고 개수만 셈
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPolicy {
+    Drop,
+    Zero,
+    Keep,
+}
+
+impl InvalidPolicy {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "drop" => Ok(InvalidPolicy::Drop),
+            "zero" => Ok(InvalidPolicy::Zero),
+            "keep" => Ok(InvalidPolicy::Keep),
+            other => bail!("알 수 없는 invalid_points '{}' (drop, zero, keep)", other),
+        }
+    }
+}
+
+fn is_valid(xyz: [f64; 3]) -> bool {
+    xyz.iter().all(|v| v.is_finite())
+}
+
+fn valid(p: &LidarPoint) -> bool {
+    is_valid([p.x as f64, p.y as f64, p.z as f64])
+}
+
+pub fn count(points: &[LidarPoint]) -> usize {
+    points.iter().filter(|p| !valid(p)).count()
+}
+
+pub fn count_msg(msg: &PointCloud2) -> Result<usize> {
+    Ok(passthrough::xyz_mask(msg, is_valid)?
+        .iter()
+        .filter(|k| !**k)
+        .count())
+}
+
+// 잘못된 포인트 수를 돌려줌 (policy 와 무관하게 처리 전 개수)
+pub fn apply(points: &mut Vec<LidarPoint>, policy: InvalidPolicy) -> usize {
+    let invalid = count(points);
+    if invalid == 0 {
+        return 0;
+    }
+    match policy {
+        InvalidPolicy::Drop => points.retain(valid),
+        InvalidPolicy::Zero => {
+            for p in points.iter_mut().filter(|p| !valid(p)) {
+                (p.x, p.y, p.z) = (0.0, 0.0, 0.0);
+            }
+        }
+        InvalidPolicy::Keep => {}
+    }
+    invalid
+}
+
+// 재인코딩 없이 원본 바이트 버퍼에 적용 (passthrough)
+pub fn apply_msg(msg: &mut PointCloud2, policy: InvalidPolicy) -> Result<usize> {
+    let keep = passthrough::xyz_mask(msg, is_valid)?;
+    let invalid = keep.iter().filter(|k| !**k).count();
+    if invalid == 0 {
+        return Ok(0);
+    }
+    match policy {
+        InvalidPolicy::Drop => {
+            passthrough::compact(msg, &keep);
+        }
+        InvalidPolicy::Zero => {
+            passthrough::map_xyz(msg, |p| if is_valid(p) { p } else { [0.0; 3] })?;
+        }
+        InvalidPolicy::Keep => {}
+    }
+    Ok(invalid)
+}
@@ -0,0 +1,208 @@
+use crate::cluster::{self, Cluster, ClusterConfig};
+use crate::point::LidarPoint;
+use crate::pose::Pose;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+// 재귀반사체(반사 테이프, 반사판) 랜드마크: 높은 intensity 포인트 덩어리를 지도에 ID 와 함께 보관하고
+// 관측과 연결 (창고처럼 반사판이 붙은 환경에서 저렴한 위치 추정용)
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectorConfig {
+    // 이 intensity 이상만 반사체 포인트로 봄 (Livox 반사체는 보통 150 이상)
+    pub min_intensity: f32,
+    pub cluster: ClusterConfig,
+    // 이보다 큰 덩어리 (x/y 긴 변, m) 는 반사체가 아님 (반사 조끼, 표지판 등)
+    pub max_size: f32,
+    // 지도 랜드마크와 같은 것으로 볼 거리 (m)
+    pub gate: f32,
+}
+
+impl Default for ReflectorConfig {
+    fn default() -> Self {
+        ReflectorConfig {
+            min_intensity: 150.0,
+            cluster: ClusterConfig {
+                cell_size: 0.1,
+                min_points: 3,
+                ..ClusterConfig::default()
+            },
+            max_size: 0.5,
+            gate: 0.5,
+        }
+    }
+}
+
+// 반사체 후보 (센서/차량 좌표계 중심)
+pub fn extract(points: &[LidarPoint], config: &ReflectorConfig) -> Vec<Cluster> {
+    let bright: Vec<[f32; 3]> = points
+        .iter()
+        .filter(|p| p.intensity >= config.min_intensity)
+        .map(|p| [p.x, p.y, p.z])
+        .collect();
+    cluster::euclidean(&bright, &config.cluster)
+        .into_iter()
+        .filter(|c| c.dimensions()[0] <= config.max_size)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Landmark {
+    pub id: u32,
+    // 지도 좌표계 위치 (m), 관측 평균
+    pub position: [f64; 3],
+    pub observations: u32,
+}
+
+// 관측 하나와 연결된 랜드마크
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Association {
+    // extract 결과의 순서
+    pub observation: usize,
+    pub id: u32,
+    // 지도 좌표계로 옮긴 관측과 랜드마크 사이 거리 (m), 새로 추가한 랜드마크면 0
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LandmarkMap {
+    pub landmarks: Vec<Landmark>,
+    next_id: u32,
+}
+
+impl LandmarkMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 관측을 pose (차량 -> 지도) 로 옮겨 가장 가까운 랜드마크와 연결
+    // 한 랜드마크에는 관측 하나만 연결, mapping 이면 연결 안 된 관측을 새 랜드마크로 추가하고 위치 평균 갱신
+    pub fn associate(
+        &mut self,
+        observations: &[Cluster],
+        pose: &Pose,
+        gate: f32,
+        mapping: bool,
+    ) -> Vec<Association> {
+        let world: Vec<[f64; 3]> = observations
+            .iter()
+            .map(|c| pose.transform_point(c.centroid.map(|v| v as f64)))
+            .collect();
+
+        // 가까운 쌍부터 연결
+        let gate = gate as f64;
+        let mut pairs = Vec::new();
+        for (i, p) in world.iter().enumerate() {
+            for (j, landmark) in self.landmarks.iter().enumerate() {
+                let d = distance(p, &landmark.position);
+                if d <= gate {
+                    pairs.push((d, i, j));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut used_observation = vec![false; world.len()];
+        let mut used_landmark = vec![false; self.landmarks.len()];
+        let mut associations = Vec::new();
+        for (d, i, j) in pairs {
+            if used_observation[i] || used_landmark[j] {
+                continue;
+            }
+            used_observation[i] = true;
+            used_landmark[j] = true;
+            let landmark = &mut self.landmarks[j];
+            if mapping {
+                landmark.observations += 1;
+                let n = landmark.observations as f64;
+                for (v, w) in landmark.position.iter_mut().zip(world[i]) {
+                    *v += (w - *v) / n;
+                }
+            }
+            associations.push(Association {
+                observation: i,
+                id: landmark.id,
+                distance: d,
+            });
+        }
+
+        if mapping {
+            for (i, p) in world.iter().enumerate() {
+                if used_observation[i] {
+                    continue;
+                }
+                let id = self.next_id;
+                self.next_id += 1;
+                self.landmarks.push(Landmark {
+                    id,
+                    position: *p,
+                    observations: 1,
+                });
+                associations.push(Association {
+                    observation: i,
+                    id,
+                    distance: 0.0,
+                });
+            }
+        }
+        associations.sort_by_key(|a| a.observation);
+        associations
+    }
+
+    // 한 줄에 랜드마크 하나: "id x y z observations"
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut text = String::from("# id x y z observations\n");
+        for l in &self.landmarks {
+            text.push_str(&format!(
+                "{} {:.4} {:.4} {:.4} {}\n",
+                l.id, l.position[0], l.position[1], l.position[2], l.observations
+            ));
+        }
+        fs::write(path, text).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        let mut map = LandmarkMap::new();
+        for (n, raw) in text.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [id, x, y, z, observations] = fields[..] else {
+                bail!(
+                    "{} {}번째 줄: 'id x y z observations' 형식이 아닙니다",
+                    path.display(),
+                    n + 1
+                );
+            };
+            let number = |v: &str| {
+                v.parse::<f64>().with_context(|| {
+                    format!(
+                        "{} {}번째 줄: 숫자가 아닌 값 '{}'",
+                        path.display(),
+                        n + 1,
+                        v
+                    )
+                })
+            };
+            let id = number(id)? as u32;
+            if map.landmarks.iter().any(|l| l.id == id) {
+                bail!("{} {}번째 줄: 중복된 id {}", path.display(), n + 1, id);
+            }
+            map.landmarks.push(Landmark {
+                id,
+                position: [number(x)?, number(y)?, number(z)?],
+                observations: number(observations)?.max(1.0) as u32,
+            });
+            map.next_id = map.next_id.max(id + 1);
+        }
+        Ok(map)
+    }
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
@@ -0,0 +1,163 @@
+use crate::bev::BevGrid;
+#[cfg(feature = "ros")]
+use crate::params;
+#[cfg(feature = "ros")]
+use crate::visual::VisualSettings;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+#[cfg(feature = "ros")]
+use sensor_msgs::msg::Image;
+#[cfg(feature = "ros")]
+use std_msgs::msg::Header;
+
+// 높이 구간별 BEV 층 (경계 [0.0, 0.5, 1.5, 3.0] -> 0~0.5, 0.5~1.5, 1.5~3 m 세 층)
+// 낮은 장애물(연석, 팔레트)과 머리 위 구조물(선반, 문틀)을 층으로 구분
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeightLayers {
+    pub edges: Vec<f32>,
+}
+
+impl HeightLayers {
+    pub fn new(edges: Vec<f32>) -> Result<Self> {
+        if edges.len() < 2 {
+            bail!("높이 층 경계는 2개 이상 필요합니다: {:?}", edges);
+        }
+        if edges.windows(2).any(|w| w[0] >= w[1]) {
+            bail!("높이 층 경계는 오름차순이어야 합니다: {:?}", edges);
+        }
+        Ok(HeightLayers { edges })
+    }
+
+    // bev_layers 파라미터 (비어 있으면 사용 안 함)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let edges = params::float_array(node, "bev_layers", &[])?;
+        if edges.is_empty() {
+            return Ok(None);
+        }
+        Self::new(edges.into_iter().map(|e| e as f32).collect()).map(Some)
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 높이가 드는 층 (아래 경계 포함, 맨 위 층만 위 경계 포함), 범위 밖이면 None
+    pub fn layer_of(&self, height: f32) -> Option<usize> {
+        let last = *self.edges.last()?;
+        if height < self.edges[0] || height > last {
+            return None;
+        }
+        Some(
+            self.edges
+                .partition_point(|edge| *edge <= height)
+                .saturating_sub(1)
+                .min(self.len() - 1),
+        )
+    }
+
+    // 층 토픽/프레임 이름에 붙일 구간 표시 (예: "0.5_1.5")
+    pub fn label(&self, layer: usize) -> String {
+        format!("{}_{}", self.edges[layer], self.edges[layer + 1])
+    }
+}
+
+// 층마다 채널 하나인 BEV 이미지 (셀 값은 층 안 포인트 수, 255 에서 포화)
+pub struct LayerImage {
+    cell_size: f32,
+    min: [f32; 2],
+    cols: usize,
+    rows: usize,
+    channels: usize,
+    data: Vec<u8>,
+}
+
+impl LayerImage {
+    // 이미지 인코딩 8UC1..8UC4 로 표현할 수 있는 최대 층 수
+    pub const MAX_CHANNELS: usize = 4;
+
+    // bev_cell_size, bev_extent_x, bev_extent_y 가 모두 양수여야 함
+    pub fn new(grid: &BevGrid, channels: usize) -> Result<Self> {
+        let (min, max) = grid.bounds();
+        if grid.cell_size <= 0.0 || !max[0].is_finite() || !max[1].is_finite() {
+            bail!("층 이미지는 bev_cell_size, bev_extent_x, bev_extent_y 가 필요합니다");
+        }
+        if channels == 0 || channels > Self::MAX_CHANNELS {
+            bail!(
+                "층 이미지는 1~{}개 층만 지원합니다 ({}개)",
+                Self::MAX_CHANNELS,
+                channels
+            );
+        }
+        let cols = ((max[0] - min[0]) / grid.cell_size).ceil() as usize;
+        let rows = ((max[1] - min[1]) / grid.cell_size).ceil() as usize;
+        Ok(LayerImage {
+            cell_size: grid.cell_size,
+            min,
+            cols,
+            rows,
+            channels,
+            data: vec![0; cols * rows * channels],
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+
+    pub fn add(&mut self, x: f32, y: f32, layer: usize) {
+        let col = ((x - self.min[0]) / self.cell_size).floor();
+        let row = ((y - self.min[1]) / self.cell_size).floor();
+        if !(col >= 0.0 && row >= 0.0) || layer >= self.channels {
+            return;
+        }
+        let (col, row) = (col as usize, row as usize);
+        if col >= self.cols || row >= self.rows {
+            return;
+        }
+        let i = (row * self.cols + col) * self.channels + layer;
+        self.data[i] = self.data[i].saturating_add(1);
+    }
+
+    // 행은 y (min 부터), 열은 x (OccupancyGrid 와 같은 배치), 픽셀마다 층 순서대로 채널
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn to_msg(&self, header: Header) -> Image {
+        Image {
+            header,
+            height: self.rows as u32,
+            width: self.cols as u32,
+            encoding: format!("8UC{}", self.channels),
+            is_bigendian: 0,
+            step: (self.cols * self.channels) as u32,
+            data: self.data.clone(),
+        }
+    }
+
+    // 층을 합친 셀별 포인트 수를 visual 의 이미지 컬러맵으로 (rgb8)
+    #[cfg(feature = "ros")]
+    pub fn to_color_msg(&self, header: Header, visual: &VisualSettings) -> Image {
+        let data = self
+            .data
+            .chunks_exact(self.channels)
+            .flat_map(|pixel| visual.image_color(pixel.iter().map(|c| *c as u32).sum()))
+            .collect();
+        Image {
+            header,
+            height: self.rows as u32,
+            width: self.cols as u32,
+            encoding: "rgb8".to_string(),
+            is_bigendian: 0,
+            step: (self.cols * 3) as u32,
+            data,
+        }
+    }
+}
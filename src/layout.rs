@@ -0,0 +1,158 @@
+use crate::builder::PointCloud2Builder;
+use crate::msg::{Header, PointCloud2, PointField};
+use crate::point::{datatype, parse_pointcloud2, parse_with_offsets, FieldOffsets, LidarPoint};
+use anyhow::{anyhow, Result};
+
+// 이름으로 선택하는 포인트 레이아웃, 파싱과 발행 양쪽에서 사용
+#[derive(Debug)]
+pub struct NamedLayout {
+    pub name: &'static str,
+    pub fields: &'static [(&'static str, u8)],
+}
+
+pub const LIVOX_26: NamedLayout = NamedLayout {
+    name: "livox_26",
+    fields: &[
+        ("x", datatype::FLOAT32),
+        ("y", datatype::FLOAT32),
+        ("z", datatype::FLOAT32),
+        ("intensity", datatype::FLOAT32),
+        ("tag", datatype::UINT8),
+        ("line", datatype::UINT8),
+        ("timestamp", datatype::FLOAT64),
+    ],
+};
+
+pub const XYZI16: NamedLayout = NamedLayout {
+    name: "xyzi16",
+    fields: &[
+        ("x", datatype::FLOAT32),
+        ("y", datatype::FLOAT32),
+        ("z", datatype::FLOAT32),
+        ("intensity", datatype::FLOAT32),
+    ],
+};
+
+pub const XYZI_LABEL: NamedLayout = NamedLayout {
+    name: "xyzi_label",
+    fields: &[
+        ("x", datatype::FLOAT32),
+        ("y", datatype::FLOAT32),
+        ("z", datatype::FLOAT32),
+        ("intensity", datatype::FLOAT32),
+        ("label", datatype::UINT32),
+    ],
+};
+
+// rgb 는 PCL 관례대로 FLOAT32 에 0x00RRGGBB 를 비트 단위로 담음
+pub const XYZRGB: NamedLayout = NamedLayout {
+    name: "xyzrgb",
+    fields: &[
+        ("x", datatype::FLOAT32),
+        ("y", datatype::FLOAT32),
+        ("z", datatype::FLOAT32),
+        ("rgb", datatype::FLOAT32),
+    ],
+};
+
+// 새 레이아웃은 여기에만 추가하면 모든 노드에서 선택 가능
+pub const LAYOUTS: &[&NamedLayout] = &[&LIVOX_26, &XYZI16, &XYZI_LABEL, &XYZRGB];
+
+pub fn lookup(name: &str) -> Result<&'static NamedLayout> {
+    LAYOUTS
+        .iter()
+        .copied()
+        .find(|l| l.name == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = LAYOUTS.iter().map(|l| l.name).collect();
+            anyhow!("알 수 없는 레이아웃 '{}' (사용 가능: {:?})", name, names)
+        })
+}
+
+// 입력 레이아웃 파라미터: "auto" 는 메시지의 fields 를 그대로 사용
+pub fn input_layout(name: &str) -> Result<Option<&'static NamedLayout>> {
+    match name {
+        "auto" => Ok(None),
+        name => lookup(name).map(Some),
+    }
+}
+
+pub fn parse(msg: &PointCloud2, layout: Option<&NamedLayout>) -> Result<Vec<LidarPoint>> {
+    match layout {
+        Some(layout) => parse_with_offsets(msg, &layout.offsets()?),
+        None => parse_pointcloud2(msg),
+    }
+}
+
+impl NamedLayout {
+    pub fn builder(&self) -> PointCloud2Builder {
+        self.fields
+            .iter()
+            .fold(PointCloud2Builder::new(), |b, &(name, dt)| {
+                b.add_field(name, dt)
+            })
+    }
+
+    pub fn point_fields(&self) -> Vec<PointField> {
+        let mut offset = 0;
+        self.fields
+            .iter()
+            .map(|&(name, dt)| {
+                let field = PointField {
+                    name: name.to_string(),
+                    offset,
+                    datatype: dt,
+                    count: 1,
+                };
+                offset += datatype::size(dt).unwrap_or(0) as u32;
+                field
+            })
+            .collect()
+    }
+
+    pub fn offsets(&self) -> Result<FieldOffsets> {
+        FieldOffsets::from_fields(&self.point_fields())
+    }
+
+    // 레이아웃 필드 순서대로 포인트 값을 추출 (빌더의 push_point 입력)
+    pub fn values(&self, point: &LidarPoint, out: &mut Vec<f64>) {
+        out.clear();
+        out.extend(self.fields.iter().map(|&(name, _)| point.field_value(name)));
+    }
+
+    pub fn encode(&self, points: &[LidarPoint], header: Header) -> PointCloud2 {
+        let mut out = PointCloud2::default();
+        self.encode_into(points, header, &mut out);
+        out
+    }
+
+    // 미리 할당된 메시지에 인코딩 (data 버퍼 재사용)
+    pub fn encode_into(&self, points: &[LidarPoint], header: Header, out: &mut PointCloud2) {
+        self.encode_into_with(points, &[], header, out, |_, _| {});
+    }
+
+    // 레이아웃 필드 뒤에 extra_fields 를 덧붙여 인코딩, extra(i, values) 가 i 번째 포인트의 추가 값을 push
+    pub fn encode_into_with(
+        &self,
+        points: &[LidarPoint],
+        extra_fields: &[(&str, u8)],
+        header: Header,
+        out: &mut PointCloud2,
+        mut extra: impl FnMut(usize, &mut Vec<f64>),
+    ) {
+        let mut builder = extra_fields
+            .iter()
+            .fold(self.builder(), |b, &(name, dt)| b.add_field(name, dt))
+            .with_buffer(std::mem::take(&mut out.data));
+        builder.reserve(points.len());
+
+        let mut values = Vec::with_capacity(self.fields.len() + extra_fields.len());
+        for (i, point) in points.iter().enumerate() {
+            self.values(point, &mut values);
+            extra(i, &mut values);
+            builder.push_point(&values);
+        }
+
+        *out = builder.finish(header);
+    }
+}
@@ -0,0 +1,183 @@
+// std 기능 없이 빌드하면 no_std + alloc 으로 core 포인트 코덱만 남음
+// ROS 에 의존하는 모듈 (노드 파라미터, 발행, 진단, 종료 처리) 은 ros 기능에서만 빌드
+// 파싱/기하 코어는 msg 모듈을 통해 ROS 없이도 (브라우저 포함) 같은 코드로 동작
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// derive(PointLayout) 이 생성하는 ::rust_lidar 경로를 크레이트 내부에서도 사용
+extern crate self as rust_lidar;
+
+#[cfg(feature = "std")]
+pub mod alloc_stats;
+#[cfg(feature = "std")]
+pub mod autolabel;
+#[cfg(feature = "std")]
+pub mod background;
+#[cfg(feature = "std")]
+pub mod bev;
+#[cfg(feature = "std")]
+pub mod bev_frame;
+#[cfg(feature = "ros")]
+pub mod bev_node;
+#[cfg(feature = "std")]
+pub mod blockage;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod calibration;
+#[cfg(feature = "std")]
+pub mod capture;
+#[cfg(feature = "std")]
+pub mod change;
+#[cfg(feature = "std")]
+pub mod classify;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod cloud;
+#[cfg(feature = "std")]
+pub mod cluster;
+#[cfg(feature = "std")]
+pub mod compute;
+pub mod core;
+#[cfg(feature = "std")]
+pub mod corridor;
+#[cfg(feature = "std")]
+pub mod crash_dump;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+#[cfg(feature = "std")]
+pub mod dataset;
+#[cfg(feature = "std")]
+pub mod deskew;
+#[cfg(feature = "ros")]
+pub mod diagnostics;
+#[cfg(feature = "std")]
+pub mod exclusion;
+#[cfg(feature = "ros")]
+pub mod executor;
+#[cfg(feature = "std")]
+pub mod filter;
+#[cfg(feature = "std")]
+pub mod frame_id;
+#[cfg(feature = "std")]
+pub mod fusion;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "std")]
+pub mod grid_map;
+#[cfg(feature = "std")]
+pub mod ground;
+#[cfg(feature = "ros")]
+pub mod harness;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod imu;
+#[cfg(feature = "std")]
+pub mod intensity;
+#[cfg(feature = "std")]
+pub mod invalid;
+#[cfg(feature = "std")]
+pub mod landmarks;
+#[cfg(feature = "std")]
+pub mod layers;
+#[cfg(feature = "std")]
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod line_timing;
+#[cfg(feature = "std")]
+pub mod load;
+#[cfg(feature = "std")]
+pub mod localization;
+#[cfg(feature = "std")]
+pub mod map_file;
+#[cfg(feature = "std")]
+pub mod mesh;
+#[cfg(feature = "std")]
+pub mod msg;
+#[cfg(feature = "std")]
+pub mod ndt;
+#[cfg(feature = "ros")]
+pub mod params;
+#[cfg(feature = "std")]
+pub mod passthrough;
+#[cfg(feature = "std")]
+pub mod pcd;
+#[cfg(feature = "std")]
+pub mod people;
+#[cfg(feature = "std")]
+pub mod persist;
+#[cfg(feature = "ros")]
+pub mod pipeline;
+#[cfg(feature = "std")]
+pub mod point;
+#[cfg(feature = "std")]
+pub mod pose;
+#[cfg(feature = "std")]
+pub mod pose_graph;
+#[cfg(feature = "ros")]
+pub mod qos;
+#[cfg(feature = "std")]
+pub mod reflection;
+#[cfg(feature = "std")]
+pub mod registration;
+#[cfg(feature = "std")]
+pub mod relocalization;
+#[cfg(feature = "std")]
+pub mod roi;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod ros1;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod rt;
+#[cfg(feature = "std")]
+pub mod scan_context;
+#[cfg(feature = "std")]
+pub mod scene_flow;
+#[cfg(feature = "ros")]
+pub mod shutdown;
+#[cfg(feature = "std")]
+pub mod sidecar;
+#[cfg(feature = "std")]
+pub mod slam;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod split;
+#[cfg(feature = "std")]
+pub mod stamp;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod step;
+#[cfg(feature = "std")]
+pub mod supervisor;
+#[cfg(feature = "std")]
+pub mod synthetic;
+#[cfg(feature = "std")]
+pub mod temporal;
+#[cfg(feature = "std")]
+pub mod tile_cache;
+#[cfg(feature = "std")]
+pub mod time_offset;
+#[cfg(feature = "ros")]
+pub mod timing;
+#[cfg(feature = "std")]
+pub mod tracking;
+#[cfg(feature = "std")]
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod visibility;
+#[cfg(feature = "std")]
+pub mod visual;
+#[cfg(feature = "std")]
+pub mod volume;
+#[cfg(feature = "std")]
+pub mod weather;
+#[cfg(feature = "std")]
+pub mod yaml;
+
+#[cfg(feature = "counting-alloc")]
+#[global_allocator]
+static GLOBAL: alloc_stats::CountingAllocator = alloc_stats::CountingAllocator;
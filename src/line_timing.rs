@@ -0,0 +1,125 @@
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::point::LidarPoint;
+use crate::yaml;
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 스캔 라인별 포인트 timestamp 보정 표
+// 일부 장비는 라인마다 일정한 시각 오차가 있어 파싱 직후 더해 deskew/특징 추출이 올바른 순서의 시각을 쓰게 함
+//
+// YAML 형식 (마이크로초, 측정 시각에 더함):
+//   lines:
+//     0: 0.0
+//     1: -12.5
+//     2: 8.0
+// 표에 없는 라인은 보정 0
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineTimingTable {
+    // 라인 -> 더할 시각 (ns, LidarPoint::timestamp 단위)
+    pub offsets: HashMap<u8, f64>,
+}
+
+impl LineTimingTable {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        Self::parse(&text).with_context(|| format!("{} 해석 실패", path.display()))
+    }
+
+    // line_timing_table 파라미터: 보정 표 경로 (빈 문자열이면 보정 안 함)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let path = params::string(node, "line_timing_table", "")?;
+        if path.is_empty() {
+            return Ok(None);
+        }
+        let table = Self::load(Path::new(&path))?;
+        println!(
+            "라인별 시각 보정 표: {} ({}개 라인)",
+            path,
+            table.offsets.len()
+        );
+        Ok(Some(table))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut table = LineTimingTable::default();
+        yaml::for_each_entry(text, |entry| {
+            match (entry.section, entry.key) {
+                (Some("lines"), key) => {
+                    let line_no: u8 = key
+                        .parse()
+                        .with_context(|| format!("라인 번호 '{}'", key))?;
+                    let micros: f64 = entry
+                        .value
+                        .parse()
+                        .with_context(|| format!("숫자가 아닌 값 '{}'", entry.value))?;
+                    table.offsets.insert(line_no, micros * 1e3);
+                }
+                (None, "lines") if entry.value.is_empty() => {}
+                (_, other) => bail!("알 수 없는 키 '{}'", other),
+            }
+            Ok(())
+        })?;
+        Ok(table)
+    }
+
+    // timestamp 가 없는 포인트 (0, 프레임 시각 사용) 는 그대로 둠
+    // 보정 후 시간 순서가 아니면 true (순서에 의존하는 단계 전에 정렬하도록)
+    pub fn apply(&self, points: &mut [LidarPoint]) -> bool {
+        let mut reordered = false;
+        let mut previous = f64::MIN;
+        for p in points.iter_mut() {
+            if p.timestamp > 0.0 {
+                if let Some(offset) = self.offsets.get(&p.line) {
+                    p.timestamp += offset;
+                }
+                reordered |= p.timestamp < previous;
+                previous = p.timestamp;
+            }
+        }
+        reordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_converts_microseconds() {
+        let table = LineTimingTable::parse("lines:\n  0: 0.0\n  1: -12.5\n  3: 8\n").unwrap();
+        assert_eq!(table.offsets.len(), 3);
+        assert_eq!(table.offsets[&1], -12_500.0);
+        assert_eq!(table.offsets[&3], 8_000.0);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys_and_values() {
+        for text in ["offset: 1.0\n", "lines:\n  a: 1.0\n", "lines:\n  0: fast\n"] {
+            assert!(LineTimingTable::parse(text).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn apply_reports_reordering() {
+        let table = LineTimingTable::parse("lines:\n  1: -2.0\n").unwrap();
+        let point = |line, timestamp| LidarPoint {
+            line,
+            timestamp,
+            ..LidarPoint::default()
+        };
+        let mut points = [point(0, 1000.0), point(1, 2500.0), point(0, 0.0)];
+        assert!(table.apply(&mut points));
+        assert_eq!(points[1].timestamp, 500.0);
+        assert_eq!(points[2].timestamp, 0.0);
+
+        let mut ordered = [point(0, 1000.0), point(1, 4000.0)];
+        assert!(!table.apply(&mut ordered));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::bev::BevGrid;
+#[cfg(feature = "ros")]
+use crate::params;
+#[cfg(feature = "ros")]
+use anyhow::Result;
+#[cfg(feature = "ros")]
+use rclrs::Node;
+
+// 처리 품질 단계, 뒤로 갈수록 앞 단계의 저하를 모두 포함
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoadLevel {
+    Full,
+    // 입력을 복셀 다운샘플 (복셀마다 첫 포인트)
+    Voxel,
+    // 지면 normal 을 다시 추정하지 않고 직전 평면 사용
+    SkipNormals,
+    // BEV 셀 크기 2배
+    CoarseBev,
+}
+
+impl LoadLevel {
+    const ALL: [LoadLevel; 4] = [
+        LoadLevel::Full,
+        LoadLevel::Voxel,
+        LoadLevel::SkipNormals,
+        LoadLevel::CoarseBev,
+    ];
+
+    fn step(self, up: bool) -> Self {
+        let i = self as usize;
+        let i = if up { i + 1 } else { i.saturating_sub(1) };
+        Self::ALL[i.min(Self::ALL.len() - 1)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadConfig {
+    // 프레임당 처리 시간 목표 (us), 보통 프레임 주기
+    pub budget_us: u64,
+    // 처리 시간 EMA 가 budget * degrade_ratio 를 넘거나 프레임을 버리면 한 단계 낮춤
+    pub degrade_ratio: f64,
+    // budget * restore_ratio 밑으로 restore_frames 프레임 연속이면 한 단계 올림
+    pub restore_ratio: f64,
+    pub restore_frames: u32,
+    // EMA 새 값 반영 비율
+    pub alpha: f64,
+    // LoadLevel::Voxel 부터 쓰는 복셀 크기 (m), 셀 크기가 0 인 BEV 를 거칠게 할 때도 사용
+    pub voxel: f32,
+    // 가장 낮출 수 있는 단계
+    pub max_level: LoadLevel,
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        LoadConfig {
+            budget_us: 100_000,
+            degrade_ratio: 0.9,
+            restore_ratio: 0.5,
+            restore_frames: 20,
+            alpha: 0.3,
+            voxel: 0.1,
+            max_level: LoadLevel::CoarseBev,
+        }
+    }
+}
+
+impl LoadConfig {
+    // load_budget_ms (0 이면 끔), load_degrade_ratio, load_restore_ratio, load_restore_frames,
+    // load_voxel (m), load_max_level (0..3)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let budget_ms = params::float(node, "load_budget_ms", 0.0)?;
+        if budget_ms <= 0.0 {
+            return Ok(None);
+        }
+        let defaults = LoadConfig::default();
+        let max_level = params::int(node, "load_max_level", 3)?.clamp(0, 3) as usize;
+        Ok(Some(LoadConfig {
+            budget_us: (budget_ms * 1000.0) as u64,
+            degrade_ratio: params::float(node, "load_degrade_ratio", defaults.degrade_ratio)?,
+            restore_ratio: params::float(node, "load_restore_ratio", defaults.restore_ratio)?,
+            restore_frames: params::int(
+                node,
+                "load_restore_frames",
+                defaults.restore_frames as i64,
+            )?
+            .max(1) as u32,
+            alpha: defaults.alpha,
+            voxel: params::float(node, "load_voxel", defaults.voxel as f64)? as f32,
+            max_level: LoadLevel::ALL[max_level],
+        }))
+    }
+}
+
+// 처리 지연을 보고 품질 단계를 자동으로 낮추고, 여유가 생기면 한 단계씩 되돌림
+pub struct LoadManager {
+    config: LoadConfig,
+    level: LoadLevel,
+    // 처리 시간 EMA (us), 단계를 바꾸면 새로 시작
+    ema: Option<f64>,
+    calm_frames: u32,
+}
+
+impl LoadManager {
+    pub fn new(config: LoadConfig) -> Self {
+        LoadManager {
+            config,
+            level: LoadLevel::Full,
+            ema: None,
+            calm_frames: 0,
+        }
+    }
+
+    pub fn level(&self) -> LoadLevel {
+        self.level
+    }
+
+    pub fn latency_us(&self) -> Option<f64> {
+        self.ema
+    }
+
+    // 프레임 처리 시간과 이번 프레임에 버린 프레임이 있는지 반영, 단계가 바뀌면 새 단계 반환
+    pub fn update(&mut self, total_us: u64, dropped: bool) -> Option<LoadLevel> {
+        let sample = total_us as f64;
+        let ema = match self.ema {
+            Some(ema) => ema + (sample - ema) * self.config.alpha,
+            None => sample,
+        };
+        self.ema = Some(ema);
+
+        let budget = self.config.budget_us as f64;
+        let next = if dropped || ema > budget * self.config.degrade_ratio {
+            self.calm_frames = 0;
+            self.level.step(true).min(self.config.max_level)
+        } else if ema < budget * self.config.restore_ratio {
+            self.calm_frames += 1;
+            if self.calm_frames < self.config.restore_frames {
+                return None;
+            }
+            self.calm_frames = 0;
+            self.level.step(false)
+        } else {
+            self.calm_frames = 0;
+            return None;
+        };
+        if next == self.level {
+            return None;
+        }
+        self.level = next;
+        // 바뀐 단계의 처리 시간만으로 다시 판단
+        self.ema = None;
+        Some(next)
+    }
+
+    pub fn voxel(&self) -> Option<f32> {
+        (self.level >= LoadLevel::Voxel && self.config.voxel > 0.0).then_some(self.config.voxel)
+    }
+
+    pub fn skip_normals(&self) -> bool {
+        self.level >= LoadLevel::SkipNormals
+    }
+
+    // CoarseBev 단계면 셀 크기 2배 (셀 크기가 0 이면 복셀 크기의 2배로 양자화)
+    pub fn grid(&self, grid: &BevGrid) -> BevGrid {
+        if self.level < LoadLevel::CoarseBev {
+            return *grid;
+        }
+        let cell_size = if grid.cell_size > 0.0 {
+            grid.cell_size
+        } else {
+            self.config.voxel
+        };
+        BevGrid {
+            cell_size: cell_size * 2.0,
+            ..*grid
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::compute::{ComputeBackend, Cpu};
+use crate::ndt::{self, NdtConfig, NdtPyramid};
+use crate::pose::Pose;
+use crate::registration::{self, IcpConfig, IcpResult, RegistrationCovariance, VoxelIndex};
+use crate::relocalization::{self, Relocalization, RelocalizeConfig};
+use crate::tile_cache::TileCache;
+use anyhow::Result;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocalizerConfig {
+    // 스캔 다운샘플 크기 (m)
+    pub voxel: f32,
+    // 정합 결과를 받아들일 최소 fitness, 최대 rmse (m)
+    pub min_fitness: f64,
+    pub max_rmse: f64,
+    // 이동/회전 Hessian 고유값 비가 이보다 작으면 퇴화 (복도처럼 한 방향 구속이 없음)
+    pub degeneracy_threshold: f64,
+}
+
+impl Default for LocalizerConfig {
+    fn default() -> Self {
+        LocalizerConfig {
+            voxel: 0.3,
+            min_fitness: 0.5,
+            max_rmse: 0.3,
+            degeneracy_threshold: 0.01,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocalizeResult {
+    pub pose: Pose,
+    pub icp: Option<IcpResult>,
+    // false 면 정합 실패로 예측 자세를 그대로 씀
+    pub matched: bool,
+    // 정합 성공 시 자세 공분산 (fitness 가 낮을수록 키움)
+    pub covariance: Option<RegistrationCovariance>,
+    pub degenerate: bool,
+}
+
+// 지도 기준 위치 추정: 직전 속도로 예측한 뒤 주변 타일 지도에 스캔 정합
+pub struct Localizer {
+    pub config: LocalizerConfig,
+    pub icp: IcpConfig,
+    pub tiles: TileCache,
+    index: Option<VoxelIndex>,
+    // 설정되면 ICP 대신 다중 해상도 NDT 로 정합 (색인은 검증과 공분산에 계속 사용)
+    ndt: Option<NdtConfig>,
+    pyramid: Option<NdtPyramid>,
+    // 스캔 다운샘플과 ICP 대응점 검색 경로 (기본 CPU)
+    compute: Box<dyn ComputeBackend>,
+    pose: Pose,
+    // 직전 스캔 간 움직임 (등속 예측용)
+    velocity: Pose,
+    // 연속 정합 실패 횟수
+    pub failures: u32,
+    // 마지막 스캔 (다운샘플), 재위치 추정에 사용
+    last_scan: Vec<[f32; 3]>,
+}
+
+impl Localizer {
+    pub fn new(config: LocalizerConfig, icp: IcpConfig, tiles: TileCache, initial: Pose) -> Self {
+        Localizer {
+            config,
+            icp,
+            tiles,
+            index: None,
+            ndt: None,
+            pyramid: None,
+            compute: Box::new(Cpu),
+            pose: initial,
+            velocity: Pose::IDENTITY,
+            failures: 0,
+            last_scan: Vec::new(),
+        }
+    }
+
+    pub fn with_ndt(mut self, config: NdtConfig) -> Self {
+        self.ndt = Some(config);
+        self.index = None;
+        self
+    }
+
+    pub fn with_compute(mut self, compute: Box<dyn ComputeBackend>) -> Self {
+        self.compute = compute;
+        self
+    }
+
+    pub fn pose(&self) -> Pose {
+        self.pose
+    }
+
+    // 현재 위치 주변 지도를 다운샘플한 부분 지도 (로드된 타일 범위 안에서만)
+    pub fn submap(&self, radius: f32, voxel: f32) -> Vec<[f32; 3]> {
+        let [x, y, _] = self.pose.position;
+        let points = self.tiles.points_within(x as f32, y as f32, radius);
+        registration::voxel_downsample(&points, voxel)
+    }
+
+    // 자세를 강제로 지정 (초기 위치 재설정), 속도는 초기화
+    pub fn reset(&mut self, pose: Pose) {
+        self.pose = pose;
+        self.velocity = Pose::IDENTITY;
+        self.failures = 0;
+    }
+
+    // hint 주변(또는 지도 전체)에서 마지막 스캔으로 전역 탐색 후 ICP 로 다듬어 자세 재설정
+    // 검증을 통과하지 못하면 None (자세는 그대로)
+    pub fn relocalize(
+        &mut self,
+        hint: Pose,
+        config: &RelocalizeConfig,
+    ) -> Result<Option<Relocalization>> {
+        if self.last_scan.is_empty() {
+            return Ok(None);
+        }
+        let [x, y, _] = hint.position;
+        let radius = if config.search_radius > 0.0 {
+            config.search_radius + config.scan_context.max_range
+        } else {
+            0.0
+        };
+        let region = self.tiles.read_region(x as f32, y as f32, radius)?;
+        let Some(found) = relocalization::relocalize(&region, &self.last_scan, &hint, config)
+        else {
+            return Ok(None);
+        };
+
+        // 격자 해상도 오차가 남아 있으므로 넓은 대응 거리로 맞춘 뒤 좁혀서 다시 정합
+        let coarse = IcpConfig {
+            max_distance: self.icp.max_distance * 3.0,
+            ..self.icp
+        };
+        let target = VoxelIndex::new(region, coarse.max_distance);
+        let Some(rough) = registration::icp(&self.last_scan, &target, found.pose, &coarse) else {
+            return Ok(None);
+        };
+        let target = VoxelIndex::new(target.points().to_vec(), self.icp.max_distance);
+        let refined = registration::icp(&self.last_scan, &target, rough.pose, &self.icp)
+            .filter(|r| r.fitness >= self.config.min_fitness && r.rmse <= self.config.max_rmse);
+        Ok(refined.map(|result| {
+            self.reset(result.pose);
+            Relocalization {
+                pose: result.pose,
+                ..found
+            }
+        }))
+    }
+
+    // 현재 위치 주변 타일을 갱신하고 바뀌었으면 검색 색인을 다시 만듦
+    fn refresh_map(&mut self) -> Result<()> {
+        let [x, y, _] = self.pose.position;
+        if self.tiles.update(x as f32, y as f32)? || self.index.is_none() {
+            let points: Vec<[f32; 3]> = self.tiles.points().copied().collect();
+            self.pyramid = self
+                .ndt
+                .as_ref()
+                .map(|config| NdtPyramid::new(&points, config));
+            self.index = Some(VoxelIndex::new(points, self.icp.max_distance));
+        }
+        Ok(())
+    }
+
+    pub fn localize(&mut self, points: &[[f32; 3]]) -> Result<LocalizeResult> {
+        let previous = self.pose;
+        let predicted = previous.compose(&self.velocity);
+        self.pose = predicted;
+        self.refresh_map()?;
+
+        self.last_scan = self.compute.voxel_downsample(points, self.config.voxel);
+        let scan = &self.last_scan;
+        let icp = self
+            .index
+            .as_ref()
+            .filter(|index| !index.is_empty())
+            .and_then(|index| match (&self.ndt, &self.pyramid) {
+                (Some(config), Some(pyramid)) => {
+                    let result = ndt::ndt(scan, pyramid, predicted, config)?;
+                    // ICP 와 같은 기준으로 받아들일지 판단하도록 최근접 대응으로 평가
+                    let (fitness, rmse) =
+                        registration::evaluate(scan, index, &result.pose, self.icp.max_distance);
+                    Some(IcpResult {
+                        pose: result.pose,
+                        fitness,
+                        rmse,
+                        iterations: result.iterations,
+                        converged: result.converged,
+                    })
+                }
+                _ => {
+                    registration::icp_with(self.compute.as_ref(), scan, index, predicted, &self.icp)
+                }
+            });
+        let accepted = icp.filter(|result| {
+            result.fitness >= self.config.min_fitness && result.rmse <= self.config.max_rmse
+        });
+        let mut covariance = None;
+        match (accepted, &self.index) {
+            (Some(result), Some(index)) => {
+                self.velocity = previous.inverse().compose(&result.pose);
+                self.pose = result.pose;
+                self.failures = 0;
+                covariance =
+                    registration::covariance(scan, index, &result.pose, self.icp.max_distance).map(
+                        |mut c| {
+                            let scale = 1.0 / result.fitness.max(1e-3);
+                            for row in c.covariance.iter_mut() {
+                                row.iter_mut().for_each(|v| *v *= scale);
+                            }
+                            c
+                        },
+                    );
+            }
+            _ => self.failures += 1,
+        }
+        Ok(LocalizeResult {
+            pose: self.pose,
+            icp,
+            matched: accepted.is_some(),
+            degenerate: covariance
+                .is_some_and(|c| c.condition() < self.config.degeneracy_threshold),
+            covariance,
+        })
+    }
+}
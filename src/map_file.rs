@@ -0,0 +1,417 @@
+use crate::cloud::PointXYZI;
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// 지도 파일 (little endian)
+//   헤더: MAGIC, 블록 크기, 양자화 해상도, 포인트/블록 수, 범위, 색인 위치, frame_id
+//   블록: x/y 격자 한 칸의 기둥, 최소 z 와 포인트 (블록 원점 기준 u16 x/y/z + u8 intensity)
+//   색인: 블록 키, 파일 위치, 포인트 수 (필요한 블록만 읽을 수 있게 파일 끝에 둠)
+const MAGIC: &[u8; 8] = b"LVXMAP01";
+const POINT_BYTES: usize = 7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapHeader {
+    // 블록 한 변 (m), 포인트 좌표 양자화 단위 (m)
+    pub block_size: f32,
+    pub resolution: f32,
+    pub points: u64,
+    pub blocks: u32,
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+    pub frame_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockKey {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BlockEntry {
+    pub key: BlockKey,
+    offset: u64,
+    pub count: u32,
+}
+
+impl BlockKey {
+    pub fn of(x: f32, y: f32, block_size: f32) -> Self {
+        BlockKey {
+            x: (x / block_size).floor() as i32,
+            y: (y / block_size).floor() as i32,
+        }
+    }
+
+    // 블록 중심 (x, y)
+    pub fn center(&self, block_size: f32) -> [f32; 2] {
+        [
+            (self.x as f32 + 0.5) * block_size,
+            (self.y as f32 + 0.5) * block_size,
+        ]
+    }
+}
+
+fn put_f32(out: &mut Vec<u8>, v: f32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+// 바이트 슬라이스를 앞에서부터 읽음
+struct Cursor<'a>(&'a [u8]);
+
+impl Cursor<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.0.len() < N {
+            bail!("지도 파일이 잘렸습니다");
+        }
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+// 포인트를 블록으로 나눠 저장, block_size / resolution 이 u16 범위를 넘으면 오류
+pub fn save(
+    path: &Path,
+    frame_id: &str,
+    points: &[PointXYZI],
+    block_size: f32,
+    resolution: f32,
+) -> Result<MapHeader> {
+    if block_size <= 0.0 || resolution <= 0.0 || block_size / resolution > u16::MAX as f32 {
+        bail!(
+            "블록 크기 {} / 해상도 {} 조합을 쓸 수 없습니다",
+            block_size,
+            resolution
+        );
+    }
+    let mut blocks: BTreeMap<BlockKey, Vec<&PointXYZI>> = BTreeMap::new();
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for p in points {
+        let xyz = [p.x, p.y, p.z];
+        if !xyz.iter().all(|v| v.is_finite()) {
+            continue;
+        }
+        for k in 0..3 {
+            min[k] = min[k].min(xyz[k]);
+            max[k] = max[k].max(xyz[k]);
+        }
+        blocks
+            .entry(BlockKey::of(p.x, p.y, block_size))
+            .or_default()
+            .push(p);
+    }
+
+    let file = File::create(path).with_context(|| format!("{} 생성 실패", path.display()))?;
+    let mut out = BufWriter::new(file);
+    let header = MapHeader {
+        block_size,
+        resolution,
+        points: blocks.values().map(|b| b.len() as u64).sum(),
+        blocks: blocks.len() as u32,
+        min,
+        max,
+        frame_id: frame_id.to_string(),
+    };
+
+    // 색인 위치는 블록을 다 쓴 뒤에 알 수 있으므로 헤더 길이를 먼저 계산
+    let mut head = Vec::new();
+    head.extend_from_slice(MAGIC);
+    put_f32(&mut head, block_size);
+    put_f32(&mut head, resolution);
+    head.extend_from_slice(&header.points.to_le_bytes());
+    head.extend_from_slice(&header.blocks.to_le_bytes());
+    for v in min.iter().chain(&max) {
+        put_f32(&mut head, *v);
+    }
+    let index_offset_at = head.len();
+    head.extend_from_slice(&0u64.to_le_bytes());
+    head.extend_from_slice(&(frame_id.len() as u32).to_le_bytes());
+    head.extend_from_slice(frame_id.as_bytes());
+
+    let mut offset = head.len() as u64;
+    let mut index = Vec::with_capacity(blocks.len());
+    let mut body = Vec::new();
+    let mut block_bytes = Vec::new();
+    for (key, block) in &blocks {
+        let base_z = block.iter().map(|p| p.z).fold(f32::INFINITY, f32::min);
+        let origin = [key.x as f32 * block_size, key.y as f32 * block_size, base_z];
+        block_bytes.clear();
+        put_f32(&mut block_bytes, base_z);
+        for p in block {
+            for (v, o) in [p.x, p.y, p.z].iter().zip(origin) {
+                let q = ((v - o) / resolution).round().clamp(0.0, u16::MAX as f32) as u16;
+                block_bytes.extend_from_slice(&q.to_le_bytes());
+            }
+            block_bytes.push(p.intensity.round().clamp(0.0, 255.0) as u8);
+        }
+        index.push(BlockEntry {
+            key: *key,
+            offset,
+            count: block.len() as u32,
+        });
+        offset += block_bytes.len() as u64;
+        body.extend_from_slice(&block_bytes);
+    }
+    head[index_offset_at..index_offset_at + 8].copy_from_slice(&offset.to_le_bytes());
+
+    out.write_all(&head)?;
+    out.write_all(&body)?;
+    for entry in &index {
+        out.write_all(&entry.key.x.to_le_bytes())?;
+        out.write_all(&entry.key.y.to_le_bytes())?;
+        out.write_all(&entry.offset.to_le_bytes())?;
+        out.write_all(&entry.count.to_le_bytes())?;
+    }
+    out.flush()?;
+    Ok(header)
+}
+
+// 헤더와 색인만 읽어두고 블록은 요청할 때 읽음
+pub struct MapReader {
+    file: BufReader<File>,
+    header: MapHeader,
+    index: HashMap<BlockKey, BlockEntry>,
+}
+
+impl MapReader {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("{} 열기 실패", path.display()))?;
+        let mut file = BufReader::new(file);
+
+        let mut fixed = [0u8; 8 + 4 + 4 + 8 + 4 + 24 + 8 + 4];
+        file.read_exact(&mut fixed)
+            .with_context(|| format!("{} 헤더 읽기 실패", path.display()))?;
+        if &fixed[..8] != MAGIC {
+            bail!("{} 는 지도 파일이 아닙니다", path.display());
+        }
+        let mut c = Cursor(&fixed[8..]);
+        let block_size = c.f32()?;
+        let resolution = c.f32()?;
+        let points = c.u64()?;
+        let blocks = c.u32()?;
+        let mut bounds = [0f32; 6];
+        for v in bounds.iter_mut() {
+            *v = c.f32()?;
+        }
+        let index_offset = c.u64()?;
+        let mut frame_id = vec![0u8; c.u32()? as usize];
+        file.read_exact(&mut frame_id)?;
+
+        file.seek(SeekFrom::Start(index_offset))?;
+        let mut raw = vec![0u8; blocks as usize * 20];
+        file.read_exact(&mut raw)
+            .with_context(|| format!("{} 색인 읽기 실패", path.display()))?;
+        let mut c = Cursor(&raw);
+        let mut index = HashMap::with_capacity(blocks as usize);
+        for _ in 0..blocks {
+            let key = BlockKey {
+                x: c.i32()?,
+                y: c.i32()?,
+            };
+            let offset = c.u64()?;
+            let count = c.u32()?;
+            index.insert(key, BlockEntry { key, offset, count });
+        }
+
+        Ok(MapReader {
+            file,
+            header: MapHeader {
+                block_size,
+                resolution,
+                points,
+                blocks,
+                min: [bounds[0], bounds[1], bounds[2]],
+                max: [bounds[3], bounds[4], bounds[5]],
+                frame_id: String::from_utf8_lossy(&frame_id).into_owned(),
+            },
+            index,
+        })
+    }
+
+    pub fn header(&self) -> &MapHeader {
+        &self.header
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &BlockEntry> {
+        self.index.values()
+    }
+
+    // 중심이 (x, y) 에서 radius 안에 드는 블록 (블록 대각선 절반만큼 여유)
+    pub fn blocks_within(&self, x: f32, y: f32, radius: f32) -> Vec<BlockKey> {
+        let size = self.header.block_size;
+        let reach = radius + size * std::f32::consts::FRAC_1_SQRT_2;
+        let mut keys: Vec<BlockKey> = self
+            .index
+            .keys()
+            .filter(|key| {
+                let [cx, cy] = key.center(size);
+                (cx - x).powi(2) + (cy - y).powi(2) <= reach * reach
+            })
+            .copied()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    // 없는 블록이면 빈 목록
+    pub fn read_block(&mut self, key: BlockKey) -> Result<Vec<PointXYZI>> {
+        let Some(entry) = self.index.get(&key).copied() else {
+            return Ok(Vec::new());
+        };
+        self.file.seek(SeekFrom::Start(entry.offset))?;
+        let mut raw = vec![0u8; 4 + entry.count as usize * POINT_BYTES];
+        self.file.read_exact(&mut raw)?;
+        let base_z = f32::from_le_bytes(raw[..4].try_into().unwrap());
+        let size = self.header.block_size;
+        let origin = [key.x as f32 * size, key.y as f32 * size, base_z];
+        let res = self.header.resolution;
+        Ok(raw[4..]
+            .chunks_exact(POINT_BYTES)
+            .map(|p| {
+                let q = |i: usize| u16::from_le_bytes([p[i * 2], p[i * 2 + 1]]) as f32 * res;
+                PointXYZI {
+                    x: origin[0] + q(0),
+                    y: origin[1] + q(1),
+                    z: origin[2] + q(2),
+                    intensity: p[6] as f32,
+                }
+            })
+            .collect())
+    }
+
+    pub fn read_all(&mut self) -> Result<Vec<PointXYZI>> {
+        let mut keys: Vec<BlockKey> = self.index.keys().copied().collect();
+        keys.sort();
+        let mut points = Vec::with_capacity(self.header.points as usize);
+        for key in keys {
+            points.extend(self.read_block(key)?);
+        }
+        Ok(points)
+    }
+}
+
+pub fn load(path: &Path) -> Result<(MapHeader, Vec<PointXYZI>)> {
+    let mut reader = MapReader::open(path)?;
+    let points = reader.read_all()?;
+    Ok((reader.header, points))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // 시험마다 다른 임시 파일 (끝나면 지움)
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            TempFile(std::env::temp_dir().join(format!(
+                "rust_lidar_{}_{}.map",
+                std::process::id(),
+                name
+            )))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn point(x: f32, y: f32, z: f32, intensity: f32) -> PointXYZI {
+        PointXYZI { x, y, z, intensity }
+    }
+
+    fn sample() -> Vec<PointXYZI> {
+        (0..500)
+            .map(|i| {
+                let t = i as f32 * 0.37;
+                point(
+                    (t * 1.3).sin() * 40.0,
+                    (t * 0.7).cos() * 25.0 - 10.0,
+                    (t * 0.11).sin() * 3.0,
+                    (i % 256) as f32,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn save_and_load_round_trip_within_resolution() {
+        let file = TempFile::new("round_trip");
+        let mut points = sample();
+        points.push(point(f32::NAN, 0.0, 0.0, 0.0));
+        let saved = save(&file.0, "map", &points, 10.0, 0.01).unwrap();
+        let (header, loaded) = load(&file.0).unwrap();
+        assert_eq!(header, saved);
+        assert_eq!(header.points, 500);
+        assert_eq!(header.frame_id, "map");
+
+        // 블록 키 순서, 블록 안은 입력 순서
+        let mut expected = sample();
+        expected.sort_by_key(|p| BlockKey::of(p.x, p.y, 10.0));
+        assert_eq!(loaded.len(), expected.len());
+        for (a, b) in expected.iter().zip(&loaded) {
+            for (u, v) in [(a.x, b.x), (a.y, b.y), (a.z, b.z)] {
+                assert!((u - v).abs() <= 0.006, "{:?} != {:?}", a, b);
+            }
+            assert_eq!(a.intensity, b.intensity);
+        }
+    }
+
+    #[test]
+    fn reader_returns_only_requested_blocks() {
+        let file = TempFile::new("blocks");
+        save(&file.0, "map", &sample(), 10.0, 0.01).unwrap();
+        let mut reader = MapReader::open(&file.0).unwrap();
+        let keys = reader.blocks_within(0.0, 0.0, 5.0);
+        assert!(!keys.is_empty());
+        let points = sample();
+        for key in keys {
+            let expected = points
+                .iter()
+                .filter(|p| BlockKey::of(p.x, p.y, 10.0) == key)
+                .count();
+            assert_eq!(reader.read_block(key).unwrap().len(), expected);
+        }
+        assert!(reader
+            .read_block(BlockKey { x: 1000, y: 1000 })
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        let file = TempFile::new("bad");
+        assert!(save(&file.0, "map", &sample(), 10.0, 0.0001).is_err());
+        std::fs::write(&file.0, b"NOTAMAP0").unwrap();
+        assert!(MapReader::open(&file.0).is_err());
+
+        save(&file.0, "map", &sample(), 10.0, 0.01).unwrap();
+        let bytes = std::fs::read(&file.0).unwrap();
+        std::fs::write(&file.0, &bytes[..bytes.len() - 10]).unwrap();
+        assert!(MapReader::open(&file.0).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+// 누적 포인트 클라우드로 현장 표면 메시를 만듦 (빠른 디지털 트윈 확인용)
+// 2.5D 높이장 격자 삼각화: 셀마다 꼭짓점 하나 (x/y 평균, z 중앙값) 를 두고
+// 이웃 네 셀이 모두 있으면 높이 차가 작은 대각선 방향으로 삼각형 두 개를 만듦
+// (세로 벽이나 처마 아래처럼 같은 x/y 에 여러 높이가 있는 곳은 윗면만 남음)
+
+#[derive(Debug, Clone, Copy)]
+pub struct MeshConfig {
+    // 격자 셀 크기 (m)
+    pub cell: f32,
+    // 이보다 포인트가 적은 셀은 비어 있는 것으로 봄
+    pub min_points: usize,
+    // 이웃 꼭짓점 높이 차가 이보다 크면 (벽, 물체 경계) 삼각형을 만들지 않음 (m)
+    pub max_step: f32,
+}
+
+impl Default for MeshConfig {
+    fn default() -> Self {
+        MeshConfig {
+            cell: 0.2,
+            min_points: 2,
+            max_step: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub vertices: Vec<[f32; 3]>,
+    // 꼭짓점 번호, 위(+z)에서 볼 때 반시계 방향
+    pub triangles: Vec<[u32; 3]>,
+}
+
+pub fn height_field(points: &[[f32; 3]], config: &MeshConfig) -> Result<Mesh> {
+    if config.cell <= 0.0 {
+        bail!("메시 셀 크기는 0 보다 커야 합니다");
+    }
+    let mut cells: HashMap<(i32, i32), Vec<[f32; 3]>> = HashMap::new();
+    for &p in points {
+        let key = (
+            (p[0] / config.cell).floor() as i32,
+            (p[1] / config.cell).floor() as i32,
+        );
+        cells.entry(key).or_default().push(p);
+    }
+
+    let mut mesh = Mesh::default();
+    let mut index = HashMap::new();
+    let mut cells: Vec<_> = cells.into_iter().collect();
+    cells.sort_unstable_by_key(|(key, _)| *key);
+    for (key, mut cell) in cells {
+        if cell.len() < config.min_points.max(1) {
+            continue;
+        }
+        let n = cell.len() as f32;
+        let x = cell.iter().map(|p| p[0]).sum::<f32>() / n;
+        let y = cell.iter().map(|p| p[1]).sum::<f32>() / n;
+        cell.sort_unstable_by(|a, b| a[2].total_cmp(&b[2]));
+        let z = cell[cell.len() / 2][2];
+        index.insert(key, mesh.vertices.len() as u32);
+        mesh.vertices.push([x, y, z]);
+    }
+
+    let z = |i: u32| mesh.vertices[i as usize][2];
+    let mut triangles = Vec::new();
+    for (&(ix, iy), &a) in &index {
+        // a: (ix, iy), b: (ix+1, iy), c: (ix+1, iy+1), d: (ix, iy+1)
+        let (Some(&b), Some(&c), Some(&d)) = (
+            index.get(&(ix + 1, iy)),
+            index.get(&(ix + 1, iy + 1)),
+            index.get(&(ix, iy + 1)),
+        ) else {
+            continue;
+        };
+        let quad = if (z(a) - z(c)).abs() <= (z(b) - z(d)).abs() {
+            [[a, b, c], [a, c, d]]
+        } else {
+            [[a, b, d], [b, c, d]]
+        };
+        for tri in quad {
+            let (lo, hi) = tri
+                .iter()
+                .map(|&v| z(v))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| {
+                    (lo.min(v), hi.max(v))
+                });
+            if hi - lo <= config.max_step {
+                triangles.push(tri);
+            }
+        }
+    }
+    triangles.sort_unstable();
+    mesh.triangles = triangles;
+    Ok(mesh)
+}
+
+impl Mesh {
+    // 확장자 (.obj, .ply) 로 형식 선택
+    pub fn write(&self, path: &Path) -> Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("obj") => self.write_obj(path),
+            Some("ply") => self.write_ply(path),
+            _ => bail!(
+                "{} : 메시 파일은 .obj 또는 .ply 여야 합니다",
+                path.display()
+            ),
+        }
+    }
+
+    // Wavefront OBJ (텍스트, 꼭짓점 번호는 1부터)
+    pub fn write_obj(&self, path: &Path) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(
+            out,
+            "# livox mesh: {} vertices, {} faces",
+            self.vertices.len(),
+            self.triangles.len()
+        )?;
+        for [x, y, z] in &self.vertices {
+            writeln!(out, "v {} {} {}", x, y, z)?;
+        }
+        for [a, b, c] in &self.triangles {
+            writeln!(out, "f {} {} {}", a + 1, b + 1, c + 1)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    // binary little endian PLY (MeshLab, CloudCompare, Blender 에서 바로 열림)
+    pub fn write_ply(&self, path: &Path) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "ply")?;
+        writeln!(out, "format binary_little_endian 1.0")?;
+        writeln!(out, "element vertex {}", self.vertices.len())?;
+        writeln!(out, "property float x")?;
+        writeln!(out, "property float y")?;
+        writeln!(out, "property float z")?;
+        writeln!(out, "element face {}", self.triangles.len())?;
+        writeln!(out, "property list uchar uint vertex_indices")?;
+        writeln!(out, "end_header")?;
+        for v in &self.vertices {
+            for c in v {
+                out.write_all(&c.to_le_bytes())?;
+            }
+        }
+        for tri in &self.triangles {
+            out.write_all(&[3u8])?;
+            for i in tri {
+                out.write_all(&i.to_le_bytes())?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+// 포인트 코덱이 쓰는 메시지 타입
+// ros 기능이 켜져 있으면 ROS 2 메시지를 그대로, 아니면 (ROS 없는 프로젝트, wasm32) 필드가 같은 일반 구조체 사용
+#[cfg(feature = "ros")]
+pub use builtin_interfaces::msg::Time;
+#[cfg(feature = "ros")]
+pub use sensor_msgs::msg::{PointCloud2, PointField};
+#[cfg(feature = "ros")]
+pub use std_msgs::msg::Header;
+
+#[cfg(not(feature = "ros"))]
+pub use plain::*;
+
+#[cfg(not(feature = "ros"))]
+mod plain {
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct Time {
+        pub sec: i32,
+        pub nanosec: u32,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct Header {
+        pub stamp: Time,
+        pub frame_id: String,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct PointField {
+        pub name: String,
+        pub offset: u32,
+        pub datatype: u8,
+        pub count: u32,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct PointCloud2 {
+        pub header: Header,
+        pub height: u32,
+        pub width: u32,
+        pub fields: Vec<PointField>,
+        pub is_bigendian: bool,
+        pub point_step: u32,
+        pub row_step: u32,
+        pub data: Vec<u8>,
+        pub is_dense: bool,
+    }
+}
@@ -0,0 +1,343 @@
+use crate::pose::Pose;
+use crate::registration::symmetric_eigen;
+use std::collections::HashMap;
+
+type Voxel = (i32, i32, i32);
+
+fn voxel_of(p: [f64; 3], size: f64) -> Voxel {
+    (
+        (p[0] / size).floor() as i32,
+        (p[1] / size).floor() as i32,
+        (p[2] / size).floor() as i32,
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct NdtConfig {
+    // 거친 것부터 고운 순서의 복셀 크기 (m), 앞 단계 결과가 다음 단계 초기값
+    pub resolutions: Vec<f32>,
+    // 단계별 최대 반복과 수렴 기준 (m, rad)
+    pub max_iterations: usize,
+    pub tolerance: f64,
+    // 분포를 만들 최소 포인트 수
+    pub min_points: usize,
+}
+
+impl Default for NdtConfig {
+    fn default() -> Self {
+        NdtConfig {
+            resolutions: vec![4.0, 2.0, 1.0],
+            max_iterations: 20,
+            tolerance: 1e-4,
+            min_points: 5,
+        }
+    }
+}
+
+// 복셀 하나의 정규 분포 (평균과 공분산 역행렬)
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    mean: [f64; 3],
+    information: [[f64; 3]; 3],
+}
+
+impl Cell {
+    fn new(points: &[[f64; 3]]) -> Option<Self> {
+        let n = points.len() as f64;
+        let mut mean = [0.0; 3];
+        for p in points {
+            for (m, v) in mean.iter_mut().zip(p) {
+                *m += v / n;
+            }
+        }
+        let mut cov = [[0.0; 3]; 3];
+        for p in points {
+            let d: [f64; 3] = std::array::from_fn(|k| p[k] - mean[k]);
+            for a in 0..3 {
+                for b in 0..3 {
+                    cov[a][b] += d[a] * d[b] / (n - 1.0).max(1.0);
+                }
+            }
+        }
+        // 평면/직선 분포에서 역행렬이 발산하지 않도록 작은 고유값을 최대값의 1% 로 올림
+        let (values, v) = symmetric_eigen(cov);
+        let max = values.iter().copied().fold(0.0, f64::max);
+        if max <= 0.0 {
+            return None;
+        }
+        let floor = (max * 0.01).max(1e-6);
+        let mut information = [[0.0; 3]; 3];
+        for k in 0..3 {
+            let inv = 1.0 / values[k].max(floor);
+            for a in 0..3 {
+                for b in 0..3 {
+                    information[a][b] += v[a][k] * v[b][k] * inv;
+                }
+            }
+        }
+        Some(Cell { mean, information })
+    }
+
+    fn mahalanobis(&self, p: [f64; 3]) -> ([f64; 3], f64) {
+        let d: [f64; 3] = std::array::from_fn(|k| p[k] - self.mean[k]);
+        let m2 = (0..3)
+            .map(|a| {
+                (0..3)
+                    .map(|b| d[a] * self.information[a][b] * d[b])
+                    .sum::<f64>()
+            })
+            .sum();
+        (d, m2)
+    }
+}
+
+// 한 해상도의 NDT 복셀 지도
+pub struct NdtMap {
+    size: f64,
+    cells: HashMap<Voxel, Cell>,
+}
+
+impl NdtMap {
+    pub fn new(points: &[[f32; 3]], size: f32, min_points: usize) -> Self {
+        let size = (size as f64).max(1e-2);
+        let mut grouped: HashMap<Voxel, Vec<[f64; 3]>> = HashMap::new();
+        for p in points {
+            let p = p.map(|v| v as f64);
+            grouped.entry(voxel_of(p, size)).or_default().push(p);
+        }
+        let cells = grouped
+            .into_iter()
+            .filter(|(_, points)| points.len() >= min_points.max(3))
+            .filter_map(|(key, points)| Cell::new(&points).map(|cell| (key, cell)))
+            .collect();
+        NdtMap { size, cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // p 가 속한 복셀과 면으로 맞닿은 6 복셀 중 마할라노비스 거리가 가장 작은 분포
+    fn nearest(&self, p: [f64; 3]) -> Option<(&Cell, [f64; 3], f64)> {
+        let (x, y, z) = voxel_of(p, self.size);
+        [
+            (0, 0, 0),
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .filter_map(|(dx, dy, dz)| self.cells.get(&(x + dx, y + dy, z + dz)))
+        .map(|cell| {
+            let (d, m2) = cell.mahalanobis(p);
+            (cell, d, m2)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+}
+
+// 해상도별 NDT 지도 (거친 것부터)
+pub struct NdtPyramid {
+    levels: Vec<NdtMap>,
+}
+
+impl NdtPyramid {
+    pub fn new(points: &[[f32; 3]], config: &NdtConfig) -> Self {
+        NdtPyramid {
+            levels: config
+                .resolutions
+                .iter()
+                .map(|&size| NdtMap::new(points, size, config.min_points))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.iter().all(NdtMap::is_empty)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NdtResult {
+    pub pose: Pose,
+    // 마지막 단계에서 분포에 대응된 포인트 비율
+    pub matched_ratio: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// 마할라노비스 거리 제곱 m2 가 gate 보다 먼 대응은 제외하고 나머지는 exp(-m2 * softness / 2) 로 가중
+// 거친 단계는 초기 오차가 크므로 넓고 완만하게, 마지막 단계는 좁고 가파르게 (정밀도)
+const COARSE_GATE: (f64, f64) = (100.0, 0.05);
+const FINE_GATE: (f64, f64) = (16.0, 1.0);
+
+// 한 번의 Gauss-Newton 갱신, 대응이 부족하면 None
+fn step(
+    source: &[[f32; 3]],
+    map: &NdtMap,
+    pose: &Pose,
+    (gate, softness): (f64, f64),
+) -> Option<(Pose, usize)> {
+    let mut h = [[0.0; 6]; 6];
+    let mut g = [0.0; 6];
+    let mut matched = 0;
+    for p in source {
+        let x = pose.transform_point(p.map(|v| v as f64));
+        let Some((cell, d, m2)) = map.nearest(x) else {
+            continue;
+        };
+        if m2 > gate {
+            continue;
+        }
+        matched += 1;
+        let w = (-0.5 * m2 * softness).exp();
+        // x' = x + t + w × x 의 야코비안 (열 단위)
+        let j: [[f64; 3]; 6] = [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, -x[2], x[1]],
+            [x[2], 0.0, -x[0]],
+            [-x[1], x[0], 0.0],
+        ];
+        let info = &cell.information;
+        let oj: [[f64; 3]; 6] =
+            j.map(|col| std::array::from_fn(|a| (0..3).map(|b| info[a][b] * col[b]).sum()));
+        for a in 0..6 {
+            g[a] += w * (0..3).map(|k| oj[a][k] * d[k]).sum::<f64>();
+            for b in 0..6 {
+                h[a][b] += w * (0..3).map(|k| j[a][k] * oj[b][k]).sum::<f64>();
+            }
+        }
+    }
+    if matched < 6 {
+        return None;
+    }
+    // 고유값 분해로 풀어서 구속이 없는 방향은 움직이지 않음
+    let (values, v) = symmetric_eigen(h);
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let mut delta = [0.0; 6];
+    for k in 0..6 {
+        if values[k] <= max * 1e-9 {
+            continue;
+        }
+        let proj = (0..6).map(|a| v[a][k] * g[a]).sum::<f64>() / values[k];
+        for (a, d) in delta.iter_mut().enumerate() {
+            *d -= v[a][k] * proj;
+        }
+    }
+    let update = Pose::from_rotation_vector(
+        [delta[0], delta[1], delta[2]],
+        [delta[3], delta[4], delta[5]],
+    );
+    Some((update, matched))
+}
+
+// 거친 해상도부터 차례로 정합 (넓은 수렴 범위), source 를 target 좌표계로 옮기는 자세
+pub fn ndt(
+    source: &[[f32; 3]],
+    target: &NdtPyramid,
+    initial: Pose,
+    config: &NdtConfig,
+) -> Option<NdtResult> {
+    let mut pose = initial;
+    let mut iterations = 0;
+    let mut converged = false;
+    let mut matched = 0;
+    let levels: Vec<&NdtMap> = target.levels.iter().filter(|map| !map.is_empty()).collect();
+    for (i, map) in levels.iter().enumerate() {
+        let gate = if i + 1 == levels.len() {
+            FINE_GATE
+        } else {
+            COARSE_GATE
+        };
+        converged = false;
+        for _ in 0..config.max_iterations.max(1) {
+            iterations += 1;
+            let Some((update, count)) = step(source, map, &pose, gate) else {
+                break;
+            };
+            matched = count;
+            pose = update.compose(&pose);
+            if update.translation_norm() < config.tolerance && update.angle() < config.tolerance {
+                converged = true;
+                break;
+            }
+        }
+    }
+    (matched > 0).then_some(NdtResult {
+        pose,
+        matched_ratio: matched as f64 / source.len().max(1) as f64,
+        iterations,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 바닥, 두 벽, 상자 하나를 불규칙하게 샘플링한 장면 (고정 시드)
+    fn scene() -> Vec<[f32; 3]> {
+        let mut seed = 12345u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut points = Vec::new();
+        for _ in 0..3000 {
+            let (a, b) = (next() * 10.0 - 5.0, next() * 10.0 - 5.0);
+            points.push([a, b, 0.0]);
+            points.push([5.0, a, b * 0.3 + 1.5]);
+            points.push([a, 5.0, b * 0.3 + 1.5]);
+        }
+        for _ in 0..1000 {
+            let (a, b) = (next(), next());
+            points.push([1.0 + a, -2.0, b]);
+            points.push([1.0, -2.0 + a, b]);
+            points.push([1.0 + a, -2.0 + b, 1.0]);
+        }
+        points
+    }
+
+    fn apply(pose: &Pose, p: [f32; 3]) -> [f32; 3] {
+        pose.transform_point(p.map(|v| v as f64)).map(|v| v as f32)
+    }
+
+    #[test]
+    fn pyramid_converges_from_large_offset() {
+        let target = scene();
+        let truth = Pose::from_rpy([0.8, -0.6, 0.1], 0.0, 0.0, 0.12);
+        let source: Vec<[f32; 3]> = target
+            .iter()
+            .step_by(5)
+            .map(|p| apply(&truth.inverse(), *p))
+            .collect();
+        let config = NdtConfig::default();
+        let pyramid = NdtPyramid::new(&target, &config);
+
+        let result = ndt(&source, &pyramid, Pose::IDENTITY, &config).unwrap();
+        let error = truth.inverse().compose(&result.pose);
+        assert!(
+            error.translation_norm() < 0.01 && error.angle() < 2e-3,
+            "{:?}",
+            result
+        );
+        assert!(result.matched_ratio > 0.9);
+    }
+
+    #[test]
+    fn empty_target_gives_no_result() {
+        let config = NdtConfig::default();
+        let pyramid = NdtPyramid::new(&[], &config);
+        assert!(pyramid.is_empty());
+        assert!(ndt(&scene(), &pyramid, Pose::IDENTITY, &config).is_none());
+    }
+}
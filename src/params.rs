@@ -0,0 +1,104 @@
+use crate::sidecar::json_string;
+use anyhow::Result;
+use rclrs::Node;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+// 노드별로 선언한 파라미터의 실제 값 (노드 전체 이름 -> (이름, params 파일 YAML 값)), 설정 스냅샷용
+// 한 프로세스에 노드가 여럿이어도 (통합 시험 등) 섞이지 않고, 스냅샷을 만들 때 가져가며 비움
+static RECORDED: Mutex<BTreeMap<String, Vec<(String, String)>>> = Mutex::new(BTreeMap::new());
+
+fn record(node: &Node, name: &str, value: String) {
+    let mut recorded = RECORDED.lock().unwrap();
+    let params = recorded.entry(node.fully_qualified_name()).or_default();
+    // 같은 이름의 노드를 다시 만들어 선언하면 값만 바꿈
+    match params.iter_mut().find(|(n, _)| n == name) {
+        Some(param) => param.1 = value,
+        None => params.push((name.to_string(), value)),
+    }
+}
+
+fn yaml_list<T>(values: &[T], item: impl Fn(&T) -> String) -> String {
+    let items: Vec<String> = values.iter().map(item).collect();
+    format!("[{}]", items.join(", "))
+}
+
+// node 가 지금까지 선언한 파라미터와 값 (선언 순서), 가져간 기록은 지움
+pub fn take_recorded(node: &Node) -> Vec<(String, String)> {
+    RECORDED
+        .lock()
+        .unwrap()
+        .remove(&node.fully_qualified_name())
+        .unwrap_or_default()
+}
+
+// 노드 파라미터 선언 헬퍼 (기본값을 가진 mandatory 파라미터)
+pub fn string(node: &Node, name: &str, default: &str) -> Result<String> {
+    let value: Arc<str> = node
+        .declare_parameter(name)
+        .default(default.into())
+        .mandatory()?
+        .get();
+    record(node, name, json_string(&value));
+    Ok(value.to_string())
+}
+
+pub fn float(node: &Node, name: &str, default: f64) -> Result<f64> {
+    let value = node
+        .declare_parameter(name)
+        .default(default)
+        .mandatory()?
+        .get();
+    record(node, name, format!("{:?}", value));
+    Ok(value)
+}
+
+pub fn int(node: &Node, name: &str, default: i64) -> Result<i64> {
+    let value = node
+        .declare_parameter(name)
+        .default(default)
+        .mandatory()?
+        .get();
+    record(node, name, value.to_string());
+    Ok(value)
+}
+
+pub fn boolean(node: &Node, name: &str, default: bool) -> Result<bool> {
+    let value = node
+        .declare_parameter(name)
+        .default(default)
+        .mandatory()?
+        .get();
+    record(node, name, value.to_string());
+    Ok(value)
+}
+
+pub fn int_array(node: &Node, name: &str, default: &[i64]) -> Result<Vec<i64>> {
+    let value: Arc<[i64]> = node
+        .declare_parameter(name)
+        .default(default.into())
+        .mandatory()?
+        .get();
+    record(node, name, yaml_list(&value, |v| v.to_string()));
+    Ok(value.to_vec())
+}
+
+pub fn float_array(node: &Node, name: &str, default: &[f64]) -> Result<Vec<f64>> {
+    let value: Arc<[f64]> = node
+        .declare_parameter(name)
+        .default(default.into())
+        .mandatory()?
+        .get();
+    record(node, name, yaml_list(&value, |v| format!("{:?}", v)));
+    Ok(value.to_vec())
+}
+
+pub fn string_array(node: &Node, name: &str, default: &[&str]) -> Result<Vec<String>> {
+    let value: Arc<[Arc<str>]> = node
+        .declare_parameter(name)
+        .default(default.iter().map(|&s| Arc::from(s)).collect())
+        .mandatory()?
+        .get();
+    record(node, name, yaml_list(&value, |v| json_string(v)));
+    Ok(value.iter().map(|s| s.to_string()).collect())
+}
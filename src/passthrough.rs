@@ -0,0 +1,341 @@
+use crate::msg::{PointCloud2, PointField};
+use crate::point::{self, datatype, FieldSpec};
+use anyhow::{anyhow, bail, Result};
+use std::borrow::Cow;
+
+// 패스스루 모드: 26바이트 레이아웃으로 다시 인코딩하지 않고 원본 data 버퍼를 그대로 다룸
+// (입력에 있던 알 수 없는 필드/패딩도 보존)
+
+pub fn point_count(msg: &PointCloud2) -> usize {
+    if msg.point_step == 0 {
+        return 0;
+    }
+    msg.data.len() / msg.point_step as usize
+}
+
+// 정렬된 입력의 행 끝 패딩을 제자리에서 제거 (height/width 유지, 이후 point_step 단위로 읽음)
+pub fn pack_rows(msg: &mut PointCloud2) -> Result<()> {
+    let packed = match point::packed_data(msg)? {
+        Cow::Owned(data) => Some(data),
+        Cow::Borrowed(_) => None,
+    };
+    if let Some(data) = packed {
+        msg.data = data;
+        msg.row_step = msg.width * msg.point_step;
+    }
+    Ok(())
+}
+
+// height 행의 정렬된 메시지로 표시 (데이터는 그대로, 포인트 수가 height 로 나누어떨어져야 함)
+pub fn set_height(msg: &mut PointCloud2, height: usize) -> Result<()> {
+    let count = point_count(msg);
+    if height == 0 || !count.is_multiple_of(height) {
+        bail!("포인트 {}개를 {}행으로 나눌 수 없습니다", count, height);
+    }
+    msg.height = height as u32;
+    msg.width = (count / height) as u32;
+    msg.row_step = msg.width * msg.point_step;
+    Ok(())
+}
+
+pub fn field_spec(msg: &PointCloud2, name: &str) -> Result<FieldSpec> {
+    msg.fields
+        .iter()
+        .find(|f| f.name == name)
+        .map(|f| FieldSpec::new(f.offset as usize, f.datatype))
+        .ok_or_else(|| anyhow!("PointCloud2 에 '{}' 필드가 없습니다", name))
+}
+
+// keep[i] 가 true 인 포인트의 원본 바이트만 복사한 새 메시지 (point_step 이 0 이면 빈 메시지)
+pub fn select(msg: &PointCloud2, keep: &[bool]) -> PointCloud2 {
+    let step = msg.point_step as usize;
+    let mut data = Vec::with_capacity(keep.iter().filter(|&&k| k).count() * step);
+    for (chunk, _) in msg
+        .data
+        .chunks_exact(step.max(1))
+        .take(point_count(msg))
+        .zip(keep)
+        .filter(|(_, &keep)| keep)
+    {
+        data.extend_from_slice(chunk);
+    }
+
+    let width = data.len() / step.max(1);
+    PointCloud2 {
+        header: msg.header.clone(),
+        height: 1,
+        width: width as u32,
+        fields: msg.fields.clone(),
+        is_bigendian: msg.is_bigendian,
+        point_step: msg.point_step,
+        row_step: (width * step) as u32,
+        data,
+        is_dense: msg.is_dense,
+    }
+}
+
+// 필드 하나만 읽어서 keep 마스크 생성 (전체 디코드 없음)
+pub fn field_mask(msg: &PointCloud2, name: &str, pred: impl Fn(f64) -> bool) -> Result<Vec<bool>> {
+    let spec = field_spec(msg, name)?;
+    let step = msg.point_step as usize;
+    if step < spec.end() {
+        bail!("point_step({}) 이 '{}' 필드 범위보다 작습니다", step, name);
+    }
+    Ok(msg
+        .data
+        .chunks_exact(step)
+        .map(|chunk| pred(spec.read(chunk, 0, msg.is_bigendian)))
+        .collect())
+}
+
+// 필드 하나의 값을 모두 읽음 (필드가 없으면 None)
+pub fn field_values(msg: &PointCloud2, name: &str) -> Result<Option<Vec<f64>>> {
+    if !msg.fields.iter().any(|f| f.name == name) {
+        return Ok(None);
+    }
+    let spec = field_spec(msg, name)?;
+    let step = msg.point_step as usize;
+    if step < spec.end() {
+        bail!("point_step({}) 이 '{}' 필드 범위보다 작습니다", step, name);
+    }
+    Ok(Some(
+        msg.data
+            .chunks_exact(step)
+            .map(|chunk| spec.read(chunk, 0, msg.is_bigendian))
+            .collect(),
+    ))
+}
+
+// x/y/z 를 함께 읽어서 keep 마스크 생성
+pub fn xyz_mask(msg: &PointCloud2, pred: impl Fn([f64; 3]) -> bool) -> Result<Vec<bool>> {
+    let specs = [
+        field_spec(msg, "x")?,
+        field_spec(msg, "y")?,
+        field_spec(msg, "z")?,
+    ];
+    let step = msg.point_step as usize;
+    if let Some(spec) = specs.iter().find(|spec| spec.end() > step) {
+        bail!(
+            "point_step({}) 이 필드 범위({})보다 작습니다",
+            step,
+            spec.end()
+        );
+    }
+    Ok(msg
+        .data
+        .chunks_exact(step)
+        .map(|chunk| pred(specs.map(|spec| spec.read(chunk, 0, msg.is_bigendian))))
+        .collect())
+}
+
+// select 의 제자리 버전: 남길 포인트를 앞으로 당겨 복사(memmove)하고 버퍼를 자름
+// 반환값은 남은 포인트 수
+pub fn compact(msg: &mut PointCloud2, keep: &[bool]) -> usize {
+    let step = msg.point_step as usize;
+    let count = point_count(msg).min(keep.len());
+
+    let mut write = 0;
+    let mut read = 0;
+    while read < count {
+        if !keep[read] {
+            read += 1;
+            continue;
+        }
+        // 연속으로 남는 구간은 한 번에 이동
+        let run_start = read;
+        while read < count && keep[read] {
+            read += 1;
+        }
+        let run = read - run_start;
+        if write != run_start {
+            msg.data
+                .copy_within(run_start * step..read * step, write * step);
+        }
+        write += run;
+    }
+
+    msg.data.truncate(write * step);
+    msg.height = 1;
+    msg.width = write as u32;
+    msg.row_step = (write * step) as u32;
+    write
+}
+
+// 모든 포인트의 기존 필드 값을 value(i) 로 덮어씀
+pub fn set_field(
+    msg: &mut PointCloud2,
+    name: &str,
+    mut value: impl FnMut(usize) -> f64,
+) -> Result<()> {
+    let spec = field_spec(msg, name)?;
+    let step = msg.point_step as usize;
+    if step < spec.end() {
+        bail!("point_step({}) 이 '{}' 필드 범위보다 작습니다", step, name);
+    }
+    let big_endian = msg.is_bigendian;
+    for (i, chunk) in msg.data.chunks_exact_mut(step).enumerate() {
+        spec.write(chunk, 0, big_endian, value(i));
+    }
+    Ok(())
+}
+
+// x/y/z 를 함께 읽어서 f 의 결과로 덮어씀 (좌표 변환용)
+pub fn map_xyz(msg: &mut PointCloud2, mut f: impl FnMut([f64; 3]) -> [f64; 3]) -> Result<()> {
+    let specs = [
+        field_spec(msg, "x")?,
+        field_spec(msg, "y")?,
+        field_spec(msg, "z")?,
+    ];
+    let step = msg.point_step as usize;
+    if let Some(spec) = specs.iter().find(|spec| spec.end() > step) {
+        bail!(
+            "point_step({}) 이 필드 범위({})보다 작습니다",
+            step,
+            spec.end()
+        );
+    }
+    let big_endian = msg.is_bigendian;
+    for chunk in msg.data.chunks_exact_mut(step) {
+        let xyz = f(specs.map(|spec| spec.read(chunk, 0, big_endian)));
+        for (spec, value) in specs.iter().zip(xyz) {
+            spec.write(chunk, 0, big_endian, value);
+        }
+    }
+    Ok(())
+}
+
+// 각 포인트 뒤에 새 필드를 덧붙임 (point_step 증가, 기존 바이트는 그대로)
+pub fn append_field(
+    msg: &mut PointCloud2,
+    name: &str,
+    field_type: u8,
+    mut value: impl FnMut(usize) -> f64,
+) -> Result<()> {
+    let Some(size) = datatype::size(field_type) else {
+        bail!("필드 '{}' 의 datatype({}) 이 잘못됨", name, field_type);
+    };
+    if msg.fields.iter().any(|f| f.name == name) {
+        bail!("'{}' 필드가 이미 있습니다", name);
+    }
+
+    let old_step = msg.point_step as usize;
+    let new_step = old_step + size;
+    let spec = FieldSpec::new(old_step, field_type);
+    let count = point_count(msg);
+
+    let mut data = vec![0u8; count * new_step];
+    for (i, (src, dst)) in msg
+        .data
+        .chunks_exact(old_step)
+        .zip(data.chunks_exact_mut(new_step))
+        .enumerate()
+    {
+        dst[..old_step].copy_from_slice(src);
+        spec.write(dst, 0, msg.is_bigendian, value(i));
+    }
+
+    msg.fields.push(PointField {
+        name: name.to_string(),
+        offset: old_step as u32,
+        datatype: field_type,
+        count: 1,
+    });
+    msg.point_step = new_step as u32;
+    msg.height = 1;
+    msg.width = count as u32;
+    msg.row_step = (count * new_step) as u32;
+    msg.data = data;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+    use crate::msg::Header;
+    use crate::point::{parse_pointcloud2, LidarPoint};
+
+    fn msg(n: usize) -> PointCloud2 {
+        let points: Vec<LidarPoint> = (0..n)
+            .map(|i| LidarPoint {
+                x: i as f32,
+                intensity: 100.0 + i as f32,
+                ..LidarPoint::default()
+            })
+            .collect();
+        layout::XYZI16.encode(&points, Header::default())
+    }
+
+    fn xs(msg: &PointCloud2) -> Vec<f32> {
+        parse_pointcloud2(msg)
+            .unwrap()
+            .iter()
+            .map(|p| p.x)
+            .collect()
+    }
+
+    #[test]
+    fn select_copies_kept_points() {
+        let msg = msg(5);
+        let keep = [true, false, false, true, true];
+        let selected = select(&msg, &keep);
+        assert_eq!(xs(&selected), [0.0, 3.0, 4.0]);
+        assert_eq!((selected.height, selected.width), (1, 3));
+        assert_eq!(selected.row_step, 3 * 16);
+        assert_eq!(selected.fields, msg.fields);
+    }
+
+    #[test]
+    fn compact_matches_select() {
+        let keep = [false, true, true, false, true, true, false];
+        let mut compacted = msg(7);
+        let expected = select(&compacted, &keep);
+        assert_eq!(compact(&mut compacted, &keep), 4);
+        assert_eq!(compacted.data, expected.data);
+        assert_eq!(
+            (compacted.width, compacted.row_step),
+            (expected.width, expected.row_step)
+        );
+    }
+
+    #[test]
+    fn compact_drops_points_past_short_mask() {
+        let mut msg = msg(4);
+        assert_eq!(compact(&mut msg, &[true, true]), 2);
+        assert_eq!(xs(&msg), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn set_height_splits_points_into_rows() {
+        let mut msg = msg(6);
+        set_height(&mut msg, 2).unwrap();
+        assert_eq!((msg.height, msg.width, msg.row_step), (2, 3, 3 * 16));
+
+        assert!(set_height(&mut msg, 4).is_err());
+        assert!(set_height(&mut msg, 0).is_err());
+        assert_eq!((msg.height, msg.width), (2, 3));
+    }
+
+    #[test]
+    fn append_field_keeps_original_bytes() {
+        let mut msg = msg(3);
+        let original = msg.clone();
+        append_field(&mut msg, "tag", datatype::UINT8, |i| i as f64 + 1.0).unwrap();
+        assert_eq!(msg.point_step, 17);
+        assert_eq!(msg.data.len(), 3 * 17);
+        for (new, old) in msg
+            .data
+            .chunks_exact(17)
+            .zip(original.data.chunks_exact(16))
+        {
+            assert_eq!(&new[..16], old);
+        }
+        let tags: Vec<u8> = parse_pointcloud2(&msg)
+            .unwrap()
+            .iter()
+            .map(|p| p.tag)
+            .collect();
+        assert_eq!(tags, [1, 2, 3]);
+        assert!(append_field(&mut msg, "tag", datatype::UINT8, |_| 0.0).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::msg::PointCloud2;
+use crate::point::datatype;
+use anyhow::{bail, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+// PCD 헤더의 FIELDS/SIZE/TYPE/COUNT 한 열
+struct Column {
+    name: String,
+    size: usize,
+    kind: char,
+    count: usize,
+}
+
+// 필드 사이 빈 공간은 PCL 관례대로 '_' 필드로 채움
+fn padding(bytes: usize) -> Column {
+    Column {
+        name: "_".to_string(),
+        size: 1,
+        kind: 'U',
+        count: bytes,
+    }
+}
+
+// PointCloud2 를 binary PCD(v0.7)로 저장, 모든 필드와 패딩을 그대로 유지
+pub fn write_pcd(path: &Path, msg: &PointCloud2) -> Result<()> {
+    if msg.is_bigendian {
+        bail!("big endian PointCloud2 는 PCD 로 저장할 수 없습니다");
+    }
+
+    let mut fields: Vec<_> = msg.fields.iter().collect();
+    fields.sort_by_key(|f| f.offset);
+
+    let mut columns = Vec::new();
+    let mut cursor = 0usize;
+    for field in fields {
+        let offset = field.offset as usize;
+        let Some(size) = datatype::size(field.datatype) else {
+            bail!(
+                "필드 '{}' 의 datatype({}) 을 지원하지 않습니다",
+                field.name,
+                field.datatype
+            );
+        };
+        if offset < cursor {
+            bail!("필드 '{}' 가 앞 필드와 겹칩니다", field.name);
+        }
+        if offset > cursor {
+            columns.push(padding(offset - cursor));
+        }
+
+        let kind = match field.datatype {
+            datatype::INT8 | datatype::INT16 | datatype::INT32 => 'I',
+            datatype::UINT8 | datatype::UINT16 | datatype::UINT32 => 'U',
+            _ => 'F',
+        };
+        let count = field.count.max(1) as usize;
+        columns.push(Column {
+            name: field.name.clone(),
+            size,
+            kind,
+            count,
+        });
+        cursor = offset + size * count;
+    }
+
+    let step = msg.point_step as usize;
+    if step < cursor {
+        bail!("point_step({}) 이 필드 범위({})보다 작습니다", step, cursor);
+    }
+    if step > cursor {
+        columns.push(padding(step - cursor));
+    }
+    let points = msg.data.len().checked_div(step).unwrap_or(0);
+
+    let row = |f: fn(&Column) -> String| columns.iter().map(f).collect::<Vec<_>>().join(" ");
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "# .PCD v0.7 - Point Cloud Data file format")?;
+    writeln!(out, "VERSION 0.7")?;
+    writeln!(out, "FIELDS {}", row(|c| c.name.clone()))?;
+    writeln!(out, "SIZE {}", row(|c| c.size.to_string()))?;
+    writeln!(out, "TYPE {}", row(|c| c.kind.to_string()))?;
+    writeln!(out, "COUNT {}", row(|c| c.count.to_string()))?;
+    writeln!(out, "WIDTH {}", points)?;
+    writeln!(out, "HEIGHT 1")?;
+    writeln!(out, "VIEWPOINT 0 0 0 1 0 0 0")?;
+    writeln!(out, "POINTS {}", points)?;
+    writeln!(out, "DATA binary")?;
+    out.write_all(&msg.data[..points * step])?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use crate::exclusion;
+use crate::sidecar::json_string;
+use crate::tracking::Track;
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+
+// 고정 설치(로비 등)용 인원 계수: 사람 크기 트랙이 계수선을 건넌 횟수와 구역 안 인원
+// 위치/포인트는 내보내지 않고 이름별 숫자만 남김 (익명 출력)
+
+// 계수선 a -> b, 진행 방향 왼쪽(외적 +)으로 건너면 in, 오른쪽으로 건너면 out
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountLine {
+    pub name: String,
+    pub a: [f32; 2],
+    pub b: [f32; 2],
+    pub entered: u64,
+    pub exited: u64,
+}
+
+// 다각형 구역: 지금 안에 있는 인원과 누적 진입 수
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountZone {
+    pub name: String,
+    pub polygon: Vec<[f32; 2]>,
+    pub occupancy: usize,
+    pub entered: u64,
+}
+
+// "이름:x0,y0,x1,y1,..." 형식 (값 개수는 호출하는 쪽에서 확인)
+fn parse_named(text: &str) -> Result<(String, Vec<[f32; 2]>)> {
+    let (name, values) = text
+        .split_once(':')
+        .with_context(|| format!("'{}': '이름:x0,y0,x1,y1,...' 형식이 아닙니다", text))?;
+    let values = values
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<f32>()
+                .with_context(|| format!("'{}' 의 숫자가 아닌 값 '{}'", text, v))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if values.len() % 2 != 0 {
+        bail!("'{}': 좌표는 x,y 쌍이어야 합니다", text);
+    }
+    Ok((
+        name.trim().to_string(),
+        values.chunks_exact(2).map(|xy| [xy[0], xy[1]]).collect(),
+    ))
+}
+
+impl CountLine {
+    pub fn parse(text: &str) -> Result<Self> {
+        let (name, points) = parse_named(text)?;
+        let [a, b] = points[..] else {
+            bail!("계수선 '{}': 끝점 2개 (x0,y0,x1,y1) 가 필요합니다", text);
+        };
+        Ok(CountLine {
+            name,
+            a,
+            b,
+            entered: 0,
+            exited: 0,
+        })
+    }
+
+    fn side(&self, p: [f32; 2]) -> f32 {
+        let (dx, dy) = (self.b[0] - self.a[0], self.b[1] - self.a[1]);
+        dx * (p[1] - self.a[1]) - dy * (p[0] - self.a[0])
+    }
+
+    // from -> to 이동이 선분을 건너면 방향 (+1 in, -1 out)
+    fn crossing(&self, from: [f32; 2], to: [f32; 2]) -> Option<i8> {
+        let (s0, s1) = (self.side(from), self.side(to));
+        if s0 == 0.0 || s0.signum() == s1.signum() || s1 == 0.0 {
+            return None;
+        }
+        // 이동 선분 위 교점이 계수선 선분 안에 있는지
+        let t = s0 / (s0 - s1);
+        let x = [
+            from[0] + (to[0] - from[0]) * t,
+            from[1] + (to[1] - from[1]) * t,
+        ];
+        let (dx, dy) = (self.b[0] - self.a[0], self.b[1] - self.a[1]);
+        let u = ((x[0] - self.a[0]) * dx + (x[1] - self.a[1]) * dy) / (dx * dx + dy * dy);
+        (0.0..=1.0)
+            .contains(&u)
+            .then_some(if s1 > 0.0 { 1 } else { -1 })
+    }
+}
+
+impl CountZone {
+    pub fn parse(text: &str) -> Result<Self> {
+        let (name, polygon) = parse_named(text)?;
+        if polygon.len() < 3 {
+            bail!("계수 구역 '{}': 꼭짓점 3개 이상이 필요합니다", text);
+        }
+        Ok(CountZone {
+            name,
+            polygon,
+            occupancy: 0,
+            entered: 0,
+        })
+    }
+
+    fn contains(&self, p: [f32; 2]) -> bool {
+        exclusion::point_in_polygon(&self.polygon, p[0], p[1])
+    }
+}
+
+pub struct PeopleCounter {
+    pub lines: Vec<CountLine>,
+    pub zones: Vec<CountZone>,
+    // 이만큼 연속으로 보인 트랙만 셈 (잡음 클러스터 제외)
+    min_hits: u32,
+    // 트랙별 직전 위치와 들어가 있던 구역
+    previous: HashMap<u32, [f32; 2]>,
+    inside: HashSet<(u32, usize)>,
+}
+
+impl PeopleCounter {
+    pub fn new(lines: Vec<CountLine>, zones: Vec<CountZone>, min_hits: u32) -> Self {
+        PeopleCounter {
+            lines,
+            zones,
+            min_hits,
+            previous: HashMap::new(),
+            inside: HashSet::new(),
+        }
+    }
+
+    // people: 사람으로 분류된 이번 프레임 트랙
+    pub fn update<'a>(&mut self, people: impl IntoIterator<Item = &'a Track>) {
+        let mut seen = HashSet::new();
+        let mut inside = HashSet::new();
+        for track in people {
+            if track.hits < self.min_hits || track.missed > 0 {
+                continue;
+            }
+            seen.insert(track.id);
+            if let Some(&from) = self.previous.get(&track.id) {
+                for line in &mut self.lines {
+                    match line.crossing(from, track.position) {
+                        Some(1) => line.entered += 1,
+                        Some(_) => line.exited += 1,
+                        None => {}
+                    }
+                }
+            }
+            self.previous.insert(track.id, track.position);
+            for (i, zone) in self.zones.iter_mut().enumerate() {
+                if zone.contains(track.position) {
+                    if !self.inside.contains(&(track.id, i)) {
+                        zone.entered += 1;
+                    }
+                    inside.insert((track.id, i));
+                }
+            }
+        }
+        self.previous.retain(|id, _| seen.contains(id));
+        for (i, zone) in self.zones.iter_mut().enumerate() {
+            zone.occupancy = inside.iter().filter(|(_, z)| *z == i).count();
+        }
+        self.inside = inside;
+    }
+
+    // 지금 세고 있는 사람 수
+    pub fn people(&self) -> usize {
+        self.previous.len()
+    }
+
+    pub fn to_json(&self, stamp: f64) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|l| {
+                format!(
+                    "{{\"name\": {}, \"in\": {}, \"out\": {}}}",
+                    json_string(&l.name),
+                    l.entered,
+                    l.exited
+                )
+            })
+            .collect();
+        let zones: Vec<String> = self
+            .zones
+            .iter()
+            .map(|z| {
+                format!(
+                    "{{\"name\": {}, \"occupancy\": {}, \"entered\": {}}}",
+                    json_string(&z.name),
+                    z.occupancy,
+                    z.entered
+                )
+            })
+            .collect();
+        format!(
+            "{{\"stamp\": {:.6}, \"people\": {}, \"lines\": [{}], \"zones\": [{}]}}",
+            stamp,
+            self.people(),
+            lines.join(", "),
+            zones.join(", ")
+        )
+    }
+}
@@ -0,0 +1,91 @@
+use crate::ground::Plane;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// 오래 학습하는 상태 (배경, 지면, 누적 점유 지도, 가림 감지 기준) 를 state_dir 에 주기적으로 저장
+// 시작할 때 파일이 있으면 읽어서 재시작 후 처음부터 다시 학습하지 않음
+// 임시 파일에 쓴 뒤 이름을 바꾸므로 저장 중 종료돼도 이전 파일은 온전함
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    dir: PathBuf,
+    period: Duration,
+    last_save: Instant,
+}
+
+impl StateStore {
+    // period: 저장 간격 (초)
+    pub fn new(dir: &str, period: f64) -> Result<Self> {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir).with_context(|| format!("{} 생성 실패", dir.display()))?;
+        Ok(StateStore {
+            dir,
+            period: Duration::from_secs_f64(period.max(1.0)),
+            last_save: Instant::now(),
+        })
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    // 저장한 파일이 있으면 그 경로
+    pub fn existing(&self, name: &str) -> Option<PathBuf> {
+        Some(self.path(name)).filter(|path| path.exists())
+    }
+
+    // 저장할 때가 됐는지 (true 를 돌려주면 다음 주기 시작)
+    pub fn due(&mut self) -> bool {
+        if self.last_save.elapsed() < self.period {
+            return false;
+        }
+        self.last_save = Instant::now();
+        true
+    }
+
+    // write 로 임시 파일에 쓴 뒤 name 으로 바꿈
+    pub fn save(&self, name: &str, write: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+        let path = self.path(name);
+        let tmp = self.path(&format!("{}.tmp", name));
+        write(&tmp)?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("{} -> {} 이름 변경 실패", tmp.display(), path.display()))
+    }
+}
+
+// 지면 평면을 "nx ny nz d" 한 줄로 저장
+pub fn save_plane(plane: &Plane, path: &Path) -> Result<()> {
+    let [nx, ny, nz] = plane.normal;
+    fs::write(path, format!("{} {} {} {}\n", nx, ny, nz, plane.d))
+        .with_context(|| format!("{} 쓰기 실패", path.display()))
+}
+
+pub fn load_plane(path: &Path) -> Result<Plane> {
+    let values = load_values(path)?;
+    let [nx, ny, nz, d] = values[..] else {
+        bail!("{}: 'nx ny nz d' 형식이 아닙니다", path.display());
+    };
+    Ok(Plane {
+        normal: [nx, ny, nz],
+        d,
+    })
+}
+
+// 실수 목록을 한 줄에 하나씩 저장
+pub fn save_values(values: &[f32], path: &Path) -> Result<()> {
+    let text: String = values.iter().map(|v| format!("{}\n", v)).collect();
+    fs::write(path, text).with_context(|| format!("{} 쓰기 실패", path.display()))
+}
+
+// 공백/줄바꿈으로 나뉜 실수 목록 (# 뒤는 주석)
+pub fn load_values(path: &Path) -> Result<Vec<f32>> {
+    let text = fs::read_to_string(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+    text.lines()
+        .flat_map(|line| line.split('#').next().unwrap_or("").split_whitespace())
+        .map(|v| {
+            v.parse::<f32>()
+                .with_context(|| format!("{}: 실수가 아닌 값 '{}'", path.display(), v))
+        })
+        .collect()
+}
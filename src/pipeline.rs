@@ -0,0 +1,431 @@
+use crate::ros1::Ros1Publisher;
+use crate::stats;
+use anyhow::{anyhow, Result};
+use rclrs::Publisher;
+use sensor_msgs::msg::PointCloud2;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+// 출력 메시지 두 개를 번갈아 사용하는 발행 파이프라인
+// 콜백이 프레임 N 을 직렬화하는 동안 발행 스레드가 프레임 N-1 을 발행
+pub struct DoubleBufferedPublisher {
+    filled: SyncSender<PointCloud2>,
+    free: Receiver<PointCloud2>,
+    worker: JoinHandle<()>,
+}
+
+impl DoubleBufferedPublisher {
+    pub fn new(publisher: Arc<Publisher<PointCloud2>>, capacity_bytes: usize) -> Self {
+        DoubleBufferedPublisher::with_publish(capacity_bytes, move |msg| {
+            publisher.publish(msg)?;
+            Ok(())
+        })
+    }
+
+    // publish 는 발행 스레드에서 메시지마다 호출 (시험에서는 발행 대신 기록)
+    pub fn with_publish(
+        capacity_bytes: usize,
+        publish: impl Fn(&PointCloud2) -> Result<()> + Send + 'static,
+    ) -> Self {
+        let (filled_tx, filled_rx) = mpsc::sync_channel::<PointCloud2>(1);
+        let (free_tx, free_rx) = mpsc::sync_channel::<PointCloud2>(2);
+
+        // 미리 할당된 버퍼 두 개
+        for _ in 0..2 {
+            let msg = PointCloud2 {
+                data: Vec::with_capacity(capacity_bytes),
+                ..Default::default()
+            };
+            free_tx.send(msg).expect("free 채널 초기화 실패");
+        }
+
+        let worker = thread::spawn(move || {
+            for msg in filled_rx {
+                if let Err(e) = publish(&msg) {
+                    eprintln!("발행 오류: {}", e);
+                }
+                // 발행이 끝난 버퍼를 돌려줌 (풀 밖에서 들어온 메시지로 가득 차면 버림)
+                if let Err(mpsc::TrySendError::Disconnected(_)) = free_tx.try_send(msg) {
+                    break;
+                }
+            }
+        });
+
+        DoubleBufferedPublisher {
+            filled: filled_tx,
+            free: free_rx,
+            worker,
+        }
+    }
+
+    // 비어있는 버퍼를 가져옴 (두 버퍼가 모두 사용 중이면 대기)
+    pub fn acquire(&self) -> Result<PointCloud2> {
+        self.free
+            .recv()
+            .map_err(|_| anyhow!("발행 스레드가 종료되었습니다"))
+    }
+
+    pub fn submit(&self, msg: PointCloud2) -> Result<()> {
+        self.filled
+            .send(msg)
+            .map_err(|_| anyhow!("발행 스레드가 종료되었습니다"))
+    }
+
+    // 대기 중인 메시지를 모두 발행한 뒤 발행 스레드 종료
+    pub fn finish(self) {
+        drop(self.filled);
+        let _ = self.worker.join();
+    }
+}
+
+// 노드가 사용하는 출력: 바로 발행하거나 더블 버퍼를 거쳐 발행
+enum Sink {
+    Direct(Arc<Publisher<PointCloud2>>),
+    DoubleBuffered(DoubleBufferedPublisher),
+}
+
+// 가장 최근 프레임 보관 (연속 구독 대신 필요할 때만 가져가는 소비자용)
+#[derive(Default)]
+pub struct LatestFrame {
+    msg: Mutex<Option<PointCloud2>>,
+}
+
+impl LatestFrame {
+    pub fn store(&self, msg: &PointCloud2) {
+        *self.msg.lock().unwrap() = Some(msg.clone());
+    }
+
+    pub fn get(&self) -> Option<PointCloud2> {
+        self.msg.lock().unwrap().clone()
+    }
+}
+
+// ros1 이 있으면 같은 메시지를 ROS 1 토픽에도 발행 (레거시 시스템용 브리지)
+// latest 가 있으면 발행한 메시지를 보관
+pub struct CloudOutput {
+    sink: Sink,
+    ros1: Option<Arc<Ros1Publisher>>,
+    latest: Option<Arc<LatestFrame>>,
+    // 발행한 메시지의 직렬화 크기 누적 (대역폭 보고용)
+    bytes: AtomicU64,
+}
+
+impl CloudOutput {
+    pub fn new(publisher: Arc<Publisher<PointCloud2>>, double_buffer: bool) -> Self {
+        let sink = if double_buffer {
+            // Livox 한 프레임(약 10만 점 x 26바이트) 기준으로 미리 할당
+            Sink::DoubleBuffered(DoubleBufferedPublisher::new(publisher, 100_000 * 26))
+        } else {
+            Sink::Direct(publisher)
+        };
+        CloudOutput {
+            sink,
+            ros1: None,
+            latest: None,
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_ros1(mut self, ros1: Option<Arc<Ros1Publisher>>) -> Self {
+        self.ros1 = ros1;
+        self
+    }
+
+    pub fn with_latest(mut self, latest: Option<Arc<LatestFrame>>) -> Self {
+        self.latest = latest;
+        self
+    }
+
+    // fill 이 출력 메시지를 채우면 발행
+    pub fn publish_with(&self, fill: impl FnOnce(&mut PointCloud2)) -> Result<()> {
+        match &self.sink {
+            Sink::Direct(publisher) => {
+                let mut msg = PointCloud2::default();
+                fill(&mut msg);
+                self.mirror(&msg);
+                publisher.publish(msg)?;
+            }
+            Sink::DoubleBuffered(buffers) => {
+                let mut msg = buffers.acquire()?;
+                fill(&mut msg);
+                self.mirror(&msg);
+                buffers.submit(msg)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn publish(&self, msg: PointCloud2) -> Result<()> {
+        self.mirror(&msg);
+        match &self.sink {
+            Sink::Direct(publisher) => publisher.publish(msg)?,
+            Sink::DoubleBuffered(buffers) => buffers.submit(msg)?,
+        }
+        Ok(())
+    }
+
+    pub fn published_bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    fn mirror(&self, msg: &PointCloud2) {
+        self.bytes
+            .fetch_add(stats::serialized_size(msg) as u64, Ordering::Relaxed);
+        if let Some(ros1) = &self.ros1 {
+            ros1.publish(msg);
+        }
+        if let Some(latest) = &self.latest {
+            latest.store(msg);
+        }
+    }
+
+    // 종료 시 호출: 더블 버퍼에 남은 메시지까지 발행
+    pub fn finish(self) {
+        if let Sink::DoubleBuffered(buffers) = self.sink {
+            buffers.finish();
+        }
+    }
+}
+
+// 처리가 수신을 따라가지 못할 때의 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    // 큐가 가득 차면 가장 오래된 프레임을 버림 (최신 프레임 우선)
+    DropOldest,
+    // 큐가 가득 차면 새로 들어온 프레임을 버림
+    DropNewest,
+    // 자리가 날 때까지 수신 콜백을 막음 (입력 구독이 자기 스레드에 있는 executor=threaded 전용)
+    Block,
+}
+
+impl Backpressure {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "drop_oldest" => Ok(Backpressure::DropOldest),
+            "drop_newest" => Ok(Backpressure::DropNewest),
+            "block" => Ok(Backpressure::Block),
+            _ => Err(anyhow!(
+                "알 수 없는 backpressure '{}' (drop_oldest, drop_newest, block)",
+                name
+            )),
+        }
+    }
+}
+
+// 수신 콜백과 처리 스레드 사이의 유한 큐
+pub struct FrameQueue<T> {
+    frames: Mutex<VecDeque<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    depth: usize,
+    policy: Backpressure,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+}
+
+impl<T> FrameQueue<T> {
+    pub fn new(depth: usize, policy: Backpressure) -> Self {
+        FrameQueue {
+            frames: Mutex::new(VecDeque::with_capacity(depth)),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            depth: depth.max(1),
+            policy,
+            dropped: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn push(&self, frame: T) {
+        let mut frames = self.frames.lock().unwrap();
+        if self.closed.load(Ordering::SeqCst) {
+            return;
+        }
+        if frames.len() >= self.depth {
+            match self.policy {
+                Backpressure::DropOldest => {
+                    frames.pop_front();
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Backpressure::DropNewest => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Backpressure::Block => {
+                    while frames.len() >= self.depth && !self.closed.load(Ordering::SeqCst) {
+                        frames = self.not_full.wait(frames).unwrap();
+                    }
+                    if self.closed.load(Ordering::SeqCst) {
+                        return;
+                    }
+                }
+            }
+        }
+        frames.push_back(frame);
+        self.not_empty.notify_one();
+    }
+
+    // 프레임이 들어올 때까지 대기, close 후 큐가 비면 None
+    pub fn pop(&self) -> Option<T> {
+        let mut frames = self.frames.lock().unwrap();
+        loop {
+            if let Some(frame) = frames.pop_front() {
+                self.not_full.notify_one();
+                return Some(frame);
+            }
+            if self.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+            frames = self.not_empty.wait(frames).unwrap();
+        }
+    }
+
+    // 새 프레임은 받지 않고, 남은 프레임을 처리한 뒤 pop 이 None 을 반환하게 함
+    pub fn close(&self) {
+        let _frames = self.frames.lock().unwrap();
+        self.closed.store(true, Ordering::SeqCst);
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn drain(queue: &FrameQueue<u32>) -> Vec<u32> {
+        queue.close();
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    #[test]
+    fn drop_oldest_keeps_latest_frames() {
+        let queue = FrameQueue::new(2, Backpressure::DropOldest);
+        (1..=5).for_each(|frame| queue.push(frame));
+        assert_eq!(queue.dropped(), 3);
+        assert_eq!(drain(&queue), vec![4, 5]);
+    }
+
+    #[test]
+    fn drop_newest_keeps_queued_frames() {
+        let queue = FrameQueue::new(2, Backpressure::DropNewest);
+        (1..=5).for_each(|frame| queue.push(frame));
+        assert_eq!(queue.dropped(), 3);
+        assert_eq!(drain(&queue), vec![1, 2]);
+    }
+
+    #[test]
+    fn close_flushes_queued_frames_and_rejects_new_ones() {
+        let queue = FrameQueue::new(4, Backpressure::DropOldest);
+        queue.push(1);
+        queue.push(2);
+        queue.close();
+        queue.push(3);
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.dropped(), 0);
+    }
+
+    #[test]
+    fn close_wakes_waiting_consumer_and_blocked_producer() {
+        // 빈 큐에서 기다리는 pop 은 close 후 None
+        let queue = Arc::new(FrameQueue::<u32>::new(1, Backpressure::Block));
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop())
+        };
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        assert_eq!(consumer.join().unwrap(), None);
+
+        // 가득 찬 큐에서 막힌 push 는 자리가 나면 이어서 넣음
+        let queue = Arc::new(FrameQueue::new(1, Backpressure::Block));
+        queue.push(1);
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push(2))
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.pop(), Some(1));
+        producer.join().unwrap();
+        assert_eq!(drain(&queue), vec![2]);
+
+        // close 하면 막힌 push 는 프레임을 버리고 돌아옴
+        let queue = Arc::new(FrameQueue::new(1, Backpressure::Block));
+        queue.push(1);
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push(2))
+        };
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        producer.join().unwrap();
+        assert_eq!(drain(&queue), vec![1]);
+    }
+
+    #[test]
+    fn double_buffer_publishes_every_frame_in_order_before_finish_returns() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let buffers = {
+            let published = Arc::clone(&published);
+            DoubleBufferedPublisher::with_publish(64, move |msg| {
+                // 발행이 느려도 finish 가 남은 메시지를 모두 발행할 때까지 기다려야 함
+                thread::sleep(Duration::from_millis(5));
+                published.lock().unwrap().push(msg.data.clone());
+                Ok(())
+            })
+        };
+        for frame in 0..6u8 {
+            let mut msg = buffers.acquire().unwrap();
+            // 풀의 버퍼는 미리 할당된 용량을 유지한 채 다시 쓰임
+            assert!(msg.data.capacity() >= 64);
+            msg.data.clear();
+            msg.data.push(frame);
+            buffers.submit(msg).unwrap();
+        }
+        buffers.finish();
+        let expected: Vec<Vec<u8>> = (0..6u8).map(|frame| vec![frame]).collect();
+        assert_eq!(*published.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn double_buffer_keeps_publishing_after_publish_error() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let buffers = {
+            let published = Arc::clone(&published);
+            DoubleBufferedPublisher::with_publish(0, move |msg| {
+                if msg.data == [1] {
+                    return Err(anyhow!("발행 실패"));
+                }
+                published.lock().unwrap().push(msg.data[0]);
+                Ok(())
+            })
+        };
+        for frame in 0..3u8 {
+            let mut msg = buffers.acquire().unwrap();
+            msg.data.clear();
+            msg.data.push(frame);
+            buffers.submit(msg).unwrap();
+        }
+        buffers.finish();
+        assert_eq!(*published.lock().unwrap(), vec![0, 2]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core as codec;
+use crate::msg::{PointCloud2, PointField};
+use anyhow::{bail, Result};
+use std::borrow::Cow;
+
+pub use crate::core::{datatype, FieldOffsets, FieldSpec, LidarPoint};
+
+impl FieldOffsets {
+    // fields 가 비어있는 메시지는 Livox 기본 레이아웃으로 간주
+    pub fn from_fields(fields: &[PointField]) -> Result<Self> {
+        let fields: Vec<(&str, usize, u8)> = fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset as usize, f.datatype))
+            .collect();
+        FieldOffsets::from_descriptors(&fields)
+    }
+}
+
+// 행 끝 패딩 (row_step > width * point_step, 정렬된 입력) 을 뺀 연속 포인트 바이트
+// 패딩이 없거나 row_step 이 0 이면 복사 없이 그대로
+pub fn packed_data(msg: &PointCloud2) -> Result<Cow<'_, [u8]>> {
+    let row = msg.width as usize * msg.point_step as usize;
+    let row_step = msg.row_step as usize;
+    if row_step == 0 || row_step == row {
+        return Ok(Cow::Borrowed(&msg.data));
+    }
+    if row_step < row {
+        bail!(
+            "row_step({}) 이 width x point_step({}) 보다 작습니다",
+            row_step,
+            row
+        );
+    }
+    let height = msg.height.max(1) as usize;
+    if msg.data.len() < row_step * (height - 1) + row {
+        bail!(
+            "data({} bytes) 가 height({}) x row_step({}) 보다 짧습니다",
+            msg.data.len(),
+            height,
+            row_step
+        );
+    }
+    let mut data = Vec::with_capacity(row * height);
+    for r in 0..height {
+        data.extend_from_slice(&msg.data[r * row_step..r * row_step + row]);
+    }
+    Ok(Cow::Owned(data))
+}
+
+pub fn parse_pointcloud2(msg: &PointCloud2) -> Result<Vec<LidarPoint>> {
+    let layout = FieldOffsets::from_fields(&msg.fields)?;
+    parse_with_offsets(msg, &layout)
+}
+
+// 메시지의 fields 대신 주어진 레이아웃으로 파싱
+pub fn parse_with_offsets(msg: &PointCloud2, layout: &FieldOffsets) -> Result<Vec<LidarPoint>> {
+    codec::decode(
+        &packed_data(msg)?,
+        msg.point_step as usize,
+        layout,
+        msg.is_bigendian,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+    use crate::msg::Header;
+
+    fn points(n: usize) -> Vec<LidarPoint> {
+        (0..n)
+            .map(|i| LidarPoint {
+                x: i as f32,
+                y: -(i as f32),
+                z: 0.5,
+                intensity: 10.0 * i as f32,
+                ..LidarPoint::default()
+            })
+            .collect()
+    }
+
+    // height 행으로 나누고 각 행 끝에 pad 바이트 패딩을 넣은 메시지
+    fn padded(points: &[LidarPoint], height: usize, pad: usize) -> PointCloud2 {
+        let mut msg = layout::XYZI16.encode(points, Header::default());
+        let row = msg.data.len() / height;
+        msg.data = msg
+            .data
+            .chunks(row)
+            .flat_map(|r| r.iter().copied().chain(std::iter::repeat_n(0xAA, pad)))
+            .collect();
+        msg.height = height as u32;
+        msg.width = (points.len() / height) as u32;
+        msg.row_step = (row + pad) as u32;
+        msg
+    }
+
+    #[test]
+    fn packed_data_borrows_unpadded_rows() {
+        let msg = padded(&points(4), 2, 0);
+        assert!(matches!(packed_data(&msg).unwrap(), Cow::Borrowed(_)));
+
+        let mut unset = msg.clone();
+        unset.row_step = 0;
+        assert!(matches!(packed_data(&unset).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn packed_data_strips_row_padding() {
+        let original = points(6);
+        let msg = padded(&original, 3, 12);
+        let data = packed_data(&msg).unwrap();
+        assert_eq!(data.len(), 6 * 16);
+
+        let parsed = parse_pointcloud2(&msg).unwrap();
+        assert_eq!(parsed.len(), 6);
+        for (a, b) in parsed.iter().zip(&original) {
+            assert_eq!((a.x, a.y, a.z, a.intensity), (b.x, b.y, b.z, b.intensity));
+        }
+    }
+
+    #[test]
+    fn packed_data_rejects_inconsistent_row_step() {
+        let mut msg = padded(&points(4), 2, 8);
+        msg.row_step = 16;
+        assert!(packed_data(&msg).is_err());
+
+        let mut short = padded(&points(4), 2, 8);
+        short.data.truncate(40 + 20);
+        assert!(packed_data(&short).is_err());
+    }
+
+    #[test]
+    fn parse_pointcloud2_reads_fields_by_name() {
+        let original = points(3);
+        let msg = layout::XYZI16.encode(&original, Header::default());
+        let parsed = parse_pointcloud2(&msg).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2].intensity, 20.0);
+        assert_eq!((parsed[2].tag, parsed[2].timestamp), (0, 0.0));
+    }
+}
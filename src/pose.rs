@@ -0,0 +1,208 @@
+#[cfg(feature = "ros")]
+use geometry_msgs::msg::Pose as PoseMsg;
+
+// 3D 자세 (위치 + 단위 쿼터니언 [x, y, z, w])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub position: [f64; 3],
+    pub orientation: [f64; 4],
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+fn quat_mul(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+    let [ax, ay, az, aw] = a;
+    let [bx, by, bz, bw] = b;
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
+
+fn normalize(q: [f64; 4]) -> [f64; 4] {
+    let len = q.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if len < 1e-12 {
+        [0.0, 0.0, 0.0, 1.0]
+    } else {
+        q.map(|v| v / len)
+    }
+}
+
+impl Pose {
+    pub const IDENTITY: Pose = Pose {
+        position: [0.0; 3],
+        orientation: [0.0, 0.0, 0.0, 1.0],
+    };
+
+    #[cfg(feature = "ros")]
+    pub fn from_msg(msg: &PoseMsg) -> Self {
+        let p = &msg.position;
+        let q = &msg.orientation;
+        Pose {
+            position: [p.x, p.y, p.z],
+            orientation: normalize([q.x, q.y, q.z, q.w]),
+        }
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn to_msg(&self) -> PoseMsg {
+        let mut msg = PoseMsg::default();
+        msg.position.x = self.position[0];
+        msg.position.y = self.position[1];
+        msg.position.z = self.position[2];
+        msg.orientation.x = self.orientation[0];
+        msg.orientation.y = self.orientation[1];
+        msg.orientation.z = self.orientation[2];
+        msg.orientation.w = self.orientation[3];
+        msg
+    }
+
+    // 평면 자세 (x, y, yaw)
+    pub fn from_xy_yaw(x: f64, y: f64, yaw: f64) -> Self {
+        let (s, c) = (yaw / 2.0).sin_cos();
+        Pose {
+            position: [x, y, 0.0],
+            orientation: [0.0, 0.0, s, c],
+        }
+    }
+
+    // roll, pitch, yaw (라디안, ROS 고정축 RPY)
+    pub fn from_rpy(position: [f64; 3], roll: f64, pitch: f64, yaw: f64) -> Self {
+        let (sr, cr) = (roll / 2.0).sin_cos();
+        let (sp, cp) = (pitch / 2.0).sin_cos();
+        let (sy, cy) = (yaw / 2.0).sin_cos();
+        Pose {
+            position,
+            orientation: [
+                sr * cp * cy - cr * sp * sy,
+                cr * sp * cy + sr * cp * sy,
+                cr * cp * sy - sr * sp * cy,
+                cr * cp * cy + sr * sp * sy,
+            ],
+        }
+    }
+
+    pub fn rpy(&self) -> (f64, f64, f64) {
+        let [x, y, z, w] = self.orientation;
+        let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+        let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+        let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+        (roll, pitch, yaw)
+    }
+
+    pub fn yaw(&self) -> f64 {
+        self.rpy().2
+    }
+
+    pub fn rotate(&self, v: [f64; 3]) -> [f64; 3] {
+        let q = self.orientation;
+        let r = quat_mul(
+            quat_mul(q, [v[0], v[1], v[2], 0.0]),
+            [-q[0], -q[1], -q[2], q[3]],
+        );
+        [r[0], r[1], r[2]]
+    }
+
+    pub fn transform_point(&self, p: [f64; 3]) -> [f64; 3] {
+        let r = self.rotate(p);
+        [
+            r[0] + self.position[0],
+            r[1] + self.position[1],
+            r[2] + self.position[2],
+        ]
+    }
+
+    // self * other (other 를 self 좌표계에 놓음)
+    pub fn compose(&self, other: &Pose) -> Pose {
+        Pose {
+            position: self.transform_point(other.position),
+            orientation: normalize(quat_mul(self.orientation, other.orientation)),
+        }
+    }
+
+    pub fn inverse(&self) -> Pose {
+        let q = self.orientation;
+        let inv = Pose {
+            position: [0.0; 3],
+            orientation: [-q[0], -q[1], -q[2], q[3]],
+        };
+        let p = inv.rotate(self.position);
+        Pose {
+            position: [-p[0], -p[1], -p[2]],
+            orientation: inv.orientation,
+        }
+    }
+
+    // 위치는 선형, 회전은 slerp (alpha 0 이면 self, 1 이면 other)
+    pub fn interpolate(&self, other: &Pose, alpha: f64) -> Pose {
+        let lerp = |a: f64, b: f64| a + (b - a) * alpha;
+        let position = [0, 1, 2].map(|i| lerp(self.position[i], other.position[i]));
+
+        let a = self.orientation;
+        let mut b = other.orientation;
+        let mut cos = (0..4).map(|i| a[i] * b[i]).sum::<f64>();
+        if cos < 0.0 {
+            b = b.map(|v| -v);
+            cos = -cos;
+        }
+        let orientation = if cos > 0.9995 {
+            normalize([0, 1, 2, 3].map(|i| lerp(a[i], b[i])))
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            let wa = ((1.0 - alpha) * theta).sin() / sin;
+            let wb = (alpha * theta).sin() / sin;
+            normalize([0, 1, 2, 3].map(|i| wa * a[i] + wb * b[i]))
+        };
+        Pose {
+            position,
+            orientation,
+        }
+    }
+
+    // 위치 + 회전 벡터 (축 * 각도, 라디안) -> 자세
+    pub fn from_rotation_vector(position: [f64; 3], w: [f64; 3]) -> Self {
+        let angle = (w[0] * w[0] + w[1] * w[1] + w[2] * w[2]).sqrt();
+        let orientation = if angle < 1e-12 {
+            normalize([w[0] / 2.0, w[1] / 2.0, w[2] / 2.0, 1.0])
+        } else {
+            let (s, c) = (angle / 2.0).sin_cos();
+            [w[0] / angle * s, w[1] / angle * s, w[2] / angle * s, c]
+        };
+        Pose {
+            position,
+            orientation,
+        }
+    }
+
+    // from_rotation_vector 의 역 (각도는 [0, pi])
+    pub fn rotation_vector(&self) -> [f64; 3] {
+        let [x, y, z, w] = self.orientation;
+        let (x, y, z, w) = if w < 0.0 {
+            (-x, -y, -z, -w)
+        } else {
+            (x, y, z, w)
+        };
+        let s = (x * x + y * y + z * z).sqrt();
+        if s < 1e-12 {
+            return [2.0 * x, 2.0 * y, 2.0 * z];
+        }
+        let angle = 2.0 * s.atan2(w);
+        [x / s * angle, y / s * angle, z / s * angle]
+    }
+
+    // 회전 각도 크기 (라디안)
+    pub fn angle(&self) -> f64 {
+        2.0 * self.orientation[3].abs().min(1.0).acos()
+    }
+
+    pub fn translation_norm(&self) -> f64 {
+        self.position.iter().map(|v| v * v).sum::<f64>().sqrt()
+    }
+}
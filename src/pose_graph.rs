@@ -0,0 +1,288 @@
+use crate::pose::Pose;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    Odometry,
+    Loop,
+}
+
+// 두 노드 사이 상대 자세 제약 (from 좌표계에서 본 to 의 자세)
+#[derive(Debug, Clone, Copy)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub measurement: Pose,
+    // 잔차 [x, y, z, rx, ry, rz] 별 가중치 (분산의 역수)
+    pub information: [f64; 6],
+    pub kind: EdgeKind,
+}
+
+// 병진/회전 표준편차로 대각 정보 행렬 생성
+pub fn information(translation_std: f64, rotation_std: f64) -> [f64; 6] {
+    let t = 1.0 / (translation_std * translation_std).max(1e-12);
+    let r = 1.0 / (rotation_std * rotation_std).max(1e-12);
+    [t, t, t, r, r, r]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OptimizeResult {
+    pub iterations: usize,
+    // 최적화 전후 가중 제곱 오차 합
+    pub initial_error: f64,
+    pub final_error: f64,
+}
+
+// 키프레임 자세 그래프, 첫 노드를 고정하고 Gauss-Newton 으로 최적화
+#[derive(Debug, Clone, Default)]
+pub struct PoseGraph {
+    pub nodes: Vec<Pose>,
+    pub edges: Vec<Edge>,
+}
+
+fn residual(edge: &Edge, a: &Pose, b: &Pose) -> [f64; 6] {
+    let error = edge.measurement.inverse().compose(&a.inverse().compose(b));
+    let [x, y, z] = error.position;
+    let [rx, ry, rz] = error.rotation_vector();
+    [x, y, z, rx, ry, rz]
+}
+
+// 노드 자세를 자기 좌표계에서 delta 만큼 움직임
+fn perturb(pose: &Pose, delta: &[f64]) -> Pose {
+    pose.compose(&Pose::from_rotation_vector(
+        [delta[0], delta[1], delta[2]],
+        [delta[3], delta[4], delta[5]],
+    ))
+}
+
+// 대칭 양정치 행렬 (n x n, 행 우선) 에 대해 H x = b 풀기 (Cholesky)
+fn solve_cholesky(h: &mut [f64], b: &[f64], n: usize) -> Option<Vec<f64>> {
+    for j in 0..n {
+        let mut d = h[j * n + j];
+        for k in 0..j {
+            d -= h[j * n + k] * h[j * n + k];
+        }
+        if d <= 0.0 {
+            return None;
+        }
+        let d = d.sqrt();
+        h[j * n + j] = d;
+        for i in j + 1..n {
+            let mut v = h[i * n + j];
+            for k in 0..j {
+                v -= h[i * n + k] * h[j * n + k];
+            }
+            h[i * n + j] = v / d;
+        }
+    }
+    // L y = b, L^T x = y
+    let mut y = b.to_vec();
+    for i in 0..n {
+        for k in 0..i {
+            y[i] -= h[i * n + k] * y[k];
+        }
+        y[i] /= h[i * n + i];
+    }
+    for i in (0..n).rev() {
+        for k in i + 1..n {
+            y[i] -= h[k * n + i] * y[k];
+        }
+        y[i] /= h[i * n + i];
+    }
+    Some(y)
+}
+
+impl PoseGraph {
+    pub fn add_node(&mut self, pose: Pose) -> usize {
+        self.nodes.push(pose);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.edges.push(edge);
+    }
+
+    pub fn error(&self) -> f64 {
+        self.edges
+            .iter()
+            .map(|edge| {
+                let r = residual(edge, &self.nodes[edge.from], &self.nodes[edge.to]);
+                (0..6)
+                    .map(|k| r[k] * r[k] * edge.information[k])
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    // 야코비안은 수치 미분, 정규 방정식은 밀집 행렬 (키프레임 수백 개 규모)
+    pub fn optimize(&mut self, max_iterations: usize) -> OptimizeResult {
+        let initial_error = self.error();
+        let n = self.nodes.len() * 6;
+        let mut iterations = 0;
+        if self.nodes.len() < 2 || self.edges.is_empty() {
+            return OptimizeResult {
+                iterations,
+                initial_error,
+                final_error: initial_error,
+            };
+        }
+
+        const EPS: f64 = 1e-6;
+        let mut error = initial_error;
+        while iterations < max_iterations {
+            iterations += 1;
+            let mut h = vec![0.0; n * n];
+            let mut b = vec![0.0; n];
+            for edge in &self.edges {
+                let (a, c) = (self.nodes[edge.from], self.nodes[edge.to]);
+                let r = residual(edge, &a, &c);
+                // 열 0..6 은 from, 6..12 는 to 에 대한 미분
+                let mut jac = [[0.0; 12]; 6];
+                for col in 0..12 {
+                    let mut delta = [0.0; 6];
+                    delta[col % 6] = EPS;
+                    let moved = if col < 6 {
+                        residual(edge, &perturb(&a, &delta), &c)
+                    } else {
+                        residual(edge, &a, &perturb(&c, &delta))
+                    };
+                    for row in 0..6 {
+                        jac[row][col] = (moved[row] - r[row]) / EPS;
+                    }
+                }
+                let index = |col: usize| {
+                    let node = if col < 6 { edge.from } else { edge.to };
+                    node * 6 + col % 6
+                };
+                for i in 0..12 {
+                    for j in 0..12 {
+                        let v: f64 = (0..6)
+                            .map(|k| jac[k][i] * edge.information[k] * jac[k][j])
+                            .sum();
+                        h[index(i) * n + index(j)] += v;
+                    }
+                    let g: f64 = (0..6).map(|k| jac[k][i] * edge.information[k] * r[k]).sum();
+                    b[index(i)] -= g;
+                }
+            }
+            // 첫 노드 고정 + 작은 감쇠
+            for i in 0..n {
+                h[i * n + i] += if i < 6 { 1e12 } else { 1e-6 };
+            }
+            let Some(delta) = solve_cholesky(&mut h, &b, n) else {
+                break;
+            };
+            let previous = self.nodes.clone();
+            for (i, node) in self.nodes.iter_mut().enumerate() {
+                *node = perturb(node, &delta[i * 6..i * 6 + 6]);
+            }
+            let next_error = self.error();
+            if next_error > error {
+                // 발산하면 되돌리고 종료
+                self.nodes = previous;
+                break;
+            }
+            let step = delta.iter().map(|v| v * v).sum::<f64>().sqrt();
+            error = next_error;
+            if step < 1e-8 {
+                break;
+            }
+        }
+        OptimizeResult {
+            iterations,
+            initial_error,
+            final_error: error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 한 변 4 m 정사각형을 1 m 간격으로 도는 자세 16 개 (모서리마다 90도 회전)
+    fn square() -> Vec<Pose> {
+        let mut poses = vec![Pose::IDENTITY];
+        for i in 1..16 {
+            let turn = if i % 4 == 0 {
+                std::f64::consts::FRAC_PI_2
+            } else {
+                0.0
+            };
+            let step = Pose::from_xy_yaw(1.0, 0.0, turn);
+            poses.push(poses[i - 1].compose(&step));
+        }
+        poses
+    }
+
+    fn between(a: &Pose, b: &Pose) -> Pose {
+        a.inverse().compose(b)
+    }
+
+    fn max_position_error(graph: &PoseGraph, truth: &[Pose]) -> f64 {
+        graph
+            .nodes
+            .iter()
+            .zip(truth)
+            .map(|(p, t)| between(t, p).translation_norm())
+            .fold(0.0, f64::max)
+    }
+
+    // 오도메트리마다 yaw 가 조금씩 어긋나 누적된 경로를 루프 제약 하나로 바로잡음
+    #[test]
+    fn loop_edge_corrects_odometry_drift() {
+        let truth = square();
+        let bias = Pose::from_xy_yaw(0.0, 0.0, 0.03);
+        let mut graph = PoseGraph::default();
+        graph.add_node(truth[0]);
+        for i in 1..truth.len() {
+            let measurement = between(&truth[i - 1], &truth[i]).compose(&bias);
+            let pose = graph.nodes[i - 1].compose(&measurement);
+            graph.add_node(pose);
+            graph.add_edge(Edge {
+                from: i - 1,
+                to: i,
+                measurement,
+                information: information(0.1, 0.05),
+                kind: EdgeKind::Odometry,
+            });
+        }
+        let last = truth.len() - 1;
+        graph.add_edge(Edge {
+            from: last,
+            to: 0,
+            measurement: between(&truth[last], &truth[0]),
+            information: information(0.01, 0.001),
+            kind: EdgeKind::Loop,
+        });
+
+        let before = max_position_error(&graph, &truth);
+        let result = graph.optimize(20);
+        let after = max_position_error(&graph, &truth);
+        assert!(result.final_error < result.initial_error * 0.1);
+        assert!(before > 0.5 && after < 0.05, "{} -> {}", before, after);
+        // 첫 노드는 고정
+        assert!(between(&truth[0], &graph.nodes[0]).translation_norm() < 1e-6);
+    }
+
+    #[test]
+    fn consistent_graph_stays_at_optimum() {
+        let truth = square();
+        let mut graph = PoseGraph::default();
+        for pose in &truth {
+            graph.add_node(*pose);
+        }
+        for i in 0..truth.len() {
+            let j = (i + 1) % truth.len();
+            graph.add_edge(Edge {
+                from: i,
+                to: j,
+                measurement: between(&truth[i], &truth[j]),
+                information: information(0.1, 0.05),
+                kind: EdgeKind::Odometry,
+            });
+        }
+        let result = graph.optimize(10);
+        assert!(result.final_error < 1e-12);
+        assert!(max_position_error(&graph, &truth) < 1e-6);
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{bail, Result};
+use rclrs::{QoSProfile, QOS_PROFILE_DEFAULT};
+use std::env;
+use std::time::Duration;
+
+// 수 MB 짜리 PointCloud2 용 QoS/전송 프리셋 (--qos 로 선택, 포인트 클라우드 발행/구독에 적용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosPreset {
+    // rclrs 기본값 (reliable, keep_last 10)
+    Default,
+    // 무선: best effort + 최신 프레임만, 작은 UDP 조각으로 IP 단편화 손실을 피함
+    LossyWifi,
+    // 유선: best effort + 최신 프레임만, 큰 UDP 데이터그램과 deadline 으로 지연 감시
+    WiredLowLatency,
+    // 기록 (rosbag): reliable + 깊은 큐, 큰 수신 버퍼
+    Recording,
+}
+
+impl QosPreset {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "default" => Ok(QosPreset::Default),
+            "lossy-wifi" => Ok(QosPreset::LossyWifi),
+            "wired-lowlatency" => Ok(QosPreset::WiredLowLatency),
+            "recording" => Ok(QosPreset::Recording),
+            _ => bail!(
+                "알 수 없는 QoS 프리셋 '{}' (default, lossy-wifi, wired-lowlatency, recording)",
+                name
+            ),
+        }
+    }
+
+    pub fn profile(&self) -> QoSProfile {
+        match self {
+            QosPreset::Default => QOS_PROFILE_DEFAULT,
+            // 200ms 넘게 밀린 프레임은 보내지 않음
+            QosPreset::LossyWifi => QOS_PROFILE_DEFAULT
+                .best_effort()
+                .keep_last(1)
+                .lifespan(Duration::from_millis(200)),
+            // Livox 10Hz 기준 1.5 프레임 안에 다음 프레임이 없으면 deadline 위반
+            QosPreset::WiredLowLatency => QOS_PROFILE_DEFAULT
+                .best_effort()
+                .keep_last(1)
+                .deadline(Duration::from_millis(150)),
+            // 약 5초 (10Hz) 분량을 버퍼링
+            QosPreset::Recording => QOS_PROFILE_DEFAULT.reliable().keep_last(50),
+        }
+    }
+
+    // CycloneDDS 설정 조각 (CYCLONEDDS_URI 는 XML 을 직접 받음), 다른 RMW 에서는 무시됨
+    pub fn cyclonedds_config(&self) -> Option<&'static str> {
+        match self {
+            QosPreset::Default => None,
+            QosPreset::LossyWifi => Some(
+                "<CycloneDDS><Domain><General>\
+                 <MaxMessageSize>1400B</MaxMessageSize>\
+                 <FragmentSize>1300B</FragmentSize>\
+                 </General><Internal>\
+                 <SocketReceiveBufferSize min=\"8MB\"/>\
+                 </Internal></Domain></CycloneDDS>",
+            ),
+            QosPreset::WiredLowLatency => Some(
+                "<CycloneDDS><Domain><General>\
+                 <MaxMessageSize>65500B</MaxMessageSize>\
+                 <FragmentSize>65000B</FragmentSize>\
+                 </General><Internal>\
+                 <SocketReceiveBufferSize min=\"16MB\"/>\
+                 </Internal></Domain></CycloneDDS>",
+            ),
+            QosPreset::Recording => Some(
+                "<CycloneDDS><Domain><General>\
+                 <MaxMessageSize>65500B</MaxMessageSize>\
+                 </General><Internal>\
+                 <SocketReceiveBufferSize min=\"64MB\"/>\
+                 <Watermarks><WhcHigh>64MB</WhcHigh></Watermarks>\
+                 </Internal></Domain></CycloneDDS>",
+            ),
+        }
+    }
+
+    // Context 생성 전에 호출: 사용자가 CYCLONEDDS_URI 를 직접 지정했으면 그대로 둠
+    // (수신 버퍼 min 값은 커널 net.core.rmem_max 가 그보다 커야 적용됨)
+    pub fn apply_transport(&self) {
+        let Some(config) = self.cyclonedds_config() else {
+            return;
+        };
+        if env::var_os("CYCLONEDDS_URI").is_some() {
+            println!("CYCLONEDDS_URI 가 이미 설정되어 QoS 프리셋의 전송 설정은 건너뜀");
+            return;
+        }
+        env::set_var("CYCLONEDDS_URI", config);
+    }
+}
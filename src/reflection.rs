@@ -0,0 +1,185 @@
+use crate::ground::{self, Plane};
+use crate::point::LidarPoint;
+
+// 유리/거울 반사 허상 제거
+// 큰 수직 평면(벽, 유리창)을 찾은 뒤, 센서에서 나온 광선이 그 평면 영역을 통과해 뒤쪽에 찍힌
+// 낮은 intensity 포인트를 반사로 판단 (실제 벽이라면 뒤쪽은 가려져서 보일 수 없음)
+
+// flag 모드에서 반사로 판단한 포인트의 tag 에 세우는 비트 (Livox 가 쓰지 않는 상위 비트)
+pub const REFLECTION_TAG: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectionConfig {
+    // 검사할 평면 최대 개수와 평면으로 인정할 최소 인라이어 수
+    pub max_planes: usize,
+    pub min_inliers: usize,
+    pub threshold: f32,
+    // 평면 뒤로 이 거리 이상 떨어져야 반사 후보 (m)
+    pub margin: f32,
+    // 이 intensity 이상이면 실제 물체(유리 너머)로 보고 남김
+    pub max_intensity: f32,
+}
+
+impl Default for ReflectionConfig {
+    fn default() -> Self {
+        ReflectionConfig {
+            max_planes: 3,
+            min_inliers: 300,
+            threshold: 0.05,
+            margin: 0.3,
+            max_intensity: 30.0,
+        }
+    }
+}
+
+// 평면과 인라이어가 차지하는 평면 내 사각형 범위
+struct Surface {
+    plane: Plane,
+    u: [f32; 3],
+    v: [f32; 3],
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+impl Surface {
+    fn new(plane: Plane, inliers: &[[f32; 3]]) -> Self {
+        // 수직 평면이므로 u 는 수평 방향, v 는 normal 과 u 에 수직
+        let n = plane.normal;
+        let h = cross([0.0, 0.0, 1.0], n);
+        let len = dot(h, h).sqrt().max(1e-6);
+        let u = h.map(|c| c / len);
+        let v = cross(n, u);
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        for &p in inliers {
+            let uv = [dot(p, u), dot(p, v)];
+            for i in 0..2 {
+                min[i] = min[i].min(uv[i]);
+                max[i] = max[i].max(uv[i]);
+            }
+        }
+        Surface {
+            plane,
+            u,
+            v,
+            min,
+            max,
+        }
+    }
+
+    // 원점에서 p 로 가는 광선이 평면 영역을 지나고 p 가 margin 이상 뒤에 있으면 true
+    fn occludes(&self, p: [f32; 3], margin: f32) -> bool {
+        // 원점은 평면 위쪽(d > 0) 이므로 뒤쪽 포인트는 distance < 0
+        let dist = self.plane.distance(p);
+        if dist > -margin {
+            return false;
+        }
+        let denom = dot(self.plane.normal, p);
+        if denom.abs() < 1e-6 {
+            return false;
+        }
+        let t = -self.plane.d / denom;
+        if !(0.0..1.0).contains(&t) {
+            return false;
+        }
+        let hit = p.map(|c| c * t);
+        let uv = [dot(hit, self.u), dot(hit, self.v)];
+        (0..2).all(|i| uv[i] >= self.min[i] && uv[i] <= self.max[i])
+    }
+}
+
+// 반사로 판단한 포인트면 true
+pub fn detect(points: &[LidarPoint], config: &ReflectionConfig) -> Vec<bool> {
+    let xyz: Vec<[f32; 3]> = points.iter().map(|p| [p.x, p.y, p.z]).collect();
+    let mut remaining: Vec<usize> = (0..xyz.len()).collect();
+    let mut surfaces = Vec::new();
+
+    // 수직에 가까운 평면을 큰 것부터 차례로 찾음
+    for _ in 0..config.max_planes {
+        let candidates: Vec<[f32; 3]> = remaining.iter().map(|&i| xyz[i]).collect();
+        let Some((plane, inliers)) = ground::fit_plane(&candidates, 200, config.threshold, |p| {
+            p.normal[2].abs() < 0.3
+        }) else {
+            break;
+        };
+        if inliers.len() < config.min_inliers {
+            break;
+        }
+        let inlier_points: Vec<[f32; 3]> = inliers.iter().map(|&i| candidates[i]).collect();
+        surfaces.push(Surface::new(plane, &inlier_points));
+
+        let mut is_inlier = vec![false; candidates.len()];
+        for &i in &inliers {
+            is_inlier[i] = true;
+        }
+        remaining = remaining
+            .into_iter()
+            .zip(is_inlier)
+            .filter(|(_, inlier)| !inlier)
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    points
+        .iter()
+        .zip(&xyz)
+        .map(|(p, &q)| {
+            p.intensity < config.max_intensity
+                && surfaces.iter().any(|s| s.occludes(q, config.margin))
+        })
+        .collect()
+}
+
+// 반사 포인트 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectionMode {
+    Off,
+    Remove,
+    // 남기되 tag 에 REFLECTION_TAG 비트 표시
+    Flag,
+}
+
+impl ReflectionMode {
+    pub fn parse(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "off" => Ok(ReflectionMode::Off),
+            "remove" => Ok(ReflectionMode::Remove),
+            "flag" => Ok(ReflectionMode::Flag),
+            _ => anyhow::bail!(
+                "알 수 없는 reflection_filter '{}' (off, remove, flag)",
+                name
+            ),
+        }
+    }
+}
+
+pub fn apply(points: &mut Vec<LidarPoint>, mode: ReflectionMode, config: &ReflectionConfig) {
+    if mode == ReflectionMode::Off {
+        return;
+    }
+    let reflected = detect(points, config);
+    match mode {
+        ReflectionMode::Remove => {
+            let mut reflected = reflected.into_iter();
+            points.retain(|_| !reflected.next().unwrap_or(false));
+        }
+        ReflectionMode::Flag => {
+            for (p, _) in points.iter_mut().zip(reflected).filter(|(_, r)| *r) {
+                p.tag |= REFLECTION_TAG;
+            }
+        }
+        ReflectionMode::Off => {}
+    }
+}
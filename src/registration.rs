@@ -0,0 +1,538 @@
+use crate::compute::{ComputeBackend, Cpu};
+use crate::pose::Pose;
+use std::collections::HashMap;
+
+type Voxel = (i32, i32, i32);
+
+fn voxel_of(p: [f32; 3], size: f32) -> Voxel {
+    (
+        (p[0] / size).floor() as i32,
+        (p[1] / size).floor() as i32,
+        (p[2] / size).floor() as i32,
+    )
+}
+
+// 복셀마다 포인트 평균 하나로 줄임 (size <= 0 이면 그대로)
+pub fn voxel_downsample(points: &[[f32; 3]], size: f32) -> Vec<[f32; 3]> {
+    if size <= 0.0 {
+        return points.to_vec();
+    }
+    let mut sums: HashMap<Voxel, ([f64; 3], u32)> = HashMap::new();
+    for p in points {
+        let (sum, count) = sums.entry(voxel_of(*p, size)).or_default();
+        for k in 0..3 {
+            sum[k] += p[k] as f64;
+        }
+        *count += 1;
+    }
+    sums.into_values()
+        .map(|(sum, count)| sum.map(|v| (v / count as f64) as f32))
+        .collect()
+}
+
+// 최근접 포인트 검색용 복셀 해시 (셀 크기 = 최대 대응 거리, 주변 27 셀만 봄)
+pub struct VoxelIndex {
+    points: Vec<[f32; 3]>,
+    cells: HashMap<Voxel, Vec<u32>>,
+    size: f32,
+}
+
+impl VoxelIndex {
+    pub fn new(points: Vec<[f32; 3]>, size: f32) -> Self {
+        let size = size.max(1e-3);
+        let mut cells: HashMap<Voxel, Vec<u32>> = HashMap::new();
+        for (i, p) in points.iter().enumerate() {
+            cells.entry(voxel_of(*p, size)).or_default().push(i as u32);
+        }
+        VoxelIndex {
+            points,
+            cells,
+            size,
+        }
+    }
+
+    pub fn points(&self) -> &[[f32; 3]] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // radius 안의 포인트 인덱스 (radius 는 셀 크기 이하로 제한)
+    pub fn within(&self, p: [f32; 3], radius: f32) -> Vec<usize> {
+        let (cx, cy, cz) = voxel_of(p, self.size);
+        let limit = radius.min(self.size).powi(2);
+        let mut found = Vec::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(cell) = self.cells.get(&(cx + dx, cy + dy, cz + dz)) else {
+                        continue;
+                    };
+                    found.extend(cell.iter().map(|&i| i as usize).filter(|&i| {
+                        let q = self.points[i];
+                        (0..3).map(|k| (p[k] - q[k]).powi(2)).sum::<f32>() <= limit
+                    }));
+                }
+            }
+        }
+        found
+    }
+
+    // max_distance 안의 가장 가까운 포인트 (인덱스, 거리 제곱)
+    pub fn nearest(&self, p: [f32; 3], max_distance: f32) -> Option<(usize, f32)> {
+        let (cx, cy, cz) = voxel_of(p, self.size);
+        let mut best: Option<(usize, f32)> = None;
+        let limit = max_distance * max_distance;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(cell) = self.cells.get(&(cx + dx, cy + dy, cz + dz)) else {
+                        continue;
+                    };
+                    for &i in cell {
+                        let q = self.points[i as usize];
+                        let d2 = (0..3).map(|k| (p[k] - q[k]).powi(2)).sum::<f32>();
+                        if d2 <= limit && best.is_none_or(|(_, b)| d2 < b) {
+                            best = Some((i as usize, d2));
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IcpConfig {
+    pub max_iterations: usize,
+    // 대응점으로 인정할 최대 거리 (m)
+    pub max_distance: f32,
+    // 한 번의 갱신이 이보다 작으면 수렴 (m, rad)
+    pub tolerance: f64,
+    pub min_correspondences: usize,
+}
+
+impl Default for IcpConfig {
+    fn default() -> Self {
+        IcpConfig {
+            max_iterations: 30,
+            max_distance: 1.0,
+            tolerance: 1e-4,
+            min_correspondences: 50,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IcpResult {
+    // source 를 target 좌표계로 옮기는 자세
+    pub pose: Pose,
+    // 대응점을 찾은 source 포인트 비율
+    pub fitness: f64,
+    pub rmse: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// 대칭 행렬의 고유값과 고유벡터 (Jacobi 회전, 열 k 가 고유값 k 의 고유벡터)
+pub fn symmetric_eigen<const N: usize>(mut a: [[f64; N]; N]) -> ([f64; N], [[f64; N]; N]) {
+    let mut v = [[0.0; N]; N];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    for _ in 0..50 {
+        let off: f64 = (0..N)
+            .flat_map(|i| (0..N).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-20 {
+            break;
+        }
+        for p in 0..N - 1 {
+            for q in p + 1..N {
+                if a[p][q].abs() < 1e-30 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    (std::array::from_fn(|i| a[i][i]), v)
+}
+
+// 대칭 4x4 행렬의 최대 고유값에 대한 고유벡터
+fn max_eigenvector(a: [[f64; 4]; 4]) -> [f64; 4] {
+    let (values, v) = symmetric_eigen(a);
+    let best = (0..4)
+        .max_by(|i, j| values[*i].total_cmp(&values[*j]))
+        .unwrap_or(0);
+    [v[0][best], v[1][best], v[2][best], v[3][best]]
+}
+
+// 대응점 쌍 (source, target) 의 최소 제곱 강체 변환 (Horn 쿼터니언 방법)
+pub fn fit_rigid(pairs: &[([f32; 3], [f32; 3])]) -> Option<Pose> {
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mut ps = [0.0; 3];
+    let mut qs = [0.0; 3];
+    for (p, q) in pairs {
+        for k in 0..3 {
+            ps[k] += p[k] as f64 / n;
+            qs[k] += q[k] as f64 / n;
+        }
+    }
+    let mut s = [[0.0; 3]; 3];
+    for (p, q) in pairs {
+        for a in 0..3 {
+            for b in 0..3 {
+                s[a][b] += (p[a] as f64 - ps[a]) * (q[b] as f64 - qs[b]);
+            }
+        }
+    }
+    let [[sxx, sxy, sxz], [syx, syy, syz], [szx, szy, szz]] = s;
+    let m = [
+        [sxx + syy + szz, syz - szy, szx - sxz, sxy - syx],
+        [syz - szy, sxx - syy - szz, sxy + syx, szx + sxz],
+        [szx - sxz, sxy + syx, -sxx + syy - szz, syz + szy],
+        [sxy - syx, szx + sxz, syz + szy, -sxx - syy + szz],
+    ];
+    // 고유벡터는 (w, x, y, z) 순서
+    let [w, x, y, z] = max_eigenvector(m);
+    let rotation = Pose {
+        position: [0.0; 3],
+        orientation: [x, y, z, w],
+    };
+    let rotated = rotation.rotate(ps);
+    Some(Pose {
+        position: [qs[0] - rotated[0], qs[1] - rotated[1], qs[2] - rotated[2]],
+        orientation: rotation.orientation,
+    })
+}
+
+fn apply(pose: &Pose, p: [f32; 3]) -> [f32; 3] {
+    pose.transform_point([p[0] as f64, p[1] as f64, p[2] as f64])
+        .map(|v| v as f32)
+}
+
+// pose 로 옮긴 source 의 최근접 대응 비율과 거리 RMS (ICP 이외 정합 결과를 같은 기준으로 검증)
+pub fn evaluate(
+    source: &[[f32; 3]],
+    target: &VoxelIndex,
+    pose: &Pose,
+    max_distance: f32,
+) -> (f64, f64) {
+    let mut matched = 0;
+    let mut error_sum = 0.0;
+    for p in source {
+        if let Some((_, d2)) = target.nearest(apply(pose, *p), max_distance) {
+            matched += 1;
+            error_sum += d2 as f64;
+        }
+    }
+    (
+        matched as f64 / source.len().max(1) as f64,
+        (error_sum / matched.max(1) as f64).sqrt(),
+    )
+}
+
+// point-to-point ICP, 대응점이 부족하면 None
+pub fn icp(
+    source: &[[f32; 3]],
+    target: &VoxelIndex,
+    initial: Pose,
+    config: &IcpConfig,
+) -> Option<IcpResult> {
+    icp_with(&Cpu, source, target, initial, config)
+}
+
+// icp 와 같음, 반복마다 대응점 검색을 compute 경로(GPU, CUDA)에 한 번에 맡김
+pub fn icp_with(
+    compute: &dyn ComputeBackend,
+    source: &[[f32; 3]],
+    target: &VoxelIndex,
+    initial: Pose,
+    config: &IcpConfig,
+) -> Option<IcpResult> {
+    let mut pose = initial;
+    let mut moved = Vec::with_capacity(source.len());
+    let mut pairs = Vec::with_capacity(source.len());
+    let mut iterations = 0;
+    let mut converged = false;
+    let mut error_sum = 0.0;
+
+    while iterations < config.max_iterations {
+        iterations += 1;
+        pairs.clear();
+        error_sum = 0.0;
+        moved.clear();
+        moved.extend(source.iter().map(|p| apply(&pose, *p)));
+        let found = compute.nearest(&moved, target, config.max_distance);
+        for (p, (i, d2)) in moved.iter().zip(found).filter_map(|(p, f)| Some((p, f?))) {
+            pairs.push((*p, target.points()[i]));
+            error_sum += d2 as f64;
+        }
+        if pairs.len() < config.min_correspondences.max(3) {
+            return None;
+        }
+        let delta = fit_rigid(&pairs)?;
+        pose = delta.compose(&pose);
+        if delta.translation_norm() < config.tolerance && delta.angle() < config.tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    Some(IcpResult {
+        pose,
+        fitness: pairs.len() as f64 / source.len().max(1) as f64,
+        rmse: (error_sum / pairs.len().max(1) as f64).sqrt(),
+        iterations,
+        converged,
+    })
+}
+
+// 정합 결과의 불확실성 (point-to-plane 잔차의 Hessian 기반)
+#[derive(Debug, Clone, Copy)]
+pub struct RegistrationCovariance {
+    // [x, y, z, roll, pitch, yaw] 순서, 회전은 target 좌표계 기준 작은 회전
+    pub covariance: [[f64; 6]; 6],
+    // 이동/회전 블록의 최소/최대 고유값 비 (0 에 가까우면 그 방향으로 구속이 없음, 예: 긴 복도)
+    pub translation_condition: f64,
+    pub rotation_condition: f64,
+    // 평면 거리 잔차 RMS (m)와 사용한 대응점 수
+    pub residual: f64,
+    pub constraints: usize,
+}
+
+impl RegistrationCovariance {
+    pub fn condition(&self) -> f64 {
+        self.translation_condition.min(self.rotation_condition)
+    }
+}
+
+// target 주변 포인트의 최소 고유벡터 (평면이 아니면 None)
+fn local_normal(target: &VoxelIndex, q: [f32; 3], radius: f32) -> Option<[f64; 3]> {
+    let neighbors = target.within(q, radius);
+    if neighbors.len() < 5 {
+        return None;
+    }
+    let n = neighbors.len() as f64;
+    let mut mean = [0.0; 3];
+    for &i in &neighbors {
+        for (m, v) in mean.iter_mut().zip(target.points[i]) {
+            *m += v as f64 / n;
+        }
+    }
+    let mut cov = [[0.0; 3]; 3];
+    for &i in &neighbors {
+        let d: [f64; 3] = std::array::from_fn(|k| target.points[i][k] as f64 - mean[k]);
+        for a in 0..3 {
+            for b in 0..3 {
+                cov[a][b] += d[a] * d[b] / n;
+            }
+        }
+    }
+    let (values, v) = symmetric_eigen(cov);
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    // 가장 작은 분산이 두 번째의 1/3 보다 커야 평면 법선으로 믿을 수 있음
+    if values[order[0]] > values[order[1]] / 3.0 {
+        return None;
+    }
+    Some([v[0][order[0]], v[1][order[0]], v[2][order[0]]])
+}
+
+fn condition(h: [[f64; 3]; 3]) -> f64 {
+    let (values, _) = symmetric_eigen(h);
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    if max > 0.0 {
+        (min / max).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+// pose 로 옮긴 source 와 target 의 평면 대응으로 6x6 Hessian 을 만들고
+// 공분산 = 잔차 분산 * Hessian^-1 (구속이 약한 방향은 큰 분산)
+pub fn covariance(
+    source: &[[f32; 3]],
+    target: &VoxelIndex,
+    pose: &Pose,
+    max_distance: f32,
+) -> Option<RegistrationCovariance> {
+    let mut h = [[0.0; 6]; 6];
+    let mut residual = 0.0;
+    let mut constraints = 0;
+    for p in source {
+        let moved = apply(pose, *p);
+        let Some((i, _)) = target.nearest(moved, max_distance) else {
+            continue;
+        };
+        let q = target.points[i];
+        let Some(n) = local_normal(target, q, max_distance) else {
+            continue;
+        };
+        let m = moved.map(|v| v as f64);
+        let r: f64 = (0..3).map(|k| n[k] * (m[k] - q[k] as f64)).sum();
+        // d r / d[t, w] = [n, m x n]
+        let cross = [
+            m[1] * n[2] - m[2] * n[1],
+            m[2] * n[0] - m[0] * n[2],
+            m[0] * n[1] - m[1] * n[0],
+        ];
+        let j = [n[0], n[1], n[2], cross[0], cross[1], cross[2]];
+        for a in 0..6 {
+            for b in 0..6 {
+                h[a][b] += j[a] * j[b];
+            }
+        }
+        residual += r * r;
+        constraints += 1;
+    }
+    if constraints < 6 {
+        return None;
+    }
+    let residual = (residual / constraints as f64).sqrt();
+
+    // 고유값 분해로 역행렬, 거의 0 인 고유값은 바닥값으로 올려 유한한 큰 분산이 되게 함
+    let (values, v) = symmetric_eigen(h);
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let floor = (max * 1e-9).max(1e-12);
+    // 잔차가 양자화 수준보다 작으면 과신하지 않도록 1 cm 를 하한으로 사용
+    let variance = residual.max(0.01).powi(2);
+    let mut covariance = [[0.0; 6]; 6];
+    for k in 0..6 {
+        let inv = variance / values[k].max(floor);
+        for a in 0..6 {
+            for b in 0..6 {
+                covariance[a][b] += v[a][k] * v[b][k] * inv;
+            }
+        }
+    }
+    let block = |o: usize| -> [[f64; 3]; 3] {
+        std::array::from_fn(|a| std::array::from_fn(|b| h[o + a][o + b]))
+    };
+    Some(RegistrationCovariance {
+        covariance,
+        translation_condition: condition(block(0)),
+        rotation_condition: condition(block(3)),
+        residual,
+        constraints,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 바닥, 두 벽, 상자 하나를 불규칙하게 샘플링한 장면 (고정 시드)
+    fn scene() -> Vec<[f32; 3]> {
+        let mut seed = 12345u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut points = Vec::new();
+        for _ in 0..3000 {
+            let (a, b) = (next() * 10.0 - 5.0, next() * 10.0 - 5.0);
+            points.push([a, b, 0.0]);
+            points.push([5.0, a, b * 0.3 + 1.5]);
+            points.push([a, 5.0, b * 0.3 + 1.5]);
+        }
+        for _ in 0..1000 {
+            let (a, b) = (next(), next());
+            points.push([1.0 + a, -2.0, b]);
+            points.push([1.0, -2.0 + a, b]);
+            points.push([1.0 + a, -2.0 + b, 1.0]);
+        }
+        points
+    }
+
+    #[test]
+    fn voxel_downsample_averages_each_voxel() {
+        let mut points = voxel_downsample(
+            &[
+                [0.1, 0.1, 0.1],
+                [0.3, 0.5, 0.7],
+                [1.5, 0.2, 0.2],
+                [-0.5, 0.0, 0.0],
+            ],
+            1.0,
+        );
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        assert_eq!(points, [[-0.5, 0.0, 0.0], [0.2, 0.3, 0.4], [1.5, 0.2, 0.2]]);
+    }
+
+    #[test]
+    fn fit_rigid_recovers_exact_transform() {
+        let truth = Pose::from_rpy([1.0, -2.0, 0.5], 0.1, -0.2, 0.7);
+        let pairs: Vec<([f32; 3], [f32; 3])> = scene()
+            .into_iter()
+            .step_by(50)
+            .map(|p| (p, apply(&truth, p)))
+            .collect();
+        let pose = fit_rigid(&pairs).unwrap();
+        let error = truth.inverse().compose(&pose);
+        assert!(error.translation_norm() < 1e-4 && error.angle() < 1e-4);
+    }
+
+    #[test]
+    fn icp_converges_to_known_offset() {
+        let target = scene();
+        let truth = Pose::from_rpy([0.2, -0.1, 0.05], 0.0, 0.0, 0.03);
+        let source: Vec<[f32; 3]> = target
+            .iter()
+            .step_by(5)
+            .map(|p| apply(&truth.inverse(), *p))
+            .collect();
+        let config = IcpConfig {
+            max_distance: 0.5,
+            ..IcpConfig::default()
+        };
+        let index = VoxelIndex::new(target, config.max_distance);
+
+        let result = icp(&source, &index, Pose::IDENTITY, &config).unwrap();
+        assert!(result.converged);
+        assert!(result.fitness > 0.99 && result.rmse < 0.01);
+        let error = truth.inverse().compose(&result.pose);
+        assert!(
+            error.translation_norm() < 1e-3 && error.angle() < 1e-3,
+            "{:?}",
+            result.pose
+        );
+    }
+
+    #[test]
+    fn icp_fails_without_correspondences() {
+        let index = VoxelIndex::new(scene(), 1.0);
+        let far: Vec<[f32; 3]> = scene().iter().map(|p| [p[0] + 100.0, p[1], p[2]]).collect();
+        assert!(icp(&far, &index, Pose::IDENTITY, &IcpConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,403 @@
+use crate::pose::Pose;
+use crate::registration;
+use crate::scan_context::{ScanContext, ScanContextConfig};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RelocalizeConfig {
+    // 힌트 위치 주변 탐색 반경 (m, 0 이면 주어진 지도 전체)
+    pub search_radius: f32,
+    // Scan Context 기술자를 만들 후보 위치 간격 (m), 분기 한정 탐색 창의 반폭이기도 함
+    pub grid_step: f32,
+    // Scan Context 거리로 고른 후보 수
+    pub candidates: usize,
+    pub scan_context: ScanContextConfig,
+    // 분기 한정 탐색 격자 해상도 (m)
+    pub resolution: f32,
+    // 후보 yaw 주변 탐색 범위와 간격 (라디안)
+    pub yaw_window: f32,
+    pub yaw_step: f32,
+    // 지면 위 이 높이보다 높은 포인트만 2D 격자 점수에 사용 (m)
+    pub min_height: f32,
+    // 점유 셀에 떨어진 스캔 포인트 비율이 이보다 낮으면 실패
+    pub min_score: f32,
+}
+
+impl Default for RelocalizeConfig {
+    fn default() -> Self {
+        RelocalizeConfig {
+            search_radius: 30.0,
+            grid_step: 4.0,
+            candidates: 10,
+            scan_context: ScanContextConfig {
+                max_range: 40.0,
+                ..ScanContextConfig::default()
+            },
+            resolution: 0.5,
+            yaw_window: 10f32.to_radians(),
+            yaw_step: 2f32.to_radians(),
+            min_height: 0.3,
+            min_score: 0.55,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Relocalization {
+    // 지도 좌표계 자세 (z 는 힌트 값, roll/pitch 는 0)
+    pub pose: Pose,
+    pub score: f32,
+    pub scan_context_distance: f32,
+}
+
+// 점유 격자와 2^k 셀 창의 최대값 격자들 (분기 한정 탐색의 상한 계산용)
+struct Grid {
+    origin: [f32; 2],
+    resolution: f32,
+    width: i64,
+    height: i64,
+    levels: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    fn new(points: &[[f32; 2]], resolution: f32, depth: usize, margin: f32) -> Option<Self> {
+        let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+        for p in points {
+            for k in 0..2 {
+                min[k] = min[k].min(p[k]);
+                max[k] = max[k].max(p[k]);
+            }
+        }
+        if points.is_empty() {
+            return None;
+        }
+        let origin = [min[0] - margin, min[1] - margin];
+        let width = ((max[0] - origin[0] + margin) / resolution) as i64 + 1;
+        let height = ((max[1] - origin[1] + margin) / resolution) as i64 + 1;
+        let mut base = vec![false; (width * height) as usize];
+        for p in points {
+            let x = ((p[0] - origin[0]) / resolution) as i64;
+            let y = ((p[1] - origin[1]) / resolution) as i64;
+            base[(y * width + x) as usize] = true;
+        }
+        let mut levels = vec![base];
+        for k in 1..=depth {
+            let half = 1i64 << (k - 1);
+            let prev = &levels[k - 1];
+            let at = |x: i64, y: i64| x < width && y < height && prev[(y * width + x) as usize];
+            let level = (0..width * height)
+                .map(|i| {
+                    let (x, y) = (i % width, i / width);
+                    at(x, y) || at(x + half, y) || at(x, y + half) || at(x + half, y + half)
+                })
+                .collect();
+            levels.push(level);
+        }
+        Some(Grid {
+            origin,
+            resolution,
+            width,
+            height,
+            levels,
+        })
+    }
+
+    fn cell(&self, p: [f32; 2]) -> (i64, i64) {
+        (
+            ((p[0] - self.origin[0]) / self.resolution).floor() as i64,
+            ((p[1] - self.origin[1]) / self.resolution).floor() as i64,
+        )
+    }
+
+    fn occupied(&self, level: usize, x: i64, y: i64) -> bool {
+        x >= 0
+            && y >= 0
+            && x < self.width
+            && y < self.height
+            && self.levels[level][(y * self.width + x) as usize]
+    }
+
+    // 셀 오프셋 (dx, dy) 에서 점유 셀에 떨어진 포인트 수 (level 이 높으면 [dx, dx+2^level) 창의 상한)
+    fn score(&self, cells: &[(i64, i64)], level: usize, dx: i64, dy: i64) -> usize {
+        cells
+            .iter()
+            .filter(|(x, y)| self.occupied(level, x + dx, y + dy))
+            .count()
+    }
+
+    // 오프셋 [-window, window] 범위에서 점수 최대 오프셋 찾기, best 보다 나은 해만 갱신
+    fn search(&self, cells: &[(i64, i64)], window: i64, best: &mut Option<(usize, i64, i64)>) {
+        let depth = self.levels.len() - 1;
+        let step = 1i64 << depth;
+        let mut roots = Vec::new();
+        let mut dx = -window;
+        while dx <= window {
+            let mut dy = -window;
+            while dy <= window {
+                roots.push((self.score(cells, depth, dx, dy), dx, dy));
+                dy += step;
+            }
+            dx += step;
+        }
+        roots.sort_by_key(|r| std::cmp::Reverse(r.0));
+        for (bound, dx, dy) in roots {
+            self.branch(cells, window, depth, bound, dx, dy, best);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn branch(
+        &self,
+        cells: &[(i64, i64)],
+        window: i64,
+        level: usize,
+        bound: usize,
+        dx: i64,
+        dy: i64,
+        best: &mut Option<(usize, i64, i64)>,
+    ) {
+        if best.is_some_and(|(score, _, _)| bound <= score) {
+            return;
+        }
+        if level == 0 {
+            *best = Some((bound, dx, dy));
+            return;
+        }
+        let half = 1i64 << (level - 1);
+        let mut children: Vec<(usize, i64, i64)> = [(0, 0), (half, 0), (0, half), (half, half)]
+            .into_iter()
+            .map(|(ox, oy)| (dx + ox, dy + oy))
+            .filter(|&(x, y)| x <= window && y <= window)
+            .map(|(x, y)| (self.score(cells, level - 1, x, y), x, y))
+            .collect();
+        children.sort_by_key(|c| std::cmp::Reverse(c.0));
+        for (bound, x, y) in children {
+            self.branch(cells, window, level - 1, bound, x, y, best);
+        }
+    }
+}
+
+// 전역 재위치 추정: 후보 위치마다 지도 Scan Context 를 만들어 스캔과 비교하고,
+// 가까운 후보 주변을 2D 점유 격자 분기 한정 탐색으로 x, y, yaw 를 찾음 (정밀 정합은 호출 측 ICP)
+// map 은 지도 좌표계, scan 은 센서(차량) 좌표계, hint 의 z 를 센서 높이로 사용
+pub fn relocalize(
+    map: &[[f32; 3]],
+    scan: &[[f32; 3]],
+    hint: &Pose,
+    config: &RelocalizeConfig,
+) -> Option<Relocalization> {
+    let [hx, hy, hz] = hint.position.map(|v| v as f32);
+    let sc = &config.scan_context;
+    let in_region = |p: &[f32; 3]| {
+        config.search_radius <= 0.0
+            || (p[0] - hx).powi(2) + (p[1] - hy).powi(2)
+                <= (config.search_radius + sc.max_range).powi(2)
+    };
+    let map: Vec<[f32; 3]> = map.iter().filter(|p| in_region(p)).copied().collect();
+    if map.is_empty() || scan.is_empty() {
+        return None;
+    }
+
+    // 1) Scan Context 후보: 지도 포인트가 있는 격자 위치에서 기술자를 만들어 비교
+    let query = ScanContext::new(scan, sc);
+    let coarse = registration::voxel_downsample(&map, config.grid_step.min(1.0));
+    let step = config.grid_step.max(config.resolution);
+    let mut positions: Vec<(i64, i64)> = coarse
+        .iter()
+        .map(|p| ((p[0] / step).round() as i64, (p[1] / step).round() as i64))
+        .filter(|&(x, y)| {
+            config.search_radius <= 0.0
+                || (x as f32 * step - hx).powi(2) + (y as f32 * step - hy).powi(2)
+                    <= config.search_radius.powi(2)
+        })
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    let ring_distance = |a: &ScanContext, b: &ScanContext| -> f32 {
+        a.ring_key()
+            .iter()
+            .zip(b.ring_key())
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum()
+    };
+    let mut contexts: Vec<(f32, [f32; 2], ScanContext)> = positions
+        .into_iter()
+        .map(|(x, y)| {
+            let center = [x as f32 * step, y as f32 * step];
+            let local: Vec<[f32; 3]> = coarse
+                .iter()
+                .map(|p| [p[0] - center[0], p[1] - center[1], p[2] - hz])
+                .filter(|p| p[0] * p[0] + p[1] * p[1] < sc.max_range * sc.max_range)
+                .collect();
+            let context = ScanContext::new(&local, sc);
+            (ring_distance(&context, &query), center, context)
+        })
+        .collect();
+    contexts.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut candidates: Vec<(f32, [f32; 2], f32)> = contexts
+        .iter()
+        .take(config.candidates.max(1) * 3)
+        .map(|(_, center, context)| {
+            let (distance, yaw) = context.distance(&query);
+            (distance, *center, yaw)
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    candidates.truncate(config.candidates.max(1));
+
+    // 2) 분기 한정 탐색: 지면 위 포인트만 2D 로 투영한 점유 격자에서 점수 최대 오프셋
+    let obstacles: Vec<[f32; 2]> = map
+        .iter()
+        .filter(|p| p[2] > hz - sc.sensor_height + config.min_height)
+        .map(|p| [p[0], p[1]])
+        .collect();
+    let scan2d: Vec<[f32; 3]> = registration::voxel_downsample(
+        &scan
+            .iter()
+            .filter(|p| p[2] > config.min_height - sc.sensor_height)
+            .map(|p| [p[0], p[1], 0.0])
+            .collect::<Vec<_>>(),
+        config.resolution,
+    );
+    if scan2d.is_empty() {
+        return None;
+    }
+    let window = (step / config.resolution).ceil() as i64;
+    let mut depth = 0;
+    while (1i64 << depth) < window {
+        depth += 1;
+    }
+    let grid = Grid::new(&obstacles, config.resolution, depth, step * 2.0)?;
+
+    let mut best: Option<(usize, i64, i64)> = None;
+    let mut best_pose: Option<(Pose, f32)> = None;
+    let yaw_steps = (config.yaw_window / config.yaw_step.max(1e-3)).round() as i32;
+    for &(distance, center, yaw) in &candidates {
+        for k in -yaw_steps..=yaw_steps {
+            let yaw = yaw + k as f32 * config.yaw_step;
+            let (s, c) = yaw.sin_cos();
+            let cells: Vec<(i64, i64)> = scan2d
+                .iter()
+                .map(|p| {
+                    grid.cell([
+                        center[0] + c * p[0] - s * p[1],
+                        center[1] + s * p[0] + c * p[1],
+                    ])
+                })
+                .collect();
+            let before = best.map(|b| b.0);
+            grid.search(&cells, window, &mut best);
+            if let Some((_, dx, dy)) = best.filter(|b| Some(b.0) != before) {
+                let x = center[0] + dx as f32 * config.resolution;
+                let y = center[1] + dy as f32 * config.resolution;
+                let yaw = (yaw + PI).rem_euclid(2.0 * PI) - PI;
+                let mut pose = Pose::from_xy_yaw(x as f64, y as f64, yaw as f64);
+                pose.position[2] = hz as f64;
+                best_pose = Some((pose, distance));
+            }
+        }
+    }
+
+    let (score, _, _) = best?;
+    let score = score as f32 / scan2d.len() as f32;
+    let (pose, scan_context_distance) = best_pose?;
+    (score >= config.min_score).then_some(Relocalization {
+        pose,
+        score,
+        scan_context_distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 센서 높이 (ScanContextConfig::default 의 sensor_height 와 같게)
+    const SENSOR_Z: f32 = 2.0;
+
+    // 60 m 구역의 불규칙한 기둥들 (고정 시드)
+    fn pillars(seed: u32) -> Vec<[f32; 3]> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut points = Vec::new();
+        for _ in 0..25 {
+            let (cx, cy) = (next() * 60.0 - 30.0, next() * 60.0 - 30.0);
+            let radius = 0.2 + next() * 0.6;
+            for k in 0..12 {
+                let (s, c) = (k as f32 * PI / 6.0).sin_cos();
+                for z in 0..6 {
+                    points.push([cx + radius * c, cy + radius * s, z as f32 * 0.5]);
+                }
+            }
+        }
+        points
+    }
+
+    // 기둥, 세 벽, 바닥 (z = 0)
+    fn map() -> Vec<[f32; 3]> {
+        let mut points = pillars(7);
+        for i in 0..300 {
+            let t = i as f32 * 0.2 - 30.0;
+            for z in 0..4 {
+                let z = z as f32 * 0.7;
+                points.push([t, 30.0, z]);
+                points.push([-30.0, t, z]);
+                points.push([30.0, t * 0.5, z]);
+            }
+        }
+        for x in -30..30 {
+            for y in -30..30 {
+                points.push([x as f32, y as f32, 0.0]);
+            }
+        }
+        points
+    }
+
+    // 지도 좌표계 (x, y, yaw) 에서 SENSOR_Z 높이로 40 m 안을 본 센서 좌표계 스캔
+    fn scan_at(points: &[[f32; 3]], x: f32, y: f32, yaw: f32) -> Vec<[f32; 3]> {
+        let (s, c) = yaw.sin_cos();
+        points
+            .iter()
+            .map(|p| [p[0] - x, p[1] - y, p[2] - SENSOR_Z])
+            .filter(|p| p[0] * p[0] + p[1] * p[1] < 40.0 * 40.0)
+            .map(|p| [c * p[0] + s * p[1], -s * p[0] + c * p[1], p[2]])
+            .collect()
+    }
+
+    fn hint() -> Pose {
+        Pose {
+            position: [0.0, 0.0, SENSOR_Z as f64],
+            ..Pose::IDENTITY
+        }
+    }
+
+    #[test]
+    fn relocalize_finds_pose_from_distant_hint() {
+        let map = map();
+        let (x, y, yaw) = (9.0, -6.0, 0.8);
+        let scan = scan_at(&map, x, y, yaw);
+
+        let config = RelocalizeConfig::default();
+        let found = relocalize(&map, &scan, &hint(), &config).expect("재위치 추정 실패");
+        let [px, py, pz] = found.pose.position;
+        let yaw_error = (found.pose.yaw() as f32 - yaw + PI).rem_euclid(2.0 * PI) - PI;
+        // 격자 해상도 (0.5 m) 와 yaw 간격 수준, 나머지는 호출 측 ICP 가 다듬음
+        assert!(
+            (px as f32 - x).hypot(py as f32 - y) < 2.0 * config.resolution
+                && yaw_error.abs() < 2.0 * config.yaw_step,
+            "{:?}",
+            found
+        );
+        assert_eq!(pz, SENSOR_Z as f64);
+        assert!(found.score >= config.min_score);
+    }
+
+    #[test]
+    fn relocalize_rejects_scan_of_other_place() {
+        let scan = scan_at(&pillars(99), 0.0, 0.0, 0.0);
+        assert!(relocalize(&map(), &scan, &hint(), &RelocalizeConfig::default()).is_none());
+    }
+}
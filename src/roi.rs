@@ -0,0 +1,103 @@
+use crate::cloud::{Point, PointCloud};
+use crate::exclusion;
+#[cfg(feature = "ros")]
+use crate::params;
+#[cfg(feature = "ros")]
+use crate::stamp;
+#[cfg(feature = "ros")]
+use anyhow::Result;
+#[cfg(feature = "ros")]
+use geometry_msgs::msg::PolygonStamped;
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoiConfig {
+    // ROI 밖 포인트를 줄이는 복셀 크기 (m, 복셀마다 첫 포인트만 남김)
+    pub outside_voxel: f32,
+    // 마지막 ROI 수신 후 이 시간(초)이 지나면 ROI 를 버리고 전체를 원래 해상도로 처리
+    pub timeout: f64,
+}
+
+impl RoiConfig {
+    // roi_topic 이 비어 있으면 None, roi_outside_voxel (m), roi_timeout (초)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<(String, Self)>> {
+        let topic = params::string(node, "roi_topic", "")?;
+        if topic.is_empty() {
+            return Ok(None);
+        }
+        let config = RoiConfig {
+            outside_voxel: params::float(node, "roi_outside_voxel", 1.0)? as f32,
+            timeout: params::float(node, "roi_timeout", 1.0)?,
+        };
+        Ok(Some((topic, config)))
+    }
+}
+
+// 외부 노드(플래너 등)가 보낸 관심 영역: 안쪽은 원래 해상도, 바깥은 크게 다운샘플해서 계산량을 줄임
+// 다각형은 장착 보정 후 좌표계 x/y, 높이와 무관
+#[derive(Debug, Clone)]
+pub struct RoiAttention {
+    config: RoiConfig,
+    polygon: Vec<[f32; 2]>,
+    // 수신 시각 (초, 메시지 header 기준)
+    time: f64,
+}
+
+impl RoiAttention {
+    pub fn new(config: RoiConfig) -> Self {
+        RoiAttention {
+            config,
+            polygon: Vec::new(),
+            time: 0.0,
+        }
+    }
+
+    // 꼭짓점이 3개 미만이면 ROI 해제
+    pub fn set(&mut self, polygon: Vec<[f32; 2]>, time: f64) {
+        self.polygon = if polygon.len() >= 3 {
+            polygon
+        } else {
+            Vec::new()
+        };
+        self.time = time;
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn set_msg(&mut self, msg: &PolygonStamped) {
+        let polygon = msg.polygon.points.iter().map(|p| [p.x, p.y]).collect();
+        self.set(polygon, stamp::to_secs(&msg.header.stamp));
+    }
+
+    // frame_time 시각에 쓸 수 있는 ROI 인지
+    pub fn active(&self, frame_time: f64) -> bool {
+        !self.polygon.is_empty() && (frame_time - self.time).abs() <= self.config.timeout
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        exclusion::point_in_polygon(&self.polygon, x, y)
+    }
+
+    // 남길 포인트 마스크 (ROI 가 없거나 오래됐으면 모두 남김)
+    pub fn mask<P: Point>(&self, points: &[P], frame_time: f64) -> Vec<bool> {
+        if !self.active(frame_time) || self.config.outside_voxel <= 0.0 {
+            return vec![true; points.len()];
+        }
+        let size = self.config.outside_voxel;
+        let mut seen = HashSet::new();
+        points
+            .iter()
+            .map(|p| {
+                let [x, y, z] = p.xyz();
+                self.contains(x, y) || seen.insert([x, y, z].map(|v| (v / size).floor() as i32))
+            })
+            .collect()
+    }
+
+    pub fn apply<P: Point>(&self, cloud: &mut PointCloud<P>, frame_time: f64) {
+        let mut keep = self.mask(&cloud.points, frame_time).into_iter();
+        cloud.retain(|_| keep.next().unwrap_or(true));
+    }
+}
@@ -0,0 +1,1003 @@
+use crate::msg::PointCloud2;
+#[cfg(feature = "ros")]
+use crate::params;
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// ROS 1 출력 브리지: 옮기지 못한 레거시 시스템에 PointCloud2 를 그대로 전달
+// rosrust 없이 master 등록 (XML-RPC) 과 TCPROS 발행만 구현, 구독(ROS 1 -> ROS 2) 은 지원하지 않음
+const POINTCLOUD2_TYPE: &str = "sensor_msgs/PointCloud2";
+const POINTCLOUD2_MD5: &str = "1158d486dd51d683ce2f1be655c3c181";
+const POINTCLOUD2_DEFINITION: &str = "\
+std_msgs/Header header
+uint32 height
+uint32 width
+sensor_msgs/PointField[] fields
+bool is_bigendian
+uint32 point_step
+uint32 row_step
+uint8[] data
+bool is_dense
+================================================================================
+MSG: std_msgs/Header
+uint32 seq
+time stamp
+string frame_id
+================================================================================
+MSG: sensor_msgs/PointField
+uint8 INT8=1
+uint8 UINT8=2
+uint8 INT16=3
+uint8 UINT16=4
+uint8 INT32=5
+uint8 UINT32=6
+uint8 FLOAT32=7
+uint8 FLOAT64=8
+string name
+uint32 offset
+uint8 datatype
+uint32 count
+";
+
+// 느린 구독자가 발행 스레드를 오래 붙잡지 않도록
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+// 상대가 보낸 길이를 그대로 믿지 않도록 TCPROS 헤더와 XML-RPC 헤더/본문 크기 상한
+const MAX_HEADER: usize = 1 << 20;
+const MAX_HTTP_BODY: usize = 1 << 20;
+
+// XML-RPC 값 (ROS master/slave API 에서 쓰는 타입만)
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i32),
+    Str(String),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn write(&self, out: &mut String) {
+        match self {
+            Value::Int(v) => out.push_str(&format!("<value><i4>{}</i4></value>", v)),
+            Value::Str(s) => {
+                out.push_str(&format!("<value><string>{}</string></value>", escape(s)))
+            }
+            Value::Array(items) => {
+                out.push_str("<value><array><data>");
+                for item in items {
+                    item.write(out);
+                }
+                out.push_str("</data></array></value>");
+            }
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// 태그 단위로 읽는 최소 XML-RPC 파서 (struct 는 건너뜀)
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_space(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, tag: &str) -> bool {
+        self.skip_space();
+        match self.rest.strip_prefix(tag) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, tag: &str) -> Result<()> {
+        if !self.eat(tag) {
+            bail!("XML-RPC: {} 가 필요합니다", tag);
+        }
+        Ok(())
+    }
+
+    // 다음 '<' 까지의 텍스트
+    fn text(&mut self) -> String {
+        let end = self.rest.find('<').unwrap_or(self.rest.len());
+        let text = unescape(&self.rest[..end]);
+        self.rest = &self.rest[end..];
+        text
+    }
+
+    // 다음 태그 이름 (여는 태그만, '<' 는 소비하지 않음)
+    fn peek_tag(&mut self) -> Option<&'a str> {
+        self.skip_space();
+        let rest = self.rest.strip_prefix('<')?;
+        let end = rest.find(['>', '/', ' '])?;
+        Some(&rest[..end])
+    }
+
+    // 요소 하나를 통째로 건너뜀 (같은 이름 중첩은 고려하지 않음)
+    fn skip_element(&mut self, name: &str) -> Result<()> {
+        let close = format!("</{}>", name);
+        let Some(end) = self.rest.find(&close) else {
+            bail!("XML-RPC: {} 가 닫히지 않았습니다", name);
+        };
+        self.rest = &self.rest[end + close.len()..];
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.expect("<value>")?;
+        if self.rest.trim_start().starts_with("</value>")
+            || !self.rest.trim_start().starts_with('<')
+        {
+            // 타입 태그가 없으면 문자열
+            let text = self.text();
+            self.expect("</value>")?;
+            return Ok(Value::Str(text));
+        }
+        let value = match self.peek_tag() {
+            Some(tag @ ("int" | "i4" | "boolean")) => {
+                self.expect(&format!("<{}>", tag))?;
+                let text = self.text();
+                self.expect(&format!("</{}>", tag))?;
+                Value::Int(text.trim().parse().context("XML-RPC: 정수가 아닙니다")?)
+            }
+            Some(tag @ ("string" | "double")) => {
+                if self.eat(&format!("<{}/>", tag)) {
+                    Value::Str(String::new())
+                } else {
+                    self.expect(&format!("<{}>", tag))?;
+                    let text = self.text();
+                    self.expect(&format!("</{}>", tag))?;
+                    Value::Str(text)
+                }
+            }
+            Some("array") => {
+                self.expect("<array>")?;
+                self.expect("<data>")?;
+                let mut items = Vec::new();
+                while !self.eat("</data>") {
+                    items.push(self.value()?);
+                }
+                self.expect("</array>")?;
+                Value::Array(items)
+            }
+            Some("struct") => {
+                self.skip_element("struct")?;
+                Value::Array(Vec::new())
+            }
+            tag => bail!("XML-RPC: 지원하지 않는 값 {:?}", tag),
+        };
+        self.expect("</value>")?;
+        Ok(value)
+    }
+
+    // <params><param><value>..</value></param>...</params>
+    fn params(&mut self) -> Result<Vec<Value>> {
+        let mut values = Vec::new();
+        if !self.eat("<params>") {
+            return Ok(values);
+        }
+        while self.eat("<param>") {
+            values.push(self.value()?);
+            self.expect("</param>")?;
+        }
+        self.expect("</params>")?;
+        Ok(values)
+    }
+}
+
+fn strip_declaration(xml: &str) -> &str {
+    let xml = xml.trim_start();
+    match xml.strip_prefix("<?xml") {
+        Some(rest) => rest.find("?>").map_or(rest, |end| &rest[end + 2..]),
+        None => xml,
+    }
+}
+
+fn method_call(method: &str, params: &[Value]) -> String {
+    let mut body = format!(
+        "<?xml version=\"1.0\"?><methodCall><methodName>{}</methodName><params>",
+        method
+    );
+    for param in params {
+        body.push_str("<param>");
+        param.write(&mut body);
+        body.push_str("</param>");
+    }
+    body.push_str("</params></methodCall>");
+    body
+}
+
+fn method_response(value: &Value) -> String {
+    let mut body = String::from("<?xml version=\"1.0\"?><methodResponse><params><param>");
+    value.write(&mut body);
+    body.push_str("</param></params></methodResponse>");
+    body
+}
+
+fn parse_call(body: &str) -> Result<(String, Vec<Value>)> {
+    let mut parser = Parser {
+        rest: strip_declaration(body),
+    };
+    parser.expect("<methodCall>")?;
+    parser.expect("<methodName>")?;
+    let method = parser.text();
+    parser.expect("</methodName>")?;
+    Ok((method, parser.params()?))
+}
+
+fn parse_response(body: &str) -> Result<Value> {
+    let mut parser = Parser {
+        rest: strip_declaration(body),
+    };
+    parser.expect("<methodResponse>")?;
+    if parser.peek_tag() == Some("fault") {
+        bail!("XML-RPC fault: {}", body);
+    }
+    parser
+        .params()?
+        .into_iter()
+        .next()
+        .context("XML-RPC: 응답 값이 없습니다")
+}
+
+// HTTP 요청/응답 하나를 읽고 본문 반환 (Content-Length 기준, 없으면 연결 끝까지)
+// 헤더와 본문은 각각 MAX_HEADER, MAX_HTTP_BODY 까지만 읽음
+fn read_http(reader: &mut impl BufRead) -> Result<String> {
+    let mut length = None;
+    let mut headers = reader.take(MAX_HEADER as u64);
+    loop {
+        let mut line = String::new();
+        if headers.read_line(&mut line)? == 0 {
+            if headers.limit() == 0 {
+                bail!("HTTP 헤더가 너무 큽니다 ({} bytes 초과)", MAX_HEADER);
+            }
+            bail!("HTTP 헤더가 끝나기 전에 연결이 닫혔습니다");
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+    let reader = headers.into_inner();
+    let mut body = Vec::new();
+    match length {
+        Some(n) if n > MAX_HTTP_BODY => {
+            bail!("HTTP 본문이 너무 큽니다 ({} bytes)", n);
+        }
+        Some(n) => {
+            body.resize(n, 0);
+            reader.read_exact(&mut body)?;
+        }
+        None => {
+            reader
+                .take(MAX_HTTP_BODY as u64 + 1)
+                .read_to_end(&mut body)?;
+            if body.len() > MAX_HTTP_BODY {
+                bail!("HTTP 본문이 너무 큽니다 ({} bytes 초과)", MAX_HTTP_BODY);
+            }
+        }
+    }
+    Ok(String::from_utf8(body)?)
+}
+
+// http://host:port/ -> (host:port, path)
+fn split_uri(uri: &str) -> Result<(&str, &str)> {
+    let Some(rest) = uri.strip_prefix("http://") else {
+        bail!("ROS 1 URI 는 http:// 로 시작해야 합니다: {}", uri);
+    };
+    Ok(match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    })
+}
+
+// master API 호출, [code, status, value] 에서 code 가 1 이 아니면 오류
+fn call(uri: &str, method: &str, params: &[Value]) -> Result<Value> {
+    let (address, path) = split_uri(uri)?;
+    let body = method_call(method, params);
+    let mut stream =
+        TcpStream::connect(address).with_context(|| format!("ROS master {} 연결 실패", uri))?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+        path,
+        address,
+        body.len(),
+        body
+    )?;
+    // 상태 줄은 헤더와 같이 건너뜀
+    let response = parse_response(&read_http(&mut BufReader::new(stream))?)?;
+    match response {
+        Value::Array(items) if items.first() == Some(&Value::Int(1)) => {
+            Ok(items.into_iter().nth(2).unwrap_or(Value::Int(0)))
+        }
+        Value::Array(items) => bail!(
+            "{} 실패: {}",
+            method,
+            items.get(1).and_then(Value::as_str).unwrap_or("")
+        ),
+        _ => bail!("{} 응답 형식이 잘못되었습니다", method),
+    }
+}
+
+// TCPROS 연결 헤더: 4 바이트 길이 + (4 바이트 길이 + "key=value") 반복
+fn write_header(stream: &mut TcpStream, fields: &[(&str, &str)]) -> Result<()> {
+    let mut body = Vec::new();
+    for (key, value) in fields {
+        let field = format!("{}={}", key, value);
+        body.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        body.extend_from_slice(field.as_bytes());
+    }
+    let mut out = (body.len() as u32).to_le_bytes().to_vec();
+    out.extend_from_slice(&body);
+    stream.write_all(&out)?;
+    Ok(())
+}
+
+fn read_header(stream: &mut TcpStream) -> Result<HashMap<String, String>> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_HEADER {
+        bail!("TCPROS 헤더가 너무 큽니다 ({} bytes)", len);
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+
+    let mut fields = HashMap::new();
+    let mut rest = &body[..];
+    while rest.len() >= 4 {
+        let n = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let Some(field) = rest.get(4..4 + n) else {
+            bail!("TCPROS 헤더 필드 길이가 잘못되었습니다");
+        };
+        if let Some((key, value)) = String::from_utf8_lossy(field).split_once('=') {
+            fields.insert(key.to_string(), value.to_string());
+        }
+        rest = &rest[4 + n..];
+    }
+    Ok(fields)
+}
+
+// ROS 1 PointCloud2 직렬화 (header.seq 포함, 앞에 메시지 길이 4 바이트)
+fn serialize(msg: &PointCloud2, seq: u32) -> Vec<u8> {
+    fn string(out: &mut Vec<u8>, s: &str) {
+        out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        out.extend_from_slice(s.as_bytes());
+    }
+    let mut out = Vec::with_capacity(msg.data.len() + 128);
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&seq.to_le_bytes());
+    out.extend_from_slice(&(msg.header.stamp.sec as u32).to_le_bytes());
+    out.extend_from_slice(&msg.header.stamp.nanosec.to_le_bytes());
+    string(&mut out, &msg.header.frame_id);
+    out.extend_from_slice(&msg.height.to_le_bytes());
+    out.extend_from_slice(&msg.width.to_le_bytes());
+    out.extend_from_slice(&(msg.fields.len() as u32).to_le_bytes());
+    for field in &msg.fields {
+        string(&mut out, &field.name);
+        out.extend_from_slice(&field.offset.to_le_bytes());
+        out.push(field.datatype);
+        out.extend_from_slice(&field.count.to_le_bytes());
+    }
+    out.push(msg.is_bigendian as u8);
+    out.extend_from_slice(&msg.point_step.to_le_bytes());
+    out.extend_from_slice(&msg.row_step.to_le_bytes());
+    out.extend_from_slice(&(msg.data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&msg.data);
+    out.push(msg.is_dense as u8);
+    let len = (out.len() - 4) as u32;
+    out[..4].copy_from_slice(&len.to_le_bytes());
+    out
+}
+
+// ROS 1 토픽 하나의 발행자, publish 는 막히지 않음 (전송 중이면 프레임을 버림)
+pub struct Ros1Publisher {
+    topic: String,
+    subscribers: Arc<Mutex<Vec<TcpStream>>>,
+    frames: SyncSender<Vec<u8>>,
+    seq: AtomicU32,
+}
+
+impl Ros1Publisher {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+
+    pub fn publish(&self, msg: &PointCloud2) {
+        if self.subscriber_count() == 0 {
+            return;
+        }
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        let _ = self.frames.try_send(serialize(msg, seq));
+    }
+}
+
+// 노드 하나의 ROS 1 쪽 상태: slave API (XML-RPC) 와 TCPROS 서버, 토픽별 발행자
+struct Shared {
+    host: String,
+    tcp_port: u16,
+    caller_id: String,
+    master_uri: String,
+    publishers: Mutex<HashMap<String, Arc<Ros1Publisher>>>,
+    // Drop 에서 세우면 두 리스너 스레드가 다음 연결에서 빠져나감
+    stop: AtomicBool,
+}
+
+pub struct Ros1Bridge {
+    api: String,
+    // ROS 2 토픽 -> ROS 1 토픽
+    topics: HashMap<String, String>,
+    shared: Arc<Shared>,
+    // 종료 시 accept 에 막힌 리스너를 깨울 주소 (TCPROS, XML-RPC)
+    listeners: [SocketAddr; 2],
+}
+
+// "ros2_topic" 또는 "ros2_topic=ros1_topic" 목록
+// ROS 2 쪽은 노드가 만드는 상대 토픽 이름 (앞의 '/' 는 무시), ROS 1 쪽을 생략하면 '/' + ROS 2 이름
+pub fn parse_topics(entries: &[String]) -> Result<HashMap<String, String>> {
+    let mut topics = HashMap::new();
+    for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let (from, to) = match entry.split_once('=') {
+            Some((from, to)) => (from.trim().trim_start_matches('/'), to.trim().to_string()),
+            None => {
+                let from = entry.trim_start_matches('/');
+                (from, format!("/{}", from))
+            }
+        };
+        if !to.starts_with('/') {
+            bail!("ROS 1 토픽은 절대 경로여야 합니다: '{}'", entry);
+        }
+        topics.insert(from.to_string(), to);
+    }
+    Ok(topics)
+}
+
+impl Ros1Bridge {
+    // ros1_topics 가 비어있으면 None (브리지 사용 안 함)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Option<Self>> {
+        let topics = parse_topics(&params::string_array(node, "ros1_topics", &[])?)?;
+        let master_uri = params::string(
+            node,
+            "ros1_master_uri",
+            &std::env::var("ROS_MASTER_URI").unwrap_or_else(|_| "http://localhost:11311".into()),
+        )?;
+        let host = params::string(
+            node,
+            "ros1_hostname",
+            &std::env::var("ROS_HOSTNAME")
+                .or_else(|_| std::env::var("ROS_IP"))
+                .unwrap_or_else(|_| "localhost".into()),
+        )?;
+        if topics.is_empty() {
+            return Ok(None);
+        }
+        Self::new(&master_uri, &node.fully_qualified_name(), &host, topics).map(Some)
+    }
+
+    pub fn new(
+        master_uri: &str,
+        caller_id: &str,
+        host: &str,
+        topics: HashMap<String, String>,
+    ) -> Result<Self> {
+        // ros1_hostname 이 가리키는 인터페이스에서만 받음
+        let tcp = TcpListener::bind((host, 0)).context("TCPROS 포트 열기 실패")?;
+        let xmlrpc = TcpListener::bind((host, 0)).context("XML-RPC 포트 열기 실패")?;
+        let listeners = [tcp.local_addr()?, xmlrpc.local_addr()?];
+        let shared = Arc::new(Shared {
+            host: host.to_string(),
+            tcp_port: listeners[0].port(),
+            caller_id: caller_id.to_string(),
+            master_uri: master_uri.to_string(),
+            publishers: Mutex::new(HashMap::new()),
+            stop: AtomicBool::new(false),
+        });
+        let api = format!("http://{}:{}/", host, listeners[1].port());
+
+        listen(tcp, Arc::clone(&shared), |shared, stream| {
+            if let Err(e) = accept_subscriber(shared, stream) {
+                eprintln!("ROS 1 구독자 연결 실패: {:#}", e);
+            }
+        });
+        listen(xmlrpc, Arc::clone(&shared), |shared, stream| {
+            if let Err(e) = serve_slave(shared, stream) {
+                eprintln!("ROS 1 slave API 오류: {:#}", e);
+            }
+        });
+
+        Ok(Ros1Bridge {
+            api,
+            topics,
+            shared,
+            listeners,
+        })
+    }
+
+    // ROS 2 토픽이 ros1_topics 에 있으면 ROS 1 발행자를 만들고 master 에 등록
+    pub fn publisher(&self, ros2_topic: &str) -> Result<Option<Arc<Ros1Publisher>>> {
+        let Some(topic) = self.topics.get(ros2_topic.trim_start_matches('/')) else {
+            return Ok(None);
+        };
+        if let Some(existing) = self.shared.publishers.lock().unwrap().get(topic) {
+            return Ok(Some(Arc::clone(existing)));
+        }
+
+        let subscribers = Arc::new(Mutex::new(Vec::<TcpStream>::new()));
+        let (frames, rx) = mpsc::sync_channel::<Vec<u8>>(1);
+        let writer_subscribers = Arc::clone(&subscribers);
+        thread::spawn(move || {
+            for frame in rx {
+                writer_subscribers
+                    .lock()
+                    .unwrap()
+                    .retain_mut(|stream| stream.write_all(&frame).is_ok());
+            }
+        });
+        let publisher = Arc::new(Ros1Publisher {
+            topic: topic.clone(),
+            subscribers,
+            frames,
+            seq: AtomicU32::new(0),
+        });
+        self.shared
+            .publishers
+            .lock()
+            .unwrap()
+            .insert(topic.clone(), Arc::clone(&publisher));
+
+        call(
+            &self.shared.master_uri,
+            "registerPublisher",
+            &[
+                Value::Str(self.shared.caller_id.clone()),
+                Value::Str(topic.clone()),
+                Value::Str(POINTCLOUD2_TYPE.into()),
+                Value::Str(self.api.clone()),
+            ],
+        )
+        .with_context(|| format!("ROS 1 토픽 {} 등록 실패", topic))?;
+        println!(
+            "ROS 1 브리지: {} -> {} ({})",
+            ros2_topic, topic, self.shared.master_uri
+        );
+        Ok(Some(publisher))
+    }
+}
+
+// 종료 시 master 에서 발행자 등록 해제 (master 가 없으면 무시)
+impl Drop for Ros1Bridge {
+    fn drop(&mut self) {
+        for topic in self.shared.publishers.lock().unwrap().keys() {
+            let _ = call(
+                &self.shared.master_uri,
+                "unregisterPublisher",
+                &[
+                    Value::Str(self.shared.caller_id.clone()),
+                    Value::Str(topic.clone()),
+                    Value::Str(self.api.clone()),
+                ],
+            );
+        }
+        // accept 에 막힌 리스너를 연결 하나로 깨워 종료 플래그를 보게 함
+        self.shared.stop.store(true, Ordering::SeqCst);
+        for address in self.listeners {
+            let _ = TcpStream::connect_timeout(&address, HANDSHAKE_TIMEOUT);
+        }
+    }
+}
+
+// 연결마다 스레드를 띄워 처리 (느린 구독자의 핸드셰이크가 다른 연결을 막지 않도록)
+// shared.stop 이 서면 다음 연결에서 리스너 스레드 종료
+fn listen(listener: TcpListener, shared: Arc<Shared>, handle: fn(&Shared, TcpStream)) {
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if shared.stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || handle(&shared, stream));
+        }
+    });
+}
+
+// TCPROS 핸드셰이크 후 구독자 목록에 추가
+fn accept_subscriber(shared: &Shared, mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let header = read_header(&mut stream)?;
+    let topic = header.get("topic").map(String::as_str).unwrap_or("");
+    let publisher = shared.publishers.lock().unwrap().get(topic).cloned();
+    let md5 = header.get("md5sum").map(String::as_str).unwrap_or("*");
+
+    let error = match &publisher {
+        None => Some(format!("{} 토픽을 발행하지 않습니다", topic)),
+        Some(_) if md5 != "*" && md5 != POINTCLOUD2_MD5 => {
+            Some(format!("md5sum 불일치 ({} != {})", md5, POINTCLOUD2_MD5))
+        }
+        Some(_) => None,
+    };
+    if let Some(error) = error {
+        write_header(&mut stream, &[("error", &error)])?;
+        bail!("{}: {}", topic, error);
+    }
+
+    write_header(
+        &mut stream,
+        &[
+            ("callerid", &shared.caller_id),
+            ("topic", topic),
+            ("type", POINTCLOUD2_TYPE),
+            ("md5sum", POINTCLOUD2_MD5),
+            ("message_definition", POINTCLOUD2_DEFINITION),
+            ("latching", "0"),
+        ],
+    )?;
+    if header.get("tcp_nodelay").map(String::as_str) == Some("1") {
+        stream.set_nodelay(true)?;
+    }
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    if let Some(publisher) = publisher {
+        publisher.subscribers.lock().unwrap().push(stream);
+    }
+    Ok(())
+}
+
+// slave API 요청 하나를 읽고 응답
+fn serve_slave(shared: &Shared, stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let (method, params) = parse_call(&read_http(&mut reader)?)?;
+    let body = method_response(&slave_response(shared, &method, &params));
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.0 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+// requestTopic 으로 TCPROS 주소를 알려주고 나머지는 성공만 응답
+fn slave_response(shared: &Shared, method: &str, params: &[Value]) -> Value {
+    let ok = |value: Value| Value::Array(vec![Value::Int(1), Value::Str(String::new()), value]);
+
+    match method {
+        "requestTopic" => {
+            let topic = params.get(1).and_then(Value::as_str).unwrap_or("");
+            let tcpros = match &params.get(2) {
+                Some(Value::Array(protocols)) => protocols.iter().any(
+                    |p| matches!(p, Value::Array(p) if p.first().and_then(Value::as_str) == Some("TCPROS")),
+                ),
+                _ => false,
+            };
+            if !shared.publishers.lock().unwrap().contains_key(topic) {
+                Value::Array(vec![
+                    Value::Int(-1),
+                    Value::Str(format!("{} 토픽을 발행하지 않습니다", topic)),
+                    Value::Int(0),
+                ])
+            } else if !tcpros {
+                Value::Array(vec![
+                    Value::Int(0),
+                    Value::Str("TCPROS 만 지원합니다".into()),
+                    Value::Array(Vec::new()),
+                ])
+            } else {
+                ok(Value::Array(vec![
+                    Value::Str("TCPROS".into()),
+                    Value::Str(shared.host.clone()),
+                    Value::Int(shared.tcp_port as i32),
+                ]))
+            }
+        }
+        "getPid" => ok(Value::Int(std::process::id() as i32)),
+        "getPublications" => ok(Value::Array(
+            shared
+                .publishers
+                .lock()
+                .unwrap()
+                .keys()
+                .map(|topic| {
+                    Value::Array(vec![
+                        Value::Str(topic.clone()),
+                        Value::Str(POINTCLOUD2_TYPE.into()),
+                    ])
+                })
+                .collect(),
+        )),
+        "getSubscriptions" | "getBusStats" | "getBusInfo" => ok(Value::Array(Vec::new())),
+        "getMasterUri" => ok(Value::Str(shared.master_uri.clone())),
+        "shutdown" | "paramUpdate" | "publisherUpdate" => ok(Value::Int(0)),
+        _ => Value::Array(vec![
+            Value::Int(-1),
+            Value::Str(format!("지원하지 않는 메서드 {}", method)),
+            Value::Int(0),
+        ]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::PointField;
+    use std::io::Cursor;
+    use std::time::Instant;
+
+    // rosmaster (python xmlrpc.client) 가 보내는 publisherUpdate
+    const PUBLISHER_UPDATE: &str = "<?xml version='1.0'?>
+<methodCall>
+<methodName>publisherUpdate</methodName>
+<params>
+<param>
+<value><string>/master</string></value>
+</param>
+<param>
+<value><string>/livox/bev</string></value>
+</param>
+<param>
+<value><array><data>
+<value><string>http://10.0.0.5:41234/</string></value>
+<value><string>http://10.0.0.7:38017/</string></value>
+</data></array></value>
+</param>
+</params>
+</methodCall>
+";
+
+    // rosmaster 의 registerPublisher 응답
+    const REGISTER_RESPONSE: &str = "<?xml version='1.0'?>
+<methodResponse>
+<params>
+<param>
+<value><array><data>
+<value><int>1</int></value>
+<value><string>Registered [/bev_pub] as publisher of [/livox/bev]</string></value>
+<value><array><data>
+</data></array></value>
+</data></array></value>
+</param>
+</params>
+</methodResponse>
+";
+
+    // roscpp (XmlRpc++) 구독자의 requestTopic, 문자열에 타입 태그가 없음
+    const REQUEST_TOPIC: &str = "<?xml version=\"1.0\"?>\r\n<methodCall><methodName>requestTopic</methodName>\r\n<params><param><value>/listener</value></param><param><value>/livox/bev</value></param><param><value><array><data><value><array><data><value>TCPROS</value></data></array></value></data></array></value></param></params></methodCall>\r\n";
+
+    fn str(s: &str) -> Value {
+        Value::Str(s.into())
+    }
+
+    fn shared() -> Shared {
+        Shared {
+            host: "10.0.0.9".into(),
+            tcp_port: 40000,
+            caller_id: "/bev_pub".into(),
+            master_uri: "http://localhost:11311".into(),
+            publishers: Mutex::new(HashMap::new()),
+            stop: AtomicBool::new(false),
+        }
+    }
+
+    #[test]
+    fn parses_master_publisher_update() {
+        let (method, params) = parse_call(PUBLISHER_UPDATE).unwrap();
+        assert_eq!(method, "publisherUpdate");
+        assert_eq!(
+            params,
+            [
+                str("/master"),
+                str("/livox/bev"),
+                Value::Array(vec![
+                    str("http://10.0.0.5:41234/"),
+                    str("http://10.0.0.7:38017/")
+                ]),
+            ]
+        );
+        let response = slave_response(&shared(), &method, &params);
+        assert_eq!(
+            response,
+            Value::Array(vec![Value::Int(1), str(""), Value::Int(0)])
+        );
+    }
+
+    #[test]
+    fn parses_register_publisher_response() {
+        assert_eq!(
+            parse_response(REGISTER_RESPONSE).unwrap(),
+            Value::Array(vec![
+                Value::Int(1),
+                str("Registered [/bev_pub] as publisher of [/livox/bev]"),
+                Value::Array(Vec::new()),
+            ])
+        );
+        let fault = "<?xml version='1.0'?><methodResponse><fault><value><struct></struct></value></fault></methodResponse>";
+        assert!(parse_response(fault).is_err());
+    }
+
+    #[test]
+    fn request_topic_answers_tcpros_address() {
+        let shared = shared();
+        let (method, params) = parse_call(REQUEST_TOPIC).unwrap();
+        assert_eq!(method, "requestTopic");
+        // 발행하지 않는 토픽
+        assert!(matches!(
+            slave_response(&shared, &method, &params),
+            Value::Array(items) if items[0] == Value::Int(-1)
+        ));
+
+        let (frames, _rx) = mpsc::sync_channel(1);
+        shared.publishers.lock().unwrap().insert(
+            "/livox/bev".into(),
+            Arc::new(Ros1Publisher {
+                topic: "/livox/bev".into(),
+                subscribers: Arc::new(Mutex::new(Vec::new())),
+                frames,
+                seq: AtomicU32::new(0),
+            }),
+        );
+        assert_eq!(
+            slave_response(&shared, &method, &params),
+            Value::Array(vec![
+                Value::Int(1),
+                str(""),
+                Value::Array(vec![str("TCPROS"), str("10.0.0.9"), Value::Int(40000)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn method_call_round_trips() {
+        let params = [
+            str("/bev_pub"),
+            str("a < b & c"),
+            Value::Array(vec![Value::Int(-3), Value::Array(Vec::new())]),
+        ];
+        let (method, parsed) = parse_call(&method_call("registerPublisher", &params)).unwrap();
+        assert_eq!(method, "registerPublisher");
+        assert_eq!(parsed, params);
+    }
+
+    #[test]
+    fn read_http_limits_body_size() {
+        let request = "POST / HTTP/1.0\r\nContent-Length: 5\r\n\r\nhello";
+        assert_eq!(read_http(&mut Cursor::new(request)).unwrap(), "hello");
+
+        let huge = format!(
+            "POST / HTTP/1.0\r\nContent-Length: {}\r\n\r\n",
+            MAX_HTTP_BODY + 1
+        );
+        assert!(read_http(&mut Cursor::new(huge)).is_err());
+
+        let mut unsized_body = b"HTTP/1.0 200 OK\r\n\r\n".to_vec();
+        unsized_body.resize(unsized_body.len() + MAX_HTTP_BODY + 1, b'a');
+        assert!(read_http(&mut Cursor::new(unsized_body)).is_err());
+
+        let mut long_header = b"POST / HTTP/1.0\r\nX: ".to_vec();
+        long_header.resize(MAX_HEADER + 10, b'a');
+        assert!(read_http(&mut Cursor::new(long_header)).is_err());
+    }
+
+    #[test]
+    fn serializes_pointcloud2_like_ros1() {
+        let mut msg = PointCloud2 {
+            height: 1,
+            width: 1,
+            fields: vec![PointField {
+                name: "x".into(),
+                offset: 0,
+                datatype: 7,
+                count: 1,
+            }],
+            point_step: 4,
+            row_step: 4,
+            data: 1.0f32.to_le_bytes().to_vec(),
+            is_dense: true,
+            ..Default::default()
+        };
+        msg.header.stamp.sec = 1;
+        msg.header.stamp.nanosec = 2;
+        msg.header.frame_id = "a".into();
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            61, 0, 0, 0,            // 메시지 길이
+            7, 0, 0, 0,             // header.seq
+            1, 0, 0, 0, 2, 0, 0, 0, // header.stamp
+            1, 0, 0, 0, b'a',       // header.frame_id
+            1, 0, 0, 0, 1, 0, 0, 0, // height, width
+            1, 0, 0, 0,             // fields 개수
+            1, 0, 0, 0, b'x', 0, 0, 0, 0, 7, 1, 0, 0, 0,
+            0,                      // is_bigendian
+            4, 0, 0, 0, 4, 0, 0, 0, // point_step, row_step
+            4, 0, 0, 0, 0, 0, 0x80, 0x3f,
+            1,                      // is_dense
+        ];
+        assert_eq!(serialize(&msg, 7), expected);
+    }
+
+    #[test]
+    fn tcpros_header_round_trips() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+        write_header(
+            &mut client,
+            &[("topic", "/livox/bev"), ("md5sum", POINTCLOUD2_MD5)],
+        )
+        .unwrap();
+        let header = read_header(&mut server).unwrap();
+        assert_eq!(header["topic"], "/livox/bev");
+        assert_eq!(header["md5sum"], POINTCLOUD2_MD5);
+    }
+
+    #[test]
+    fn drop_stops_listeners_on_configured_host() {
+        let bridge = Ros1Bridge::new(
+            "http://127.0.0.1:1/",
+            "/bev_pub",
+            "127.0.0.1",
+            HashMap::new(),
+        )
+        .unwrap();
+        let listeners = bridge.listeners;
+        assert!(listeners.iter().all(|a| a.ip().is_loopback()));
+        drop(bridge);
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while listeners
+            .iter()
+            .any(|address| TcpStream::connect(address).is_ok())
+        {
+            assert!(Instant::now() < deadline, "리스너가 종료되지 않았습니다");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
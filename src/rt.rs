@@ -0,0 +1,73 @@
+use anyhow::{bail, Result};
+
+// 실시간 스케줄링/CPU 고정 (리눅스 전용, 다른 OS 에서는 오류 반환)
+
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &[usize]) -> Result<()> {
+    if cpus.is_empty() {
+        return Ok(());
+    }
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            bail!(
+                "CPU 고정 실패 {:?}: {}",
+                cpus,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+    Ok(())
+}
+
+// SCHED_FIFO 우선순위 요청, 권한(CAP_SYS_NICE/rtprio) 이 없으면 오류
+#[cfg(target_os = "linux")]
+pub fn set_fifo_priority(priority: i32) -> Result<()> {
+    unsafe {
+        let min = libc::sched_get_priority_min(libc::SCHED_FIFO);
+        let max = libc::sched_get_priority_max(libc::SCHED_FIFO);
+        if priority < min || priority > max {
+            bail!("SCHED_FIFO 우선순위는 {}~{} 범위여야 합니다", min, max);
+        }
+        let param = libc::sched_param {
+            sched_priority: priority,
+        };
+        if libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param) != 0 {
+            bail!(
+                "SCHED_FIFO({}) 설정 실패 (권한 확인 필요): {}",
+                priority,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(cpus: &[usize]) -> Result<()> {
+    if cpus.is_empty() {
+        return Ok(());
+    }
+    bail!("CPU 고정은 리눅스에서만 지원합니다")
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_fifo_priority(_priority: i32) -> Result<()> {
+    bail!("SCHED_FIFO 는 리눅스에서만 지원합니다")
+}
+
+// 스레드 시작 시 호출, 실패해도 경고만 출력하고 계속 동작 (priority 0 은 사용 안 함)
+pub fn apply_thread_options(name: &str, cpus: &[usize], priority: i32) {
+    if let Err(e) = pin_current_thread(cpus) {
+        eprintln!("[{}] {}", name, e);
+    }
+    if priority > 0 {
+        if let Err(e) = set_fifo_priority(priority) {
+            eprintln!("[{}] {}", name, e);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScanContextConfig {
+    pub rings: usize,
+    pub sectors: usize,
+    // 이 거리보다 먼 포인트는 무시 (m)
+    pub max_range: f32,
+    // 셀 값 = 센서 높이만큼 올린 z 의 최대값 (지면 아래 음수를 피함)
+    pub sensor_height: f32,
+}
+
+impl Default for ScanContextConfig {
+    fn default() -> Self {
+        ScanContextConfig {
+            rings: 20,
+            sectors: 60,
+            max_range: 80.0,
+            sensor_height: 2.0,
+        }
+    }
+}
+
+// 극좌표 (거리 링 x 방위 섹터) 격자의 최대 높이 기술자 (Scan Context, Kim & Kim 2018)
+#[derive(Debug, Clone)]
+pub struct ScanContext {
+    rings: usize,
+    sectors: usize,
+    // 행 우선 [ring][sector]
+    cells: Vec<f32>,
+    // 링별 평균 (회전 불변, 후보 검색용)
+    ring_key: Vec<f32>,
+}
+
+impl ScanContext {
+    pub fn new(points: &[[f32; 3]], config: &ScanContextConfig) -> Self {
+        let (rings, sectors) = (config.rings.max(1), config.sectors.max(1));
+        let mut cells = vec![0f32; rings * sectors];
+        for p in points {
+            let range = (p[0] * p[0] + p[1] * p[1]).sqrt();
+            if range >= config.max_range || !range.is_finite() {
+                continue;
+            }
+            let ring = ((range / config.max_range) * rings as f32) as usize;
+            let angle = p[1].atan2(p[0]) + PI;
+            let sector = ((angle / (2.0 * PI)) * sectors as f32) as usize % sectors;
+            let cell = &mut cells[ring.min(rings - 1) * sectors + sector];
+            *cell = cell.max(p[2] + config.sensor_height);
+        }
+        let ring_key = cells
+            .chunks(sectors)
+            .map(|row| row.iter().sum::<f32>() / sectors as f32)
+            .collect();
+        ScanContext {
+            rings,
+            sectors,
+            cells,
+            ring_key,
+        }
+    }
+
+    pub fn ring_key(&self) -> &[f32] {
+        &self.ring_key
+    }
+
+    // 섹터를 shift 만큼 밀어서 비교한 열별 코사인 거리 평균 (0 이면 같음)
+    fn distance_at(&self, other: &ScanContext, shift: usize) -> f32 {
+        let mut sum = 0.0;
+        let mut columns = 0;
+        for j in 0..self.sectors {
+            let k = (j + shift) % self.sectors;
+            let (mut dot, mut na, mut nb) = (0.0, 0.0, 0.0);
+            for r in 0..self.rings {
+                let a = self.cells[r * self.sectors + k];
+                let b = other.cells[r * self.sectors + j];
+                dot += a * b;
+                na += a * a;
+                nb += b * b;
+            }
+            if na > 0.0 && nb > 0.0 {
+                sum += 1.0 - dot / (na.sqrt() * nb.sqrt());
+                columns += 1;
+            }
+        }
+        if columns == 0 {
+            1.0
+        } else {
+            sum / columns as f32
+        }
+    }
+
+    // 가장 가까운 거리와 그때의 yaw (self 좌표계에서 본 other 의 방향, 라디안)
+    pub fn distance(&self, other: &ScanContext) -> (f32, f32) {
+        if self.rings != other.rings || self.sectors != other.sectors {
+            return (1.0, 0.0);
+        }
+        let (shift, distance) = (0..self.sectors)
+            .map(|shift| (shift, self.distance_at(other, shift)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 1.0));
+        let yaw = shift as f32 * 2.0 * PI / self.sectors as f32;
+        (distance, if yaw > PI { yaw - 2.0 * PI } else { yaw })
+    }
+}
+
+// 키프레임 기술자 목록, 링 키로 후보를 고른 뒤 전체 거리 비교
+#[derive(Debug, Clone, Default)]
+pub struct ScanContextDb {
+    contexts: Vec<ScanContext>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PlaceMatch {
+    pub index: usize,
+    pub distance: f32,
+    // 후보 좌표계에서 본 질의 스캔의 yaw (라디안)
+    pub yaw: f32,
+}
+
+impl ScanContextDb {
+    pub fn push(&mut self, context: ScanContext) -> usize {
+        self.contexts.push(context);
+        self.contexts.len() - 1
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ScanContext> {
+        self.contexts.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    // 최근 exclude_recent 개는 제외 (방금 지나온 곳은 루프가 아님), threshold 보다 가까운 것만
+    pub fn query(
+        &self,
+        query: &ScanContext,
+        exclude_recent: usize,
+        candidates: usize,
+        threshold: f32,
+    ) -> Option<PlaceMatch> {
+        let searchable = self.contexts.len().saturating_sub(exclude_recent);
+        let mut by_key: Vec<(usize, f32)> = self.contexts[..searchable]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let d = c
+                    .ring_key
+                    .iter()
+                    .zip(&query.ring_key)
+                    .map(|(a, b)| (a - b) * (a - b))
+                    .sum::<f32>();
+                (i, d)
+            })
+            .collect();
+        by_key.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        by_key
+            .into_iter()
+            .take(candidates.max(1))
+            .map(|(index, _)| {
+                let (distance, yaw) = self.contexts[index].distance(query);
+                PlaceMatch {
+                    index,
+                    distance,
+                    yaw,
+                }
+            })
+            .filter(|m| m.distance <= threshold)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 장소마다 다른 기둥 배치 (seed), 기둥은 높이가 다른 세로 포인트 열
+    fn place(seed: u32) -> Vec<[f32; 3]> {
+        let mut state = seed.wrapping_mul(2654435761).wrapping_add(1);
+        let mut next = || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut points = Vec::new();
+        for _ in 0..40 {
+            let (range, angle, height) = (3.0 + next() * 50.0, next() * 2.0 * PI, next() * 6.0);
+            let (s, c) = angle.sin_cos();
+            for k in 0..=10 {
+                points.push([range * c, range * s, -1.5 + height * k as f32 / 10.0]);
+            }
+        }
+        points
+    }
+
+    // 센서가 (x, y) 로 옮기고 yaw 만큼 돌았을 때 센서 좌표계로 본 포인트
+    fn observe(points: &[[f32; 3]], x: f32, y: f32, yaw: f32) -> Vec<[f32; 3]> {
+        let (s, c) = yaw.sin_cos();
+        points
+            .iter()
+            .map(|p| {
+                let (dx, dy) = (p[0] - x, p[1] - y);
+                [c * dx + s * dy, -s * dx + c * dy, p[2]]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distance_recovers_yaw_of_rotated_scan() {
+        let config = ScanContextConfig::default();
+        let points = place(1);
+        let candidate = ScanContext::new(&points, &config);
+        let other = ScanContext::new(&place(2), &config);
+        assert!(candidate.distance(&other).0 > 0.5);
+        for yaw in [0.5f32, -1.2, 3.0] {
+            let query = ScanContext::new(&observe(&points, 0.0, 0.0, yaw), &config);
+            let (distance, found) = candidate.distance(&query);
+            let sector = 2.0 * PI / config.sectors as f32;
+            let error = (found - yaw + PI).rem_euclid(2.0 * PI) - PI;
+            // 섹터 경계를 넘는 기둥 때문에 0 은 아니지만 다른 장소 (아래) 보다 훨씬 가까움
+            assert!(distance < 0.3, "yaw {}: 거리 {}", yaw, distance);
+            assert!(error.abs() <= sector, "yaw {} -> {}", yaw, found);
+        }
+    }
+
+    #[test]
+    fn query_detects_revisited_place() {
+        let config = ScanContextConfig::default();
+        let mut db = ScanContextDb::default();
+        for seed in 0..8 {
+            db.push(ScanContext::new(&place(seed), &config));
+        }
+
+        // 3 번 장소를 조금 옮겨서 반대 방향으로 다시 지남
+        let revisit = ScanContext::new(&observe(&place(3), 0.3, -0.2, 2.5), &config);
+        let found = db.query(&revisit, 0, 5, 0.3).expect("루프를 찾지 못함");
+        assert_eq!(found.index, 3);
+        assert!((found.yaw - 2.5).abs() < 0.2, "{:?}", found);
+
+        // 최근 키프레임으로 제외하면 찾지 않음, 처음 보는 장소도 찾지 않음
+        assert!(db.query(&revisit, 5, 5, 0.3).is_none_or(|m| m.index != 3));
+        let unseen = ScanContext::new(&place(100), &config);
+        assert!(db.query(&unseen, 0, 5, 0.3).is_none());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::point::LidarPoint;
+use std::collections::HashMap;
+
+// 실험적 scene flow: 연속 프레임의 x/y 격자 높이 지도를 셀 주변 패치로 상관 비교해 셀별 이동을 추정
+// 셀 이동 / 프레임 간격 = 속도, 센서 원점 방향 성분이 radial velocity (멀어지면 양수)
+// 자차 움직임은 보정하지 않으므로 고정 센서나 오도메트리 좌표계(deskew_to_odom) 출력에서 의미가 있음
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneFlowConfig {
+    // 격자 셀 크기 (m)
+    pub cell: f32,
+    // 찾을 최대 속도 (m/s), 프레임 간격과 함께 탐색 범위(셀) 결정
+    pub max_speed: f32,
+    // 비교 패치 반경 (셀, 1 이면 3x3)
+    pub patch: i32,
+    // 한쪽에만 있는 셀의 비용 (m 단위 높이 차와 같은 척도)
+    pub miss_cost: f32,
+}
+
+impl Default for SceneFlowConfig {
+    fn default() -> Self {
+        SceneFlowConfig {
+            cell: 0.5,
+            max_speed: 15.0,
+            patch: 1,
+            miss_cost: 1.0,
+        }
+    }
+}
+
+// 탐색 범위 상한 (셀), 셀마다 (2r+1)^2 번 패치 비교
+const MAX_SEARCH: i32 = 4;
+
+// 프레임 시각 (초) 과 셀별 최고 높이
+struct HeightFrame {
+    time: f64,
+    heights: HashMap<(i32, i32), f32>,
+}
+
+pub struct SceneFlow {
+    config: SceneFlowConfig,
+    previous: Option<HeightFrame>,
+    // 이번 프레임 셀별 속도 [vx, vy] (m/s)
+    velocity: HashMap<(i32, i32), [f32; 2]>,
+}
+
+impl SceneFlow {
+    pub fn new(config: SceneFlowConfig) -> Self {
+        SceneFlow {
+            config,
+            previous: None,
+            velocity: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.config.cell).floor() as i32,
+            (y / self.config.cell).floor() as i32,
+        )
+    }
+
+    fn heights(&self, points: &[LidarPoint]) -> HashMap<(i32, i32), f32> {
+        let mut heights: HashMap<(i32, i32), f32> = HashMap::new();
+        for p in points {
+            let h = heights.entry(self.cell_of(p.x, p.y)).or_insert(p.z);
+            *h = h.max(p.z);
+        }
+        heights
+    }
+
+    // time (초) 프레임의 포인트로 셀별 속도 갱신, 첫 프레임이나 시간이 되돌아가면 속도 없음
+    pub fn update(&mut self, time: f64, points: &[LidarPoint]) {
+        let current = self.heights(points);
+        self.velocity.clear();
+        if let Some(previous) = &self.previous {
+            let dt = time - previous.time;
+            if dt > 0.0 {
+                let reach = self.config.max_speed * dt as f32 / self.config.cell;
+                let search = (reach.ceil() as i32).clamp(1, MAX_SEARCH);
+                for &cell in current.keys() {
+                    let (dx, dy) = self.best_shift(&current, &previous.heights, cell, search);
+                    let scale = self.config.cell / dt as f32;
+                    self.velocity
+                        .insert(cell, [dx as f32 * scale, dy as f32 * scale]);
+                }
+            }
+        }
+        self.previous = Some(HeightFrame {
+            time,
+            heights: current,
+        });
+    }
+
+    // current 의 cell 주변 패치가 previous 에서 -shift 위치 패치와 가장 비슷한 shift (동점이면 작은 이동)
+    fn best_shift(
+        &self,
+        current: &HashMap<(i32, i32), f32>,
+        previous: &HashMap<(i32, i32), f32>,
+        (cx, cy): (i32, i32),
+        search: i32,
+    ) -> (i32, i32) {
+        let r = self.config.patch.max(0);
+        let cost = |(sx, sy): (i32, i32)| {
+            let mut cost = 0.0;
+            for ox in -r..=r {
+                for oy in -r..=r {
+                    let now = current.get(&(cx + ox, cy + oy));
+                    let before = previous.get(&(cx + ox - sx, cy + oy - sy));
+                    cost += match (now, before) {
+                        (Some(a), Some(b)) => (a - b).abs().min(self.config.miss_cost),
+                        (None, None) => 0.0,
+                        _ => self.config.miss_cost,
+                    };
+                }
+            }
+            cost
+        };
+        let mut best = ((0, 0), cost((0, 0)));
+        for sx in -search..=search {
+            for sy in -search..=search {
+                let c = cost((sx, sy));
+                let closer = sx * sx + sy * sy < best.0 .0 * best.0 .0 + best.0 .1 * best.0 .1;
+                if c < best.1 || (c == best.1 && closer) {
+                    best = ((sx, sy), c);
+                }
+            }
+        }
+        best.0
+    }
+
+    // x/y 위치가 속한 셀의 속도 (추정이 없으면 0)
+    pub fn velocity_at(&self, x: f32, y: f32) -> [f32; 2] {
+        self.velocity
+            .get(&self.cell_of(x, y))
+            .copied()
+            .unwrap_or([0.0, 0.0])
+    }
+
+    // origin 에서 본 radial velocity (멀어지면 양수)
+    pub fn radial_velocity(&self, x: f32, y: f32, origin: [f32; 2]) -> f32 {
+        let [vx, vy] = self.velocity_at(x, y);
+        let (rx, ry) = (x - origin[0], y - origin[1]);
+        let range = rx.hypot(ry);
+        if range < 1e-3 {
+            return 0.0;
+        }
+        (vx * rx + vy * ry) / range
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use rclrs::{Node, RclReturnCode, RclrsError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Ctrl-C 를 받으면 플래그만 세우고, spin 루프가 빠져나온 뒤 정리 작업을 수행
+pub struct Shutdown {
+    requested: AtomicBool,
+}
+
+impl Shutdown {
+    // Ctrl-C 처리기 없이 request 로만 종료 (한 프로세스에 노드 여럿, 통합 시험)
+    pub fn new() -> Arc<Self> {
+        Arc::new(Shutdown {
+            requested: AtomicBool::new(false),
+        })
+    }
+
+    pub fn install() -> Result<Arc<Self>> {
+        let shutdown = Shutdown::new();
+        let flag = Arc::clone(&shutdown);
+        ctrlc::set_handler(move || {
+            // 두 번째 Ctrl-C 는 정리를 기다리지 않고 바로 종료
+            if flag.requested.swap(true, Ordering::SeqCst) {
+                std::process::exit(130);
+            }
+        })?;
+        Ok(shutdown)
+    }
+
+    // 노드가 스스로 작업을 마쳤을 때 (Ctrl-C 와 같은 종료 경로 사용)
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+    }
+
+    pub fn requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    // rclrs::spin 대신 사용: 종료 요청이 들어올 때까지 콜백 처리
+    pub fn spin(&self, node: &Arc<Node>) -> Result<()> {
+        self.spin_with(node, || Ok(()))
+    }
+
+    // spin 과 같고, 콜백 처리 사이 (최대 100ms 마다) tick 호출 (상태 감시 등)
+    pub fn spin_with(&self, node: &Arc<Node>, mut tick: impl FnMut() -> Result<()>) -> Result<()> {
+        while !self.requested() {
+            tick()?;
+            match rclrs::spin_once(Arc::clone(node), Some(Duration::from_millis(100))) {
+                Ok(())
+                | Err(RclrsError::RclError {
+                    code: RclReturnCode::Timeout,
+                    ..
+                }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::msg::PointCloud2;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 내보낸 프레임 파일 옆에 두는 색인 (index.json): 파일을 다시 읽지 않고 시각/좌표계/포인트 수로 찾기 위함
+//   {"config_hash": "...", "frames": [{"file": ..., "frame_id": ..., "sec": ..., "nanosec": ..., "points": ...}, ...]}
+// 시각은 헤더의 sec/nanosec 그대로 (음수 sec 를 소수 하나로 합치면 틀리므로)
+// config_hash 는 만든 설정의 해시 (snapshot::ConfigSnapshot::hash, 같은 설정으로 만든 프레임끼리 묶음)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SidecarIndex {
+    pub config_hash: String,
+    pub frames: Vec<FrameEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameEntry {
+    pub file: String,
+    pub frame_id: String,
+    pub sec: i32,
+    pub nanosec: u32,
+    pub points: u64,
+}
+
+impl SidecarIndex {
+    pub fn new(config_hash: &str) -> Self {
+        SidecarIndex {
+            config_hash: config_hash.to_string(),
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, file: &str, msg: &PointCloud2) {
+        self.frames.push(FrameEntry {
+            file: file.to_string(),
+            frame_id: msg.header.frame_id.clone(),
+            sec: msg.header.stamp.sec,
+            nanosec: msg.header.stamp.nanosec,
+            points: msg.width as u64 * msg.height as u64,
+        });
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = format!(
+            "{{\n  \"config_hash\": {},\n  \"frames\": [",
+            json_string(&self.config_hash)
+        );
+        for (i, f) in self.frames.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!(
+                "\n    {{\"file\": {}, \"frame_id\": {}, \"sec\": {}, \"nanosec\": {}, \"points\": {}}}",
+                json_string(&f.file),
+                json_string(&f.frame_id),
+                f.sec,
+                f.nanosec,
+                f.points
+            ));
+        }
+        out.push_str("\n  ]\n}\n");
+        out
+    }
+
+    // 파일 하나로 내보낼 때 옆에 둘 색인 경로 (map.pcd -> map.index.json)
+    pub fn path_for(export: &Path) -> PathBuf {
+        export.with_extension("index.json")
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_json()).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+}
+
+// FNV-1a 64비트 (외부 의존성 없이 설정 비교용, 암호용 아님)
+pub fn config_hash(config: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in config.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+// JSON 문자열 리터럴 (따옴표, 역슬래시, 제어 문자 이스케이프)
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_keeps_stamp_as_sec_and_nanosec() {
+        let mut msg = PointCloud2::default();
+        msg.header.frame_id = "livox_frame".to_string();
+        msg.header.stamp.sec = -1;
+        msg.header.stamp.nanosec = 250_000_000;
+        msg.width = 10;
+        msg.height = 2;
+        let mut index = SidecarIndex::new("0123456789abcdef");
+        index.push("frame_00.pcd", &msg);
+        assert_eq!(
+            index.to_json(),
+            "{\n  \"config_hash\": \"0123456789abcdef\",\n  \"frames\": [\n    \
+             {\"file\": \"frame_00.pcd\", \"frame_id\": \"livox_frame\", \
+             \"sec\": -1, \"nanosec\": 250000000, \"points\": 20}\n  ]\n}\n"
+        );
+        assert_eq!(
+            SidecarIndex::path_for(Path::new("out/map.pcd")),
+            Path::new("out/map.index.json")
+        );
+    }
+}
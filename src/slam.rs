@@ -0,0 +1,258 @@
+use crate::pose::Pose;
+use crate::pose_graph::{self, Edge, EdgeKind, OptimizeResult, PoseGraph};
+use crate::registration::{self, IcpConfig, IcpResult, VoxelIndex};
+use crate::scan_context::{ScanContext, ScanContextConfig, ScanContextDb};
+
+#[derive(Debug, Clone, Copy)]
+pub struct KeyframeConfig {
+    // 직전 키프레임에서 이만큼 움직이거나 (m) 돌면 (rad) 새 키프레임
+    pub distance: f64,
+    pub angle: f64,
+    // 키프레임 간 오도메트리 제약의 표준편차 (m, rad)
+    pub odom_translation_std: f64,
+    pub odom_rotation_std: f64,
+}
+
+impl Default for KeyframeConfig {
+    fn default() -> Self {
+        KeyframeConfig {
+            distance: 1.0,
+            angle: 15f64.to_radians(),
+            odom_translation_std: 0.1,
+            odom_rotation_std: 0.02,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoopConfig {
+    pub scan_context: ScanContextConfig,
+    // 최근 키프레임 몇 개는 후보에서 제외, 링 키로 고를 후보 수, Scan Context 거리 기준
+    pub exclude_recent: usize,
+    pub candidates: usize,
+    pub threshold: f32,
+    // 기하 검증 (ICP) 통과 기준
+    pub min_fitness: f64,
+    pub max_rmse: f64,
+    // 루프 제약의 표준편차 (m, rad)
+    pub translation_std: f64,
+    pub rotation_std: f64,
+}
+
+impl Default for LoopConfig {
+    fn default() -> Self {
+        LoopConfig {
+            scan_context: ScanContextConfig::default(),
+            exclude_recent: 30,
+            candidates: 10,
+            threshold: 0.3,
+            min_fitness: 0.6,
+            max_rmse: 0.15,
+            translation_std: 0.05,
+            rotation_std: 0.01,
+        }
+    }
+}
+
+// 검증된 루프 (from 좌표계에서 본 to 키프레임 자세)
+#[derive(Debug, Clone, Copy)]
+pub struct LoopClosure {
+    pub from: usize,
+    pub to: usize,
+    pub measurement: Pose,
+    pub distance: f32,
+    pub icp: IcpResult,
+}
+
+struct LoopDetector {
+    config: LoopConfig,
+    contexts: ScanContextDb,
+}
+
+pub struct Keyframe {
+    pub time: f64,
+    // 키프레임을 만들 때의 오도메트리 자세 (보정 전)
+    pub odom_pose: Pose,
+    // 다운샘플한 스캔 (키프레임 좌표계)
+    pub points: Vec<[f32; 3]>,
+}
+
+// 키프레임 선택 + 자세 그래프 (노드 i = 키프레임 i, 그래프 자세는 map 좌표계)
+pub struct Slam {
+    pub config: KeyframeConfig,
+    pub keyframes: Vec<Keyframe>,
+    pub graph: PoseGraph,
+    // None 이면 루프 검출 안 함
+    loops: Option<LoopDetector>,
+}
+
+impl Slam {
+    pub fn new(config: KeyframeConfig) -> Self {
+        Slam {
+            config,
+            keyframes: Vec::new(),
+            graph: PoseGraph::default(),
+            loops: None,
+        }
+    }
+
+    // 키프레임을 추가하기 전에 켜야 기술자 인덱스가 키프레임과 맞음
+    pub fn with_loop_closure(mut self, config: LoopConfig) -> Self {
+        self.loops = Some(LoopDetector {
+            config,
+            contexts: ScanContextDb::default(),
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    // 직전 키프레임 대비 충분히 움직였는지
+    pub fn is_keyframe(&self, odom_pose: &Pose) -> bool {
+        let Some(last) = self.keyframes.last() else {
+            return true;
+        };
+        let delta = last.odom_pose.inverse().compose(odom_pose);
+        delta.translation_norm() >= self.config.distance || delta.angle() >= self.config.angle
+    }
+
+    // 키프레임이면 노드와 오도메트리 제약을 추가하고 인덱스 반환
+    pub fn add_scan(&mut self, time: f64, odom_pose: Pose, points: Vec<[f32; 3]>) -> Option<usize> {
+        if !self.is_keyframe(&odom_pose) {
+            return None;
+        }
+        let index = match self.keyframes.last() {
+            Some(last) => {
+                let delta = last.odom_pose.inverse().compose(&odom_pose);
+                let from = self.keyframes.len() - 1;
+                let pose = self.graph.nodes[from].compose(&delta);
+                let to = self.graph.add_node(pose);
+                self.graph.add_edge(Edge {
+                    from,
+                    to,
+                    measurement: delta,
+                    information: pose_graph::information(
+                        self.config.odom_translation_std,
+                        self.config.odom_rotation_std,
+                    ),
+                    kind: EdgeKind::Odometry,
+                });
+                to
+            }
+            None => self.graph.add_node(odom_pose),
+        };
+        if let Some(loops) = &mut self.loops {
+            loops
+                .contexts
+                .push(ScanContext::new(&points, &loops.config.scan_context));
+        }
+        self.keyframes.push(Keyframe {
+            time,
+            odom_pose,
+            points,
+        });
+        Some(index)
+    }
+
+    pub fn add_loop(&mut self, from: usize, to: usize, measurement: Pose, information: [f64; 6]) {
+        self.graph.add_edge(Edge {
+            from,
+            to,
+            measurement,
+            information,
+            kind: EdgeKind::Loop,
+        });
+    }
+
+    // index 키프레임과 같은 장소의 과거 키프레임을 찾아 ICP 로 검증
+    pub fn detect_loop(&self, index: usize, icp: &IcpConfig) -> Option<LoopClosure> {
+        let loops = self.loops.as_ref()?;
+        let config = &loops.config;
+        let query = loops.contexts.get(index)?;
+        let place = loops.contexts.query(
+            query,
+            self.keyframes.len() - index + config.exclude_recent,
+            config.candidates,
+            config.threshold,
+        )?;
+
+        // Scan Context 는 yaw 만 알려주므로 넓은 대응 거리로 맞춘 뒤 좁혀서 다시 정합
+        let source = &self.keyframes[index].points;
+        let target_points = self.keyframes[place.index].points.clone();
+        let coarse = IcpConfig {
+            max_distance: icp.max_distance * 3.0,
+            ..*icp
+        };
+        let initial = Pose::from_xy_yaw(0.0, 0.0, place.yaw as f64);
+        let target = VoxelIndex::new(target_points, coarse.max_distance);
+        let rough = registration::icp(source, &target, initial, &coarse)?;
+        let target = VoxelIndex::new(target.points().to_vec(), icp.max_distance);
+        let result = registration::icp(source, &target, rough.pose, icp)?;
+        (result.fitness >= config.min_fitness && result.rmse <= config.max_rmse).then_some(
+            LoopClosure {
+                from: place.index,
+                to: index,
+                measurement: result.pose,
+                distance: place.distance,
+                icp: result,
+            },
+        )
+    }
+
+    // 루프 제약을 추가하고 그래프를 다시 최적화
+    pub fn close_loop(&mut self, closure: &LoopClosure, max_iterations: usize) -> OptimizeResult {
+        let information =
+            self.loops
+                .as_ref()
+                .map_or(pose_graph::information(0.05, 0.01), |loops| {
+                    pose_graph::information(loops.config.translation_std, loops.config.rotation_std)
+                });
+        self.add_loop(closure.from, closure.to, closure.measurement, information);
+        self.optimize(max_iterations)
+    }
+
+    // 최적화된 자세로 키프레임 스캔을 모은 지도 (voxel 크기로 다시 다운샘플)
+    pub fn map_points(&self, voxel: f32) -> Vec<[f32; 3]> {
+        let mut points = Vec::new();
+        for (keyframe, pose) in self.keyframes.iter().zip(&self.graph.nodes) {
+            points.extend(keyframe.points.iter().map(|p| {
+                pose.transform_point([p[0] as f64, p[1] as f64, p[2] as f64])
+                    .map(|v| v as f32)
+            }));
+        }
+        registration::voxel_downsample(&points, voxel)
+    }
+
+    pub fn optimize(&mut self, max_iterations: usize) -> OptimizeResult {
+        self.graph.optimize(max_iterations)
+    }
+
+    pub fn pose(&self, index: usize) -> Pose {
+        self.graph.nodes[index]
+    }
+
+    // 오도메트리 자세 -> map 좌표계 자세 (마지막 키프레임의 보정량 적용)
+    pub fn correct(&self, odom_pose: &Pose) -> Pose {
+        match self.keyframes.last() {
+            Some(last) => {
+                let index = self.keyframes.len() - 1;
+                self.graph.nodes[index].compose(&last.odom_pose.inverse().compose(odom_pose))
+            }
+            None => *odom_pose,
+        }
+    }
+
+    // (시각, 최적화된 자세) 목록
+    pub fn trajectory(&self) -> impl Iterator<Item = (f64, Pose)> + '_ {
+        self.keyframes
+            .iter()
+            .zip(&self.graph.nodes)
+            .map(|(keyframe, pose)| (keyframe.time, *pose))
+    }
+}
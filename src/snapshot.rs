@@ -0,0 +1,81 @@
+use crate::sidecar::config_hash;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+// 실행 설정 스냅샷: 노드가 실제로 쓴 파라미터 값, 필터 순서, 버전을 ROS 2 params 파일 형식으로 기록
+// 메타데이터는 주석으로 넣어 그대로 --params-file 로 읽을 수 있음 (--replay-config 로 같은 설정 재실행)
+//   # node: lidar_bev_publisher
+//   # version: 0.1.0
+//   # filter_chain: parse, mount, ...
+//   # config_hash: <파라미터 부분의 FNV-1a 해시>
+//   /**:
+//     ros__parameters:
+//       input_layout: "auto"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSnapshot {
+    pub node: String,
+    pub version: String,
+    pub filter_chain: Vec<String>,
+    // (이름, YAML 값) 선언 순서
+    pub parameters: Vec<(String, String)>,
+}
+
+impl ConfigSnapshot {
+    pub fn new(node: &str, parameters: Vec<(String, String)>) -> Self {
+        ConfigSnapshot {
+            node: node.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            filter_chain: Vec::new(),
+            parameters,
+        }
+    }
+
+    pub fn with_filter_chain(mut self, chain: &[&str]) -> Self {
+        self.filter_chain = chain.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    fn parameters_yaml(&self) -> String {
+        let mut text = String::from("/**:\n  ros__parameters:\n");
+        for (name, value) in &self.parameters {
+            // 빈 배열은 params 파일에서 타입을 알 수 없어 기본값에 맡김
+            if value == "[]" {
+                text.push_str(&format!("    # {}: []\n", name));
+            } else {
+                text.push_str(&format!("    {}: {}\n", name, value));
+            }
+        }
+        text
+    }
+
+    // 파라미터 값만으로 계산 (같은 설정이면 버전/필터 순서 표기와 무관하게 같음)
+    pub fn hash(&self) -> String {
+        config_hash(&self.parameters_yaml())
+    }
+
+    pub fn to_yaml(&self) -> String {
+        format!(
+            "# node: {}\n# version: {}\n# filter_chain: {}\n# config_hash: {}\n{}",
+            self.node,
+            self.version,
+            self.filter_chain.join(", "),
+            self.hash(),
+            self.parameters_yaml()
+        )
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("{} 만들기 실패", dir.display()))?;
+        }
+        fs::write(path, self.to_yaml()).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+}
+
+// 스냅샷 파일을 params 파일로 덧붙인 rcl 인자 (마지막 --params-file 이 앞선 값을 덮어씀)
+pub fn replay_args(args: impl IntoIterator<Item = String>, path: &str) -> Vec<String> {
+    let mut args: Vec<String> = args.into_iter().collect();
+    args.extend(["--ros-args", "--params-file", path].map(str::to_string));
+    args
+}
@@ -0,0 +1,294 @@
+use crate::msg::PointCloud2;
+use crate::passthrough;
+use crate::stamp;
+use anyhow::{bail, Result};
+
+// 프레임을 포인트 timestamp 기준 시간 구간으로 나눈 조각
+pub struct SubFrame {
+    pub msg: PointCloud2,
+    // 프레임 첫 포인트 시각부터 이 조각 시작까지 (초), 발행 간격 맞추기용
+    pub offset: f64,
+}
+
+// 한 프레임을 per-point timestamp 로 parts 개 시간 구간으로 나눔 (프레임 주기의 1/parts 마다 발행)
+// 조각의 header stamp 는 원본 stamp + 구간 시작 시각, 비어 있는 구간은 건너뜀
+// timestamp 필드가 없거나 모두 같으면 나누지 않고 원본 하나만 반환
+pub fn split(msg: PointCloud2, parts: usize) -> Result<Vec<SubFrame>> {
+    let whole = |msg| -> Result<Vec<SubFrame>> { Ok(vec![SubFrame { msg, offset: 0.0 }]) };
+    if parts <= 1 {
+        return whole(msg);
+    }
+    let Ok(spec) = passthrough::field_spec(&msg, "timestamp") else {
+        return whole(msg);
+    };
+    let step = msg.point_step as usize;
+    if step < spec.end() {
+        bail!("point_step({}) 이 'timestamp' 필드 범위보다 작습니다", step);
+    }
+
+    // timestamp 는 나노초, 0 은 측정 시각 없음
+    let times: Vec<f64> = msg
+        .data
+        .chunks_exact(step)
+        .map(|chunk| spec.read(chunk, 0, msg.is_bigendian))
+        .collect();
+    let valid = times.iter().copied().filter(|t| *t > 0.0 && t.is_finite());
+    let (first, last) = valid.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), t| {
+        (lo.min(t), hi.max(t))
+    });
+    if last <= first {
+        return whole(msg);
+    }
+
+    let span = (last - first) * 1e-9;
+    let slot = |t: f64| {
+        if t > 0.0 && t.is_finite() {
+            (((t - first) * 1e-9 / span * parts as f64) as usize).min(parts - 1)
+        } else {
+            0
+        }
+    };
+    let slots: Vec<usize> = times.into_iter().map(slot).collect();
+    let frame_time = stamp::to_secs(&msg.header.stamp);
+    let mut sub_frames = Vec::with_capacity(parts);
+    for part in 0..parts {
+        let keep: Vec<bool> = slots.iter().map(|s| *s == part).collect();
+        if !keep.contains(&true) {
+            continue;
+        }
+        let offset = span * part as f64 / parts as f64;
+        let mut sub = passthrough::select(&msg, &keep);
+        sub.header.stamp = stamp::from_secs(frame_time + offset);
+        sub_frames.push(SubFrame { msg: sub, offset });
+    }
+    Ok(sub_frames)
+}
+
+// 저지연 부분 프레임: 드라이버가 프레임을 작은 패킷으로 나눠 보낼 때 프레임 전체를 기다리지 않고
+// 쌓인 포인트 timestamp 가 window (프레임 주기 x 비율) 를 넘기면 그 구간 포인트를 바로 한 조각으로 내보냄
+// timestamp 필드가 없으면 메시지 header stamp 를 포인트 시각으로 사용
+pub struct PartialAssembler {
+    // 나노초
+    window: f64,
+    pending: Option<PointCloud2>,
+    // pending 포인트별 시각 (나노초)
+    times: Vec<f64>,
+    // pending 첫 메시지의 (header 시각 초, 첫 포인트 시각 나노초), 조각 header stamp 계산용
+    anchor: (f64, f64),
+}
+
+impl PartialAssembler {
+    pub fn new(frame_period: f64, fraction: f64) -> Self {
+        PartialAssembler {
+            window: (frame_period * fraction * 1e9).max(1.0),
+            pending: None,
+            times: Vec::new(),
+            anchor: (0.0, 0.0),
+        }
+    }
+
+    fn point_times(msg: &PointCloud2) -> Result<Vec<f64>> {
+        let header_time = stamp::to_secs(&msg.header.stamp) * 1e9;
+        let count = passthrough::point_count(msg);
+        let Ok(spec) = passthrough::field_spec(msg, "timestamp") else {
+            return Ok(vec![header_time; count]);
+        };
+        let step = msg.point_step as usize;
+        if step < spec.end() {
+            bail!("point_step({}) 이 'timestamp' 필드 범위보다 작습니다", step);
+        }
+        Ok(msg
+            .data
+            .chunks_exact(step)
+            .map(|chunk| spec.read(chunk, 0, msg.is_bigendian))
+            .map(|t| {
+                if t > 0.0 && t.is_finite() {
+                    t
+                } else {
+                    header_time
+                }
+            })
+            .collect())
+    }
+
+    fn stamp_of(&self, time: f64) -> f64 {
+        self.anchor.0 + (time - self.anchor.1) * 1e-9
+    }
+
+    // msg 를 쌓고 시간 창이 다 찬 조각을 시간순으로 반환
+    pub fn push(&mut self, msg: PointCloud2) -> Result<Vec<PointCloud2>> {
+        let times = Self::point_times(&msg)?;
+        let mut ready = Vec::new();
+        // 레이아웃이 바뀌면 남은 포인트를 먼저 내보냄
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|p| p.fields != msg.fields || p.point_step != msg.point_step)
+        {
+            ready.extend(self.flush());
+        }
+        match &mut self.pending {
+            Some(pending) => {
+                pending.data.extend_from_slice(&msg.data);
+                let count = passthrough::point_count(pending);
+                pending.height = 1;
+                pending.width = count as u32;
+                pending.row_step = pending.data.len() as u32;
+                pending.is_dense &= msg.is_dense;
+                self.times.extend(times);
+            }
+            None => {
+                let first = times.iter().copied().fold(f64::INFINITY, f64::min);
+                if !first.is_finite() {
+                    return Ok(ready);
+                }
+                self.anchor = (stamp::to_secs(&msg.header.stamp), first);
+                self.pending = Some(msg);
+                self.times = times;
+            }
+        }
+
+        while self.pending.is_some() {
+            let (start, latest) = self
+                .times
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &t| {
+                    (lo.min(t), hi.max(t))
+                });
+            let end = start + self.window;
+            if latest < end {
+                break;
+            }
+            let stamp = stamp::from_secs(self.stamp_of(start));
+            let keep: Vec<bool> = self.times.iter().map(|t| *t < end).collect();
+            let rest: Vec<bool> = keep.iter().map(|k| !k).collect();
+            let Some(pending) = self.pending.as_mut() else {
+                break;
+            };
+            let mut part = passthrough::select(pending, &keep);
+            part.header.stamp = stamp;
+            passthrough::compact(pending, &rest);
+            self.times.retain(|t| *t >= end);
+            ready.push(part);
+            if self.times.is_empty() {
+                self.pending = None;
+            }
+        }
+        Ok(ready)
+    }
+
+    // 남은 포인트를 한 조각으로 (창이 다 차지 않았어도)
+    pub fn flush(&mut self) -> Option<PointCloud2> {
+        let mut pending = self.pending.take()?;
+        let start = self.times.iter().copied().fold(f64::INFINITY, f64::min);
+        if start.is_finite() {
+            pending.header.stamp = stamp::from_secs(self.stamp_of(start));
+        }
+        self.times.clear();
+        Some(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+    use crate::msg::Header;
+    use crate::point::{parse_pointcloud2, LidarPoint};
+
+    // times_ms: 포인트별 시각 (ms, 음수면 timestamp 0 = 시각 없음)
+    fn msg(stamp: f64, times_ms: &[f64]) -> PointCloud2 {
+        let points: Vec<LidarPoint> = times_ms
+            .iter()
+            .enumerate()
+            .map(|(i, ms)| LidarPoint {
+                x: i as f32,
+                timestamp: if *ms < 0.0 { 0.0 } else { 1.0e18 + ms * 1e6 },
+                ..LidarPoint::default()
+            })
+            .collect();
+        let header = Header {
+            stamp: stamp::from_secs(stamp),
+            ..Header::default()
+        };
+        layout::LIVOX_26.encode(&points, header)
+    }
+
+    fn xs(msg: &PointCloud2) -> Vec<f32> {
+        parse_pointcloud2(msg)
+            .unwrap()
+            .iter()
+            .map(|p| p.x)
+            .collect()
+    }
+
+    fn secs(msg: &PointCloud2) -> f64 {
+        stamp::to_secs(&msg.header.stamp)
+    }
+
+    #[test]
+    fn split_by_point_time() {
+        let parts = split(msg(100.0, &[0.0, 20.0, 40.0, 60.0, 80.0, 100.0]), 2).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(xs(&parts[0].msg), [0.0, 1.0, 2.0]);
+        assert_eq!(xs(&parts[1].msg), [3.0, 4.0, 5.0]);
+        assert!((parts[1].offset - 0.05).abs() < 1e-9);
+        assert!((secs(&parts[1].msg) - 100.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn split_skips_empty_slots_and_keeps_untimed_points_first() {
+        // 시각 없는 포인트 (1번) 는 첫 구간, 가운데 구간은 비어 있음
+        let parts = split(msg(100.0, &[0.0, -1.0, 90.0]), 3).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(xs(&parts[0].msg), [0.0, 1.0]);
+        assert_eq!(xs(&parts[1].msg), [2.0]);
+    }
+
+    #[test]
+    fn split_returns_whole_frame_without_time_spread() {
+        let same = msg(100.0, &[10.0, 10.0, 10.0]);
+        let parts = split(same.clone(), 4).unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].msg, same);
+
+        let untimed = layout::XYZI16.encode(&[LidarPoint::default(); 3], Header::default());
+        assert_eq!(split(untimed, 4).unwrap().len(), 1);
+        assert_eq!(split(msg(100.0, &[0.0, 50.0]), 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn partial_assembler_emits_full_windows() {
+        // 창 50ms
+        let mut assembler = PartialAssembler::new(0.1, 0.5);
+        let ready = assembler
+            .push(msg(100.0, &[0.0, 10.0, 20.0, 30.0]))
+            .unwrap();
+        assert!(ready.is_empty());
+
+        let ready = assembler
+            .push(msg(100.04, &[40.0, 50.0, 60.0, 70.0]))
+            .unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(xs(&ready[0]), [0.0, 1.0, 2.0, 3.0, 0.0]);
+        assert!((secs(&ready[0]) - 100.0).abs() < 1e-6);
+
+        let rest = assembler.flush().unwrap();
+        assert_eq!(xs(&rest), [1.0, 2.0, 3.0]);
+        assert_eq!(rest.width, 3);
+        assert!((secs(&rest) - 100.05).abs() < 1e-6);
+        assert!(assembler.flush().is_none());
+    }
+
+    #[test]
+    fn partial_assembler_flushes_on_layout_change() {
+        let mut assembler = PartialAssembler::new(0.1, 0.5);
+        assert!(assembler.push(msg(100.0, &[0.0, 10.0])).unwrap().is_empty());
+
+        let other = layout::XYZI16.encode(&[LidarPoint::default()], Header::default());
+        let ready = assembler.push(other).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].point_step, 26);
+        assert_eq!(assembler.flush().unwrap().point_step, 16);
+    }
+}
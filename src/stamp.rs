@@ -0,0 +1,25 @@
+use crate::msg::Time;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 시스템 시계 기준 현재 시각
+pub fn now() -> Time {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Time {
+        sec: elapsed.as_secs() as i32,
+        nanosec: elapsed.subsec_nanos(),
+    }
+}
+
+pub fn to_secs(stamp: &Time) -> f64 {
+    stamp.sec as f64 + stamp.nanosec as f64 * 1e-9
+}
+
+pub fn from_secs(secs: f64) -> Time {
+    let sec = secs.floor();
+    Time {
+        sec: sec as i32,
+        nanosec: ((secs - sec) * 1e9) as u32,
+    }
+}
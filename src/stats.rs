@@ -0,0 +1,268 @@
+use crate::msg::PointCloud2;
+use crate::point::LidarPoint;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+// 한 프레임의 요약 통계 (livox_scan2 출력/변화 감지용)
+#[derive(Debug, Clone, Default)]
+pub struct FrameSummary {
+    pub points: usize,
+    pub x_range: (f32, f32),
+    pub y_range: (f32, f32),
+    pub z_range: (f32, f32),
+    pub intensity_range: (f32, f32),
+    // 라인 번호별 포인트 개수
+    pub line_counts: BTreeMap<u8, usize>,
+}
+
+fn range_of(points: &[LidarPoint], f: impl Fn(&LidarPoint) -> f32) -> (f32, f32) {
+    points
+        .iter()
+        .map(f)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        })
+}
+
+impl FrameSummary {
+    pub fn from_points(points: &[LidarPoint]) -> Self {
+        let mut line_counts = BTreeMap::new();
+        for point in points {
+            *line_counts.entry(point.line).or_insert(0) += 1;
+        }
+
+        FrameSummary {
+            points: points.len(),
+            x_range: range_of(points, |p| p.x),
+            y_range: range_of(points, |p| p.y),
+            z_range: range_of(points, |p| p.z),
+            intensity_range: range_of(points, |p| p.intensity),
+            line_counts,
+        }
+    }
+
+    // prev 대비 임계값을 넘는 변화 목록 (비어있으면 변화 없음)
+    pub fn changes_from(&self, prev: &FrameSummary, thresholds: &ChangeThresholds) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        let base = prev.points.max(1) as f64;
+        let ratio = (self.points as f64 - prev.points as f64).abs() / base;
+        if ratio > thresholds.count_ratio {
+            changes.push(format!(
+                "point count {} -> {} ({:+.1}%)",
+                prev.points,
+                self.points,
+                (self.points as f64 - prev.points as f64) / base * 100.0
+            ));
+        }
+
+        let ranges = [
+            ("X", self.x_range, prev.x_range),
+            ("Y", self.y_range, prev.y_range),
+            ("Z", self.z_range, prev.z_range),
+        ];
+        for (axis, now, before) in ranges {
+            if (now.0 - before.0).abs() > thresholds.range_m
+                || (now.1 - before.1).abs() > thresholds.range_m
+            {
+                changes.push(format!(
+                    "{} range {:.3}~{:.3} -> {:.3}~{:.3} m",
+                    axis, before.0, before.1, now.0, now.1
+                ));
+            }
+        }
+
+        let new_lines: Vec<u8> = self
+            .line_counts
+            .keys()
+            .filter(|line| !prev.line_counts.contains_key(line))
+            .copied()
+            .collect();
+        if !new_lines.is_empty() {
+            changes.push(format!("new lines {:?}", new_lines));
+        }
+
+        changes
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeThresholds {
+    // 포인트 개수 변화 비율 (0.05 = ±5%)
+    pub count_ratio: f64,
+    // 축별 최소/최대값 변화 (m)
+    pub range_m: f32,
+}
+
+// 노드 실행 동안의 누적 통계 (종료 시 출력)
+#[derive(Debug, Clone, Default)]
+pub struct RunTotals {
+    pub frames: u64,
+    pub errors: u64,
+    pub input_points: u64,
+    pub output_points: u64,
+    // 좌표가 NaN/Inf 인 입력 포인트 수
+    pub invalid_points: u64,
+    // 입력/출력 메시지 직렬화 크기 합 (출력은 모든 출력 토픽 합)
+    pub input_bytes: u64,
+    pub output_bytes: u64,
+    pub latency_us_sum: u64,
+    pub latency_us_max: u64,
+}
+
+impl RunTotals {
+    pub fn add_frame(&mut self, input_points: usize, output_points: usize, latency_us: u64) {
+        self.frames += 1;
+        self.input_points += input_points as u64;
+        self.output_points += output_points as u64;
+        self.latency_us_sum += latency_us;
+        self.latency_us_max = self.latency_us_max.max(latency_us);
+    }
+
+    pub fn add_invalid(&mut self, invalid_points: usize) {
+        self.invalid_points += invalid_points as u64;
+    }
+
+    pub fn add_error(&mut self) {
+        self.errors += 1;
+    }
+
+    pub fn mean_latency_us(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.latency_us_sum as f64 / self.frames as f64
+        }
+    }
+
+    // dropped 는 큐에서 버린 프레임 수 (큐가 없는 노드는 0)
+    pub fn print(&self, dropped: u64) {
+        println!("=== 누적 통계 ===");
+        println!(
+            "처리 프레임: {} (오류 {}, 버림 {})",
+            self.frames, self.errors, dropped
+        );
+        println!(
+            "포인트: 입력 {} -> 출력 {}",
+            self.input_points, self.output_points
+        );
+        if self.invalid_points > 0 {
+            println!("NaN/Inf 포인트: {}", self.invalid_points);
+        }
+        if self.input_bytes > 0 {
+            println!(
+                "전송량: 입력 {:.1} MB -> 출력 {:.1} MB ({:.0}%)",
+                self.input_bytes as f64 / 1e6,
+                self.output_bytes as f64 / 1e6,
+                self.output_bytes as f64 / self.input_bytes as f64 * 100.0
+            );
+        }
+        println!(
+            "처리 시간: 평균 {:.0} us, 최대 {} us",
+            self.mean_latency_us(),
+            self.latency_us_max
+        );
+    }
+}
+
+// 일정 구간의 토픽별 수신 통계 (여러 센서 토픽 비교용, 구간마다 새로 시작)
+// latency 는 수신 시각 - header stamp (센서와 이 PC 의 시계가 동기화되어 있어야 의미 있음)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TopicWindow {
+    pub frames: u64,
+    pub points: u64,
+    // 직렬화 크기 합
+    pub bytes: u64,
+    pub latency_ms_sum: f64,
+    pub latency_ms_max: f64,
+}
+
+impl TopicWindow {
+    pub fn add(&mut self, points: usize, bytes: usize, latency_ms: f64) {
+        self.frames += 1;
+        self.points += points as u64;
+        self.bytes += bytes as u64;
+        self.latency_ms_sum += latency_ms;
+        self.latency_ms_max = if self.frames == 1 {
+            latency_ms
+        } else {
+            self.latency_ms_max.max(latency_ms)
+        };
+    }
+
+    // 구간 길이 secs 기준 프레임 주기 (Hz)
+    pub fn rate(&self, secs: f64) -> f64 {
+        if secs <= 0.0 {
+            0.0
+        } else {
+            self.frames as f64 / secs
+        }
+    }
+
+    // 구간 길이 secs 기준 전송률 (kbit/s)
+    pub fn kbps(&self, secs: f64) -> f64 {
+        if secs <= 0.0 {
+            0.0
+        } else {
+            self.bytes as f64 * 8.0 / secs / 1e3
+        }
+    }
+
+    pub fn mean_points(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.points as f64 / self.frames as f64
+        }
+    }
+
+    pub fn mean_latency_ms(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.latency_ms_sum / self.frames as f64
+        }
+    }
+}
+
+// PointCloud2 의 CDR 직렬화 크기 (문자열/배열 길이 4바이트와 필드 정렬 패딩은 근사)
+pub fn serialized_size(msg: &PointCloud2) -> usize {
+    let header = 8 + 4 + msg.header.frame_id.len() + 1;
+    let fields: usize = msg
+        .fields
+        .iter()
+        .map(|f| 4 + f.name.len() + 1 + 4 + 1 + 4)
+        .sum();
+    4 + header + 8 + 4 + fields + 1 + 8 + 4 + msg.data.len() + 1
+}
+
+// 누적 바이트로 구간 평균 전송률 (bit/s), period 가 지날 때마다 갱신
+#[derive(Debug, Clone)]
+pub struct ByteRate {
+    period: Duration,
+    start: Instant,
+    start_bytes: u64,
+    bits_per_sec: f64,
+}
+
+impl ByteRate {
+    pub fn new(period: Duration) -> Self {
+        ByteRate {
+            period,
+            start: Instant::now(),
+            start_bytes: 0,
+            bits_per_sec: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, total_bytes: u64) -> f64 {
+        let elapsed = self.start.elapsed();
+        if elapsed >= self.period {
+            let bytes = total_bytes.saturating_sub(self.start_bytes);
+            self.bits_per_sec = bytes as f64 * 8.0 / elapsed.as_secs_f64();
+            self.start = Instant::now();
+            self.start_bytes = total_bytes;
+        }
+        self.bits_per_sec
+    }
+}
@@ -0,0 +1,119 @@
+// 근거리 지면에서 작은 턱(과속방지턱, 단차) 검출
+// 전방 통로를 x 방향 슬라이스로 나눠 지면 높이를 구하고, 주변보다 min_step~max_step 만큼 높은 구간을 찾음
+
+#[derive(Debug, Clone, Copy)]
+pub struct StepConfig {
+    // 검사할 전방 거리와 좌우 폭의 절반 (m)
+    pub min_range: f32,
+    pub max_range: f32,
+    pub half_width: f32,
+    // 슬라이스 길이 (m) 와 슬라이스당 최소 포인트 수
+    pub slice: f32,
+    pub min_points: usize,
+    // 턱으로 볼 높이 범위 (m), 이보다 높으면 장애물
+    pub min_step: f32,
+    pub max_step: f32,
+}
+
+impl Default for StepConfig {
+    fn default() -> Self {
+        StepConfig {
+            min_range: 0.5,
+            max_range: 8.0,
+            half_width: 1.0,
+            slice: 0.2,
+            min_points: 5,
+            min_step: 0.03,
+            max_step: 0.15,
+        }
+    }
+}
+
+// 검출된 턱 하나 (x_start~x_end 구간, y 는 포인트 평균, height 는 기준 지면 대비 최대 높이)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub x_start: f32,
+    pub x_end: f32,
+    pub y: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Slice {
+    sum_h: f32,
+    sum_y: f32,
+    count: usize,
+}
+
+// points: (x, y, 지면 위 높이)
+pub fn detect_steps(points: impl IntoIterator<Item = [f32; 3]>, config: &StepConfig) -> Vec<Step> {
+    if config.slice <= 0.0 || config.max_range <= config.min_range {
+        return Vec::new();
+    }
+    let count = ((config.max_range - config.min_range) / config.slice).ceil() as usize;
+    let mut slices = vec![Slice::default(); count];
+    for [x, y, h] in points {
+        // 턱보다 확실히 높은 포인트(장애물)는 지면 높이 계산에서 제외
+        if x < config.min_range
+            || x >= config.max_range
+            || y.abs() > config.half_width
+            || h > config.max_step * 2.0
+        {
+            continue;
+        }
+        let i = ((x - config.min_range) / config.slice) as usize;
+        if let Some(slice) = slices.get_mut(i) {
+            slice.sum_h += h;
+            slice.sum_y += y;
+            slice.count += 1;
+        }
+    }
+
+    let means: Vec<Option<f32>> = slices
+        .iter()
+        .map(|s| (s.count >= config.min_points.max(1)).then(|| s.sum_h / s.count as f32))
+        .collect();
+    let mut observed: Vec<f32> = means.iter().flatten().copied().collect();
+    if observed.len() < 3 {
+        return Vec::new();
+    }
+    // 기준 지면 높이는 슬라이스 평균들의 중앙값
+    let mid = observed.len() / 2;
+    observed.select_nth_unstable_by(mid, f32::total_cmp);
+    let baseline = observed[mid];
+
+    let mut steps = Vec::new();
+    let mut current: Option<(usize, usize, f32)> = None; // (시작, 끝, 최대 높이)
+    for (i, mean) in means.iter().enumerate() {
+        let raised = mean
+            .map(|m| m - baseline)
+            .filter(|d| *d >= config.min_step && *d <= config.max_step);
+        match (raised, current.as_mut()) {
+            (Some(d), Some((_, end, height))) => {
+                *end = i;
+                *height = height.max(d);
+            }
+            (Some(d), None) => current = Some((i, i, d)),
+            (None, Some(_)) => steps.extend(current.take().map(|c| make_step(c, &slices, config))),
+            (None, None) => {}
+        }
+    }
+    steps.extend(current.map(|c| make_step(c, &slices, config)));
+    steps
+}
+
+fn make_step(
+    (start, end, height): (usize, usize, f32),
+    slices: &[Slice],
+    config: &StepConfig,
+) -> Step {
+    let (sum_y, count) = slices[start..=end]
+        .iter()
+        .fold((0.0, 0), |(y, n), s| (y + s.sum_y, n + s.count));
+    Step {
+        x_start: config.min_range + start as f32 * config.slice,
+        x_end: config.min_range + (end + 1) as f32 * config.slice,
+        y: sum_y / count.max(1) as f32,
+        height,
+    }
+}
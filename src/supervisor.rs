@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// 상태 감시: 발행자가 살아 있는데 구독 콜백이 stall_timeout 동안 오지 않거나 (구독 멈춤)
+// 처리 중 패닉이 panic_window 안에 max_panics 번 나면 재시작이 필요하다고 알림
+// 실제 재시작 (구독 재생성, 처리 상태 초기화) 은 노드가 하고, 여기서는 판단과 기록만
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SupervisorConfig {
+    // 초 (0 이면 구독 멈춤 감시 안 함)
+    pub stall_timeout: f64,
+    // 0 이면 패닉을 잡지 않고 그대로 전파
+    pub max_panics: usize,
+    pub panic_window: f64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        SupervisorConfig {
+            stall_timeout: 5.0,
+            max_panics: 3,
+            panic_window: 60.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthEvent {
+    // 발행자 publishers 개가 있는데 silent 초 동안 콜백 없음
+    Stalled { publishers: usize, silent: f64 },
+    // panic_window 안에 count 번 패닉
+    Panicked { count: usize, message: String },
+}
+
+impl HealthEvent {
+    pub fn describe(&self) -> String {
+        match self {
+            HealthEvent::Stalled { publishers, silent } => format!(
+                "구독 멈춤: 발행자 {} 개, {:.1} 초 동안 수신 없음 -> 구독 재생성",
+                publishers, silent
+            ),
+            HealthEvent::Panicked { count, message } => {
+                format!("처리 패닉 {} 회 ({}) -> 처리 상태 초기화", count, message)
+            }
+        }
+    }
+}
+
+struct Health {
+    last_callback: Instant,
+    panics: VecDeque<Instant>,
+    restarts: u64,
+    last_event: Option<(Instant, HealthEvent)>,
+}
+
+// 구독 콜백, 작업 스레드, spin 루프가 함께 쓰므로 내부 Mutex 로 공유
+pub struct Supervisor {
+    pub config: SupervisorConfig,
+    health: Mutex<Health>,
+}
+
+impl Supervisor {
+    pub fn new(config: SupervisorConfig) -> Self {
+        Supervisor {
+            config,
+            health: Mutex::new(Health {
+                last_callback: Instant::now(),
+                panics: VecDeque::new(),
+                restarts: 0,
+                last_event: None,
+            }),
+        }
+    }
+
+    fn health(&self) -> std::sync::MutexGuard<'_, Health> {
+        // 패닉 중에도 기록할 수 있도록 poison 은 무시
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn restart(health: &mut Health, event: HealthEvent) -> HealthEvent {
+        health.restarts += 1;
+        health.last_event = Some((Instant::now(), event.clone()));
+        event
+    }
+
+    // 구독 콜백에서 호출
+    pub fn alive(&self) {
+        self.health().last_callback = Instant::now();
+    }
+
+    pub fn catches_panics(&self) -> bool {
+        self.config.max_panics > 0
+    }
+
+    // spin 루프에서 주기적으로 호출, 구독을 다시 만들어야 하면 Some (대기 시간도 다시 셈)
+    pub fn check_stall(&self, publishers: usize) -> Option<HealthEvent> {
+        if self.config.stall_timeout <= 0.0 {
+            return None;
+        }
+        let mut health = self.health();
+        let silent = health.last_callback.elapsed().as_secs_f64();
+        if publishers == 0 {
+            // 발행자가 없으면 조용한 게 정상, 나타난 뒤부터 다시 셈
+            health.last_callback = Instant::now();
+            return None;
+        }
+        if silent < self.config.stall_timeout {
+            return None;
+        }
+        health.last_callback = Instant::now();
+        Some(Self::restart(
+            &mut health,
+            HealthEvent::Stalled { publishers, silent },
+        ))
+    }
+
+    // 작업 스레드에서 패닉을 잡았을 때 호출, 처리 상태를 초기화해야 하면 Some
+    pub fn record_panic(&self, message: &str) -> Option<HealthEvent> {
+        let window = Duration::from_secs_f64(self.config.panic_window.max(0.0));
+        let mut health = self.health();
+        let now = Instant::now();
+        health.panics.push_back(now);
+        while health
+            .panics
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > window)
+        {
+            health.panics.pop_front();
+        }
+        let count = health.panics.len();
+        if count < self.config.max_panics {
+            return None;
+        }
+        health.panics.clear();
+        Some(Self::restart(
+            &mut health,
+            HealthEvent::Panicked {
+                count,
+                message: message.to_string(),
+            },
+        ))
+    }
+
+    pub fn restarts(&self) -> u64 {
+        self.health().restarts
+    }
+
+    // hold 초 안에 있었던 마지막 재시작 사유 (진단 경고 유지용)
+    pub fn recent_event(&self, hold: f64) -> Option<HealthEvent> {
+        self.health()
+            .last_event
+            .as_ref()
+            .filter(|(at, _)| at.elapsed().as_secs_f64() <= hold)
+            .map(|(_, event)| event.clone())
+    }
+}
+
+// catch_unwind 결과의 패닉 메시지
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "알 수 없는 패닉".to_string())
+}
@@ -0,0 +1,143 @@
+use crate::point::LidarPoint;
+
+// 합성 LiDAR 프레임 생성기: 평평한 바닥과 사각형 벽으로 된 방을 레이캐스팅
+// 프레임 동안 센서가 움직이면(ego motion) 포인트마다 측정 시점의 자세를 사용해 왜곡된 프레임과
+// 프레임 시작 자세 기준의 정답(왜곡 없는) 프레임을 함께 만듦 (deskew 검증용)
+
+// 프레임 시작 시점 센서 좌표계 기준 등속 운동
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EgoMotion {
+    // 선속도 (m/s)
+    pub velocity: [f32; 3],
+    // z 축 회전 속도 (rad/s)
+    pub yaw_rate: f32,
+}
+
+impl EgoMotion {
+    // 프레임 시작 후 t 초의 센서 위치와 yaw
+    pub fn pose_at(&self, t: f32) -> ([f32; 3], f32) {
+        let yaw = self.yaw_rate * t;
+        // 등속 원운동을 중간 각도로 근사
+        let (s, c) = (yaw / 2.0).sin_cos();
+        let [vx, vy, vz] = self.velocity;
+        ([(c * vx - s * vy) * t, (s * vx + c * vy) * t, vz * t], yaw)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SyntheticConfig {
+    // 스캔 라인 수와 라인별 포인트 수, 수직 시야각 (도)
+    pub lines: u8,
+    pub points_per_line: usize,
+    pub fov_up_deg: f32,
+    pub fov_down_deg: f32,
+    // 프레임 길이 (s)
+    pub frame_duration: f32,
+    // 센서 높이와 방 크기 (센서 기준 ±half_size, m)
+    pub sensor_height: f32,
+    pub half_size: f32,
+    pub motion: EgoMotion,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        SyntheticConfig {
+            lines: 4,
+            points_per_line: 2500,
+            fov_up_deg: 15.0,
+            fov_down_deg: -30.0,
+            frame_duration: 0.1,
+            sensor_height: 1.5,
+            half_size: 10.0,
+            motion: EgoMotion::default(),
+        }
+    }
+}
+
+// 한 프레임의 생성 결과
+pub struct SyntheticFrame {
+    // 센서가 실제로 출력하는 값 (측정 시점 자세 기준, 움직이면 왜곡됨)
+    pub distorted: Vec<LidarPoint>,
+    // 같은 포인트를 프레임 시작 자세 기준으로 표현한 정답
+    pub truth: Vec<LidarPoint>,
+}
+
+// 원점 o 에서 방향 d 로 쏜 광선이 바닥/벽에 닿는 거리
+fn cast(o: [f32; 3], d: [f32; 3], config: &SyntheticConfig) -> Option<f32> {
+    let mut best: Option<f32> = None;
+    let mut consider = |t: f32| {
+        if t > 0.0 && best.is_none_or(|b| t < b) {
+            best = Some(t);
+        }
+    };
+    if d[2] < 0.0 {
+        consider((-config.sensor_height - o[2]) / d[2]);
+    }
+    for axis in 0..2 {
+        if d[axis] != 0.0 {
+            let wall = config.half_size.copysign(d[axis]);
+            consider((wall - o[axis]) / d[axis]);
+        }
+    }
+    best
+}
+
+// stamp_ns: 프레임 시작 시각, 포인트 timestamp 는 livox_ros_driver2 처럼 절대 시각(ns)
+pub fn generate(config: &SyntheticConfig, stamp_ns: f64) -> SyntheticFrame {
+    let total = config.lines as usize * config.points_per_line;
+    let mut distorted = Vec::with_capacity(total);
+    let mut truth = Vec::with_capacity(total);
+
+    // 라인들이 동시에 회전하며 프레임 동안 한 바퀴 스캔
+    for i in 0..config.points_per_line {
+        let t = config.frame_duration * i as f32 / config.points_per_line as f32;
+        let azimuth = std::f32::consts::TAU * i as f32 / config.points_per_line as f32;
+        let (position, yaw) = config.motion.pose_at(t);
+        let (sy, cy) = yaw.sin_cos();
+
+        for line in 0..config.lines {
+            let ratio = if config.lines > 1 {
+                line as f32 / (config.lines - 1) as f32
+            } else {
+                0.5
+            };
+            let elevation = (config.fov_down_deg
+                + (config.fov_up_deg - config.fov_down_deg) * ratio)
+                .to_radians();
+            let local = [
+                elevation.cos() * azimuth.cos(),
+                elevation.cos() * azimuth.sin(),
+                elevation.sin(),
+            ];
+            let world_dir = [
+                cy * local[0] - sy * local[1],
+                sy * local[0] + cy * local[1],
+                local[2],
+            ];
+            let Some(range) = cast(position, world_dir, config) else {
+                continue;
+            };
+
+            let timestamp = stamp_ns + t as f64 * 1e9;
+            let intensity =
+                if world_dir[2] < 0.0 && range * -world_dir[2] >= config.sensor_height - 0.01 {
+                    20.0 // 바닥
+                } else {
+                    80.0 // 벽
+                };
+            let point = |xyz: [f32; 3]| LidarPoint {
+                x: xyz[0],
+                y: xyz[1],
+                z: xyz[2],
+                intensity,
+                tag: 0,
+                line,
+                timestamp,
+            };
+            distorted.push(point(local.map(|c| c * range)));
+            truth.push(point([0, 1, 2].map(|k| position[k] + world_dir[k] * range)));
+        }
+    }
+
+    SyntheticFrame { distorted, truth }
+}
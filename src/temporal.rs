@@ -0,0 +1,226 @@
+use crate::point::LidarPoint;
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+
+// 먼지/배기가스 제거: intensity 가 낮고 최근 몇 프레임 동안 같은 자리에 계속 있지 않았던 포인트를 버림
+// (자갈길 뒤 먼지처럼 나타났다 사라지는 흐린 덩어리 대상, 오도메트리 보정이 없으므로 복셀을 크게 잡음)
+#[derive(Debug, Clone, Copy)]
+pub struct DustConfig {
+    // 복셀 크기 (m)
+    pub voxel: f32,
+    // 최근 history 프레임 중 min_frames 이상 점유된 곳이면 지속적인 물체로 봄 (history <= 8)
+    pub history: u32,
+    pub min_frames: u32,
+    // 이 intensity 이상이면 항상 남김
+    pub max_intensity: f32,
+}
+
+impl Default for DustConfig {
+    fn default() -> Self {
+        DustConfig {
+            voxel: 0.5,
+            history: 5,
+            min_frames: 3,
+            max_intensity: 20.0,
+        }
+    }
+}
+
+pub struct DustFilter {
+    config: DustConfig,
+    // 복셀별 점유 기록 (bit0 = 직전 프레임)
+    occupancy: HashMap<(i32, i32, i32), u8>,
+}
+
+impl DustFilter {
+    pub fn new(config: DustConfig) -> Self {
+        DustFilter {
+            config: DustConfig {
+                history: config.history.clamp(1, 8),
+                ..config
+            },
+            occupancy: HashMap::new(),
+        }
+    }
+
+    fn key(&self, p: &LidarPoint) -> (i32, i32, i32) {
+        let v = self.config.voxel;
+        (
+            (p.x / v).floor() as i32,
+            (p.y / v).floor() as i32,
+            (p.z / v).floor() as i32,
+        )
+    }
+
+    // 주변 27 복셀 중 하나라도 점유된 프레임 수
+    fn persistence(&self, (x, y, z): (i32, i32, i32)) -> u32 {
+        let mut bits = 0u8;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    bits |= self
+                        .occupancy
+                        .get(&(x + dx, y + dy, z + dz))
+                        .copied()
+                        .unwrap_or(0);
+                }
+            }
+        }
+        bits.count_ones()
+    }
+
+    // 남길 포인트면 true, 이번 프레임을 기록에 추가
+    pub fn mask(&mut self, points: &[LidarPoint]) -> Vec<bool> {
+        let keep = points
+            .iter()
+            .map(|p| {
+                p.intensity >= self.config.max_intensity
+                    || self.persistence(self.key(p)) >= self.config.min_frames
+            })
+            .collect();
+
+        let current: HashSet<_> = points.iter().map(|p| self.key(p)).collect();
+        let window = ((1u16 << self.config.history) - 1) as u8;
+        self.occupancy.retain(|_, bits| {
+            *bits = (*bits << 1) & window;
+            *bits != 0
+        });
+        for key in current {
+            *self.occupancy.entry(key).or_insert(0) |= 1;
+        }
+        keep
+    }
+
+    pub fn apply(&mut self, points: &mut Vec<LidarPoint>) {
+        let keep = self.mask(points);
+        let mut keep = keep.into_iter();
+        points.retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+// BEV 셀 시간 평활: 희박한 Livox 반사로 셀이 프레임마다 켜졌다 꺼지는 깜빡임 억제
+//   decay: 점유 점수를 지수 평균 (관측 1, 미관측 0) 으로 갱신해 threshold 이상이면 점유
+//   persistence: 최근 n 프레임 중 m 프레임 이상 관측되면 점유 (M-of-N, n <= 8)
+// 이번 프레임에 안 보였지만 점유로 남은 셀은 마지막 값으로 다시 발행
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellSmoothing {
+    Decay { alpha: f32, threshold: f32 },
+    Persistence { m: u32, n: u32 },
+}
+
+impl CellSmoothing {
+    // bev_smoothing: off, decay, persistence (off 면 None)
+    pub fn parse(name: &str, alpha: f32, threshold: f32, m: u32, n: u32) -> Result<Option<Self>> {
+        match name {
+            "off" => Ok(None),
+            "decay" => {
+                if !(alpha > 0.0 && alpha <= 1.0) {
+                    bail!(
+                        "bev_smoothing_alpha 는 0 초과 1 이하여야 합니다 ({})",
+                        alpha
+                    );
+                }
+                Ok(Some(CellSmoothing::Decay { alpha, threshold }))
+            }
+            "persistence" => {
+                let n = n.clamp(1, 8);
+                if m == 0 || m > n {
+                    bail!(
+                        "bev_smoothing_m 은 1 이상 bev_smoothing_n ({}) 이하여야 합니다",
+                        n
+                    );
+                }
+                Ok(Some(CellSmoothing::Persistence { m, n }))
+            }
+            other => bail!(
+                "알 수 없는 bev_smoothing '{}' (off, decay, persistence)",
+                other
+            ),
+        }
+    }
+}
+
+struct SmoothedCell<T> {
+    score: f32,
+    // 관측 기록 (bit0 = 이번 프레임)
+    bits: u8,
+    last: T,
+}
+
+pub struct CellSmoother<T> {
+    mode: CellSmoothing,
+    // 셀 크기가 바뀌면 (부하 단계) 인덱스가 달라지므로 기록을 지움
+    cell_size: f32,
+    cells: HashMap<(i32, i32), SmoothedCell<T>>,
+}
+
+impl<T: Copy> CellSmoother<T> {
+    pub fn new(mode: CellSmoothing) -> Self {
+        CellSmoother {
+            mode,
+            cell_size: 0.0,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn occupied(&self, cell: &SmoothedCell<T>) -> bool {
+        match self.mode {
+            CellSmoothing::Decay { threshold, .. } => cell.score >= threshold,
+            CellSmoothing::Persistence { m, .. } => cell.bits.count_ones() >= m,
+        }
+    }
+
+    // 이번 프레임에 관측된 셀 (인덱스, 값) 으로 기록을 갱신하고 점유로 판단된 셀을 돌려줌
+    // 순서: 관측된 셀 (입력 순서), 이어서 기록으로 남은 셀 (인덱스 순)
+    pub fn update(
+        &mut self,
+        cell_size: f32,
+        observed: impl IntoIterator<Item = ((i32, i32), T)>,
+    ) -> Vec<((i32, i32), T)> {
+        if cell_size != self.cell_size {
+            self.cells.clear();
+            self.cell_size = cell_size;
+        }
+        let (alpha, window) = match self.mode {
+            CellSmoothing::Decay { alpha, .. } => (alpha, 0xff),
+            CellSmoothing::Persistence { n, .. } => (1.0, ((1u16 << n) - 1) as u8),
+        };
+        for cell in self.cells.values_mut() {
+            cell.score *= 1.0 - alpha;
+            cell.bits = (cell.bits << 1) & window;
+        }
+        let mut seen = Vec::new();
+        for (index, value) in observed {
+            let cell = self.cells.entry(index).or_insert(SmoothedCell {
+                score: 0.0,
+                bits: 0,
+                last: value,
+            });
+            cell.score += alpha;
+            cell.bits |= 1;
+            cell.last = value;
+            seen.push(index);
+        }
+        // 점수가 거의 0 이고 기록도 없는 셀은 버림
+        self.cells
+            .retain(|_, cell| cell.bits != 0 || cell.score >= 1e-3);
+
+        let mut out: Vec<((i32, i32), T)> = seen
+            .iter()
+            .filter_map(|index| {
+                let cell = &self.cells[index];
+                self.occupied(cell).then_some((*index, cell.last))
+            })
+            .collect();
+        let seen: HashSet<(i32, i32)> = seen.into_iter().collect();
+        let mut held: Vec<((i32, i32), T)> = self
+            .cells
+            .iter()
+            .filter(|(index, cell)| !seen.contains(*index) && self.occupied(cell))
+            .map(|(index, cell)| (*index, cell.last))
+            .collect();
+        held.sort_by_key(|(index, _)| *index);
+        out.extend(held);
+        out
+    }
+}
@@ -0,0 +1,106 @@
+use crate::map_file::{BlockKey, MapHeader, MapReader};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+// 현재 위치 주변 블록(타일)만 메모리에 유지, 움직이면 디스크에서 읽고 멀어지면 버림
+pub struct TileCache {
+    reader: MapReader,
+    // load_radius 안의 타일은 읽고, unload_radius 밖으로 벗어난 타일은 버림 (경계에서 반복 로드 방지)
+    load_radius: f32,
+    unload_radius: f32,
+    tiles: HashMap<BlockKey, Vec<[f32; 3]>>,
+    pub loaded: u64,
+    pub unloaded: u64,
+}
+
+impl TileCache {
+    pub fn open(path: &Path, load_radius: f32, unload_radius: f32) -> Result<Self> {
+        Ok(TileCache {
+            reader: MapReader::open(path)?,
+            load_radius,
+            unload_radius: unload_radius.max(load_radius),
+            tiles: HashMap::new(),
+            loaded: 0,
+            unloaded: 0,
+        })
+    }
+
+    pub fn header(&self) -> &MapHeader {
+        self.reader.header()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn point_count(&self) -> usize {
+        self.tiles.values().map(Vec::len).sum()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = &[f32; 3]> {
+        self.tiles.values().flatten()
+    }
+
+    // 수평 거리 radius 안의 포인트 (시각화용 부분 지도)
+    pub fn points_within(&self, x: f32, y: f32, radius: f32) -> Vec<[f32; 3]> {
+        let r2 = radius * radius;
+        self.points()
+            .filter(|p| (p[0] - x).powi(2) + (p[1] - y).powi(2) <= r2)
+            .copied()
+            .collect()
+    }
+
+    // 캐시와 무관하게 반경 안 블록을 디스크에서 바로 읽음 (radius <= 0 이면 지도 전체, 재위치 추정용)
+    pub fn read_region(&mut self, x: f32, y: f32, radius: f32) -> Result<Vec<[f32; 3]>> {
+        let keys: Vec<BlockKey> = if radius > 0.0 {
+            self.reader.blocks_within(x, y, radius)
+        } else {
+            self.reader.blocks().map(|b| b.key).collect()
+        };
+        let mut points = Vec::new();
+        for key in keys {
+            points.extend(
+                self.reader
+                    .read_block(key)?
+                    .into_iter()
+                    .map(|p| [p.x, p.y, p.z]),
+            );
+        }
+        Ok(points)
+    }
+
+    // (x, y) 기준으로 타일 갱신, 바뀐 타일이 있으면 true
+    pub fn update(&mut self, x: f32, y: f32) -> Result<bool> {
+        let size = self.header().block_size;
+        let reach = self.unload_radius + size * std::f32::consts::FRAC_1_SQRT_2;
+        let before = self.tiles.len();
+        self.tiles.retain(|key, _| {
+            let [cx, cy] = key.center(size);
+            (cx - x).powi(2) + (cy - y).powi(2) <= reach * reach
+        });
+        let dropped = before - self.tiles.len();
+        self.unloaded += dropped as u64;
+
+        let mut added = 0;
+        for key in self.reader.blocks_within(x, y, self.load_radius) {
+            if self.tiles.contains_key(&key) {
+                continue;
+            }
+            let points = self
+                .reader
+                .read_block(key)?
+                .into_iter()
+                .map(|p| [p.x, p.y, p.z])
+                .collect();
+            self.tiles.insert(key, points);
+            added += 1;
+        }
+        self.loaded += added;
+        Ok(dropped > 0 || added > 0)
+    }
+}
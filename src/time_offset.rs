@@ -0,0 +1,179 @@
+use crate::pose::Pose;
+use crate::registration::{self, IcpConfig, VoxelIndex};
+
+// 두 센서(LiDAR-LiDAR, LiDAR-IMU) 사이 고정 시간 오프셋 추정
+// 각자 측정한 회전 속도 크기(rad/s) 시계열을 같은 간격으로 다시 뽑아 정규화 상호상관이 가장 큰 이동을 찾음
+// offset: target 시각 + offset = reference 시각 (PoseBuffer::time_offset 과 같은 방향)
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffsetConfig {
+    // 찾을 오프셋 범위 (초, +-)
+    pub max_offset: f64,
+    // 오프셋 탐색 간격이자 재표본 간격 (초)
+    pub step: f64,
+    // 상관 계산에 필요한 최소 겹치는 표본 수
+    pub min_samples: usize,
+}
+
+impl Default for OffsetConfig {
+    fn default() -> Self {
+        OffsetConfig {
+            max_offset: 0.2,
+            step: 0.002,
+            min_samples: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffsetEstimate {
+    pub offset: f64,
+    // 최대 정규화 상관 (-1~1, 움직임이 충분했는지 판단용)
+    pub correlation: f64,
+    pub samples: usize,
+}
+
+// 시간순 (시각, 값) 표본
+#[derive(Debug, Clone, Default)]
+pub struct MotionSignal {
+    samples: Vec<(f64, f64)>,
+}
+
+impl MotionSignal {
+    pub fn new() -> Self {
+        MotionSignal::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    // 시간이 되돌아가는 표본은 버림
+    pub fn push(&mut self, time: f64, value: f64) {
+        if self.samples.last().is_some_and(|(t, _)| time <= *t) {
+            return;
+        }
+        self.samples.push((time, value));
+    }
+
+    // 자이로 각속도 크기
+    pub fn push_gyro(&mut self, time: f64, gyro: [f64; 3]) {
+        self.push(time, gyro.iter().map(|w| w * w).sum::<f64>().sqrt());
+    }
+
+    // 두 스캔 사이 상대 자세로 구한 회전 속도 크기, 시각은 두 스캔의 가운데
+    pub fn push_delta(&mut self, start: f64, end: f64, delta: &Pose) {
+        let dt = end - start;
+        if dt > 0.0 {
+            self.push((start + end) / 2.0, delta.angle() / dt);
+        }
+    }
+
+    pub fn span(&self) -> Option<(f64, f64)> {
+        Some((self.samples.first()?.0, self.samples.last()?.0))
+    }
+
+    // 선형 보간, 범위 밖이면 None
+    pub fn value_at(&self, time: f64) -> Option<f64> {
+        let (first, last) = self.span()?;
+        if time < first || time > last {
+            return None;
+        }
+        let i = self.samples.partition_point(|(t, _)| *t <= time);
+        if i == self.samples.len() {
+            return Some(self.samples[i - 1].1);
+        }
+        let (t0, v0) = self.samples[i - 1];
+        let (t1, v1) = self.samples[i];
+        Some(v0 + (v1 - v0) * (time - t0) / (t1 - t0))
+    }
+}
+
+// 연속 스캔을 ICP 로 맞춰 LiDAR 회전 속도 신호를 만듦
+pub struct ScanMotion {
+    pub signal: MotionSignal,
+    voxel: f32,
+    icp: IcpConfig,
+    previous: Option<(f64, VoxelIndex)>,
+}
+
+impl ScanMotion {
+    pub fn new(voxel: f32, icp: IcpConfig) -> Self {
+        ScanMotion {
+            signal: MotionSignal::new(),
+            voxel,
+            icp,
+            previous: None,
+        }
+    }
+
+    // time 스캔 추가, 직전 스캔과 정합에 실패하면 표본 없이 기준만 바꿈
+    pub fn add(&mut self, time: f64, points: &[[f32; 3]]) {
+        let points = registration::voxel_downsample(points, self.voxel);
+        if let Some((prev_time, previous)) = &self.previous {
+            if let Some(result) = registration::icp(&points, previous, Pose::IDENTITY, &self.icp) {
+                self.signal.push_delta(*prev_time, time, &result.pose);
+            }
+        }
+        self.previous = Some((time, VoxelIndex::new(points, self.icp.max_distance)));
+    }
+}
+
+// 정규화 상호상관 (표본이 겹치지 않거나 분산이 0 이면 None)
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    let (ma, mb) = pairs
+        .iter()
+        .fold((0.0, 0.0), |(sa, sb), (a, b)| (sa + a / n, sb + b / n));
+    let (mut ab, mut aa, mut bb) = (0.0, 0.0, 0.0);
+    for (a, b) in pairs {
+        ab += (a - ma) * (b - mb);
+        aa += (a - ma) * (a - ma);
+        bb += (b - mb) * (b - mb);
+    }
+    let norm = (aa * bb).sqrt();
+    (norm > 1e-12).then(|| ab / norm)
+}
+
+pub fn estimate(
+    reference: &MotionSignal,
+    target: &MotionSignal,
+    config: &OffsetConfig,
+) -> Option<OffsetEstimate> {
+    let (start, end) = reference.span()?;
+    if config.step <= 0.0 {
+        return None;
+    }
+    let times: Vec<f64> = (0..)
+        .map(|i| start + i as f64 * config.step)
+        .take_while(|t| *t <= end)
+        .collect();
+    let steps = (config.max_offset / config.step).round() as i64;
+
+    let mut best: Option<OffsetEstimate> = None;
+    for k in -steps..=steps {
+        let offset = k as f64 * config.step;
+        let pairs: Vec<(f64, f64)> = times
+            .iter()
+            .filter_map(|&t| Some((reference.value_at(t)?, target.value_at(t - offset)?)))
+            .collect();
+        if pairs.len() < config.min_samples {
+            continue;
+        }
+        let Some(c) = correlation(&pairs) else {
+            continue;
+        };
+        if best.is_none_or(|b| c > b.correlation) {
+            best = Some(OffsetEstimate {
+                offset,
+                correlation: c,
+                samples: pairs.len(),
+            });
+        }
+    }
+    best
+}
@@ -0,0 +1,85 @@
+use diagnostic_msgs::msg::{DiagnosticArray, DiagnosticStatus, KeyValue};
+use std::time::Instant;
+use std_msgs::msg::Header;
+
+// 프레임 처리 단계별 소요 시간 (마이크로초)
+#[derive(Debug, Clone)]
+pub struct StageTimer {
+    start: Instant,
+    last: Instant,
+    stages: Vec<(&'static str, u64)>,
+}
+
+impl Default for StageTimer {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
+impl StageTimer {
+    pub fn start() -> Self {
+        let now = Instant::now();
+        StageTimer {
+            start: now,
+            last: now,
+            stages: Vec::with_capacity(4),
+        }
+    }
+
+    // 직전 mark 이후 경과 시간을 stage 로 기록
+    pub fn mark(&mut self, stage: &'static str) {
+        let now = Instant::now();
+        self.stages
+            .push((stage, now.duration_since(self.last).as_micros() as u64));
+        self.last = now;
+    }
+
+    pub fn stage_us(&self, stage: &str) -> Option<u64> {
+        self.stages
+            .iter()
+            .find(|(name, _)| *name == stage)
+            .map(|&(_, us)| us)
+    }
+
+    // "parse=120 filter=30 ... total=200" 형태 (us)
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = self
+            .stages
+            .iter()
+            .map(|(stage, us)| format!("{}={}", stage, us))
+            .collect();
+        parts.push(format!("total={}", self.total_us()));
+        parts.join(" ")
+    }
+
+    pub fn total_us(&self) -> u64 {
+        self.last.duration_since(self.start).as_micros() as u64
+    }
+
+    // <stage>_us 와 total_us 를 담은 메시지, header 는 원본 프레임 것을 사용해서 로그와 대조 가능
+    pub fn to_msg(&self, name: &str, header: &Header) -> DiagnosticArray {
+        let mut values: Vec<KeyValue> = self
+            .stages
+            .iter()
+            .map(|(stage, us)| KeyValue {
+                key: format!("{}_us", stage),
+                value: us.to_string(),
+            })
+            .collect();
+        values.push(KeyValue {
+            key: "total_us".to_string(),
+            value: self.total_us().to_string(),
+        });
+
+        DiagnosticArray {
+            header: header.clone(),
+            status: vec![DiagnosticStatus {
+                level: 0,
+                name: name.to_string(),
+                message: String::new(),
+                hardware_id: String::new(),
+                values,
+            }],
+        }
+    }
+}
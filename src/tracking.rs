@@ -0,0 +1,147 @@
+use crate::cluster::Cluster;
+
+// 프레임 사이 클러스터 연결 (최근접 + 거리 제한) 과 등속 모델 속도 추정
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackerConfig {
+    // 이전 위치(예측)에서 이 거리 안의 클러스터만 같은 물체로 봄 (m)
+    pub gate: f32,
+    // 이 프레임 수만큼 연속으로 못 찾으면 트랙 삭제
+    pub max_missed: u32,
+    // 속도 갱신 비율 (0..1, 클수록 새 측정을 많이 반영)
+    pub velocity_alpha: f32,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        TrackerConfig {
+            gate: 1.5,
+            max_missed: 3,
+            velocity_alpha: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Track {
+    pub id: u32,
+    // x/y 위치 (m) 와 속도 (m/s)
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    // 마지막으로 연결된 클러스터
+    pub cluster: Cluster,
+    // 연결된 프레임 수, 연속으로 못 찾은 프레임 수
+    pub hits: u32,
+    pub missed: u32,
+    pub time: f64,
+}
+
+impl Track {
+    pub fn speed(&self) -> f32 {
+        self.velocity[0].hypot(self.velocity[1])
+    }
+
+    // 등속 모델로 time 시각의 위치
+    pub fn predict(&self, time: f64) -> [f32; 2] {
+        let dt = (time - self.time) as f32;
+        [
+            self.position[0] + self.velocity[0] * dt,
+            self.position[1] + self.velocity[1] * dt,
+        ]
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Tracker {
+    config: TrackerConfig,
+    tracks: Vec<Track>,
+    next_id: u32,
+}
+
+impl Tracker {
+    pub fn new(config: TrackerConfig) -> Self {
+        Tracker {
+            config,
+            tracks: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    // 이번 프레임 클러스터로 트랙 갱신 (예측 위치에서 가까운 쌍부터 연결), 남은 클러스터는 새 트랙
+    pub fn update(&mut self, time: f64, clusters: &[Cluster]) {
+        let mut pairs: Vec<(f32, usize, usize)> = Vec::new();
+        for (t, track) in self.tracks.iter().enumerate() {
+            let [px, py] = track.predict(time);
+            for (c, cluster) in clusters.iter().enumerate() {
+                let d = (cluster.centroid[0] - px).hypot(cluster.centroid[1] - py);
+                if d <= self.config.gate {
+                    pairs.push((d, t, c));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut track_used = vec![false; self.tracks.len()];
+        let mut cluster_used = vec![false; clusters.len()];
+        let alpha = self.config.velocity_alpha.clamp(0.0, 1.0);
+        for (_, t, c) in pairs {
+            if track_used[t] || cluster_used[c] {
+                continue;
+            }
+            track_used[t] = true;
+            cluster_used[c] = true;
+            let track = &mut self.tracks[t];
+            let cluster = &clusters[c];
+            let position = [cluster.centroid[0], cluster.centroid[1]];
+            let dt = (time - track.time) as f32;
+            if dt > 1e-3 {
+                let first = track.hits == 1;
+                for ((v, new), old) in track.velocity.iter_mut().zip(position).zip(track.position) {
+                    let measured = (new - old) / dt;
+                    *v = if first {
+                        measured
+                    } else {
+                        *v + alpha * (measured - *v)
+                    };
+                }
+            }
+            track.position = position;
+            track.cluster = *cluster;
+            track.hits += 1;
+            track.missed = 0;
+            track.time = time;
+        }
+
+        let max_missed = self.config.max_missed;
+        let mut i = 0;
+        self.tracks.retain_mut(|track| {
+            let seen = track_used[i];
+            i += 1;
+            if !seen {
+                track.missed += 1;
+            }
+            track.missed <= max_missed
+        });
+
+        for (cluster, _) in clusters
+            .iter()
+            .zip(&cluster_used)
+            .filter(|(_, &used)| !used)
+        {
+            self.tracks.push(Track {
+                id: self.next_id,
+                position: [cluster.centroid[0], cluster.centroid[1]],
+                velocity: [0.0, 0.0],
+                cluster: *cluster,
+                hits: 1,
+                missed: 0,
+                time,
+            });
+            self.next_id = self.next_id.wrapping_add(1);
+        }
+    }
+}
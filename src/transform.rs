@@ -0,0 +1,209 @@
+use crate::cloud::{Point, PointCloud};
+use crate::ground::{self, Plane};
+use crate::msg::PointCloud2;
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::passthrough;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+
+// 센서 좌표 -> 기준 좌표 고정 변환 (기울어지거나 뒤집혀 장착된 센서 보정용, TF 없이 사용)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub rotation: [[f32; 3]; 3],
+    pub translation: [f32; 3],
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        translation: [0.0, 0.0, 0.0],
+    };
+
+    // 라디안, R = Rz(yaw) * Ry(pitch) * Rx(roll) (ROS 의 고정축 RPY 와 같은 순서)
+    pub fn from_rpy(roll: f64, pitch: f64, yaw: f64, translation: [f64; 3]) -> Self {
+        let (sr, cr) = roll.sin_cos();
+        let (sp, cp) = pitch.sin_cos();
+        let (sy, cy) = yaw.sin_cos();
+        let rotation = [
+            [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+            [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+            [-sp, cp * sr, cp * cr],
+        ];
+        Transform {
+            rotation: rotation.map(|row| row.map(|v| v as f32)),
+            translation: translation.map(|v| v as f32),
+        }
+    }
+
+    // (roll, pitch, yaw) 라디안, from_rpy 의 역
+    pub fn rpy(&self) -> (f64, f64, f64) {
+        let r = self.rotation.map(|row| row.map(|v| v as f64));
+        let pitch = (-r[2][0]).clamp(-1.0, 1.0).asin();
+        let roll = r[2][1].atan2(r[2][2]);
+        let yaw = r[1][0].atan2(r[0][0]);
+        (roll, pitch, yaw)
+    }
+
+    // mount_roll_deg / mount_pitch_deg / mount_yaw_deg (도) 와 mount_x / mount_y / mount_z (m)
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        let roll = params::float(node, "mount_roll_deg", 0.0)?;
+        let pitch = params::float(node, "mount_pitch_deg", 0.0)?;
+        let yaw = params::float(node, "mount_yaw_deg", 0.0)?;
+        let translation = [
+            params::float(node, "mount_x", 0.0)?,
+            params::float(node, "mount_y", 0.0)?,
+            params::float(node, "mount_z", 0.0)?,
+        ];
+        Ok(Transform::from_rpy(
+            roll.to_radians(),
+            pitch.to_radians(),
+            yaw.to_radians(),
+            translation,
+        ))
+    }
+
+    // 위쪽 방향(지면 normal, IMU 중력 반대 방향)을 +z 로 돌리는 회전, yaw 는 그대로
+    pub fn leveling(up: [f64; 3]) -> Self {
+        let (roll, pitch) = ground::level_angles(up);
+        Transform::from_rpy(roll, pitch, 0.0, [0.0; 3])
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    pub fn apply_xyz(&self, [x, y, z]: [f32; 3]) -> [f32; 3] {
+        let r = &self.rotation;
+        let t = &self.translation;
+        [
+            r[0][0] * x + r[0][1] * y + r[0][2] * z + t[0],
+            r[1][0] * x + r[1][1] * y + r[1][2] * z + t[1],
+            r[2][0] * x + r[2][1] * y + r[2][2] * z + t[2],
+        ]
+    }
+
+    // 변환 전 좌표계의 평면을 변환 후 좌표계로
+    pub fn apply_plane(&self, plane: &Plane) -> Plane {
+        let r = &self.rotation;
+        let n = plane.normal;
+        let normal = [0, 1, 2].map(|i| r[i][0] * n[0] + r[i][1] * n[1] + r[i][2] * n[2]);
+        let t = self.translation;
+        Plane {
+            normal,
+            d: plane.d - (normal[0] * t[0] + normal[1] * t[1] + normal[2] * t[2]),
+        }
+    }
+
+    pub fn apply<P: Point>(&self, cloud: &mut PointCloud<P>) {
+        if self.is_identity() {
+            return;
+        }
+        for p in cloud.points.iter_mut() {
+            p.set_xyz(self.apply_xyz(p.xyz()));
+        }
+    }
+
+    // 패스스루 모드용: 원본 버퍼의 x/y/z 를 제자리에서 변환
+    pub fn apply_msg(&self, msg: &mut PointCloud2) -> Result<()> {
+        if self.is_identity() {
+            return Ok(());
+        }
+        passthrough::map_xyz(msg, |[x, y, z]| {
+            let [x, y, z] = self.apply_xyz([x as f32, y as f32, z as f32]);
+            [x as f64, y as f64, z as f64]
+        })
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+// 출력 좌표계를 중력 방향에 맞추는 기준 (off, imu, ground)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GravityAlign {
+    Off,
+    // IMU 자세의 roll/pitch (정지 초기화 + 가속도 보정)
+    Imu,
+    // 프레임마다 추정한 지면 normal
+    Ground,
+}
+
+impl GravityAlign {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "off" => Ok(GravityAlign::Off),
+            "imu" => Ok(GravityAlign::Imu),
+            "ground" => Ok(GravityAlign::Ground),
+            _ => bail!("알 수 없는 gravity_align '{}' (off, imu, ground)", name),
+        }
+    }
+}
+
+// 출력 좌표축 규약 (센서/차량 좌표는 x 전방, y 왼쪽, z 위 = FLU, REP-103)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Convention {
+    // x 전방, y 왼쪽, z 위 (그대로, ENU 지도 좌표와 같은 축 방향)
+    Flu,
+    // x 전방, y 오른쪽, z 아래 (항공/NED 계열)
+    Frd,
+    // 카메라 광학 좌표: x 오른쪽, y 아래, z 전방 (REP-103 *_optical_frame)
+    Optical,
+}
+
+impl Convention {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "flu" | "enu" => Ok(Convention::Flu),
+            "frd" | "ned" => Ok(Convention::Frd),
+            "optical" => Ok(Convention::Optical),
+            _ => bail!(
+                "알 수 없는 output_convention '{}' (flu, enu, frd, ned, optical)",
+                name
+            ),
+        }
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        Self::parse(&params::string(node, "output_convention", "flu")?)
+    }
+
+    // FLU 좌표를 이 규약으로 바꾸는 축 교환/뒤집기
+    pub fn transform(&self) -> Transform {
+        let rotation = match self {
+            Convention::Flu => return Transform::IDENTITY,
+            Convention::Frd => [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0]],
+            Convention::Optical => [[0.0, -1.0, 0.0], [0.0, 0.0, -1.0], [1.0, 0.0, 0.0]],
+        };
+        Transform {
+            rotation,
+            translation: [0.0, 0.0, 0.0],
+        }
+    }
+
+    // 출력 frame_id 에 붙일 접미사 (축이 바뀌면 같은 frame_id 를 쓰면 안 됨)
+    pub fn frame_suffix(&self) -> &'static str {
+        match self {
+            Convention::Flu => "",
+            Convention::Frd => "_frd",
+            Convention::Optical => "_optical",
+        }
+    }
+
+    pub fn apply<P: Point>(&self, cloud: &mut PointCloud<P>) {
+        self.transform().apply(cloud);
+        cloud.header.frame_id.push_str(self.frame_suffix());
+    }
+
+    pub fn apply_msg(&self, msg: &mut PointCloud2) -> Result<()> {
+        self.transform().apply_msg(msg)?;
+        msg.header.frame_id.push_str(self.frame_suffix());
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use crate::bev::BevGrid;
+#[cfg(feature = "ros")]
+use crate::msg::Time;
+#[cfg(feature = "ros")]
+use crate::stamp;
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "ros")]
+use geometry_msgs::msg::Pose;
+#[cfg(feature = "ros")]
+use nav_msgs::msg::{MapMetaData, OccupancyGrid};
+use std::fs;
+use std::path::Path;
+#[cfg(feature = "ros")]
+use std_msgs::msg::Header;
+
+// nav_msgs/OccupancyGrid 값
+pub const FREE: i8 = 0;
+pub const OCCUPIED: i8 = 100;
+pub const UNKNOWN: i8 = -1;
+
+// 센서 원점에서 BEV 격자 위로 2D 광선을 쏘아 셀을 free / occupied / unknown(관측 안 됨, 가려짐) 으로 구분
+pub struct VisibilityGrid {
+    cell_size: f32,
+    min: [f32; 2],
+    cols: usize,
+    rows: usize,
+    cells: Vec<i8>,
+    // 이번 프레임에 포인트가 끝난 셀 (광선은 셀마다 한 번만)
+    hits: Vec<i8>,
+}
+
+impl VisibilityGrid {
+    // bev_cell_size, bev_extent_x, bev_extent_y 가 모두 양수여야 함
+    pub fn new(grid: &BevGrid) -> Result<Self> {
+        let (min, max) = grid.bounds();
+        if grid.cell_size <= 0.0 || !max[0].is_finite() || !max[1].is_finite() {
+            bail!("가시성 격자는 bev_cell_size, bev_extent_x, bev_extent_y 가 필요합니다");
+        }
+        let cols = ((max[0] - min[0]) / grid.cell_size).ceil() as usize;
+        let rows = ((max[1] - min[1]) / grid.cell_size).ceil() as usize;
+        Ok(VisibilityGrid {
+            cell_size: grid.cell_size,
+            min,
+            cols,
+            rows,
+            cells: vec![UNKNOWN; cols * rows],
+            hits: vec![UNKNOWN; cols * rows],
+        })
+    }
+
+    fn cell(&self, x: f32, y: f32) -> Option<(i64, i64)> {
+        let col = ((x - self.min[0]) / self.cell_size).floor();
+        let row = ((y - self.min[1]) / self.cell_size).floor();
+        col.is_finite().then_some((col as i64, row as i64))
+    }
+
+    fn index(&self, (col, row): (i64, i64)) -> Option<usize> {
+        (col >= 0 && row >= 0 && (col as usize) < self.cols && (row as usize) < self.rows)
+            .then(|| row as usize * self.cols + col as usize)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(UNKNOWN);
+        self.hits.fill(UNKNOWN);
+    }
+
+    // 포인트 하나의 관측 결과 기록 (occupied: 장애물 높이 범위에 든 포인트인지)
+    pub fn add_hit(&mut self, x: f32, y: f32, occupied: bool) {
+        let Some(i) = self.cell(x, y).and_then(|c| self.index(c)) else {
+            return;
+        };
+        if occupied {
+            self.hits[i] = OCCUPIED;
+        } else if self.hits[i] == UNKNOWN {
+            self.hits[i] = FREE;
+        }
+    }
+
+    // 기록된 셀마다 origin 에서 광선을 쏴서 지나간 셀은 free, 끝 셀은 관측 결과로 표시
+    pub fn cast(&mut self, origin: [f32; 2]) {
+        let Some(start) = self.cell(origin[0], origin[1]) else {
+            return;
+        };
+        for i in 0..self.hits.len() {
+            if self.hits[i] == UNKNOWN {
+                continue;
+            }
+            let end = ((i % self.cols) as i64, (i / self.cols) as i64);
+            self.trace(start, end);
+            self.cells[i] = self.cells[i].max(self.hits[i]);
+        }
+    }
+
+    // Bresenham, 끝 셀은 제외
+    fn trace(&mut self, (mut x, mut y): (i64, i64), (x1, y1): (i64, i64)) {
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        while (x, y) != (x1, y1) {
+            if let Some(i) = self.index((x, y)) {
+                if self.cells[i] == UNKNOWN {
+                    self.cells[i] = FREE;
+                }
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    #[cfg(feature = "ros")]
+    pub fn to_msg(&self, header: Header) -> OccupancyGrid {
+        let map_load_time = header.stamp.clone();
+        self.grid_msg(header, map_load_time, self.cells.clone())
+    }
+
+    #[cfg(feature = "ros")]
+    fn grid_msg(&self, header: Header, map_load_time: Time, data: Vec<i8>) -> OccupancyGrid {
+        let mut origin = Pose::default();
+        origin.position.x = self.min[0] as f64;
+        origin.position.y = self.min[1] as f64;
+        origin.orientation.w = 1.0;
+        OccupancyGrid {
+            info: MapMetaData {
+                map_load_time,
+                resolution: self.cell_size,
+                width: self.cols as u32,
+                height: self.rows as u32,
+                origin,
+            },
+            header,
+            data,
+        }
+    }
+}
+
+// 프레임별 가시성 결과를 log-odds 로 누적한 점유 지도 (고정 설치 센서나 odom 좌표계 출력용)
+// 재시작 후 같은 장소에서 이어 쓸 수 있게 파일로 저장/복원
+//   파일 (little endian): MAGIC, 해상도, 원점 x/y, 열/행 수, 마지막 갱신 시각(초), frame_id, 셀별 log-odds
+pub struct TemporalOccupancy {
+    log_odds: Vec<f32>,
+    // 마지막으로 누적한 프레임 시각 (초) 과 좌표계
+    pub stamp: f64,
+    pub frame_id: String,
+}
+
+const OCCUPANCY_MAGIC: &[u8; 8] = b"LVXOCC01";
+// 관측 한 번의 log-odds 변화량과 누적 한계 (한계가 있어야 바뀐 장면을 다시 따라감)
+const LOG_ODDS_HIT: f32 = 0.85;
+const LOG_ODDS_MISS: f32 = -0.4;
+const LOG_ODDS_LIMIT: f32 = 3.5;
+
+impl TemporalOccupancy {
+    pub fn new(grid: &VisibilityGrid) -> Self {
+        TemporalOccupancy {
+            log_odds: vec![0.0; grid.cells.len()],
+            stamp: 0.0,
+            frame_id: String::new(),
+        }
+    }
+
+    // 이번 프레임의 가시성 격자 (cast 후) 를 누적
+    pub fn update(&mut self, grid: &VisibilityGrid, stamp: f64, frame_id: &str) {
+        for (l, &cell) in self.log_odds.iter_mut().zip(&grid.cells) {
+            let delta = match cell {
+                OCCUPIED => LOG_ODDS_HIT,
+                FREE => LOG_ODDS_MISS,
+                _ => continue,
+            };
+            *l = (*l + delta).clamp(-LOG_ODDS_LIMIT, LOG_ODDS_LIMIT);
+        }
+        self.stamp = stamp;
+        if self.frame_id != frame_id {
+            self.frame_id = frame_id.to_string();
+        }
+    }
+
+    // OccupancyGrid 값 (0..100 점유 확률, 관측된 적 없으면 -1)
+    pub fn cells(&self) -> Vec<i8> {
+        self.log_odds
+            .iter()
+            .map(|&l| {
+                if l == 0.0 {
+                    UNKNOWN
+                } else {
+                    (100.0 / (1.0 + (-l).exp())).round() as i8
+                }
+            })
+            .collect()
+    }
+
+    // map_load_time 은 마지막 누적 시각
+    #[cfg(feature = "ros")]
+    pub fn to_msg(&self, grid: &VisibilityGrid, header: Header) -> OccupancyGrid {
+        grid.grid_msg(header, stamp::from_secs(self.stamp), self.cells())
+    }
+
+    pub fn save(&self, grid: &VisibilityGrid, path: &Path) -> Result<()> {
+        let mut out = Vec::with_capacity(48 + self.frame_id.len() + self.log_odds.len() * 4);
+        out.extend_from_slice(OCCUPANCY_MAGIC);
+        out.extend_from_slice(&grid.cell_size.to_le_bytes());
+        out.extend_from_slice(&grid.min[0].to_le_bytes());
+        out.extend_from_slice(&grid.min[1].to_le_bytes());
+        out.extend_from_slice(&(grid.cols as u32).to_le_bytes());
+        out.extend_from_slice(&(grid.rows as u32).to_le_bytes());
+        out.extend_from_slice(&self.stamp.to_le_bytes());
+        out.extend_from_slice(&(self.frame_id.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.frame_id.as_bytes());
+        for l in &self.log_odds {
+            out.extend_from_slice(&l.to_le_bytes());
+        }
+        fs::write(path, out).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+
+    // 저장할 때와 격자 (해상도, 원점, 크기) 가 다르면 오류
+    pub fn load(grid: &VisibilityGrid, path: &Path) -> Result<Self> {
+        let data = fs::read(path).with_context(|| format!("{} 읽기 실패", path.display()))?;
+        let mut rest = data.as_slice();
+        let mut take = |n: usize| -> Result<&[u8]> {
+            if rest.len() < n {
+                bail!("{} 점유 지도 파일이 잘렸습니다", path.display());
+            }
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            Ok(head)
+        };
+        if take(8)? != OCCUPANCY_MAGIC {
+            bail!("{} 는 점유 지도 파일이 아닙니다", path.display());
+        }
+        let f32_at = |b: &[u8]| f32::from_le_bytes(b.try_into().unwrap());
+        let u32_at = |b: &[u8]| u32::from_le_bytes(b.try_into().unwrap());
+        let resolution = f32_at(take(4)?);
+        let origin = [f32_at(take(4)?), f32_at(take(4)?)];
+        let (cols, rows) = (u32_at(take(4)?) as usize, u32_at(take(4)?) as usize);
+        if resolution != grid.cell_size
+            || origin != grid.min
+            || (cols, rows) != (grid.cols, grid.rows)
+        {
+            bail!(
+                "{} 의 격자 (해상도 {}, 원점 {:?}, {}x{}) 가 현재 설정 (해상도 {}, 원점 {:?}, {}x{}) 과 다릅니다",
+                path.display(),
+                resolution,
+                origin,
+                cols,
+                rows,
+                grid.cell_size,
+                grid.min,
+                grid.cols,
+                grid.rows
+            );
+        }
+        let stamp = f64::from_le_bytes(take(8)?.try_into().unwrap());
+        let name_len = u32_at(take(4)?) as usize;
+        let frame_id = String::from_utf8(take(name_len)?.to_vec())
+            .with_context(|| format!("{} 의 frame_id 가 UTF-8 이 아닙니다", path.display()))?;
+        let log_odds = take(cols * rows * 4)?.chunks_exact(4).map(f32_at).collect();
+        Ok(TemporalOccupancy {
+            log_odds,
+            stamp,
+            frame_id,
+        })
+    }
+}
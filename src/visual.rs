@@ -0,0 +1,200 @@
+use crate::msg::PointCloud2;
+#[cfg(feature = "ros")]
+use crate::params;
+use crate::passthrough;
+use crate::point::LidarPoint;
+use anyhow::{bail, Result};
+#[cfg(feature = "ros")]
+use rclrs::Node;
+
+// 값 0..1 을 색으로 바꾸는 컬러맵 (제어점 사이 선형 보간)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    Gray,
+    Jet,
+    Turbo,
+    Viridis,
+}
+
+const JET: &[[f32; 3]] = &[
+    [0.0, 0.0, 0.5],
+    [0.0, 0.0, 1.0],
+    [0.0, 1.0, 1.0],
+    [1.0, 1.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [0.5, 0.0, 0.0],
+];
+
+const TURBO: &[[f32; 3]] = &[
+    [0.190, 0.072, 0.232],
+    [0.276, 0.421, 0.891],
+    [0.158, 0.736, 0.923],
+    [0.197, 0.949, 0.595],
+    [0.644, 0.990, 0.234],
+    [0.933, 0.812, 0.227],
+    [0.984, 0.493, 0.128],
+    [0.816, 0.185, 0.018],
+    [0.480, 0.016, 0.011],
+];
+
+#[allow(clippy::approx_constant)]
+const VIRIDIS: &[[f32; 3]] = &[
+    [0.267, 0.005, 0.329],
+    [0.283, 0.141, 0.458],
+    [0.254, 0.265, 0.530],
+    [0.207, 0.372, 0.553],
+    [0.164, 0.471, 0.558],
+    [0.128, 0.567, 0.551],
+    [0.135, 0.659, 0.518],
+    [0.267, 0.749, 0.441],
+    [0.478, 0.821, 0.318],
+    [0.741, 0.873, 0.150],
+    [0.993, 0.906, 0.144],
+];
+
+impl Colormap {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "gray" => Ok(Colormap::Gray),
+            "jet" => Ok(Colormap::Jet),
+            "turbo" => Ok(Colormap::Turbo),
+            "viridis" => Ok(Colormap::Viridis),
+            other => bail!("알 수 없는 컬러맵 '{}' (gray, jet, turbo, viridis)", other),
+        }
+    }
+
+    // t 는 0..1 로 잘라서 사용 (NaN 은 0)
+    pub fn color(&self, t: f32) -> [u8; 3] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let stops = match self {
+            Colormap::Gray => return [(t * 255.0).round() as u8; 3],
+            Colormap::Jet => JET,
+            Colormap::Turbo => TURBO,
+            Colormap::Viridis => VIRIDIS,
+        };
+        let x = t * (stops.len() - 1) as f32;
+        let i = (x.floor() as usize).min(stops.len() - 2);
+        let f = x - i as f32;
+        [0, 1, 2].map(|c| {
+            let v = stops[i][c] + (stops[i + 1][c] - stops[i][c]) * f;
+            (v * 255.0).round() as u8
+        })
+    }
+}
+
+// PCL 관례대로 FLOAT32 rgb 필드에 0x00RRGGBB 를 비트 단위로 담음
+pub fn pack_rgb([r, g, b]: [u8; 3]) -> f32 {
+    f32::from_bits(((r as u32) << 16) | ((g as u32) << 8) | b as u32)
+}
+
+// 출력 노드들이 같이 쓰는 시각화 설정
+//   colormap/color_field/color_range: rgb 필드가 있는 출력 (output_layout=xyzrgb) 의 포인트 색
+//     color_field 는 LidarPoint 필드 이름 (intensity, z 등, bev_cells 모드에서 z 는 셀 높이)
+//   image_colormap: BEV 층 이미지를 층별 포인트 수 채널 대신 전체 포인트 수 컬러 이미지 (rgb8) 로
+//     image_max_count 개에서 컬러맵 끝 색
+//   marker_lifetime/namespace_prefix: MarkerArray 출력의 마커 수명 (초) 과 ns 앞에 붙일 이름
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisualSettings {
+    pub colormap: Colormap,
+    pub color_field: String,
+    pub color_range: [f32; 2],
+    pub image_colormap: Option<Colormap>,
+    pub image_max_count: u32,
+    pub marker_lifetime: f64,
+    pub namespace_prefix: String,
+}
+
+impl Default for VisualSettings {
+    fn default() -> Self {
+        VisualSettings {
+            colormap: Colormap::Gray,
+            color_field: "intensity".to_string(),
+            color_range: [0.0, 255.0],
+            image_colormap: None,
+            image_max_count: 20,
+            marker_lifetime: 0.2,
+            namespace_prefix: String::new(),
+        }
+    }
+}
+
+impl VisualSettings {
+    // visual_colormap, visual_color_field, visual_color_range ([min, max]),
+    // visual_image_colormap (빈 문자열이면 층별 채널 이미지), visual_image_max_count,
+    // visual_marker_lifetime (초), visual_namespace_prefix
+    #[cfg(feature = "ros")]
+    pub fn from_node(node: &Node) -> Result<Self> {
+        let defaults = VisualSettings::default();
+        let range = params::float_array(
+            node,
+            "visual_color_range",
+            &defaults.color_range.map(|v| v as f64),
+        )?;
+        let [min, max] = range[..] else {
+            bail!(
+                "visual_color_range 는 [min, max] 두 값이어야 합니다: {:?}",
+                range
+            );
+        };
+        if min >= max {
+            bail!("visual_color_range 는 min < max 여야 합니다: {:?}", range);
+        }
+        let image_colormap = params::string(node, "visual_image_colormap", "")?;
+        Ok(VisualSettings {
+            colormap: Colormap::parse(&params::string(node, "visual_colormap", "gray")?)?,
+            color_field: params::string(node, "visual_color_field", &defaults.color_field)?,
+            color_range: [min as f32, max as f32],
+            image_colormap: if image_colormap.is_empty() {
+                None
+            } else {
+                Some(Colormap::parse(&image_colormap)?)
+            },
+            image_max_count: params::int(
+                node,
+                "visual_image_max_count",
+                defaults.image_max_count as i64,
+            )?
+            .max(1) as u32,
+            marker_lifetime: params::float(
+                node,
+                "visual_marker_lifetime",
+                defaults.marker_lifetime,
+            )?,
+            namespace_prefix: params::string(node, "visual_namespace_prefix", "")?,
+        })
+    }
+
+    // color_range 기준 색
+    pub fn color(&self, value: f32) -> [u8; 3] {
+        let [min, max] = self.color_range;
+        self.colormap.color((value - min) / (max - min))
+    }
+
+    // 포인트 수 기준 이미지 색 (image_colormap 이 없으면 회색조)
+    pub fn image_color(&self, count: u32) -> [u8; 3] {
+        let t = count as f32 / self.image_max_count.max(1) as f32;
+        self.image_colormap.unwrap_or(Colormap::Gray).color(t)
+    }
+
+    pub fn marker_namespace(&self, name: &str) -> String {
+        if self.namespace_prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.namespace_prefix, name)
+        }
+    }
+
+    // rgb 필드가 있는 출력이면 color_field 값의 색으로 덮어씀 (없으면 그대로)
+    // points 는 msg 와 같은 순서 (정렬 출력이면 빈 셀 포함)
+    pub fn colorize(&self, msg: &mut PointCloud2, points: &[LidarPoint]) -> Result<()> {
+        if !msg.fields.iter().any(|f| f.name == "rgb") {
+            return Ok(());
+        }
+        passthrough::set_field(msg, "rgb", |i| {
+            let value = points
+                .get(i)
+                .map_or(0.0, |p| p.field_value(&self.color_field));
+            pack_rgb(self.color(value as f32)) as f64
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use crate::exclusion;
+use crate::ground::Plane;
+use crate::sidecar::json_string;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 적치물(골재, 석탄 더미 등) 부피 측정: 기준 지면 위 다각형 안 재료의 부피
+// 다각형 안을 격자로 나누고 셀마다 평균 높이(기준 평면 위, 음수는 0) x 셀 면적을 더함
+// 포인트가 없는 셀(가려진 뒷면 등)은 부피에 넣지 않고 coverage 로 따로 알림
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeConfig {
+    // 측정 영역 x/y 다각형 (장착 보정 후 좌표계, 꼭짓점 3개 이상)
+    pub polygon: Vec<[f32; 2]>,
+    // 격자 셀 크기 (m)
+    pub cell: f32,
+    // 셀 하나로 인정할 최소 포인트 수
+    pub min_points: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeReport {
+    pub volume: f64,
+    // 다각형 안 셀 면적과 그중 포인트가 있는 셀 면적 (m^2)
+    pub area: f64,
+    pub covered_area: f64,
+    pub max_height: f32,
+    pub mean_height: f32,
+    pub points: usize,
+    // 기준 평면 (normal, d)
+    pub plane: Plane,
+    pub frame_id: String,
+    pub stamp: f64,
+}
+
+impl VolumeReport {
+    pub fn coverage(&self) -> f64 {
+        if self.area > 0.0 {
+            self.covered_area / self.area
+        } else {
+            0.0
+        }
+    }
+
+    // 서비스 응답 메시지용 한 줄 요약
+    pub fn summary(&self) -> String {
+        format!(
+            "부피 {:.3} m^3, 면적 {:.2} m^2 (측정 {:.0}%), 최대 높이 {:.2} m, 평균 높이 {:.2} m",
+            self.volume,
+            self.area,
+            self.coverage() * 100.0,
+            self.max_height,
+            self.mean_height
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\n  \"frame_id\": {},\n  \"stamp\": {:.6},\n  \"volume_m3\": {:.6},\n  \"area_m2\": {:.6},\n  \"covered_area_m2\": {:.6},\n  \"coverage\": {:.4},\n  \"max_height_m\": {:.4},\n  \"mean_height_m\": {:.4},\n  \"points\": {},\n  \"plane\": {{\"normal\": [{}, {}, {}], \"d\": {}}}\n}}\n",
+            json_string(&self.frame_id),
+            self.stamp,
+            self.volume,
+            self.area,
+            self.covered_area,
+            self.coverage(),
+            self.max_height,
+            self.mean_height,
+            self.points,
+            self.plane.normal[0],
+            self.plane.normal[1],
+            self.plane.normal[2],
+            self.plane.d
+        )
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_json()).with_context(|| format!("{} 쓰기 실패", path.display()))
+    }
+}
+
+impl VolumeConfig {
+    // 다각형 밖 포인트 (기준 지면 추정용)
+    pub fn outside<'a>(&'a self, points: &'a [[f32; 3]]) -> impl Iterator<Item = [f32; 3]> + 'a {
+        points
+            .iter()
+            .copied()
+            .filter(|p| !exclusion::point_in_polygon(&self.polygon, p[0], p[1]))
+    }
+
+    pub fn measure(
+        &self,
+        points: &[[f32; 3]],
+        plane: &Plane,
+        frame_id: &str,
+        stamp: f64,
+    ) -> Result<VolumeReport> {
+        if self.polygon.len() < 3 {
+            bail!("부피 측정 다각형은 꼭짓점 3개 이상이 필요합니다");
+        }
+        if self.cell <= 0.0 {
+            bail!("부피 측정 셀 크기는 0 보다 커야 합니다");
+        }
+        let cell_of = |x: f32, y: f32| {
+            (
+                (x / self.cell).floor() as i32,
+                (y / self.cell).floor() as i32,
+            )
+        };
+        let center =
+            |(ix, iy): (i32, i32)| ((ix as f32 + 0.5) * self.cell, (iy as f32 + 0.5) * self.cell);
+        let inside = |(ix, iy): (i32, i32)| {
+            let (x, y) = center((ix, iy));
+            exclusion::point_in_polygon(&self.polygon, x, y)
+        };
+
+        // 셀별 (높이 합, 포인트 수)
+        let mut cells: HashMap<(i32, i32), (f64, usize)> = HashMap::new();
+        let mut used = 0;
+        for &p in points {
+            let key = cell_of(p[0], p[1]);
+            if !inside(key) {
+                continue;
+            }
+            let entry = cells.entry(key).or_default();
+            entry.0 += plane.distance(p).max(0.0) as f64;
+            entry.1 += 1;
+            used += 1;
+        }
+
+        // 다각형을 덮는 셀 수 (경계 상자 안 셀 중심이 다각형 안인 것)
+        let (mut lo, mut hi) = ((i32::MAX, i32::MAX), (i32::MIN, i32::MIN));
+        for &[x, y] in &self.polygon {
+            let (ix, iy) = cell_of(x, y);
+            lo = (lo.0.min(ix), lo.1.min(iy));
+            hi = (hi.0.max(ix), hi.1.max(iy));
+        }
+        let total_cells = (lo.0..=hi.0)
+            .flat_map(|ix| (lo.1..=hi.1).map(move |iy| (ix, iy)))
+            .filter(|&key| inside(key))
+            .count();
+
+        let cell_area = (self.cell as f64).powi(2);
+        let mut volume = 0.0;
+        let mut covered = 0;
+        let mut max_height = 0.0f32;
+        for &(sum, count) in cells.values() {
+            if count < self.min_points.max(1) {
+                continue;
+            }
+            let height = sum / count as f64;
+            volume += height * cell_area;
+            max_height = max_height.max(height as f32);
+            covered += 1;
+        }
+        Ok(VolumeReport {
+            volume,
+            area: total_cells as f64 * cell_area,
+            covered_area: covered as f64 * cell_area,
+            max_height,
+            mean_height: if covered > 0 {
+                (volume / (covered as f64 * cell_area)) as f32
+            } else {
+                0.0
+            },
+            points: used,
+            plane: *plane,
+            frame_id: frame_id.to_string(),
+            stamp,
+        })
+    }
+}
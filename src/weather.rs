@@ -0,0 +1,99 @@
+use crate::compute::{ComputeBackend, Cpu};
+use crate::point::LidarPoint;
+
+// 비/눈/안개 노이즈 제거
+// Livox 노이즈 태그, 낮은 intensity, 거리에 따라 반경이 커지는 고립점 검사(DROR)를 함께 사용
+// aggressiveness 0..1: 클수록 더 많이 제거 (0 이면 끔)
+#[derive(Debug, Clone, Copy)]
+pub struct WeatherFilter {
+    pub aggressiveness: f32,
+}
+
+// 이웃 검색 셀 크기이자 최대 검색 반경 (m)
+const CELL: f32 = 0.5;
+// 최소 검색 반경 (m) 과 거리 비례 계수 (약 0.2도 각해상도 x 3배)
+const MIN_RADIUS: f32 = 0.1;
+const RADIUS_PER_M: f32 = 0.01;
+// 이보다 낮은 intensity 는 빗방울/눈송이일 가능성이 큼 (aggressiveness 1 기준)
+const LOW_INTENSITY: f32 = 10.0;
+
+impl WeatherFilter {
+    pub fn new(aggressiveness: f32) -> Self {
+        WeatherFilter {
+            aggressiveness: aggressiveness.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.aggressiveness > 0.0
+    }
+
+    // Livox tag: bit[1:0] 공간 노이즈, bit[3:2] intensity 노이즈
+    // 01 = 노이즈일 확률 높음, 10 = 중간, 11 = 낮음 (aggressiveness 에 따라 허용 범위를 넓힘)
+    fn tagged_noise(&self, tag: u8) -> bool {
+        [tag & 0b11, (tag >> 2) & 0b11]
+            .into_iter()
+            .any(|code| match code {
+                0b01 => true,
+                0b10 => self.aggressiveness >= 0.34,
+                0b11 => self.aggressiveness >= 0.67,
+                _ => false,
+            })
+    }
+
+    // 남길 포인트면 true
+    pub fn mask(&self, points: &[LidarPoint]) -> Vec<bool> {
+        self.mask_with(&Cpu, points)
+    }
+
+    // 이웃 수 세기(가장 무거운 부분)를 compute 경로로 처리
+    pub fn mask_with(&self, compute: &dyn ComputeBackend, points: &[LidarPoint]) -> Vec<bool> {
+        if !self.enabled() {
+            return vec![true; points.len()];
+        }
+        let min_neighbors = 1 + (self.aggressiveness * 4.0).round() as usize;
+        let low_intensity = LOW_INTENSITY * self.aggressiveness;
+
+        let xyz: Vec<[f32; 3]> = points.iter().map(|p| [p.x, p.y, p.z]).collect();
+        let (radii, required): (Vec<f32>, Vec<usize>) = points
+            .iter()
+            .map(|p| {
+                // 태그로 이미 노이즈인 포인트는 이웃을 셀 필요 없음
+                if self.tagged_noise(p.tag) {
+                    return (0.0, 0);
+                }
+                // 멀수록 포인트 간격이 벌어지므로 반경도 키움
+                let range = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+                let radius =
+                    (range * RADIUS_PER_M * (1.0 + self.aggressiveness)).clamp(MIN_RADIUS, CELL);
+                // 낮은 intensity 포인트는 이웃을 두 배로 요구
+                let required = if p.intensity < low_intensity {
+                    min_neighbors * 2
+                } else {
+                    min_neighbors
+                };
+                (radius, required)
+            })
+            .unzip();
+        let counts = compute.neighbor_counts(&xyz, &radii, &required, CELL);
+
+        required
+            .iter()
+            .zip(counts)
+            .map(|(&required, count)| required > 0 && count >= required)
+            .collect()
+    }
+
+    pub fn apply(&self, points: &mut Vec<LidarPoint>) {
+        self.apply_with(&Cpu, points)
+    }
+
+    pub fn apply_with(&self, compute: &dyn ComputeBackend, points: &mut Vec<LidarPoint>) {
+        if !self.enabled() {
+            return;
+        }
+        let keep = self.mask_with(compute, points);
+        let mut keep = keep.into_iter();
+        points.retain(|_| keep.next().unwrap_or(true));
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{bail, Context, Result};
+
+// 보정 표/영역 설정 파일이 쓰는 YAML 일부 (최상위 키와 그 아래 한 단계 항목)
+//
+//   range_bins: [10.0, 30.0]     # 최상위 "key: value"
+//   lines:                       # 값이 없는 최상위 키는 아래 항목의 section
+//     0: 1.05                    # 들여쓴 항목
+//     - box: [...]               # '-' 로 시작하는 목록 항목 (들여쓰기 없어도 됨)
+//
+// '#' 뒤는 주석, 빈 줄은 무시
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    // 항목이 속한 최상위 키, 최상위 줄이면 None
+    pub section: Option<&'a str>,
+    // '-' 로 시작하는 목록 항목의 첫 줄
+    pub item: bool,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+// text 의 줄마다 entry 를 호출, 오류에는 줄 번호를 붙임
+pub fn for_each_entry<'a>(
+    text: &'a str,
+    mut entry: impl FnMut(Entry<'a>) -> Result<()>,
+) -> Result<()> {
+    let mut section = None;
+    for (n, raw) in text.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        let (rest, item) = match line.trim().strip_prefix('-') {
+            Some(rest) => (rest.trim(), true),
+            None => (line.trim(), false),
+        };
+        let Some((key, value)) = rest.split_once(':') else {
+            bail!("{}번째 줄: 'key: value' 형식이 아닙니다", n + 1);
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let current = if indented || item {
+            match section {
+                Some(section) => Some(section),
+                None => bail!("{}번째 줄: 최상위 키 밖의 항목입니다", n + 1),
+            }
+        } else {
+            section = value.is_empty().then_some(key);
+            None
+        };
+        entry(Entry {
+            section: current,
+            item,
+            key,
+            value,
+        })
+        .with_context(|| format!("{}번째 줄", n + 1))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(text: &str) -> Result<Vec<Entry<'_>>> {
+        let mut out = Vec::new();
+        for_each_entry(text, |entry| {
+            out.push(entry);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    #[test]
+    fn nested_entries_take_section() {
+        let text = "\
+# 주석
+range_bins: [10.0, 30.0]   # 끝 주석
+
+lines:
+  0: 1.05
+  1: [1.1, 1.0]
+zones:
+- box: [0, 0, 0, 1, 1, 1]
+  z: [0, 1]
+";
+        let got = entries(text).unwrap();
+        let summary: Vec<_> = got
+            .iter()
+            .map(|e| (e.section, e.item, e.key, e.value))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (None, false, "range_bins", "[10.0, 30.0]"),
+                (None, false, "lines", ""),
+                (Some("lines"), false, "0", "1.05"),
+                (Some("lines"), false, "1", "[1.1, 1.0]"),
+                (None, false, "zones", ""),
+                (Some("zones"), true, "box", "[0, 0, 0, 1, 1, 1]"),
+                (Some("zones"), false, "z", "[0, 1]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn top_level_value_closes_section() {
+        let err = entries("lines:\n  0: 1.0\nscale: 2\n  1: 1.0\n").unwrap_err();
+        assert!(format!("{:#}", err).contains("4번째 줄"));
+    }
+
+    #[test]
+    fn errors_carry_line_number() {
+        let err = entries("lines:\n  0 1.0\n").unwrap_err();
+        assert!(format!("{:#}", err).contains("2번째 줄"));
+
+        let err = for_each_entry("a: 1\nb: 2\n", |entry| {
+            if entry.key == "b" {
+                bail!("거부");
+            }
+            Ok(())
+        })
+        .unwrap_err();
+        assert_eq!(format!("{:#}", err), "2번째 줄: 거부");
+    }
+}
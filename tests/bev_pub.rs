@@ -0,0 +1,136 @@
+// bev_pub 통합 시험: 합성 입력 -> BEV 노드 (bev_node::run, 같은 프로세스) -> 출력 수집
+// 실행 중인 ROS 2 그래프가 필요해 ros-integration 기능을 켤 때만 빌드, ROS 2 환경
+// (source install/setup.bash) 에서 cargo test --features ros-integration --test bev_pub
+// 시험마다 자기 TestGraph (Context 하나, 네임스페이스 하나) 를 쓰므로 같은 프로세스에서 병렬로 실행해도 됨
+#![cfg(feature = "ros-integration")]
+
+use anyhow::Result;
+use rust_lidar::bev_frame::BevFrame;
+use rust_lidar::bev_node;
+use rust_lidar::cli::{OutputOptions, Verbosity};
+use rust_lidar::harness::{self, TestGraph};
+use rust_lidar::point::parse_pointcloud2;
+use rust_lidar::synthetic::SyntheticConfig;
+use sensor_msgs::msg::PointCloud2;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+// 출력이 frames 개 모일 때까지 합성 프레임 발행, (입력 포인트 수, 출력) 목록
+fn run(params: &[(&str, &str)], frames: usize) -> Result<Vec<(usize, PointCloud2)>> {
+    let mut graph = TestGraph::new()?;
+    let capture = graph.capture("livox/lidar_bev")?;
+    let publisher = graph.publisher("livox/lidar")?;
+    graph.launch("lidar_bev_publisher", params, |context, node, shutdown| {
+        let mut options = OutputOptions::default();
+        options.verbosity = Verbosity::Quiet;
+        bev_node::run(context, node, shutdown, options)
+    })?;
+    graph.wait_ready(TIMEOUT, &["livox/lidar"], &["livox/lidar_bev"])?;
+
+    let config = SyntheticConfig {
+        points_per_line: 500,
+        ..SyntheticConfig::default()
+    };
+    let mut inputs = Vec::new();
+    let received = graph.spin_until(
+        TIMEOUT,
+        || {
+            let (msg, points) = harness::synthetic_frame(&config, "livox_frame");
+            inputs.push((msg.header.stamp.clone(), points.len()));
+            publisher.publish(msg)?;
+            std::thread::sleep(Duration::from_millis(50));
+            Ok(())
+        },
+        || capture.len() >= frames,
+    )?;
+    assert!(
+        received,
+        "{} 초 안에 출력 {} 개를 받지 못함",
+        TIMEOUT.as_secs(),
+        frames
+    );
+
+    // 출력 시각으로 입력을 찾아 짝지음 (헤더 시각 유지 확인)
+    Ok(capture
+        .take()
+        .into_iter()
+        .map(|out| {
+            let input = inputs
+                .iter()
+                .find(|(stamp, _)| *stamp == out.header.stamp)
+                .map(|(_, count)| *count)
+                .expect("출력 시각이 입력 시각과 다름");
+            (input, out)
+        })
+        .collect())
+}
+
+fn field_names(msg: &PointCloud2) -> Vec<&str> {
+    msg.fields.iter().map(|f| f.name.as_str()).collect()
+}
+
+#[test]
+fn points_mode_flattens_and_keeps_header() -> Result<()> {
+    let outputs = run(&[("output_frame_id", "{frame}_test")], 3)?;
+    for (input, out) in outputs {
+        assert_eq!(out.header.frame_id, "livox_frame_test");
+        let names = field_names(&out);
+        for name in ["x", "y", "z", "intensity"] {
+            assert!(names.contains(&name), "{} 필드 없음: {:?}", name, names);
+        }
+        let points = parse_pointcloud2(&out)?;
+        assert_eq!(points.len(), out.width as usize * out.height as usize);
+        assert!(points.len() <= input);
+        assert!(points.iter().all(|p| p.z == 0.0));
+    }
+    Ok(())
+}
+
+#[test]
+fn density_mode_outputs_one_point_per_cell() -> Result<()> {
+    let outputs = run(
+        &[
+            ("bev_mode", "density"),
+            ("bev_cell_size", "0.5"),
+            ("min_points_per_cell", "2"),
+        ],
+        2,
+    )?;
+    for (input, out) in outputs {
+        assert!(field_names(&out).contains(&"density"));
+        let frame = BevFrame::from_msg(&out)?;
+        assert!(!frame.is_empty());
+        let cells = frame.by_cell(0.5);
+        assert_eq!(
+            cells.len(),
+            frame.occupied_count(),
+            "셀마다 포인트 하나여야 함"
+        );
+        let total: u32 = frame.occupied().filter_map(|c| c.density).sum();
+        assert!(total as usize <= input);
+        assert!(frame.occupied().all(|c| c.density.is_some_and(|d| d >= 2)));
+    }
+    Ok(())
+}
+
+#[test]
+fn organized_output_covers_grid() -> Result<()> {
+    let outputs = run(
+        &[
+            ("bev_mode", "cells"),
+            ("bev_cell_size", "1.0"),
+            ("bev_extent_x", "20.0"),
+            ("bev_extent_y", "10.0"),
+            ("bev_organized", "true"),
+        ],
+        1,
+    )?;
+    for (_, out) in outputs {
+        assert_eq!((out.height, out.width), (20, 10));
+        let frame = BevFrame::from_msg(&out)?;
+        assert_eq!(frame.shape, Some((20, 10)));
+        assert!(frame.occupied_count() > 0);
+    }
+    Ok(())
+}